@@ -0,0 +1,4 @@
+-- Per-session CSRF token (synchronizer token) for cookie-authenticated writes.
+-- Existing sessions get a random token so they keep working after deploy.
+ALTER TABLE sessions ADD COLUMN csrf_token TEXT NOT NULL DEFAULT '';
+UPDATE sessions SET csrf_token = lower(hex(randomblob(32))) WHERE csrf_token = '';
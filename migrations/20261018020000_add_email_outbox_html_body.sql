@@ -0,0 +1,2 @@
+-- HTML alternative for multipart emails; NULL for plain-text-only messages.
+ALTER TABLE email_outbox ADD COLUMN html_body TEXT;
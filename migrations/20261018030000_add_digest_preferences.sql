@@ -0,0 +1,12 @@
+-- Digest emails: per-user cadence (opt-in, off by default) and when the last one went out.
+ALTER TABLE users ADD COLUMN digest_frequency TEXT NOT NULL DEFAULT 'off' CHECK(digest_frequency IN ('off', 'daily', 'weekly'));
+ALTER TABLE users ADD COLUMN digest_last_sent_at INTEGER;
+
+-- Blocked count per project at the user's last digest, so the next one can report newly blocked work.
+CREATE TABLE IF NOT EXISTS digest_project_snapshots (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    blocked_count INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY (user_id, project_id)
+);
@@ -0,0 +1,24 @@
+-- Full-text index over node title and description (external content on nodes, synced by triggers).
+CREATE VIRTUAL TABLE IF NOT EXISTS nodes_fts USING fts5(
+    title,
+    description,
+    content = 'nodes',
+    content_rowid = 'rowid',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS nodes_fts_after_insert AFTER INSERT ON nodes BEGIN
+    INSERT INTO nodes_fts (rowid, title, description) VALUES (new.rowid, new.title, new.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS nodes_fts_after_delete AFTER DELETE ON nodes BEGIN
+    INSERT INTO nodes_fts (nodes_fts, rowid, title, description) VALUES ('delete', old.rowid, old.title, old.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS nodes_fts_after_update AFTER UPDATE OF title, description ON nodes BEGIN
+    INSERT INTO nodes_fts (nodes_fts, rowid, title, description) VALUES ('delete', old.rowid, old.title, old.description);
+    INSERT INTO nodes_fts (rowid, title, description) VALUES (new.rowid, new.title, new.description);
+END;
+
+-- Index nodes that existed before this migration.
+INSERT INTO nodes_fts (nodes_fts) VALUES ('rebuild');
@@ -0,0 +1,6 @@
+-- Allow 'board' as a project default view mode. SQLite cannot alter a CHECK constraint, so the
+-- column is replaced: add the new one, copy values, drop the old one and take over its name.
+ALTER TABLE projects ADD COLUMN default_view_mode_new TEXT NOT NULL DEFAULT 'graph' CHECK(default_view_mode_new IN ('graph', 'list', 'board'));
+UPDATE projects SET default_view_mode_new = default_view_mode;
+ALTER TABLE projects DROP COLUMN default_view_mode;
+ALTER TABLE projects RENAME COLUMN default_view_mode_new TO default_view_mode;
@@ -0,0 +1,5 @@
+-- Allow 'timeline' as a project default view mode (same column replacement as the board migration).
+ALTER TABLE projects ADD COLUMN default_view_mode_new TEXT NOT NULL DEFAULT 'graph' CHECK(default_view_mode_new IN ('graph', 'list', 'board', 'timeline'));
+UPDATE projects SET default_view_mode_new = default_view_mode;
+ALTER TABLE projects DROP COLUMN default_view_mode;
+ALTER TABLE projects RENAME COLUMN default_view_mode_new TO default_view_mode;
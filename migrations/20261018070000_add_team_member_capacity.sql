@@ -0,0 +1,2 @@
+-- Weekly capacity per team membership, in minutes of work (default 40 hours), for the workload report.
+ALTER TABLE team_members ADD COLUMN weekly_capacity_minutes INTEGER NOT NULL DEFAULT 2400 CHECK(weekly_capacity_minutes >= 0);
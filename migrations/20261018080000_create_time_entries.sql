@@ -0,0 +1,18 @@
+-- Time tracking: effort logged against nodes, and at most one running timer per user.
+CREATE TABLE time_entries (
+    id TEXT PRIMARY KEY,
+    node_id TEXT NOT NULL REFERENCES nodes(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    minutes INTEGER NOT NULL CHECK(minutes > 0),
+    entry_date TEXT NOT NULL,
+    note TEXT,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX idx_time_entries_node_id ON time_entries(node_id);
+CREATE INDEX idx_time_entries_user_id ON time_entries(user_id);
+
+CREATE TABLE running_timers (
+    user_id TEXT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    node_id TEXT NOT NULL REFERENCES nodes(id) ON DELETE CASCADE,
+    started_at INTEGER NOT NULL
+);
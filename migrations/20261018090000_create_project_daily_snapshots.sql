@@ -0,0 +1,13 @@
+-- One row per project per UTC day with the task counts and estimate left, for burndown/burnup charts.
+-- The snapshot job rewrites today's row until the day ends, so each row holds that day's last state.
+CREATE TABLE IF NOT EXISTS project_daily_snapshots (
+    project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    snapshot_date TEXT NOT NULL,
+    todo_count INTEGER NOT NULL,
+    in_progress_count INTEGER NOT NULL,
+    done_count INTEGER NOT NULL,
+    blocked_count INTEGER NOT NULL,
+    estimated_remaining_minutes INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY (project_id, snapshot_date)
+);
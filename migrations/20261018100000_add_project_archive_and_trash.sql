@@ -0,0 +1,5 @@
+-- Archived projects are hidden from the projects list but stay readable.
+-- Trashed projects (deleted_at set) are unreachable until restored, and are purged after the retention window.
+ALTER TABLE projects ADD COLUMN archived_at INTEGER;
+ALTER TABLE projects ADD COLUMN deleted_at INTEGER;
+CREATE INDEX IF NOT EXISTS idx_projects_deleted_at ON projects(deleted_at);
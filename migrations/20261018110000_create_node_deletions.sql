@@ -0,0 +1,12 @@
+-- What a node deletion removed or rewired, so it can be undone: the node row, its edges, group
+-- members, bypass edges added between its parents and children, and cascaded time entries (JSON).
+CREATE TABLE IF NOT EXISTS node_deletions (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    node_id TEXT NOT NULL,
+    deleted_by TEXT REFERENCES users(id) ON DELETE SET NULL,
+    snapshot TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_node_deletions_project_node ON node_deletions(project_id, node_id);
+CREATE INDEX IF NOT EXISTS idx_node_deletions_created_at ON node_deletions(created_at);
@@ -0,0 +1,15 @@
+-- Pending ownership transfers (recipient confirms by email link) and scheduled organization deletion.
+
+ALTER TABLE organizations ADD COLUMN deletion_requested_at INTEGER;
+
+CREATE TABLE IF NOT EXISTS organization_ownership_transfers (
+    id TEXT PRIMARY KEY,
+    organization_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    from_user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    to_user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token TEXT NOT NULL UNIQUE,
+    expires_at INTEGER NOT NULL,
+    created_at INTEGER NOT NULL
+);
+-- At most one pending transfer per organization.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_organization_ownership_transfers_org ON organization_ownership_transfers(organization_id);
@@ -0,0 +1,17 @@
+-- Invites can name the team the invitee joins (bulk CSV invites); NULL means the org's default team.
+ALTER TABLE organization_invites ADD COLUMN team_id TEXT REFERENCES teams(id) ON DELETE SET NULL;
+
+-- Reusable invite links: anyone with the token can join until it expires, runs out of uses or is revoked.
+CREATE TABLE IF NOT EXISTS organization_invite_links (
+    id TEXT PRIMARY KEY,
+    organization_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    token TEXT NOT NULL UNIQUE,
+    role TEXT NOT NULL CHECK(role IN ('admin', 'member', 'viewer')),
+    team_id TEXT REFERENCES teams(id) ON DELETE SET NULL,
+    max_uses INTEGER NOT NULL CHECK(max_uses > 0),
+    use_count INTEGER NOT NULL DEFAULT 0,
+    created_by_user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    expires_at INTEGER NOT NULL,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_organization_invite_links_organization_id ON organization_invite_links(organization_id);
@@ -0,0 +1,15 @@
+-- Email domains claimed by an organization. Once verified through a DNS TXT record, people who sign
+-- up with an address at the domain are offered to join at default_role, in team_id (NULL: default team).
+CREATE TABLE IF NOT EXISTS organization_domains (
+    id TEXT PRIMARY KEY,
+    organization_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    domain TEXT NOT NULL COLLATE NOCASE,
+    verification_token TEXT NOT NULL,
+    verified_at INTEGER,
+    default_role TEXT NOT NULL CHECK(default_role IN ('admin', 'member', 'viewer')),
+    team_id TEXT REFERENCES teams(id) ON DELETE SET NULL,
+    created_at INTEGER NOT NULL,
+    UNIQUE (organization_id, domain)
+);
+-- Only one organization can hold a verified claim on a domain.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_organization_domains_verified_domain ON organization_domains(domain) WHERE verified_at IS NOT NULL;
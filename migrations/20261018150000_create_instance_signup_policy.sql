@@ -0,0 +1,10 @@
+-- Signup policy set by an instance admin. At most one row; when absent, SIGNUP_MODE and
+-- SIGNUP_ALLOWED_DOMAINS from the environment apply.
+CREATE TABLE IF NOT EXISTS instance_signup_policy (
+    id INTEGER PRIMARY KEY CHECK(id = 1),
+    mode TEXT NOT NULL CHECK(mode IN ('open', 'invite_only', 'closed')),
+    -- Comma-separated lowercase domains; empty allows any domain.
+    allowed_domains TEXT NOT NULL DEFAULT '',
+    updated_by_user_id TEXT REFERENCES users(id) ON DELETE SET NULL,
+    updated_at INTEGER NOT NULL
+);
@@ -0,0 +1,17 @@
+-- Colored labels, shared by every project in an organization, and which nodes carry them.
+CREATE TABLE IF NOT EXISTS labels (
+    id TEXT PRIMARY KEY,
+    organization_id TEXT NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name TEXT NOT NULL COLLATE NOCASE,
+    -- #rrggbb
+    color TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    UNIQUE (organization_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS node_labels (
+    node_id TEXT NOT NULL REFERENCES nodes(id) ON DELETE CASCADE,
+    label_id TEXT NOT NULL REFERENCES labels(id) ON DELETE CASCADE,
+    PRIMARY KEY (node_id, label_id)
+);
+CREATE INDEX IF NOT EXISTS idx_node_labels_label_id ON node_labels(label_id);
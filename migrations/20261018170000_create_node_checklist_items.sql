@@ -0,0 +1,12 @@
+-- Ordered checklist items inside a node, for steps too small to be nodes of their own.
+CREATE TABLE IF NOT EXISTS node_checklist_items (
+    id TEXT PRIMARY KEY,
+    node_id TEXT NOT NULL REFERENCES nodes(id) ON DELETE CASCADE,
+    text TEXT NOT NULL,
+    done INTEGER NOT NULL DEFAULT 0 CHECK(done IN (0, 1)),
+    assigned_user_id TEXT REFERENCES users(id) ON DELETE SET NULL,
+    sort_order INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER
+);
+CREATE INDEX IF NOT EXISTS idx_node_checklist_items_node_id ON node_checklist_items(node_id, sort_order);
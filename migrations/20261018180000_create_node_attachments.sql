@@ -0,0 +1,20 @@
+-- Files attached to nodes. Blobs are content-addressed (SHA-256) and shared between attachments
+-- with identical bytes; a blob is removed from storage once nothing references it.
+CREATE TABLE IF NOT EXISTS attachment_blobs (
+    content_hash TEXT PRIMARY KEY,
+    size_bytes INTEGER NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS node_attachments (
+    id TEXT PRIMARY KEY,
+    node_id TEXT NOT NULL REFERENCES nodes(id) ON DELETE CASCADE,
+    content_hash TEXT NOT NULL REFERENCES attachment_blobs(content_hash),
+    filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    uploaded_by_user_id TEXT REFERENCES users(id) ON DELETE SET NULL,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_node_attachments_node_id ON node_attachments(node_id);
+CREATE INDEX IF NOT EXISTS idx_node_attachments_content_hash ON node_attachments(content_hash);
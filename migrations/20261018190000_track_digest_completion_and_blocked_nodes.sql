@@ -0,0 +1,16 @@
+-- When a node last moved to Done, so digests count completions rather than edits to done nodes.
+-- Existing done nodes get their last update as the best available estimate.
+ALTER TABLE nodes ADD COLUMN completed_at INTEGER;
+UPDATE nodes SET completed_at = COALESCE(updated_at, created_at) WHERE status_id = '01JSTATUS00000000DONE0000';
+
+-- Which nodes were blocked at the user's last digest, so the next one reports the ones newly blocked.
+CREATE TABLE IF NOT EXISTS digest_blocked_nodes (
+    user_id TEXT NOT NULL,
+    project_id TEXT NOT NULL,
+    node_id TEXT NOT NULL REFERENCES nodes(id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id, project_id, node_id),
+    FOREIGN KEY (user_id, project_id) REFERENCES digest_project_snapshots(user_id, project_id) ON DELETE CASCADE
+);
+
+-- Snapshots that only hold a count can't be diffed; the next digest starts a fresh baseline.
+DELETE FROM digest_project_snapshots;
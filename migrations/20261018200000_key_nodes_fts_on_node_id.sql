@@ -0,0 +1,27 @@
+-- Rebuild the node search index keyed on nodes.id. The original external-content table followed the
+-- implicit rowid of nodes, which VACUUM may renumber since nodes has a TEXT primary key.
+DROP TRIGGER IF EXISTS nodes_fts_after_insert;
+DROP TRIGGER IF EXISTS nodes_fts_after_delete;
+DROP TRIGGER IF EXISTS nodes_fts_after_update;
+DROP TABLE IF EXISTS nodes_fts;
+
+CREATE VIRTUAL TABLE nodes_fts USING fts5(
+    node_id UNINDEXED,
+    title,
+    description,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER nodes_fts_after_insert AFTER INSERT ON nodes BEGIN
+    INSERT INTO nodes_fts (node_id, title, description) VALUES (new.id, new.title, new.description);
+END;
+
+CREATE TRIGGER nodes_fts_after_delete AFTER DELETE ON nodes BEGIN
+    DELETE FROM nodes_fts WHERE node_id = old.id;
+END;
+
+CREATE TRIGGER nodes_fts_after_update AFTER UPDATE OF title, description ON nodes BEGIN
+    UPDATE nodes_fts SET title = new.title, description = new.description WHERE node_id = old.id;
+END;
+
+INSERT INTO nodes_fts (node_id, title, description) SELECT id, title, description FROM nodes;
@@ -0,0 +1,13 @@
+-- Blobs a pending node deletion still needs to undo, so the orphan sweep can look them up by hash
+-- instead of searching every snapshot. Rows go with their deletion record.
+CREATE TABLE IF NOT EXISTS node_deletion_blobs (
+    deletion_id TEXT NOT NULL REFERENCES node_deletions(id) ON DELETE CASCADE,
+    content_hash TEXT NOT NULL REFERENCES attachment_blobs(content_hash),
+    PRIMARY KEY (deletion_id, content_hash)
+);
+CREATE INDEX IF NOT EXISTS idx_node_deletion_blobs_content_hash ON node_deletion_blobs(content_hash);
+
+INSERT OR IGNORE INTO node_deletion_blobs (deletion_id, content_hash)
+SELECT d.id, b.content_hash
+FROM node_deletions d, json_each(d.snapshot, '$.attachments') a
+INNER JOIN attachment_blobs b ON b.content_hash = json_extract(a.value, '$.content_hash');
@@ -19,6 +19,18 @@ const TODO_STATUS_ID = '01JSTATUS00000000TODO0000';
 const IN_PROGRESS_STATUS_ID = '01JSTATUS00000000INPROG00';
 const DONE_STATUS_ID = '01JSTATUS00000000DONE0000';
 
+/** Project default view modes; anything else falls back to the graph. */
+function normalizeViewMode(raw) {
+    const mode = (typeof raw === 'string' ? raw : 'graph').toLowerCase();
+    return ['graph', 'list', 'board', 'timeline'].includes(mode) ? mode : 'graph';
+}
+
+/** CSRF token for writes; rendered into <meta name="csrf-token"> by the page layout. */
+function csrfToken() {
+    const meta = document.querySelector('meta[name="csrf-token"]');
+    return meta ? meta.getAttribute('content') : '';
+}
+
 const SEMANTIC_COLORS = {
     epic: '#9B6BCA',
     task: '#5A8FF0',
@@ -104,6 +116,8 @@ function buildCyNodeElement(node, options) {
             slot_id: node.slot_id ?? '',
             slot_name: slot ? slot.name : '',
             estimated_minutes: node.estimated_minutes ?? null,
+            checklist_done: 0,
+            checklist_total: 0,
             muted: !!muted,
             filteredOut: !!filteredOut,
             created_at: node.created_at,
@@ -156,7 +170,7 @@ function escapeHtml(str) {
 
 /**
  * Build cytoscape node label HTML with all API/DB-derived values escaped.
- * @param {object} data - Node data (label, node_type_name, node_type_color, status_name, slot_name, estimated_minutes, muted)
+ * @param {object} data - Node data (label, node_type_name, node_type_color, status_name, slot_name, estimated_minutes, checklist_done, checklist_total, muted)
  * @param {{ selected: boolean, muted: boolean, filteredOut: boolean }} opts
  */
 function buildNodeLabelHtml(data, opts) {
@@ -195,9 +209,13 @@ function buildNodeLabelHtml(data, opts) {
     const estimateStrRaw = formatEstimatedMinutes(data.estimated_minutes);
     const estimateStr = escapeHtml(estimateStrRaw);
     const estimateHtml = estimateStr ? `<div class="cy-node__estimate block text-10 font-sans font-bold text-taupe">${estimateStr}</div>` : '';
+    const checklistTotal = Number(data.checklist_total) || 0;
+    const checklistHtml = checklistTotal > 0
+        ? `<div class="cy-node__checklist block text-10 font-sans font-bold text-taupe" title="Checklist">${Number(data.checklist_done) || 0}/${checklistTotal}</div>`
+        : '';
     const typeClass = ' cy-node--' + typeSlug;
     const warningClass = isBlocked ? ' cy-node--warning' : '';
-    const compactClass = (!estimateStrRaw && !data.status_name && !data.slot_name && !avatarUrl && !initials && !isDone) ? ' cy-node--compact' : '';
+    const compactClass = (!estimateStrRaw && !data.status_name && !data.slot_name && !avatarUrl && !initials && !isDone && !checklistTotal) ? ' cy-node--compact' : '';
     const mutedClass = (opts.muted) ? ' cy-node--muted' : '';
     const filteredClass = (opts.filteredOut) ? ' cy-node--filtered' : '';
     const doneClass = isDone ? ' cy-node--done' : '';
@@ -211,7 +229,7 @@ function buildNodeLabelHtml(data, opts) {
                                         ${headerRightHtml}
                                     </div>
                                     <div class="cy-node__label${isDone ? ' cy-node__label--done' : ''}">${label}</div>
-                                    <div class="cy-node__meta">${statusHtml}${estimateHtml}</div>
+                                    <div class="cy-node__meta">${statusHtml}${checklistHtml}${estimateHtml}</div>
                                 </div>
                             </div>`;
 }
@@ -430,6 +448,7 @@ function createRemoveNode() {
         try {
             Alpine.store('projectAction', { active: true, label: 'Deleting...' });
             let needReload = false;
+            const deletedIds = [];
             for (const node of nodes) {
                 const id = node.id();
                 if (graph.isTemporaryGroupNode(node)) {
@@ -440,6 +459,7 @@ function createRemoveNode() {
                 }
                 if (node.data('isGroup') === true) {
                     await graph.api(`/api/projects/${graph.projectId}/nodes/${id}`, 'DELETE');
+                    deletedIds.push(id);
                     node.children().move({ parent: null });
                     graph.cy.remove(node);
                     graph.groupListVersion++;
@@ -448,8 +468,10 @@ function createRemoveNode() {
                 }
                 await graph.api(`/api/projects/${graph.projectId}/nodes/${id}`, 'DELETE');
                 graph.cy.remove(node);
+                deletedIds.push(id);
                 needReload = true;
             }
+            graph.lastDeletedNodeIds = deletedIds;
             graph.selectedNodeIds = [];
             graph.editingNode = null;
             graph.editingNodeOriginal = null;
@@ -465,9 +487,29 @@ function createRemoveNode() {
         }
     }
 
+    /** Restore the nodes removed by the last delete (newest first), with their original edges. */
+    async function undoRemoveNode() {
+        const graph = this;
+        const ids = graph.lastDeletedNodeIds || [];
+        if (ids.length === 0) return;
+
+        try {
+            Alpine.store('projectAction', { active: true, label: 'Restoring...' });
+            for (const id of [...ids].reverse()) {
+                await graph.api(`/api/projects/${graph.projectId}/nodes/${id}/restore`, 'POST');
+            }
+            graph.lastDeletedNodeIds = [];
+            await graph.fetchGraph();
+        } catch (error) {
+            alert(`Error restoring node: ${error.message}`);
+        } finally {
+            Alpine.store('projectAction', { active: false, label: '' });
+        }
+    }
+
     function setup(graph) {
-        Object.assign(graph, { removeNode });
-        return { removeNode };
+        Object.assign(graph, { removeNode, undoRemoveNode });
+        return { removeNode, undoRemoveNode };
     }
 
     return { setup };
@@ -484,8 +526,7 @@ const registerGraph = () => {
     if (Alpine.data('projectList')) return;
 
     Alpine.data('projectList', (projectId, defaultViewModeInitial = 'graph') => {
-        const mode = (typeof defaultViewModeInitial === 'string' ? defaultViewModeInitial : 'graph').toLowerCase();
-        const validMode = mode === 'list' ? 'list' : 'graph';
+        const validMode = normalizeViewMode(defaultViewModeInitial);
         return {
         projectId,
         defaultViewMode: validMode,
@@ -553,11 +594,11 @@ const registerGraph = () => {
         },
 
         async updateDefaultViewMode() {
-            const mode = this.defaultViewMode === 'list' ? 'list' : 'graph';
+            const mode = normalizeViewMode(this.defaultViewMode);
             try {
                 const res = await fetch(`/api/projects/${this.projectId}`, {
                     method: 'PATCH',
-                    headers: { 'Content-Type': 'application/json' },
+                    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken() },
                     body: JSON.stringify({ default_view_mode: mode }),
                     credentials: 'same-origin'
                 });
@@ -575,7 +616,7 @@ const registerGraph = () => {
             try {
                 const res = await fetch(`/api/projects/${this.projectId}`, {
                     method: 'PATCH',
-                    headers: { 'Content-Type': 'application/json' },
+                    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken() },
                     body: JSON.stringify({ default_view_mode: mode }),
                     credentials: 'same-origin'
                 });
@@ -649,7 +690,7 @@ const registerGraph = () => {
         async api(url, method, body = null) {
             const options = {
                 method,
-                headers: { 'Content-Type': 'application/json' }
+                headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken() }
             };
             if (body) options.body = JSON.stringify(body);
             const response = await fetch(url, options);
@@ -695,6 +736,53 @@ const registerGraph = () => {
     };
     });
 
+    Alpine.data('projectBoard', (projectId) => ({
+        draggingNodeId: null,
+        draggingFromStatusId: null,
+        hoverStatusId: null,
+        error: '',
+        dragStart(nodeId, statusId) {
+            this.draggingNodeId = nodeId;
+            this.draggingFromStatusId = statusId;
+        },
+        async drop(statusId) {
+            const nodeId = this.draggingNodeId;
+            const fromStatusId = this.draggingFromStatusId;
+            this.draggingNodeId = null;
+            this.hoverStatusId = null;
+            if (!nodeId || statusId === fromStatusId) return;
+            Alpine.store('projectAction', { active: true, label: 'Updating...' });
+            try {
+                const response = await fetch(`/api/projects/${projectId}/nodes/${nodeId}`, {
+                    method: 'PATCH',
+                    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken() },
+                    body: JSON.stringify({ status_id: statusId }),
+                    credentials: 'include'
+                });
+                if (!response.ok) {
+                    const data = await response.json().catch(() => ({}));
+                    this.error = data.error || 'Failed to move card';
+                    return;
+                }
+                // Reload so columns, counts and blocked flags reflect the new status.
+                window.location.reload();
+            } catch (e) {
+                this.error = e.message || 'Failed to move card';
+            } finally {
+                Alpine.store('projectAction', { active: false, label: '' });
+            }
+        }
+    }));
+
+    Alpine.data('bulkInvite', () => ({
+        async loadCsv(event) {
+            const file = event.target.files && event.target.files[0];
+            if (file) {
+                this.$refs.entries.value = await file.text();
+            }
+        }
+    }));
+
     Alpine.data('projectImport', () => ({
         importError: '',
         async importFromFile() {
@@ -709,7 +797,7 @@ const registerGraph = () => {
                 const text = await file.text();
                 const response = await fetch('/api/projects/import', {
                     method: 'POST',
-                    headers: { 'Content-Type': 'application/json' },
+                    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken() },
                     body: text,
                     credentials: 'include',
                     redirect: 'follow'
@@ -727,14 +815,14 @@ const registerGraph = () => {
     }));
 
     Alpine.data('graph', (projectId, defaultViewModeInitial = 'graph') => {
-        const raw = (typeof defaultViewModeInitial === 'string' ? defaultViewModeInitial : 'graph').toLowerCase();
-        const validMode = (raw === 'list' ? 'list' : 'graph');
+        const validMode = normalizeViewMode(defaultViewModeInitial);
         Alpine.store('projectDefaultViewMode').value = validMode;
         return {
         projectId: projectId,
         defaultViewMode: validMode,
         cy: null,
         selectedNodeIds: [],
+        lastDeletedNodeIds: [], // nodes removed by the last delete, for undo
         selectedEdge: null, // { sourceId, targetId } when one edge is selected
         layoutDirection: 'LR',
         nodeTypeId: DEFAULTS.NODE_TYPE,
@@ -1082,6 +1170,7 @@ const registerGraph = () => {
                 const data = await response.json();
 
                 const groupIds = new Set(data.nodes.map(n => n.parent_id).filter(Boolean));
+                const checklistByNode = new Map((data.checklist_progress || []).map(p => [p.node_id, p]));
                 const isRoot = (id) => !data.edges.some(e => e.child_id === id);
                 const parentFor = (childId) => {
                     const edge = data.edges.find(e => e.child_id === childId);
@@ -1103,6 +1192,7 @@ const registerGraph = () => {
                         const muted = !root && !selfDone && !parentDone;
                         const isGroupNode = groupIds.has(n.id);
                         const filteredOut = this.progressFilter ? !this.matchesProgressFilter(statusId) : false;
+                        const checklist = checklistByNode.get(n.id);
                         return {
                             group: 'nodes',
                             data: {
@@ -1118,6 +1208,8 @@ const registerGraph = () => {
                                 slot_id: n.slot_id ?? '',
                                 slot_name: slot ? slot.name : '',
                                 estimated_minutes: n.estimated_minutes ?? null,
+                                checklist_done: checklist ? checklist.done : 0,
+                                checklist_total: checklist ? checklist.total : 0,
                                 muted: !!muted,
                                 filteredOut: !!filteredOut,
                                 isGroup: isGroupNode,
@@ -1258,7 +1350,7 @@ const registerGraph = () => {
         async api(url, method, body = null) {
             const options = {
                 method,
-                headers: { 'Content-Type': 'application/json' }
+                headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken() }
             };
             if (body) options.body = JSON.stringify(body);
 
@@ -1877,11 +1969,11 @@ const registerGraph = () => {
         },
 
         async updateDefaultViewMode() {
-            const mode = Alpine.store('projectDefaultViewMode').value === 'list' ? 'list' : 'graph';
+            const mode = normalizeViewMode(Alpine.store('projectDefaultViewMode').value);
             try {
                 const res = await fetch(`/api/projects/${this.projectId}`, {
                     method: 'PATCH',
-                    headers: { 'Content-Type': 'application/json' },
+                    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken() },
                     body: JSON.stringify({ default_view_mode: mode }),
                     credentials: 'same-origin'
                 });
@@ -1900,7 +1992,7 @@ const registerGraph = () => {
             try {
                 const res = await fetch(`/api/projects/${this.projectId}`, {
                     method: 'PATCH',
-                    headers: { 'Content-Type': 'application/json' },
+                    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken() },
                     body: JSON.stringify({ default_view_mode: mode }),
                     credentials: 'same-origin'
                 });
@@ -2056,4 +2148,259 @@ if (window.Alpine) {
     document.addEventListener('alpine:init', registerGraph);
 }
 
+/** Header search box: queries /api/search as the user types. snippet_html is escaped server-side. */
+const registerSearch = () => {
+    Alpine.data('globalSearch', () => ({
+        query: '',
+        results: [],
+        open: false,
+        async search() {
+            const q = this.query.trim();
+            if (q === '') {
+                this.results = [];
+                this.open = false;
+                return;
+            }
+            try {
+                const response = await fetch(`/api/search?q=${encodeURIComponent(q)}`, { credentials: 'include' });
+                if (!response.ok) return;
+                const data = await response.json();
+                if (this.query.trim() !== q) return;
+                this.results = data.results || [];
+                this.open = true;
+            } catch (e) {
+                console.error('Search failed', e);
+            }
+        }
+    }));
+};
+
+if (window.Alpine) {
+    registerSearch();
+} else {
+    document.addEventListener('alpine:init', registerSearch);
+}
+
+/** Workload report: inline weekly capacity editor (hours in the field, minutes on the wire). */
+const registerWorkload = () => {
+    Alpine.data('workloadCapacity', (teamId, userId, hours) => ({
+        hours,
+        error: null,
+        async save() {
+            this.error = null;
+            const minutes = Math.round(parseFloat(this.hours) * 60);
+            if (!Number.isFinite(minutes) || minutes < 0) {
+                this.error = 'Enter a number of hours';
+                return;
+            }
+            try {
+                const res = await fetch(`/api/teams/${teamId}/members/${userId}`, {
+                    method: 'PATCH',
+                    headers: { 'Content-Type': 'application/json', 'X-CSRF-Token': csrfToken() },
+                    body: JSON.stringify({ weekly_capacity_minutes: minutes }),
+                    credentials: 'same-origin'
+                });
+                if (!res.ok) {
+                    const data = await res.json().catch(() => ({}));
+                    throw new Error(data.error || 'Failed to update');
+                }
+                window.location.reload();
+            } catch (e) {
+                this.error = e.message || 'Failed to update capacity';
+            }
+        }
+    }));
+};
+
+if (window.Alpine) {
+    registerWorkload();
+} else {
+    document.addEventListener('alpine:init', registerWorkload);
+}
+
+/** Node drawer: time logged against the estimate, manual entries and the per-user running timer. */
+const registerTimeTracking = () => {
+    Alpine.data('nodeTimeTracking', (projectId, nodeId) => ({
+        nodeId,
+        entries: [],
+        actualMinutes: 0,
+        estimatedMinutes: null,
+        overEstimate: false,
+        timer: null,
+        logMinutes: '',
+        logNote: '',
+        error: null,
+        get timerRunningHere() {
+            return !!this.timer && this.timer.node_id === this.nodeId;
+        },
+        get timerRunningElsewhere() {
+            return !!this.timer && this.timer.node_id !== this.nodeId;
+        },
+        init() {
+            this.load(nodeId);
+        },
+        formatMinutes(total) {
+            const m = Number(total) || 0;
+            if (m === 0) return '0 min';
+            if (m < 60) return `${m} min`;
+            const h = Math.floor(m / 60);
+            const rest = m % 60;
+            return rest ? `${h} h ${rest} min` : `${h} h`;
+        },
+        async request(url, method = 'GET', body) {
+            const options = { method, credentials: 'same-origin', headers: {} };
+            if (method !== 'GET') options.headers['X-CSRF-Token'] = csrfToken();
+            if (body !== undefined) {
+                options.headers['Content-Type'] = 'application/json';
+                options.body = JSON.stringify(body);
+            }
+            const res = await fetch(url, options);
+            const data = await res.json().catch(() => ({}));
+            if (!res.ok) throw new Error(data.error || 'Request failed');
+            return data;
+        },
+        async load(id) {
+            if (!id) return;
+            this.nodeId = id;
+            this.error = null;
+            try {
+                const [time, timer] = await Promise.all([
+                    this.request(`/api/projects/${projectId}/nodes/${id}/time-entries`),
+                    this.request('/api/timer')
+                ]);
+                if (this.nodeId !== id) return;
+                this.entries = time.entries || [];
+                this.actualMinutes = time.actual_minutes;
+                this.estimatedMinutes = time.estimated_minutes;
+                this.overEstimate = time.over_estimate;
+                this.timer = timer.timer;
+            } catch (e) {
+                this.error = e.message;
+            }
+        },
+        async logTime() {
+            const minutes = parseInt(this.logMinutes, 10);
+            if (!Number.isFinite(minutes) || minutes <= 0) {
+                this.error = 'Enter a number of minutes';
+                return;
+            }
+            try {
+                await this.request(`/api/projects/${projectId}/nodes/${this.nodeId}/time-entries`, 'POST', {
+                    minutes,
+                    note: this.logNote
+                });
+                this.logMinutes = '';
+                this.logNote = '';
+                await this.load(this.nodeId);
+            } catch (e) {
+                this.error = e.message;
+            }
+        },
+        async startTimer() {
+            try {
+                const data = await this.request(`/api/projects/${projectId}/nodes/${this.nodeId}/timer`, 'POST');
+                this.timer = data.timer;
+            } catch (e) {
+                this.error = e.message;
+            }
+        },
+        async stopTimer() {
+            try {
+                await this.request('/api/timer/stop', 'POST');
+                await this.load(this.nodeId);
+            } catch (e) {
+                this.error = e.message;
+            }
+        }
+    }));
+};
+
+if (window.Alpine) {
+    registerTimeTracking();
+} else {
+    document.addEventListener('alpine:init', registerTimeTracking);
+}
+
+/** Node drawer: files attached to the task (list, upload, download and delete). */
+const registerAttachments = () => {
+    Alpine.data('nodeAttachments', (projectId, nodeId) => ({
+        nodeId,
+        attachments: [],
+        uploading: false,
+        error: null,
+        init() {
+            this.load(nodeId);
+        },
+        baseUrl(id) {
+            return `/api/projects/${projectId}/nodes/${id}/attachments`;
+        },
+        downloadUrl(attachment) {
+            return `${this.baseUrl(this.nodeId)}/${attachment.id}`;
+        },
+        formatSize(bytes) {
+            const n = Number(bytes) || 0;
+            if (n < 1024) return `${n} B`;
+            if (n < 1024 * 1024) return `${(n / 1024).toFixed(1)} KB`;
+            return `${(n / (1024 * 1024)).toFixed(1)} MB`;
+        },
+        async request(url, options = {}) {
+            const res = await fetch(url, { credentials: 'same-origin', ...options });
+            const data = await res.json().catch(() => ({}));
+            if (!res.ok) throw new Error(data.error || 'Request failed');
+            return data;
+        },
+        async load(id) {
+            if (!id) return;
+            this.nodeId = id;
+            this.error = null;
+            try {
+                const data = await this.request(this.baseUrl(id));
+                if (this.nodeId !== id) return;
+                this.attachments = data.attachments || [];
+            } catch (e) {
+                this.error = e.message;
+            }
+        },
+        async upload(event) {
+            const file = event.target.files && event.target.files[0];
+            if (!file) return;
+            const form = new FormData();
+            form.append('file', file);
+            this.uploading = true;
+            this.error = null;
+            try {
+                await this.request(this.baseUrl(this.nodeId), {
+                    method: 'POST',
+                    headers: { 'X-CSRF-Token': csrfToken() },
+                    body: form
+                });
+                await this.load(this.nodeId);
+            } catch (e) {
+                this.error = e.message;
+            } finally {
+                this.uploading = false;
+                event.target.value = '';
+            }
+        },
+        async remove(attachment) {
+            if (!confirm(`Delete ${attachment.filename}?`)) return;
+            try {
+                await this.request(this.downloadUrl(attachment), {
+                    method: 'DELETE',
+                    headers: { 'X-CSRF-Token': csrfToken() }
+                });
+                this.attachments = this.attachments.filter(a => a.id !== attachment.id);
+            } catch (e) {
+                this.error = e.message;
+            }
+        }
+    }));
+};
+
+if (window.Alpine) {
+    registerAttachments();
+} else {
+    document.addEventListener('alpine:init', registerAttachments);
+}
+
 console.log('Boardtask graph persistence active');
@@ -1,3 +1,5 @@
+use crate::app::{domain::SignupMode, features::auth::signup_policy};
+
 /// Centralized environment configuration.
 /// All env vars and defaults are defined here.
 #[derive(Debug, Clone)]
@@ -29,6 +31,35 @@ pub struct Config {
 
     /// SMTP password. Optional for some servers.
     pub smtp_pass: Option<String>,
+
+    /// Instance administrators (lowercased emails) allowed to see instance-wide pages like the email outbox.
+    /// From comma-separated ADMIN_EMAILS. Default: none
+    pub admin_emails: Vec<String>,
+
+    /// Days a trashed project can be restored before the purge job deletes it for good.
+    /// Default: 30
+    pub trash_retention_days: u32,
+    /// Days between an owner deleting an organization and it being purged; it can be cancelled
+    /// until then. Default: 14
+    pub org_deletion_grace_days: u32,
+
+    /// Nameserver (host:port) used to look up domain verification TXT records.
+    /// Default: 1.1.1.1:53
+    pub dns_server: String,
+
+    /// Who may create accounts: "open", "invite_only" or "closed". Instance admins can override
+    /// this from /app/admin/signup. Default: open
+    pub signup_mode: SignupMode,
+    /// Email domains (lowercased) new accounts must belong to; empty allows any domain.
+    /// From comma-separated SIGNUP_ALLOWED_DOMAINS. Default: none
+    pub signup_allowed_domains: Vec<String>,
+
+    /// Directory node attachments are stored in.
+    /// Default: ./data/attachments
+    pub attachments_dir: String,
+    /// Largest attachment accepted, in bytes.
+    /// Default: 10485760 (10 MiB)
+    pub attachment_max_bytes: usize,
 }
 
 impl Config {
@@ -54,6 +85,34 @@ impl Config {
             .map_err(|_| "SMTP_PORT must be a valid port number")?;
         let smtp_user = std::env::var("SMTP_USER").ok();
         let smtp_pass = std::env::var("SMTP_PASS").ok();
+        let admin_emails = std::env::var("ADMIN_EMAILS")
+            .map(|v| parse_email_list(&v))
+            .unwrap_or_default();
+        let trash_retention_days = std::env::var("TRASH_RETENTION_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<u32>()
+            .map_err(|_| "TRASH_RETENTION_DAYS must be a whole number of days")?;
+        let org_deletion_grace_days = std::env::var("ORG_DELETION_GRACE_DAYS")
+            .unwrap_or_else(|_| "14".to_string())
+            .parse::<u32>()
+            .map_err(|_| "ORG_DELETION_GRACE_DAYS must be a whole number of days")?;
+        let dns_server = std::env::var("DNS_SERVER")
+            .unwrap_or_else(|_| "1.1.1.1:53".to_string());
+        let signup_mode = std::env::var("SIGNUP_MODE")
+            .unwrap_or_else(|_| "open".to_string())
+            .parse::<SignupMode>()
+            .map_err(|_| "SIGNUP_MODE must be open, invite_only or closed")?;
+        let signup_allowed_domains = match std::env::var("SIGNUP_ALLOWED_DOMAINS") {
+            Ok(v) => signup_policy::parse_allowed_domains(&v)
+                .map_err(|entry| format!("SIGNUP_ALLOWED_DOMAINS has an invalid domain: {}", entry))?,
+            Err(_) => Vec::new(),
+        };
+        let attachments_dir = std::env::var("ATTACHMENTS_DIR")
+            .unwrap_or_else(|_| "./data/attachments".to_string());
+        let attachment_max_bytes = std::env::var("ATTACHMENT_MAX_BYTES")
+            .unwrap_or_else(|_| "10485760".to_string())
+            .parse::<usize>()
+            .map_err(|_| "ATTACHMENT_MAX_BYTES must be a whole number of bytes")?;
 
         Ok(Self {
             database_url,
@@ -64,6 +123,14 @@ impl Config {
             smtp_port,
             smtp_user,
             smtp_pass,
+            admin_emails,
+            trash_retention_days,
+            org_deletion_grace_days,
+            dns_server,
+            signup_mode,
+            signup_allowed_domains,
+            attachments_dir,
+            attachment_max_bytes,
         })
     }
 
@@ -72,6 +139,12 @@ impl Config {
         self.app_url.trim_end_matches('/')
     }
 
+    /// Whether the email belongs to an instance administrator (case-insensitive).
+    pub fn is_admin_email(&self, email: &str) -> bool {
+        let email = email.trim().to_lowercase();
+        self.admin_emails.contains(&email)
+    }
+
     /// Config for tests. Uses in-memory database URL and console mailer.
     pub fn for_tests() -> Self {
         Self {
@@ -83,6 +156,23 @@ impl Config {
             smtp_port: 587,
             smtp_user: None,
             smtp_pass: None,
+            admin_emails: Vec::new(),
+            trash_retention_days: 30,
+            org_deletion_grace_days: 14,
+            dns_server: "127.0.0.1:53".to_string(),
+            signup_mode: SignupMode::Open,
+            signup_allowed_domains: Vec::new(),
+            attachments_dir: "./data/attachments".to_string(),
+            attachment_max_bytes: 1024 * 1024,
         }
     }
 }
+
+/// Split a comma-separated env value into trimmed, lowercased, non-empty email addresses.
+fn parse_email_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
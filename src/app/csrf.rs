@@ -0,0 +1,158 @@
+//! CSRF protection for cookie-authenticated writes.
+//!
+//! **Rule**: Every state-changing request that carries a valid `session_id` cookie must echo the
+//! session's synchronizer token, either in the `X-CSRF-Token` header (fetch from `app.js`) or in
+//! a `csrf_token` form field (Askama forms). Requests without a session fall through so the
+//! handler's own auth check rejects them as usual.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use axum_extra::extract::cookie::CookieJar;
+use serde_json::json;
+
+use crate::app::{db, AppState};
+
+/// Header carrying the token for JSON/fetch requests.
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+/// Hidden form field carrying the token for HTML form posts.
+pub const CSRF_FORM_FIELD: &str = "csrf_token";
+
+/// Max form body buffered while looking for the token (forms are small; uploads use the header).
+const MAX_FORM_BYTES: usize = 1024 * 1024;
+
+/// Middleware: reject unsafe requests from an authenticated session unless the CSRF token matches.
+pub async fn require_csrf_token(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_safe_method(request.method()) {
+        return next.run(request).await;
+    }
+
+    let jar = CookieJar::from_headers(request.headers());
+    let Some(session_id) = jar.get("session_id").map(|c| c.value().to_string()) else {
+        return next.run(request).await;
+    };
+    let session = match db::sessions::find_valid(&state.db, &session_id).await {
+        Ok(Some(s)) => s,
+        _ => return next.run(request).await,
+    };
+
+    let is_api = request.uri().path().starts_with("/api/");
+    let header_token = request
+        .headers()
+        .get(CSRF_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let (request, submitted) = match header_token {
+        Some(token) => (request, Some(token)),
+        None if is_form_request(&request) => match token_from_form(request).await {
+            Some(pair) => pair,
+            None => return rejection(is_api),
+        },
+        None => (request, None),
+    };
+
+    match submitted {
+        Some(token) if tokens_match(&token, &session.csrf_token) => next.run(request).await,
+        _ => rejection(is_api),
+    }
+}
+
+fn is_safe_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE)
+}
+
+fn is_form_request(request: &Request) -> bool {
+    request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.starts_with("application/x-www-form-urlencoded"))
+        .unwrap_or(false)
+}
+
+/// Buffer the urlencoded body, pull out `csrf_token`, and rebuild the request for the handler.
+/// Returns None when the body cannot be read.
+async fn token_from_form(request: Request) -> Option<(Request, Option<String>)> {
+    let (parts, body) = request.into_parts();
+    let bytes = to_bytes(body, MAX_FORM_BYTES).await.ok()?;
+    let token = form_field(&bytes, CSRF_FORM_FIELD);
+    Some((Request::from_parts(parts, Body::from(bytes)), token))
+}
+
+/// Find a single field in an `application/x-www-form-urlencoded` body.
+fn form_field(body: &[u8], name: &str) -> Option<String> {
+    let body = std::str::from_utf8(body).ok()?;
+    body.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        if key != name {
+            return None;
+        }
+        urlencoding::decode(&value.replace('+', " "))
+            .ok()
+            .map(|v| v.into_owned())
+    })
+}
+
+/// Constant-time comparison so the token can't be recovered byte-by-byte through timing.
+fn tokens_match(submitted: &str, expected: &str) -> bool {
+    if expected.is_empty() || submitted.len() != expected.len() {
+        return false;
+    }
+    submitted
+        .bytes()
+        .zip(expected.bytes())
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+        == 0
+}
+
+fn rejection(is_api: bool) -> Response {
+    if is_api {
+        (
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "Invalid CSRF token" })),
+        )
+            .into_response()
+    } else {
+        (StatusCode::FORBIDDEN, "Invalid CSRF token").into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn form_field_finds_token_among_other_fields() {
+        let body = b"title=My+Project&csrf_token=abc123&team_id=t1";
+        assert_eq!(form_field(body, "csrf_token").as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn form_field_decodes_percent_encoding() {
+        let body = b"csrf_token=a%2Bb";
+        assert_eq!(form_field(body, "csrf_token").as_deref(), Some("a+b"));
+    }
+
+    #[test]
+    fn form_field_missing_returns_none() {
+        assert_eq!(form_field(b"title=x", "csrf_token"), None);
+    }
+
+    #[test]
+    fn tokens_match_requires_exact_non_empty_value() {
+        assert!(tokens_match("abcd", "abcd"));
+        assert!(!tokens_match("abce", "abcd"));
+        assert!(!tokens_match("abc", "abcd"));
+        assert!(!tokens_match("", ""));
+    }
+}
@@ -0,0 +1,167 @@
+//! Node attachments and the content-addressed blobs they point at. The bytes themselves live in
+//! `crate::app::storage`; a blob row exists for every stored blob.
+
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// Database row for node_attachments table.
+#[derive(Clone, Debug, FromRow, serde::Serialize, serde::Deserialize)]
+pub struct Attachment {
+    pub id: String,
+    pub node_id: String,
+    pub content_hash: String,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub uploaded_by_user_id: Option<String>,
+    pub created_at: i64,
+}
+
+/// Data structure for inserting a new attachment.
+pub struct NewAttachment {
+    pub id: String,
+    pub node_id: String,
+    pub content_hash: String,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub uploaded_by_user_id: Option<String>,
+}
+
+const COLUMNS: &str =
+    "id, node_id, content_hash, filename, content_type, size_bytes, uploaded_by_user_id, created_at";
+
+/// Record a blob, unless one with the same hash is already recorded.
+pub async fn insert_blob<'e, E>(executor: E, content_hash: &str, size_bytes: i64) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query("INSERT OR IGNORE INTO attachment_blobs (content_hash, size_bytes, created_at) VALUES (?, ?, ?)")
+        .bind(content_hash)
+        .bind(size_bytes)
+        .bind(now)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Whether a blob with this hash is recorded.
+pub async fn blob_exists<'e, E>(executor: E, content_hash: &str) -> Result<bool, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let found: Option<i64> = sqlx::query_scalar("SELECT 1 FROM attachment_blobs WHERE content_hash = ?")
+        .bind(content_hash)
+        .fetch_optional(executor)
+        .await?;
+    Ok(found.is_some())
+}
+
+/// Record that a node deletion's undo record needs this blob kept.
+pub async fn insert_deletion_hold<'e, E>(executor: E, deletion_id: &str, content_hash: &str) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("INSERT OR IGNORE INTO node_deletion_blobs (deletion_id, content_hash) VALUES (?, ?)")
+        .bind(deletion_id)
+        .bind(content_hash)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Hashes and sizes of blobs no attachment uses and no pending node deletion could bring back.
+pub async fn find_orphaned_blobs(pool: &sqlx::SqlitePool) -> Result<Vec<(String, i64)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT b.content_hash, b.size_bytes FROM attachment_blobs b WHERE NOT EXISTS (SELECT 1 FROM node_attachments a WHERE a.content_hash = b.content_hash) AND NOT EXISTS (SELECT 1 FROM node_deletion_blobs h WHERE h.content_hash = b.content_hash)",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Delete a blob row if it is still orphaned. Returns whether it was deleted, i.e. whether the
+/// stored bytes may now be removed.
+pub async fn delete_blob_if_orphaned(pool: &sqlx::SqlitePool, content_hash: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM attachment_blobs WHERE content_hash = ? AND NOT EXISTS (SELECT 1 FROM node_attachments a WHERE a.content_hash = attachment_blobs.content_hash) AND NOT EXISTS (SELECT 1 FROM node_deletion_blobs h WHERE h.content_hash = attachment_blobs.content_hash)",
+    )
+    .bind(content_hash)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a new attachment. Its blob must already be recorded.
+pub async fn insert<'e, E>(executor: E, attachment: &NewAttachment) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query(
+        "INSERT INTO node_attachments (id, node_id, content_hash, filename, content_type, size_bytes, uploaded_by_user_id, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&attachment.id)
+    .bind(&attachment.node_id)
+    .bind(&attachment.content_hash)
+    .bind(&attachment.filename)
+    .bind(&attachment.content_type)
+    .bind(attachment.size_bytes)
+    .bind(&attachment.uploaded_by_user_id)
+    .bind(now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Re-insert an attachment removed with its node, keeping its original timestamp.
+pub async fn insert_restored<'e, E>(executor: E, attachment: &Attachment) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO node_attachments (id, node_id, content_hash, filename, content_type, size_bytes, uploaded_by_user_id, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&attachment.id)
+    .bind(&attachment.node_id)
+    .bind(&attachment.content_hash)
+    .bind(&attachment.filename)
+    .bind(&attachment.content_type)
+    .bind(attachment.size_bytes)
+    .bind(&attachment.uploaded_by_user_id)
+    .bind(attachment.created_at)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Find an attachment by ID.
+pub async fn find_by_id(pool: &sqlx::SqlitePool, id: &str) -> Result<Option<Attachment>, sqlx::Error> {
+    sqlx::query_as::<_, Attachment>(&format!("SELECT {} FROM node_attachments WHERE id = ?", COLUMNS))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// A node's attachments, oldest first.
+pub async fn find_by_node<'e, E>(executor: E, node_id: &str) -> Result<Vec<Attachment>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_as::<_, Attachment>(&format!(
+        "SELECT {} FROM node_attachments WHERE node_id = ? ORDER BY created_at, id",
+        COLUMNS
+    ))
+    .bind(node_id)
+    .fetch_all(executor)
+    .await
+}
+
+/// Delete an attachment. Its blob is left for [`find_orphaned_blobs`] to pick up.
+pub async fn delete(pool: &sqlx::SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM node_attachments WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
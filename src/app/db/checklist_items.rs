@@ -0,0 +1,195 @@
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// Database row for node_checklist_items table.
+#[derive(Clone, Debug, FromRow, serde::Serialize, serde::Deserialize)]
+pub struct ChecklistItem {
+    pub id: String,
+    pub node_id: String,
+    pub text: String,
+    pub done: bool,
+    pub assigned_user_id: Option<String>,
+    pub sort_order: i64,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+}
+
+/// Data structure for inserting a new checklist item.
+pub struct NewChecklistItem {
+    pub id: String,
+    pub node_id: String,
+    pub text: String,
+    pub done: bool,
+    pub assigned_user_id: Option<String>,
+    pub sort_order: i64,
+}
+
+/// Done and total item counts for one node.
+#[derive(Debug, Clone, FromRow, serde::Serialize)]
+pub struct ChecklistProgress {
+    pub node_id: String,
+    pub done: i64,
+    pub total: i64,
+}
+
+const COLUMNS: &str = "id, node_id, text, done, assigned_user_id, sort_order, created_at, updated_at";
+
+/// Insert a new checklist item.
+pub async fn insert<'e, E>(executor: E, item: &NewChecklistItem) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query(
+        "INSERT INTO node_checklist_items (id, node_id, text, done, assigned_user_id, sort_order, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&item.id)
+    .bind(&item.node_id)
+    .bind(&item.text)
+    .bind(item.done)
+    .bind(&item.assigned_user_id)
+    .bind(item.sort_order)
+    .bind(now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Re-insert an item removed with its node, keeping its timestamps.
+pub async fn insert_restored<'e, E>(executor: E, item: &ChecklistItem) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO node_checklist_items (id, node_id, text, done, assigned_user_id, sort_order, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&item.id)
+    .bind(&item.node_id)
+    .bind(&item.text)
+    .bind(item.done)
+    .bind(&item.assigned_user_id)
+    .bind(item.sort_order)
+    .bind(item.created_at)
+    .bind(item.updated_at)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Find a checklist item by ID.
+pub async fn find_by_id(pool: &sqlx::SqlitePool, id: &str) -> Result<Option<ChecklistItem>, sqlx::Error> {
+    sqlx::query_as::<_, ChecklistItem>(&format!("SELECT {} FROM node_checklist_items WHERE id = ?", COLUMNS))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// A node's items in checklist order.
+pub async fn find_by_node<'e, E>(executor: E, node_id: &str) -> Result<Vec<ChecklistItem>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_as::<_, ChecklistItem>(&format!(
+        "SELECT {} FROM node_checklist_items WHERE node_id = ? ORDER BY sort_order, created_at, id",
+        COLUMNS
+    ))
+    .bind(node_id)
+    .fetch_all(executor)
+    .await
+}
+
+/// Items of every node in a project, grouped by node and in checklist order.
+pub async fn find_by_project(pool: &sqlx::SqlitePool, project_id: &str) -> Result<Vec<ChecklistItem>, sqlx::Error> {
+    sqlx::query_as::<_, ChecklistItem>(
+        "SELECT c.id, c.node_id, c.text, c.done, c.assigned_user_id, c.sort_order, c.created_at, c.updated_at FROM node_checklist_items c INNER JOIN nodes n ON n.id = c.node_id WHERE n.project_id = ? ORDER BY c.node_id, c.sort_order, c.created_at, c.id",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Done/total counts for the nodes of a project that have a checklist.
+pub async fn progress_by_project(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+) -> Result<Vec<ChecklistProgress>, sqlx::Error> {
+    sqlx::query_as::<_, ChecklistProgress>(
+        "SELECT c.node_id, SUM(c.done) AS done, COUNT(*) AS total FROM node_checklist_items c INNER JOIN nodes n ON n.id = c.node_id WHERE n.project_id = ? GROUP BY c.node_id",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Number of items on a node.
+pub async fn count_by_node(pool: &sqlx::SqlitePool, node_id: &str) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM node_checklist_items WHERE node_id = ?")
+        .bind(node_id)
+        .fetch_one(pool)
+        .await
+}
+
+/// Sort order that puts a new item after the node's existing items.
+pub async fn next_sort_order<'e, E>(executor: E, node_id: &str) -> Result<i64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_scalar("SELECT COALESCE(MAX(sort_order) + 1, 0) FROM node_checklist_items WHERE node_id = ?")
+        .bind(node_id)
+        .fetch_one(executor)
+        .await
+}
+
+/// Update an item's text, done flag, assignee and position.
+pub async fn update(
+    pool: &sqlx::SqlitePool,
+    id: &str,
+    text: &str,
+    done: bool,
+    assigned_user_id: Option<&str>,
+    sort_order: i64,
+) -> Result<(), sqlx::Error> {
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query(
+        "UPDATE node_checklist_items SET text = ?, done = ?, assigned_user_id = ?, sort_order = ?, updated_at = ? WHERE id = ?",
+    )
+    .bind(text)
+    .bind(done)
+    .bind(assigned_user_id)
+    .bind(sort_order)
+    .bind(now)
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Delete a checklist item.
+pub async fn delete(pool: &sqlx::SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM node_checklist_items WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Unassign a user from every checklist item in an organisation's projects.
+pub async fn unassign_user_in_org<'e, E>(
+    executor: E,
+    organization_id: &str,
+    user_id: &str,
+) -> Result<u64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let result = sqlx::query(
+        "UPDATE node_checklist_items SET assigned_user_id = NULL, updated_at = ? WHERE assigned_user_id = ? AND node_id IN (SELECT n.id FROM nodes n INNER JOIN projects p ON p.id = n.project_id WHERE p.organization_id = ?)",
+    )
+    .bind(now)
+    .bind(user_id)
+    .bind(organization_id)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,61 @@
+//! Per-user, per-project state captured when a digest is sent, compared on the next send.
+
+/// Node IDs that were blocked at the user's previous digest for this project, or None if no
+/// digest has covered the project yet.
+pub async fn find_blocked_node_ids(
+    pool: &sqlx::SqlitePool,
+    user_id: &str,
+    project_id: &str,
+) -> Result<Option<Vec<String>>, sqlx::Error> {
+    let exists: Option<i64> = sqlx::query_scalar(
+        "SELECT 1 FROM digest_project_snapshots WHERE user_id = ? AND project_id = ?",
+    )
+    .bind(user_id)
+    .bind(project_id)
+    .fetch_optional(pool)
+    .await?;
+    if exists.is_none() {
+        return Ok(None);
+    }
+    let ids = sqlx::query_scalar(
+        "SELECT node_id FROM digest_blocked_nodes WHERE user_id = ? AND project_id = ?",
+    )
+    .bind(user_id)
+    .bind(project_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(Some(ids))
+}
+
+/// Record the nodes blocked in this digest, replacing the previous snapshot.
+pub async fn upsert(
+    conn: &mut sqlx::SqliteConnection,
+    user_id: &str,
+    project_id: &str,
+    blocked_node_ids: &[String],
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO digest_project_snapshots (user_id, project_id, blocked_count, created_at) VALUES (?, ?, ?, ?) ON CONFLICT(user_id, project_id) DO UPDATE SET blocked_count = excluded.blocked_count, created_at = excluded.created_at",
+    )
+    .bind(user_id)
+    .bind(project_id)
+    .bind(blocked_node_ids.len() as i64)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query("DELETE FROM digest_blocked_nodes WHERE user_id = ? AND project_id = ?")
+        .bind(user_id)
+        .bind(project_id)
+        .execute(&mut *conn)
+        .await?;
+    for node_id in blocked_node_ids {
+        sqlx::query("INSERT INTO digest_blocked_nodes (user_id, project_id, node_id) VALUES (?, ?, ?)")
+            .bind(user_id)
+            .bind(project_id)
+            .bind(node_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use sqlx::{FromRow, SqliteExecutor};
+use time::OffsetDateTime;
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_SENT: &str = "sent";
+pub const STATUS_DEAD: &str = "dead";
+
+/// Database row for email_outbox table.
+#[derive(Debug, Clone, FromRow)]
+pub struct OutboxEmail {
+    pub id: String,
+    pub to_email: String,
+    pub from_email: String,
+    pub subject: String,
+    pub body: String,
+    pub html_body: Option<String>,
+    pub status: String,
+    pub attempts: i64,
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub sent_at: Option<i64>,
+}
+
+/// Data structure for enqueueing a new email.
+pub struct NewOutboxEmail {
+    pub id: String,
+    pub to_email: String,
+    pub from_email: String,
+    pub subject: String,
+    pub body: String,
+    pub html_body: Option<String>,
+}
+
+const COLUMNS: &str = "id, to_email, from_email, subject, body, html_body, status, attempts, next_attempt_at, last_error, created_at, sent_at";
+
+/// Enqueue an email for immediate delivery.
+pub async fn insert<'e, E>(executor: E, email: &NewOutboxEmail) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query(
+        "INSERT INTO email_outbox (id, to_email, from_email, subject, body, html_body, status, attempts, next_attempt_at, created_at) VALUES (?, ?, ?, ?, ?, ?, 'pending', 0, ?, ?)",
+    )
+    .bind(&email.id)
+    .bind(&email.to_email)
+    .bind(&email.from_email)
+    .bind(&email.subject)
+    .bind(&email.body)
+    .bind(&email.html_body)
+    .bind(now)
+    .bind(now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Pending emails whose next attempt is due at `now`, oldest first.
+pub async fn find_due<'e, E>(executor: E, now: i64, limit: i64) -> Result<Vec<OutboxEmail>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, OutboxEmail>(&format!(
+        "SELECT {COLUMNS} FROM email_outbox WHERE status = 'pending' AND next_attempt_at <= ? ORDER BY next_attempt_at, created_at LIMIT ?"
+    ))
+    .bind(now)
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+}
+
+/// Find an outbox email by ID.
+pub async fn find_by_id<'e, E>(executor: E, id: &str) -> Result<Option<OutboxEmail>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, OutboxEmail>(&format!("SELECT {COLUMNS} FROM email_outbox WHERE id = ?"))
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+}
+
+/// Dead-lettered emails, most recent first (admin view).
+pub async fn find_dead<'e, E>(executor: E, limit: i64) -> Result<Vec<OutboxEmail>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, OutboxEmail>(&format!(
+        "SELECT {COLUMNS} FROM email_outbox WHERE status = 'dead' ORDER BY created_at DESC LIMIT ?"
+    ))
+    .bind(limit)
+    .fetch_all(executor)
+    .await
+}
+
+/// Count emails in a given status.
+pub async fn count_by_status<'e, E>(executor: E, status: &str) -> Result<i64, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM email_outbox WHERE status = ?")
+        .bind(status)
+        .fetch_one(executor)
+        .await
+}
+
+/// Record a successful delivery.
+pub async fn mark_sent<'e, E>(executor: E, id: &str, now: i64) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query(
+        "UPDATE email_outbox SET status = 'sent', attempts = attempts + 1, sent_at = ?, last_error = NULL WHERE id = ?",
+    )
+    .bind(now)
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Record a failed attempt and schedule the next one.
+pub async fn mark_retry<'e, E>(
+    executor: E,
+    id: &str,
+    next_attempt_at: i64,
+    error: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query(
+        "UPDATE email_outbox SET attempts = attempts + 1, next_attempt_at = ?, last_error = ? WHERE id = ?",
+    )
+    .bind(next_attempt_at)
+    .bind(error)
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Record a final failed attempt and stop retrying.
+pub async fn mark_dead<'e, E>(executor: E, id: &str, error: &str) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query(
+        "UPDATE email_outbox SET status = 'dead', attempts = attempts + 1, last_error = ? WHERE id = ?",
+    )
+    .bind(error)
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Move a dead-lettered email back to pending with a fresh attempt budget. Returns false if not dead.
+pub async fn requeue_dead<'e, E>(executor: E, id: &str) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let result = sqlx::query(
+        "UPDATE email_outbox SET status = 'pending', attempts = 0, next_attempt_at = ? WHERE id = ? AND status = 'dead'",
+    )
+    .bind(now)
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// Database row for labels table.
+#[derive(Debug, Clone, FromRow, serde::Serialize)]
+pub struct Label {
+    pub id: String,
+    pub organization_id: String,
+    pub name: String,
+    pub color: String,
+    pub created_at: i64,
+}
+
+/// Data structure for inserting a new label.
+pub struct NewLabel {
+    pub id: String,
+    pub organization_id: String,
+    pub name: String,
+    pub color: String,
+}
+
+/// One label on one node (row of node_labels).
+#[derive(Debug, Clone, FromRow, serde::Serialize)]
+pub struct NodeLabel {
+    pub node_id: String,
+    pub label_id: String,
+}
+
+/// Insert a new label.
+pub async fn insert<'e, E>(executor: E, label: &NewLabel) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query("INSERT INTO labels (id, organization_id, name, color, created_at) VALUES (?, ?, ?, ?, ?)")
+        .bind(&label.id)
+        .bind(&label.organization_id)
+        .bind(&label.name)
+        .bind(&label.color)
+        .bind(now)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Find a label by ID.
+pub async fn find_by_id(pool: &sqlx::SqlitePool, id: &str) -> Result<Option<Label>, sqlx::Error> {
+    sqlx::query_as::<_, Label>("SELECT id, organization_id, name, color, created_at FROM labels WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// All labels of an organization, by name.
+pub async fn find_by_organization<'e, E>(executor: E, organization_id: &str) -> Result<Vec<Label>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_as::<_, Label>(
+        "SELECT id, organization_id, name, color, created_at FROM labels WHERE organization_id = ? ORDER BY name",
+    )
+    .bind(organization_id)
+    .fetch_all(executor)
+    .await
+}
+
+/// Rename or recolor a label.
+pub async fn update(pool: &sqlx::SqlitePool, id: &str, name: &str, color: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE labels SET name = ?, color = ? WHERE id = ?")
+        .bind(name)
+        .bind(color)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Delete a label; it comes off every node through the cascade.
+pub async fn delete(pool: &sqlx::SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM labels WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Labels on every node of a project.
+pub async fn find_node_labels_by_project(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+) -> Result<Vec<NodeLabel>, sqlx::Error> {
+    sqlx::query_as::<_, NodeLabel>(
+        "SELECT nl.node_id, nl.label_id FROM node_labels nl INNER JOIN nodes n ON n.id = nl.node_id INNER JOIN labels l ON l.id = nl.label_id WHERE n.project_id = ? ORDER BY l.name",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Label IDs on one node, by label name.
+pub async fn find_ids_for_node<'e, E>(executor: E, node_id: &str) -> Result<Vec<String>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_scalar(
+        "SELECT nl.label_id FROM node_labels nl INNER JOIN labels l ON l.id = nl.label_id WHERE nl.node_id = ? ORDER BY l.name",
+    )
+    .bind(node_id)
+    .fetch_all(executor)
+    .await
+}
+
+/// Put a label on a node. Does nothing if it is already there or the label no longer exists.
+pub async fn add_to_node<'e, E>(executor: E, node_id: &str, label_id: &str) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT OR IGNORE INTO node_labels (node_id, label_id) SELECT ?, id FROM labels WHERE id = ?",
+    )
+    .bind(node_id)
+    .bind(label_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Take every label off a node.
+pub async fn clear_node<'e, E>(executor: E, node_id: &str) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("DELETE FROM node_labels WHERE node_id = ?")
+        .bind(node_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Replace a node's labels with `label_ids`, in one transaction.
+pub async fn set_for_node(pool: &sqlx::SqlitePool, node_id: &str, label_ids: &[String]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    clear_node(&mut *tx, node_id).await?;
+    for label_id in label_ids {
+        add_to_node(&mut *tx, node_id, label_id).await?;
+    }
+    tx.commit().await
+}
+
+/// Group node labels by node: node ID → label IDs.
+pub fn by_node(node_labels: &[NodeLabel]) -> HashMap<&str, Vec<&str>> {
+    let mut map: HashMap<&str, Vec<&str>> = HashMap::new();
+    for nl in node_labels {
+        map.entry(nl.node_id.as_str()).or_default().push(nl.label_id.as_str());
+    }
+    map
+}
@@ -1,18 +1,31 @@
 pub mod users;
 pub mod sessions;
+pub mod signup_policy;
+pub mod attachments;
+pub mod checklist_items;
 pub mod email_verification;
 pub mod password_reset;
+pub mod project_snapshots;
 pub mod projects;
+pub mod organization_domains;
 pub mod organization_invites;
+pub mod organization_invite_links;
+pub mod organization_ownership_transfers;
 pub mod organizations;
 pub mod node_types;
 pub mod nodes;
+pub mod node_deletions;
 pub mod node_edges;
 pub mod project_slots;
 pub mod task_statuses;
 pub mod integrations;
+pub mod labels;
 pub mod teams;
 pub mod team_members;
+pub mod email_outbox;
+pub mod digest_snapshots;
+pub mod node_search;
+pub mod time_entries;
 
 pub use project_slots::{NewProjectSlot, ProjectSlot};
 pub use teams::{Team, NewTeam};
@@ -0,0 +1,72 @@
+//! Undo records for deleted nodes. `snapshot` is JSON owned by `features::graph::delete_node`.
+
+use sqlx::FromRow;
+
+/// Database row for node_deletions table.
+#[derive(Debug, FromRow)]
+pub struct NodeDeletion {
+    pub id: String,
+    pub project_id: String,
+    pub node_id: String,
+    pub deleted_by: Option<String>,
+    pub snapshot: String,
+    pub created_at: i64,
+}
+
+/// Record a node deletion.
+pub async fn insert<'e, E>(executor: E, deletion: &NodeDeletion) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO node_deletions (id, project_id, node_id, deleted_by, snapshot, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&deletion.id)
+    .bind(&deletion.project_id)
+    .bind(&deletion.node_id)
+    .bind(&deletion.deleted_by)
+    .bind(&deletion.snapshot)
+    .bind(deletion.created_at)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// The most recent deletion of a node in a project.
+pub async fn find_latest_for_node<'e, E>(
+    executor: E,
+    project_id: &str,
+    node_id: &str,
+) -> Result<Option<NodeDeletion>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_as::<_, NodeDeletion>(
+        "SELECT id, project_id, node_id, deleted_by, snapshot, created_at FROM node_deletions WHERE project_id = ? AND node_id = ? ORDER BY created_at DESC, id DESC LIMIT 1",
+    )
+    .bind(project_id)
+    .bind(node_id)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Delete a deletion record (once it has been undone).
+pub async fn delete<'e, E>(executor: E, id: &str) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("DELETE FROM node_deletions WHERE id = ?")
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Drop deletion records older than `cutoff`; those deletions can no longer be undone.
+pub async fn delete_older_than(pool: &sqlx::SqlitePool, cutoff: i64) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM node_deletions WHERE created_at < ?")
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
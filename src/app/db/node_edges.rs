@@ -38,26 +38,37 @@ where
 }
 
 /// Insert a new node edge into the database, ignoring the insert when the edge
-/// already exists (no error is returned in that case).
+/// already exists (no error is returned in that case). Returns whether a row was added.
 pub async fn insert_if_not_exists<'e, E>(
     executor: E,
     edge: &NewNodeEdge,
-) -> Result<(), sqlx::Error>
+) -> Result<bool, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
 {
     let now = OffsetDateTime::now_utc().unix_timestamp();
+    insert_if_not_exists_at(executor, edge, now).await
+}
 
-    sqlx::query(
+/// Like [`insert_if_not_exists`], stamping the edge with the given `created_at`.
+pub async fn insert_if_not_exists_at<'e, E>(
+    executor: E,
+    edge: &NewNodeEdge,
+    created_at: i64,
+) -> Result<bool, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let result = sqlx::query(
         "INSERT OR IGNORE INTO node_edges (parent_id, child_id, created_at) VALUES (?, ?, ?)",
     )
     .bind(&edge.parent_id)
     .bind(&edge.child_id)
-    .bind(now)
+    .bind(created_at)
     .execute(executor)
     .await?;
 
-    Ok(())
+    Ok(result.rows_affected() > 0)
 }
 
 /// Delete a node edge by parent and child IDs using a pooled connection.
@@ -97,6 +108,28 @@ where
     Ok(())
 }
 
+/// Delete a node edge only if it still carries `created_at`, i.e. it was not removed and re-added since.
+pub async fn delete_if_created_at<'e, E>(
+    executor: E,
+    parent_id: &str,
+    child_id: &str,
+    created_at: i64,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "DELETE FROM node_edges WHERE parent_id = ? AND child_id = ? AND created_at = ?",
+    )
+    .bind(parent_id)
+    .bind(child_id)
+    .bind(created_at)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
 /// Find all child IDs for a given parent node.
 pub async fn find_children_of(
     pool: &sqlx::SqlitePool,
@@ -0,0 +1,39 @@
+//! Full-text search over node titles and descriptions (`nodes_fts`).
+
+use sqlx::FromRow;
+
+/// Marks the start of a matched term in [`SearchHit::snippet`].
+pub const MATCH_START: char = '\u{2}';
+/// Marks the end of a matched term in [`SearchHit::snippet`].
+pub const MATCH_END: char = '\u{3}';
+
+/// A node matching a search, with its project and status.
+#[derive(Debug, FromRow)]
+pub struct SearchHit {
+    pub node_id: String,
+    pub title: String,
+    pub project_id: String,
+    pub project_title: String,
+    pub status_id: String,
+    pub status_name: String,
+    /// Excerpt around the match, with terms wrapped in [`MATCH_START`] / [`MATCH_END`].
+    pub snippet: String,
+}
+
+/// Search nodes in an organisation's projects, best match first.
+/// `fts_query` must already be valid FTS5 query syntax.
+pub async fn search_in_org(
+    pool: &sqlx::SqlitePool,
+    organization_id: &str,
+    fts_query: &str,
+    limit: i64,
+) -> Result<Vec<SearchHit>, sqlx::Error> {
+    sqlx::query_as::<_, SearchHit>(
+        "SELECT n.id AS node_id, n.title, n.project_id, p.title AS project_title, n.status_id, COALESCE(s.name, '') AS status_name, snippet(nodes_fts, -1, char(2), char(3), '…', 12) AS snippet FROM nodes_fts INNER JOIN nodes n ON n.id = nodes_fts.node_id INNER JOIN projects p ON p.id = n.project_id LEFT JOIN task_statuses s ON s.id = n.status_id WHERE nodes_fts MATCH ? AND p.organization_id = ? AND p.deleted_at IS NULL ORDER BY bm25(nodes_fts, 0.0, 10.0, 1.0) LIMIT ?",
+    )
+    .bind(fts_query)
+    .bind(organization_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
@@ -1,8 +1,10 @@
 use sqlx::FromRow;
 use time::OffsetDateTime;
 
+use super::task_statuses;
+
 /// Database row for nodes table.
-#[derive(Clone, Debug, FromRow, serde::Serialize)]
+#[derive(Clone, Debug, FromRow, serde::Serialize, serde::Deserialize)]
 pub struct Node {
     pub id: String,
     pub project_id: String,
@@ -16,6 +18,10 @@ pub struct Node {
     pub slot_id: Option<String>,
     pub parent_id: Option<String>,
     pub assigned_user_id: Option<String>,
+    /// When the node last moved to Done; None while it isn't done. Absent in undo snapshots from
+    /// before completion tracking.
+    #[serde(default)]
+    pub completed_at: Option<i64>,
 }
 
 /// Data structure for inserting a new node.
@@ -32,7 +38,7 @@ pub struct NewNode {
     pub assigned_user_id: Option<String>,
 }
 
-/// Insert a new node into the database.
+/// Insert a new node into the database. A node created as Done counts as completed now.
 pub async fn insert<'e, E>(
     executor: E,
     node: &NewNode,
@@ -41,9 +47,10 @@ where
     E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
 {
     let now = OffsetDateTime::now_utc().unix_timestamp();
+    let completed_at = (node.status_id == task_statuses::DONE_STATUS_ID).then_some(now);
 
     sqlx::query(
-        "INSERT INTO nodes (id, project_id, node_type_id, status_id, title, description, created_at, estimated_minutes, slot_id, parent_id, assigned_user_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO nodes (id, project_id, node_type_id, status_id, title, description, created_at, estimated_minutes, slot_id, parent_id, assigned_user_id, completed_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&node.id)
     .bind(&node.project_id)
@@ -56,6 +63,37 @@ where
     .bind(&node.slot_id)
     .bind(&node.parent_id)
     .bind(&node.assigned_user_id)
+    .bind(completed_at)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Re-insert a previously deleted node exactly as it was, keeping its timestamps.
+pub async fn insert_restored<'e, E>(
+    executor: E,
+    node: &Node,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO nodes (id, project_id, node_type_id, status_id, title, description, created_at, updated_at, estimated_minutes, slot_id, parent_id, assigned_user_id, completed_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&node.id)
+    .bind(&node.project_id)
+    .bind(&node.node_type_id)
+    .bind(&node.status_id)
+    .bind(&node.title)
+    .bind(&node.description)
+    .bind(node.created_at)
+    .bind(node.updated_at)
+    .bind(node.estimated_minutes)
+    .bind(&node.slot_id)
+    .bind(&node.parent_id)
+    .bind(&node.assigned_user_id)
+    .bind(node.completed_at)
     .execute(executor)
     .await?;
 
@@ -86,34 +124,110 @@ pub async fn count_by_project_and_status(
         .await
 }
 
+/// Count nodes in a project that moved to Done after `since` and are still done.
+pub async fn count_completed_since(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    since: i64,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM nodes WHERE project_id = ? AND status_id = ? AND completed_at > ?")
+        .bind(project_id)
+        .bind(task_statuses::DONE_STATUS_ID)
+        .bind(since)
+        .fetch_one(pool)
+        .await
+}
+
 /// Find all nodes for a project.
 pub async fn find_by_project(
     pool: &sqlx::SqlitePool,
     project_id: &str,
 ) -> Result<Vec<Node>, sqlx::Error> {
     sqlx::query_as::<_, Node>(
-        "SELECT id, project_id, node_type_id, status_id, title, description, created_at, updated_at, estimated_minutes, slot_id, parent_id, assigned_user_id FROM nodes WHERE project_id = ? ORDER BY created_at",
+        "SELECT id, project_id, node_type_id, status_id, title, description, created_at, updated_at, estimated_minutes, slot_id, parent_id, assigned_user_id, completed_at FROM nodes WHERE project_id = ? ORDER BY created_at",
     )
     .bind(project_id)
     .fetch_all(pool)
     .await
 }
 
-/// Find a node by ID.
-pub async fn find_by_id(
+/// Find task nodes in an organisation's projects assigned to a user, either directly or (when the
+/// node has no assignee) through its slot's assignee. Group nodes are excluded.
+pub async fn find_assigned_in_org(
+    pool: &sqlx::SqlitePool,
+    organization_id: &str,
+    user_id: &str,
+) -> Result<Vec<Node>, sqlx::Error> {
+    sqlx::query_as::<_, Node>(
+        "SELECT n.id, n.project_id, n.node_type_id, n.status_id, n.title, n.description, n.created_at, n.updated_at, n.estimated_minutes, n.slot_id, n.parent_id, n.assigned_user_id, n.completed_at FROM nodes n INNER JOIN projects p ON p.id = n.project_id LEFT JOIN project_slots s ON s.id = n.slot_id WHERE p.organization_id = ? AND p.archived_at IS NULL AND p.deleted_at IS NULL AND COALESCE(n.assigned_user_id, s.assigned_user_id) = ? AND NOT EXISTS (SELECT 1 FROM nodes c WHERE c.parent_id = n.id) ORDER BY n.created_at",
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Find the most recently changed task nodes in an organisation's projects on teams the user
+/// belongs to, newest first. Nodes never updated count from their creation time.
+pub async fn find_recently_updated_for_team_member(
+    pool: &sqlx::SqlitePool,
+    organization_id: &str,
+    user_id: &str,
+    limit: i64,
+) -> Result<Vec<Node>, sqlx::Error> {
+    sqlx::query_as::<_, Node>(
+        "SELECT n.id, n.project_id, n.node_type_id, n.status_id, n.title, n.description, n.created_at, n.updated_at, n.estimated_minutes, n.slot_id, n.parent_id, n.assigned_user_id, n.completed_at FROM nodes n INNER JOIN projects p ON p.id = n.project_id INNER JOIN team_members tm ON tm.team_id = p.team_id AND tm.user_id = ? WHERE p.organization_id = ? AND p.archived_at IS NULL AND p.deleted_at IS NULL AND NOT EXISTS (SELECT 1 FROM nodes c WHERE c.parent_id = n.id) ORDER BY COALESCE(n.updated_at, n.created_at) DESC, n.id DESC LIMIT ?",
+    )
+    .bind(user_id)
+    .bind(organization_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Open (not done) task work for one assignee; `user_id` is None for unassigned work.
+#[derive(Debug, FromRow)]
+pub struct AssigneeLoad {
+    pub user_id: Option<String>,
+    pub open_count: i64,
+    pub open_minutes: i64,
+}
+
+/// Sum open task estimates per assignee across an organisation's projects. The assignee is the
+/// node's own, falling back to its slot's. Nodes without an estimate count but add no minutes.
+pub async fn open_load_by_assignee_in_org(
     pool: &sqlx::SqlitePool,
+    organization_id: &str,
+    done_status_id: &str,
+) -> Result<Vec<AssigneeLoad>, sqlx::Error> {
+    sqlx::query_as::<_, AssigneeLoad>(
+        "SELECT COALESCE(n.assigned_user_id, s.assigned_user_id) AS user_id, COUNT(*) AS open_count, COALESCE(SUM(n.estimated_minutes), 0) AS open_minutes FROM nodes n INNER JOIN projects p ON p.id = n.project_id LEFT JOIN project_slots s ON s.id = n.slot_id WHERE p.organization_id = ? AND p.archived_at IS NULL AND p.deleted_at IS NULL AND n.status_id != ? AND NOT EXISTS (SELECT 1 FROM nodes c WHERE c.parent_id = n.id) GROUP BY 1",
+    )
+    .bind(organization_id)
+    .bind(done_status_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Find a node by ID.
+pub async fn find_by_id<'e, E>(
+    executor: E,
     id: &str,
-) -> Result<Option<Node>, sqlx::Error> {
+) -> Result<Option<Node>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
     sqlx::query_as::<_, Node>(
-        "SELECT id, project_id, node_type_id, status_id, title, description, created_at, updated_at, estimated_minutes, slot_id, parent_id, assigned_user_id FROM nodes WHERE id = ?",
+        "SELECT id, project_id, node_type_id, status_id, title, description, created_at, updated_at, estimated_minutes, slot_id, parent_id, assigned_user_id, completed_at FROM nodes WHERE id = ?",
     )
     .bind(id)
-    .fetch_optional(pool)
+    .fetch_optional(executor)
     .await
 }
 
 /// Update a node's title, description, node_type_id, status_id, estimated_minutes, slot_id, parent_id, assigned_user_id, and updated_at timestamp.
 /// parent_id: None means set column to NULL (clear parent), Some(pid) means set to pid.
+/// completed_at is stamped when the status moves to Done and cleared when it leaves Done.
 pub async fn update(
     pool: &sqlx::SqlitePool,
     id: &str,
@@ -129,12 +243,16 @@ pub async fn update(
     let now = OffsetDateTime::now_utc().unix_timestamp();
 
     sqlx::query(
-        "UPDATE nodes SET title = ?, description = ?, node_type_id = ?, status_id = ?, estimated_minutes = ?, slot_id = ?, parent_id = ?, assigned_user_id = ?, updated_at = ? WHERE id = ?",
+        "UPDATE nodes SET title = ?, description = ?, node_type_id = ?, completed_at = CASE WHEN ? != ? THEN NULL WHEN status_id = ? THEN completed_at ELSE ? END, status_id = ?, estimated_minutes = ?, slot_id = ?, parent_id = ?, assigned_user_id = ?, updated_at = ? WHERE id = ?",
     )
     .bind(title)
     .bind(description)
     .bind(node_type_id)
     .bind(status_id)
+    .bind(task_statuses::DONE_STATUS_ID)
+    .bind(task_statuses::DONE_STATUS_ID)
+    .bind(now)
+    .bind(status_id)
     .bind(estimated_minutes)
     .bind(slot_id)
     .bind(parent_id)
@@ -193,4 +311,63 @@ where
         .execute(executor)
         .await?;
     Ok(())
-}
\ No newline at end of file
+}
+/// Put a node back into a group, unless it has been moved into another group since.
+pub async fn set_parent_if_unset<'e, E>(
+    executor: E,
+    id: &str,
+    parent_id: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("UPDATE nodes SET parent_id = ? WHERE id = ? AND parent_id IS NULL")
+        .bind(parent_id)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// IDs of nodes whose parent_id is the given node (its group members).
+pub async fn find_child_ids_by_parent<'e, E>(
+    executor: E,
+    parent_id: &str,
+) -> Result<Vec<String>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_scalar("SELECT id FROM nodes WHERE parent_id = ? ORDER BY created_at")
+        .bind(parent_id)
+        .fetch_all(executor)
+        .await
+}
+
+/// Unassign a user from every node in an organisation's projects. Returns how many nodes changed.
+pub async fn unassign_user_in_org<'e, E>(
+    executor: E,
+    organization_id: &str,
+    user_id: &str,
+) -> Result<u64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let result = sqlx::query(
+        "UPDATE nodes SET assigned_user_id = NULL, updated_at = ? WHERE assigned_user_id = ? AND project_id IN (SELECT id FROM projects WHERE organization_id = ?)",
+    )
+    .bind(now)
+    .bind(user_id)
+    .bind(organization_id)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Number of nodes assigned to a user (anywhere).
+pub async fn count_assigned_to_user(pool: &sqlx::SqlitePool, user_id: &str) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM nodes WHERE assigned_user_id = ?")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+}
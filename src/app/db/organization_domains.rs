@@ -0,0 +1,143 @@
+use sqlx::{FromRow, SqliteExecutor};
+use time::OffsetDateTime;
+
+use crate::app::domain::OrganizationRole;
+
+/// Database row for organization_domains table: an email domain an organization has claimed, and
+/// how people from it join once the claim is verified.
+#[derive(Debug, Clone, FromRow)]
+pub struct OrganizationDomain {
+    pub id: String,
+    pub organization_id: String,
+    pub domain: String,
+    pub verification_token: String,
+    pub verified_at: Option<i64>,
+    pub default_role: String,
+    /// Team joiners are added to; None means the org's default team.
+    pub team_id: Option<String>,
+    pub created_at: i64,
+}
+
+/// Data structure for inserting a new domain claim.
+pub struct NewOrganizationDomain {
+    pub id: String,
+    pub organization_id: String,
+    pub domain: String,
+    pub verification_token: String,
+    pub default_role: OrganizationRole,
+    pub team_id: Option<String>,
+}
+
+/// A verified domain's organization, as offered to someone signing up from it.
+#[derive(Debug, Clone, FromRow)]
+pub struct DomainOffer {
+    pub domain_id: String,
+    pub organization_id: String,
+    pub organization_name: String,
+    pub default_role: String,
+    pub team_id: Option<String>,
+}
+
+/// Insert an unverified claim.
+pub async fn insert<'e, E>(executor: E, claim: &NewOrganizationDomain) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query(
+        "INSERT INTO organization_domains (id, organization_id, domain, verification_token, default_role, team_id, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&claim.id)
+    .bind(&claim.organization_id)
+    .bind(&claim.domain)
+    .bind(&claim.verification_token)
+    .bind(claim.default_role.to_string())
+    .bind(&claim.team_id)
+    .bind(now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// An organization's claim by id.
+pub async fn find_for_org<'e, E>(executor: E, id: &str, organization_id: &str) -> Result<Option<OrganizationDomain>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, OrganizationDomain>(
+        "SELECT id, organization_id, domain, verification_token, verified_at, default_role, team_id, created_at FROM organization_domains WHERE id = ? AND organization_id = ?",
+    )
+    .bind(id)
+    .bind(organization_id)
+    .fetch_optional(executor)
+    .await
+}
+
+/// All of an organization's claims, by domain.
+pub async fn list_for_org<'e, E>(executor: E, organization_id: &str) -> Result<Vec<OrganizationDomain>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, OrganizationDomain>(
+        "SELECT id, organization_id, domain, verification_token, verified_at, default_role, team_id, created_at FROM organization_domains WHERE organization_id = ? ORDER BY domain",
+    )
+    .bind(organization_id)
+    .fetch_all(executor)
+    .await
+}
+
+/// Whether any organization holds a verified claim on `domain`.
+pub async fn is_verified_anywhere<'e, E>(executor: E, domain: &str) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM organization_domains WHERE domain = ? AND verified_at IS NOT NULL")
+        .bind(domain)
+        .fetch_one(executor)
+        .await?;
+    Ok(count > 0)
+}
+
+/// Mark a claim verified.
+pub async fn mark_verified<'e, E>(executor: E, id: &str) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query("UPDATE organization_domains SET verified_at = ? WHERE id = ?")
+        .bind(now)
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Remove a claim. Returns false if the organization has no such claim.
+pub async fn delete<'e, E>(executor: E, id: &str, organization_id: &str) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let result = sqlx::query("DELETE FROM organization_domains WHERE id = ? AND organization_id = ?")
+        .bind(id)
+        .bind(organization_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// The organization with a verified claim on `domain` that `user_id` doesn't already belong to.
+pub async fn find_offer_for_user<'e, E>(executor: E, domain: &str, user_id: &str) -> Result<Option<DomainOffer>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, DomainOffer>(
+        "SELECT d.id AS domain_id, d.organization_id, o.name AS organization_name, d.default_role, d.team_id \
+         FROM organization_domains d JOIN organizations o ON o.id = d.organization_id \
+         WHERE d.domain = ? AND d.verified_at IS NOT NULL \
+           AND NOT EXISTS (SELECT 1 FROM organization_members m WHERE m.organization_id = d.organization_id AND m.user_id = ?)",
+    )
+    .bind(domain)
+    .bind(user_id)
+    .fetch_optional(executor)
+    .await
+}
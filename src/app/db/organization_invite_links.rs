@@ -0,0 +1,133 @@
+use sqlx::{FromRow, SqliteExecutor};
+use time::OffsetDateTime;
+
+use crate::app::domain::OrganizationRole;
+
+/// Database row for organization_invite_links table: a shareable link anyone can use to join an
+/// organization, up to `max_uses` times before `expires_at`.
+#[derive(Debug, Clone, FromRow)]
+pub struct InviteLink {
+    pub id: String,
+    pub organization_id: String,
+    pub token: String,
+    pub role: String,
+    /// Team joiners are added to; None means the org's default team.
+    pub team_id: Option<String>,
+    pub max_uses: i64,
+    pub use_count: i64,
+    pub created_by_user_id: String,
+    pub expires_at: i64,
+    pub created_at: i64,
+}
+
+/// Data structure for inserting a new invite link.
+pub struct NewInviteLink {
+    pub id: String,
+    pub organization_id: String,
+    pub token: String,
+    pub role: OrganizationRole,
+    pub team_id: Option<String>,
+    pub max_uses: i64,
+    pub created_by_user_id: String,
+    pub expires_at: i64,
+}
+
+/// Insert a new invite link.
+pub async fn insert<'e, E>(executor: E, link: &NewInviteLink) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query(
+        "INSERT INTO organization_invite_links (id, organization_id, token, role, team_id, max_uses, use_count, created_by_user_id, expires_at, created_at) VALUES (?, ?, ?, ?, ?, ?, 0, ?, ?, ?)",
+    )
+    .bind(&link.id)
+    .bind(&link.organization_id)
+    .bind(&link.token)
+    .bind(link.role.to_string())
+    .bind(&link.team_id)
+    .bind(link.max_uses)
+    .bind(&link.created_by_user_id)
+    .bind(link.expires_at)
+    .bind(now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Find a link by token if it has not expired and still has uses left.
+pub async fn find_usable_by_token<'e, E>(executor: E, token: &str) -> Result<Option<InviteLink>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query_as::<_, InviteLink>(
+        "SELECT id, organization_id, token, role, team_id, max_uses, use_count, created_by_user_id, expires_at, created_at FROM organization_invite_links WHERE token = ? AND expires_at > ? AND use_count < max_uses",
+    )
+    .bind(token)
+    .bind(now)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Usable links for an organization, newest first (for the settings page).
+pub async fn list_active_for_org<'e, E>(executor: E, organization_id: &str) -> Result<Vec<InviteLink>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query_as::<_, InviteLink>(
+        "SELECT id, organization_id, token, role, team_id, max_uses, use_count, created_by_user_id, expires_at, created_at FROM organization_invite_links WHERE organization_id = ? AND expires_at > ? AND use_count < max_uses ORDER BY created_at DESC",
+    )
+    .bind(organization_id)
+    .bind(now)
+    .fetch_all(executor)
+    .await
+}
+
+/// Count one use of a link. Returns false if it was used up in the meantime.
+pub async fn record_use<'e, E>(executor: E, id: &str) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let result = sqlx::query("UPDATE organization_invite_links SET use_count = use_count + 1 WHERE id = ? AND use_count < max_uses")
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Revoke (delete) a link. Returns false if no such link exists in the organization.
+pub async fn delete<'e, E>(executor: E, id: &str, organization_id: &str) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let result = sqlx::query("DELETE FROM organization_invite_links WHERE id = ? AND organization_id = ?")
+        .bind(id)
+        .bind(organization_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Re-attribute the links `from_user_id` created in an organization to `to_user_id`, so they
+/// survive the creator's account being deleted.
+pub async fn transfer_creator<'e, E>(
+    executor: E,
+    organization_id: &str,
+    from_user_id: &str,
+    to_user_id: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query(
+        "UPDATE organization_invite_links SET created_by_user_id = ? WHERE organization_id = ? AND created_by_user_id = ?",
+    )
+    .bind(to_user_id)
+    .bind(organization_id)
+    .bind(from_user_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
@@ -14,6 +14,8 @@ pub struct OrganizationInvite {
     pub token: String,
     pub expires_at: i64,
     pub created_at: i64,
+    /// Team to join on acceptance; None means the org's default team.
+    pub team_id: Option<String>,
 }
 
 /// Data structure for inserting a new organization invite.
@@ -26,6 +28,7 @@ pub struct NewOrganizationInvite {
     pub token: String,
     pub expires_at: i64,
     pub created_at: i64,
+    pub team_id: Option<String>,
 }
 
 /// Insert a new organization invite.
@@ -37,7 +40,7 @@ where
     E: SqliteExecutor<'e>,
 {
     sqlx::query(
-        "INSERT INTO organization_invites (id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO organization_invites (id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at, team_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&invite.id)
     .bind(invite.organization_id.as_str())
@@ -47,6 +50,7 @@ where
     .bind(&invite.token)
     .bind(invite.expires_at)
     .bind(invite.created_at)
+    .bind(&invite.team_id)
     .execute(executor)
     .await?;
     Ok(())
@@ -58,7 +62,7 @@ where
     E: SqliteExecutor<'e>,
 {
     sqlx::query_as::<_, OrganizationInvite>(
-        "SELECT id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at FROM organization_invites WHERE id = ?",
+        "SELECT id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at, team_id FROM organization_invites WHERE id = ?",
     )
     .bind(id)
     .fetch_optional(executor)
@@ -76,7 +80,7 @@ where
 {
     let now = OffsetDateTime::now_utc().unix_timestamp();
     sqlx::query_as::<_, OrganizationInvite>(
-        "SELECT id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at FROM organization_invites WHERE token = ? AND expires_at > ?",
+        "SELECT id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at, team_id FROM organization_invites WHERE token = ? AND expires_at > ?",
     )
     .bind(token)
     .bind(now)
@@ -107,7 +111,7 @@ where
 {
     let now = OffsetDateTime::now_utc().unix_timestamp();
     sqlx::query_as::<_, OrganizationInvite>(
-        "SELECT id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at FROM organization_invites WHERE organization_id = ? AND email = ? AND expires_at > ?",
+        "SELECT id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at, team_id FROM organization_invites WHERE organization_id = ? AND email = ? AND expires_at > ?",
     )
     .bind(organization_id.as_str())
     .bind(email)
@@ -126,10 +130,32 @@ where
 {
     let now = OffsetDateTime::now_utc().unix_timestamp();
     sqlx::query_as::<_, OrganizationInvite>(
-        "SELECT id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at FROM organization_invites WHERE organization_id = ? AND expires_at > ? ORDER BY created_at DESC",
+        "SELECT id, organization_id, email, role, invited_by_user_id, token, expires_at, created_at, team_id FROM organization_invites WHERE organization_id = ? AND expires_at > ? ORDER BY created_at DESC",
     )
     .bind(organization_id.as_str())
     .bind(now)
     .fetch_all(executor)
     .await
 }
+
+/// Re-attribute the invites `from_user_id` sent for an organization to `to_user_id`, so they
+/// survive the sender's account being deleted.
+pub async fn transfer_inviter<'e, E>(
+    executor: E,
+    organization_id: &str,
+    from_user_id: &str,
+    to_user_id: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "UPDATE organization_invites SET invited_by_user_id = ? WHERE organization_id = ? AND invited_by_user_id = ?",
+    )
+    .bind(to_user_id)
+    .bind(organization_id)
+    .bind(from_user_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
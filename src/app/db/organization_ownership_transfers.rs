@@ -0,0 +1,72 @@
+use sqlx::{FromRow, SqliteExecutor};
+
+/// Database row for organization_ownership_transfers table: an owner's offer to hand an
+/// organization to another member, pending the recipient's confirmation.
+#[derive(Debug, Clone, FromRow)]
+pub struct OwnershipTransfer {
+    pub id: String,
+    pub organization_id: String,
+    pub from_user_id: String,
+    pub to_user_id: String,
+    pub token: String,
+    pub expires_at: i64,
+    pub created_at: i64,
+}
+
+/// Insert a transfer. There can be only one per organization; delete the old one first.
+pub async fn insert<'e, E>(executor: E, transfer: &OwnershipTransfer) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query(
+        "INSERT INTO organization_ownership_transfers (id, organization_id, from_user_id, to_user_id, token, expires_at, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&transfer.id)
+    .bind(&transfer.organization_id)
+    .bind(&transfer.from_user_id)
+    .bind(&transfer.to_user_id)
+    .bind(&transfer.token)
+    .bind(transfer.expires_at)
+    .bind(transfer.created_at)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// The organization's pending transfer, if any (expired ones included).
+pub async fn find_for_org<'e, E>(executor: E, organization_id: &str) -> Result<Option<OwnershipTransfer>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, OwnershipTransfer>(
+        "SELECT id, organization_id, from_user_id, to_user_id, token, expires_at, created_at FROM organization_ownership_transfers WHERE organization_id = ?",
+    )
+    .bind(organization_id)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Find a transfer by its emailed token.
+pub async fn find_by_token<'e, E>(executor: E, token: &str) -> Result<Option<OwnershipTransfer>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, OwnershipTransfer>(
+        "SELECT id, organization_id, from_user_id, to_user_id, token, expires_at, created_at FROM organization_ownership_transfers WHERE token = ?",
+    )
+    .bind(token)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Delete the organization's pending transfer.
+pub async fn delete_for_org<'e, E>(executor: E, organization_id: &str) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query("DELETE FROM organization_ownership_transfers WHERE organization_id = ?")
+        .bind(organization_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
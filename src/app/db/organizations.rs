@@ -9,6 +9,8 @@ pub struct Organization {
     pub id: String,
     pub name: String,
     pub created_at: i64,
+    /// Set while the organization is scheduled for deletion.
+    pub deletion_requested_at: Option<i64>,
 }
 
 /// Data structure for inserting a new organization.
@@ -26,7 +28,7 @@ where
     E: SqliteExecutor<'e>,
 {
     sqlx::query_as::<_, Organization>(
-        "SELECT id, name, created_at FROM organizations WHERE id = ?",
+        "SELECT id, name, created_at, deletion_requested_at FROM organizations WHERE id = ?",
     )
     .bind(organization_id.as_str())
     .fetch_optional(executor)
@@ -51,6 +53,56 @@ where
     Ok(())
 }
 
+/// Schedule (`Some(now)`) or cancel (`None`) an organization's deletion.
+pub async fn set_deletion_requested_at<'e, E>(
+    executor: E,
+    organization_id: &OrganizationId,
+    value: Option<i64>,
+) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query("UPDATE organizations SET deletion_requested_at = ? WHERE id = ?")
+        .bind(value)
+        .bind(organization_id.as_str())
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// IDs of organizations whose deletion was requested before `cutoff`.
+pub async fn list_ids_due_for_deletion(pool: &sqlx::SqlitePool, cutoff: i64) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT id FROM organizations WHERE deletion_requested_at IS NOT NULL AND deletion_requested_at < ?")
+        .bind(cutoff)
+        .fetch_all(pool)
+        .await
+}
+
+/// Delete an organization row. Teams, memberships, invites, integrations and ownership transfers
+/// cascade; projects, task statuses, sessions and users pointing at it must be dealt with first.
+pub async fn delete<'e, E>(executor: E, organization_id: &OrganizationId) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query("DELETE FROM organizations WHERE id = ?")
+        .bind(organization_id.as_str())
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Remove every member of an organization.
+pub async fn remove_all_members<'e, E>(executor: E, organization_id: &OrganizationId) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query("DELETE FROM organization_members WHERE organization_id = ?")
+        .bind(organization_id.as_str())
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
 /// Add a user to an organization with a specific role.
 pub async fn add_member<'e, E>(
     executor: E,
@@ -114,6 +166,126 @@ where
     Ok(row.and_then(|r| r.parse::<OrganizationRole>().ok()))
 }
 
+/// Change a member's role. Returns false if the user is not a member.
+pub async fn update_member_role<'e, E>(
+    executor: E,
+    organization_id: &OrganizationId,
+    user_id: &UserId,
+    role: OrganizationRole,
+) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let result = sqlx::query(
+        "UPDATE organization_members SET role = ? WHERE organization_id = ? AND user_id = ?",
+    )
+    .bind(role.to_string())
+    .bind(organization_id.as_str())
+    .bind(user_id.as_str())
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove a user from an organization. Returns false if they were not a member.
+pub async fn remove_member<'e, E>(
+    executor: E,
+    organization_id: &OrganizationId,
+    user_id: &UserId,
+) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let result = sqlx::query(
+        "DELETE FROM organization_members WHERE organization_id = ? AND user_id = ?",
+    )
+    .bind(organization_id.as_str())
+    .bind(user_id.as_str())
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Count an organization's members with the given role (e.g. owners, to protect the last one).
+pub async fn count_members_with_role<'e, E>(
+    executor: E,
+    organization_id: &OrganizationId,
+    role: OrganizationRole,
+) -> Result<i64, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM organization_members WHERE organization_id = ? AND role = ?",
+    )
+    .bind(organization_id.as_str())
+    .bind(role.to_string())
+    .fetch_one(executor)
+    .await
+}
+
+/// Count an organization's members.
+pub async fn count_members<'e, E>(executor: E, organization_id: &OrganizationId) -> Result<i64, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_scalar("SELECT COUNT(*) FROM organization_members WHERE organization_id = ?")
+        .bind(organization_id.as_str())
+        .fetch_one(executor)
+        .await
+}
+
+/// One of a user's memberships, with the org's name.
+#[derive(Debug, FromRow)]
+pub struct UserMembership {
+    pub organization_id: String,
+    pub name: String,
+    pub role: String,
+}
+
+/// List the organizations a user belongs to, oldest membership first.
+pub async fn list_memberships_for_user<'e, E>(
+    executor: E,
+    user_id: &UserId,
+) -> Result<Vec<UserMembership>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, UserMembership>(
+        "SELECT om.organization_id, o.name, om.role FROM organization_members om JOIN organizations o ON o.id = om.organization_id WHERE om.user_id = ? ORDER BY om.created_at",
+    )
+    .bind(user_id.as_str())
+    .fetch_all(executor)
+    .await
+}
+
+/// A member who can take over another member's responsibilities.
+#[derive(Debug, FromRow)]
+pub struct Successor {
+    pub user_id: String,
+    pub email: String,
+}
+
+/// The org's longest-standing owner, else admin, other than `except_user_id`.
+pub async fn find_successor_admin<'e, E>(
+    executor: E,
+    organization_id: &OrganizationId,
+    except_user_id: &UserId,
+) -> Result<Option<Successor>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, Successor>(
+        "SELECT om.user_id, u.email FROM organization_members om JOIN users u ON u.id = om.user_id \
+         WHERE om.organization_id = ? AND om.user_id != ? AND om.role IN ('owner', 'admin') \
+         ORDER BY CASE om.role WHEN 'owner' THEN 0 ELSE 1 END, om.created_at LIMIT 1",
+    )
+    .bind(organization_id.as_str())
+    .bind(except_user_id.as_str())
+    .fetch_optional(executor)
+    .await
+}
+
 /// One row for listing org members with email (for settings page).
 #[derive(Debug, FromRow)]
 pub struct OrgMemberWithEmail {
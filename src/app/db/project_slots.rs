@@ -116,3 +116,22 @@ pub async fn delete(pool: &sqlx::SqlitePool, id: &str) -> Result<(), sqlx::Error
 
     Ok(())
 }
+
+/// Unassign a user from every slot in an organisation's projects.
+pub async fn unassign_user_in_org<'e, E>(
+    executor: E,
+    organization_id: &str,
+    user_id: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "UPDATE project_slots SET assigned_user_id = NULL WHERE assigned_user_id = ? AND project_id IN (SELECT id FROM projects WHERE organization_id = ?)",
+    )
+    .bind(user_id)
+    .bind(organization_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! Daily per-project counts behind the burndown and burnup charts.
+
+use sqlx::FromRow;
+
+/// Database row for project_daily_snapshots. `snapshot_date` is a UTC day (YYYY-MM-DD).
+#[derive(Clone, Debug, FromRow, serde::Serialize)]
+pub struct ProjectSnapshot {
+    pub snapshot_date: String,
+    pub todo_count: i64,
+    pub in_progress_count: i64,
+    pub done_count: i64,
+    pub blocked_count: i64,
+    pub estimated_remaining_minutes: i64,
+}
+
+/// Record a project's state for a day, replacing any earlier snapshot for the same day.
+pub async fn upsert<'e, E>(
+    executor: E,
+    project_id: &str,
+    snapshot: &ProjectSnapshot,
+    now: i64,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO project_daily_snapshots (project_id, snapshot_date, todo_count, in_progress_count, done_count, blocked_count, estimated_remaining_minutes, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT(project_id, snapshot_date) DO UPDATE SET todo_count = excluded.todo_count, in_progress_count = excluded.in_progress_count, done_count = excluded.done_count, blocked_count = excluded.blocked_count, estimated_remaining_minutes = excluded.estimated_remaining_minutes, created_at = excluded.created_at",
+    )
+    .bind(project_id)
+    .bind(&snapshot.snapshot_date)
+    .bind(snapshot.todo_count)
+    .bind(snapshot.in_progress_count)
+    .bind(snapshot.done_count)
+    .bind(snapshot.blocked_count)
+    .bind(snapshot.estimated_remaining_minutes)
+    .bind(now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Snapshots for a project between two days (inclusive, YYYY-MM-DD), oldest first.
+pub async fn find_in_range(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<ProjectSnapshot>, sqlx::Error> {
+    sqlx::query_as::<_, ProjectSnapshot>(
+        "SELECT snapshot_date, todo_count, in_progress_count, done_count, blocked_count, estimated_remaining_minutes FROM project_daily_snapshots WHERE project_id = ? AND snapshot_date >= ? AND snapshot_date <= ? ORDER BY snapshot_date",
+    )
+    .bind(project_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+}
@@ -15,6 +15,10 @@ pub struct Project {
     pub organization_id: String,
     pub team_id: Option<String>,
     pub default_view_mode: String,
+    /// When the project was archived (hidden from the projects list, still readable).
+    pub archived_at: Option<i64>,
+    /// When the project was moved to the trash; trashed projects are only reachable to restore or purge.
+    pub deleted_at: Option<i64>,
 }
 
 impl Project {
@@ -58,7 +62,7 @@ where
     Ok(())
 }
 
-/// Delete a project by ID and organization ID (tenant-scoped).
+/// Permanently delete a project by ID and organization ID (tenant-scoped), cascading its nodes, edges and slots.
 pub async fn delete_by_id_and_org<'e, E>(
     executor: E,
     project_id: &str,
@@ -83,7 +87,7 @@ pub async fn find_by_user_and_org(
     organization_id: &str,
 ) -> Result<Vec<Project>, sqlx::Error> {
     sqlx::query_as::<_, Project>(
-        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode FROM projects WHERE user_id = ? AND organization_id = ? ORDER BY created_at DESC",
+        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode, archived_at, deleted_at FROM projects WHERE user_id = ? AND organization_id = ? AND deleted_at IS NULL ORDER BY created_at DESC",
     )
     .bind(user_id)
     .bind(organization_id)
@@ -91,27 +95,47 @@ pub async fn find_by_user_and_org(
     .await
 }
 
-/// List all projects for an organisation. Caller must have verified org membership.
+/// List an organisation's active (not archived, not trashed) projects. Caller must have verified org membership.
 pub async fn list_for_org(
     pool: &sqlx::SqlitePool,
     organization_id: &str,
 ) -> Result<Vec<Project>, sqlx::Error> {
     sqlx::query_as::<_, Project>(
-        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode FROM projects WHERE organization_id = ? ORDER BY created_at DESC",
+        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode, archived_at, deleted_at FROM projects WHERE organization_id = ? AND archived_at IS NULL AND deleted_at IS NULL ORDER BY created_at DESC",
     )
     .bind(organization_id)
     .fetch_all(pool)
     .await
 }
 
-/// Find a project by ID and organisation. Returns None if project doesn't exist or belongs to another org.
+/// IDs of every project not in the trash, for background jobs that visit all of them.
+pub async fn list_all_ids(pool: &sqlx::SqlitePool) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT id FROM projects WHERE deleted_at IS NULL ORDER BY created_at")
+        .fetch_all(pool)
+        .await
+}
+
+/// List projects on teams the user belongs to, limited to organisations they are still a member of.
+pub async fn list_for_team_member(
+    pool: &sqlx::SqlitePool,
+    user_id: &str,
+) -> Result<Vec<Project>, sqlx::Error> {
+    sqlx::query_as::<_, Project>(
+        "SELECT p.id, p.title, p.user_id, p.created_at, p.organization_id, p.team_id, p.default_view_mode, p.archived_at, p.deleted_at FROM projects p INNER JOIN team_members tm ON tm.team_id = p.team_id AND tm.user_id = ? INNER JOIN organization_members om ON om.organization_id = p.organization_id AND om.user_id = tm.user_id WHERE p.archived_at IS NULL AND p.deleted_at IS NULL ORDER BY p.created_at DESC",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Find a project by ID and organisation. Returns None if project doesn't exist, belongs to another org or is in the trash.
 pub async fn find_by_id_and_org(
     pool: &sqlx::SqlitePool,
     id: &str,
     organization_id: &str,
 ) -> Result<Option<Project>, sqlx::Error> {
     sqlx::query_as::<_, Project>(
-        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode FROM projects WHERE id = ? AND organization_id = ?",
+        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode, archived_at, deleted_at FROM projects WHERE id = ? AND organization_id = ? AND deleted_at IS NULL",
     )
     .bind(id)
     .bind(organization_id)
@@ -121,19 +145,92 @@ pub async fn find_by_id_and_org(
 
 /// Count projects assigned to a team.
 pub async fn count_by_team(pool: &sqlx::SqlitePool, team_id: &str) -> Result<i64, sqlx::Error> {
-    sqlx::query_scalar("SELECT COUNT(*) FROM projects WHERE team_id = ?")
+    sqlx::query_scalar("SELECT COUNT(*) FROM projects WHERE team_id = ? AND deleted_at IS NULL")
         .bind(team_id)
         .fetch_one(pool)
         .await
 }
 
-/// Find a project by ID.
+/// List a team's projects (archived included, trash excluded), by title.
+pub async fn list_by_team(pool: &sqlx::SqlitePool, team_id: &str) -> Result<Vec<Project>, sqlx::Error> {
+    sqlx::query_as::<_, Project>(
+        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode, archived_at, deleted_at FROM projects WHERE team_id = ? AND deleted_at IS NULL ORDER BY title COLLATE NOCASE",
+    )
+    .bind(team_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Move a project (trashed ones included) to another team of its organisation. Returns false if
+/// the project is not in the org.
+pub async fn set_team(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    organization_id: &str,
+    team_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE projects SET team_id = ? WHERE id = ? AND organization_id = ?")
+        .bind(team_id)
+        .bind(project_id)
+        .bind(organization_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Move every project on `from_team_id`, trashed ones included, to `to_team_id`. Returns how many.
+pub async fn reassign_team<'e, E>(
+    executor: E,
+    from_team_id: &str,
+    to_team_id: &str,
+) -> Result<u64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let result = sqlx::query("UPDATE projects SET team_id = ? WHERE team_id = ?")
+        .bind(to_team_id)
+        .bind(from_team_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// List every project a user created, archived and trashed ones included.
+pub async fn list_created_by_user(pool: &sqlx::SqlitePool, user_id: &str) -> Result<Vec<Project>, sqlx::Error> {
+    sqlx::query_as::<_, Project>(
+        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode, archived_at, deleted_at FROM projects WHERE user_id = ? ORDER BY organization_id, title COLLATE NOCASE",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Hand every project `from_user_id` created in an organisation over to `to_user_id`.
+pub async fn transfer_creator<'e, E>(
+    executor: E,
+    organization_id: &str,
+    from_user_id: &str,
+    to_user_id: &str,
+) -> Result<u64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let result = sqlx::query("UPDATE projects SET user_id = ? WHERE organization_id = ? AND user_id = ?")
+        .bind(to_user_id)
+        .bind(organization_id)
+        .bind(from_user_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Find a project by ID. Trashed projects are treated as missing.
 pub async fn find_by_id(
     pool: &sqlx::SqlitePool,
     id: &str,
 ) -> Result<Option<Project>, sqlx::Error> {
     sqlx::query_as::<_, Project>(
-        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode FROM projects WHERE id = ?",
+        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode, archived_at, deleted_at FROM projects WHERE id = ? AND deleted_at IS NULL",
     )
     .bind(id)
     .fetch_optional(pool)
@@ -164,3 +261,117 @@ pub async fn update_default_view_mode(
     find_by_id_and_org(pool, project_id, organization_id).await
 }
 
+/// List an organisation's archived projects that are not in the trash, most recently archived first.
+pub async fn list_archived_for_org(
+    pool: &sqlx::SqlitePool,
+    organization_id: &str,
+) -> Result<Vec<Project>, sqlx::Error> {
+    sqlx::query_as::<_, Project>(
+        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode, archived_at, deleted_at FROM projects WHERE organization_id = ? AND archived_at IS NOT NULL AND deleted_at IS NULL ORDER BY archived_at DESC",
+    )
+    .bind(organization_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// List an organisation's trashed projects, most recently deleted first.
+pub async fn list_trashed_for_org(
+    pool: &sqlx::SqlitePool,
+    organization_id: &str,
+) -> Result<Vec<Project>, sqlx::Error> {
+    sqlx::query_as::<_, Project>(
+        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode, archived_at, deleted_at FROM projects WHERE organization_id = ? AND deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+    )
+    .bind(organization_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Find a trashed project by ID and organisation.
+pub async fn find_trashed_by_id_and_org(
+    pool: &sqlx::SqlitePool,
+    id: &str,
+    organization_id: &str,
+) -> Result<Option<Project>, sqlx::Error> {
+    sqlx::query_as::<_, Project>(
+        "SELECT id, title, user_id, created_at, organization_id, team_id, default_view_mode, archived_at, deleted_at FROM projects WHERE id = ? AND organization_id = ? AND deleted_at IS NOT NULL",
+    )
+    .bind(id)
+    .bind(organization_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Set or clear archived_at on a project that is not in the trash. Returns false if nothing matched.
+pub async fn set_archived_at(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    organization_id: &str,
+    archived_at: Option<i64>,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE projects SET archived_at = ? WHERE id = ? AND organization_id = ? AND deleted_at IS NULL",
+    )
+    .bind(archived_at)
+    .bind(project_id)
+    .bind(organization_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Move a project to the trash. Returns false if it doesn't exist or is already trashed.
+pub async fn move_to_trash(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    organization_id: &str,
+    now: i64,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE projects SET deleted_at = ? WHERE id = ? AND organization_id = ? AND deleted_at IS NULL",
+    )
+    .bind(now)
+    .bind(project_id)
+    .bind(organization_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Take a project out of the trash. Returns false if it isn't trashed.
+pub async fn restore_from_trash(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    organization_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE projects SET deleted_at = NULL WHERE id = ? AND organization_id = ? AND deleted_at IS NOT NULL",
+    )
+    .bind(project_id)
+    .bind(organization_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Permanently delete every project trashed before `cutoff`. Returns how many were deleted.
+pub async fn purge_trashed_before(pool: &sqlx::SqlitePool, cutoff: i64) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM projects WHERE deleted_at IS NOT NULL AND deleted_at < ?")
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Delete every project of an organisation, trashed ones included. Nodes, edges and everything
+/// else hanging off the projects cascade.
+pub async fn delete_all_for_org<'e, E>(executor: E, organization_id: &str) -> Result<u64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM projects WHERE organization_id = ?")
+        .bind(organization_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected())
+}
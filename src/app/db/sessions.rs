@@ -1,3 +1,4 @@
+use rand_core::RngCore;
 use sqlx::FromRow;
 use time::OffsetDateTime;
 
@@ -11,9 +12,18 @@ pub struct Session {
     pub organization_id: String,
     pub expires_at: i64,
     pub created_at: i64,
+    /// Synchronizer token required on cookie-authenticated writes (see `app::csrf`).
+    pub csrf_token: String,
 }
 
-/// Create a new session for a user. Returns the session ID.
+/// Generate a high-entropy CSRF token (64 hex chars = 32 bytes).
+fn generate_csrf_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand_core::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Create a new session (with a fresh CSRF token) for a user. Returns the session ID.
 pub async fn create<'e, E>(
     executor: E,
     user_id: &UserId,
@@ -27,15 +37,17 @@ where
 
     let session_id = UserId::new().as_str();
     let now = OffsetDateTime::now_utc().unix_timestamp();
+    let csrf_token = generate_csrf_token();
 
     sqlx::query(
-        "INSERT INTO sessions (id, user_id, organization_id, expires_at, created_at) VALUES (?, ?, ?, ?, ?)",
+        "INSERT INTO sessions (id, user_id, organization_id, expires_at, created_at, csrf_token) VALUES (?, ?, ?, ?, ?, ?)",
     )
     .bind(&session_id)
     .bind(user_id.as_str())
     .bind(organization_id.as_str())
     .bind(expires_at.unix_timestamp())
     .bind(now)
+    .bind(&csrf_token)
     .execute(executor)
     .await?;
 
@@ -50,7 +62,7 @@ pub async fn find_valid(
     let now = OffsetDateTime::now_utc().unix_timestamp();
 
     sqlx::query_as::<_, Session>(
-        "SELECT id, user_id, organization_id, expires_at, created_at FROM sessions WHERE id = ? AND expires_at > ?",
+        "SELECT id, user_id, organization_id, expires_at, created_at, csrf_token FROM sessions WHERE id = ? AND expires_at > ?",
     )
     .bind(session_id)
     .bind(now)
@@ -68,4 +80,33 @@ where
         .execute(executor)
         .await?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Delete a user's sessions in one organization (e.g. after they are removed from it).
+pub async fn delete_for_user_in_org<'e, E>(
+    executor: E,
+    user_id: &str,
+    organization_id: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("DELETE FROM sessions WHERE user_id = ? AND organization_id = ?")
+        .bind(user_id)
+        .bind(organization_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Delete every session scoped to an organization (e.g. before it is deleted).
+pub async fn delete_for_org<'e, E>(executor: E, organization_id: &str) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("DELETE FROM sessions WHERE organization_id = ?")
+        .bind(organization_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
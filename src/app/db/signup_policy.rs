@@ -0,0 +1,62 @@
+//! Instance-wide signup policy override, edited by instance admins. A single row, or none.
+
+use sqlx::{FromRow, SqliteExecutor};
+use time::OffsetDateTime;
+
+use crate::app::domain::SignupMode;
+
+/// Database row for instance_signup_policy.
+#[derive(Debug, Clone, FromRow)]
+pub struct SignupPolicyRow {
+    pub mode: String,
+    /// Comma-separated lowercase domains; empty allows any domain.
+    pub allowed_domains: String,
+    pub updated_by_user_id: Option<String>,
+    pub updated_at: i64,
+}
+
+/// The admin override, if one has been saved.
+pub async fn find<'e, E>(executor: E) -> Result<Option<SignupPolicyRow>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, SignupPolicyRow>(
+        "SELECT mode, allowed_domains, updated_by_user_id, updated_at FROM instance_signup_policy WHERE id = 1",
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Save the override, replacing any previous one.
+pub async fn upsert<'e, E>(
+    executor: E,
+    mode: SignupMode,
+    allowed_domains: &[String],
+    updated_by_user_id: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query(
+        "INSERT INTO instance_signup_policy (id, mode, allowed_domains, updated_by_user_id, updated_at) VALUES (1, ?, ?, ?, ?) ON CONFLICT(id) DO UPDATE SET mode = excluded.mode, allowed_domains = excluded.allowed_domains, updated_by_user_id = excluded.updated_by_user_id, updated_at = excluded.updated_at",
+    )
+    .bind(mode.to_string())
+    .bind(allowed_domains.join(","))
+    .bind(updated_by_user_id)
+    .bind(now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Drop the override so the environment configuration applies again.
+pub async fn delete<'e, E>(executor: E) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query("DELETE FROM instance_signup_policy WHERE id = 1")
+        .execute(executor)
+        .await?;
+    Ok(())
+}
@@ -71,3 +71,15 @@ pub async fn find_by_id(
     .fetch_optional(pool)
     .await
 }
+
+/// Delete an organization's own statuses (system statuses have no organization).
+pub async fn delete_for_org<'e, E>(executor: E, organization_id: &str) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("DELETE FROM task_statuses WHERE organization_id = ?")
+        .bind(organization_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
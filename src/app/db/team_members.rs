@@ -9,8 +9,12 @@ pub struct TeamMember {
     pub team_id: String,
     pub user_id: String,
     pub created_at: i64,
+    pub weekly_capacity_minutes: i64,
 }
 
+/// Weekly capacity new memberships start with (40 hours); matches the column default.
+pub const DEFAULT_WEEKLY_CAPACITY_MINUTES: i64 = 2400;
+
 /// Data structure for inserting a new team member.
 pub struct NewTeamMember {
     pub team_id: String,
@@ -121,6 +125,7 @@ pub struct TeamMemberWithUser {
     pub first_name: String,
     pub last_name: String,
     pub profile_image_url: Option<String>,
+    pub weekly_capacity_minutes: i64,
 }
 
 /// List all members of a team with email and name (for display).
@@ -132,7 +137,7 @@ where
     E: SqliteExecutor<'e>,
 {
     sqlx::query_as::<_, TeamMemberWithUser>(
-        "SELECT tm.user_id, u.email, COALESCE(u.first_name, '') AS first_name, COALESCE(u.last_name, '') AS last_name, u.profile_image_url \
+        "SELECT tm.user_id, u.email, COALESCE(u.first_name, '') AS first_name, COALESCE(u.last_name, '') AS last_name, u.profile_image_url, tm.weekly_capacity_minutes \
          FROM team_members tm JOIN users u ON u.id = tm.user_id \
          WHERE tm.team_id = ? ORDER BY u.email",
     )
@@ -140,3 +145,84 @@ where
     .fetch_all(executor)
     .await
 }
+
+/// Set a member's weekly capacity on a team. Returns false if the user is not on the team.
+pub async fn update_capacity<'e, E>(
+    executor: E,
+    team_id: &str,
+    user_id: &str,
+    weekly_capacity_minutes: i64,
+) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let result = sqlx::query(
+        "UPDATE team_members SET weekly_capacity_minutes = ? WHERE team_id = ? AND user_id = ?",
+    )
+    .bind(weekly_capacity_minutes)
+    .bind(team_id)
+    .bind(user_id)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// A user's weekly capacity across an organisation's teams.
+#[derive(Debug, FromRow)]
+pub struct UserCapacity {
+    pub user_id: String,
+    pub weekly_capacity_minutes: i64,
+}
+
+/// Weekly capacity per user over all teams in an organisation. A user on several teams gets the
+/// lowest capacity set on any of them, so lowering it on one team is enough to flag overload.
+pub async fn list_capacity_for_org<'e, E>(
+    executor: E,
+    organization_id: &str,
+) -> Result<Vec<UserCapacity>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, UserCapacity>(
+        "SELECT tm.user_id, MIN(tm.weekly_capacity_minutes) AS weekly_capacity_minutes FROM team_members tm JOIN teams t ON t.id = tm.team_id WHERE t.organization_id = ? GROUP BY tm.user_id",
+    )
+    .bind(organization_id)
+    .fetch_all(executor)
+    .await
+}
+
+/// Remove a user from a team. Returns false if they were not on it.
+pub async fn remove_member<'e, E>(
+    executor: E,
+    team_id: &str,
+    user_id: &str,
+) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let result = sqlx::query("DELETE FROM team_members WHERE team_id = ? AND user_id = ?")
+        .bind(team_id)
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Take a user off every team of an organisation (e.g. when they leave the org).
+pub async fn remove_from_org_teams<'e, E>(
+    executor: E,
+    organization_id: &str,
+    user_id: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query(
+        "DELETE FROM team_members WHERE user_id = ? AND team_id IN (SELECT id FROM teams WHERE organization_id = ?)",
+    )
+    .bind(user_id)
+    .bind(organization_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
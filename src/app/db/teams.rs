@@ -84,3 +84,54 @@ where
     .fetch_optional(executor)
     .await
 }
+
+/// `team_id` if it is a team of the organization, otherwise the org's default team. Used when
+/// someone joins through an invite, link or domain that names a team which may since have gone.
+pub async fn find_in_org_or_default<'e, E>(
+    executor: E,
+    organization_id: &OrganizationId,
+    team_id: Option<&str>,
+) -> Result<Option<Team>, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query_as::<_, Team>(
+        "SELECT id, organization_id, name, created_at FROM teams WHERE organization_id = ? ORDER BY id = COALESCE(?, '') DESC, created_at LIMIT 1",
+    )
+    .bind(organization_id.as_str())
+    .bind(team_id)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Rename a team within an organization. Returns false if no such team in the org.
+pub async fn rename<'e, E>(
+    executor: E,
+    id: &str,
+    organization_id: &str,
+    name: &str,
+) -> Result<bool, sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    let result = sqlx::query("UPDATE teams SET name = ? WHERE id = ? AND organization_id = ?")
+        .bind(name)
+        .bind(id)
+        .bind(organization_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Delete a team (memberships cascade). Projects must be moved off it first; see
+/// `projects::reassign_team`.
+pub async fn delete<'e, E>(executor: E, id: &str) -> Result<(), sqlx::Error>
+where
+    E: SqliteExecutor<'e>,
+{
+    sqlx::query("DELETE FROM teams WHERE id = ?")
+        .bind(id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// Database row for time_entries table. `entry_date` is the day the work was done (YYYY-MM-DD).
+#[derive(Clone, Debug, FromRow, serde::Serialize, serde::Deserialize)]
+pub struct TimeEntry {
+    pub id: String,
+    pub node_id: String,
+    pub user_id: String,
+    pub minutes: i64,
+    pub entry_date: String,
+    pub note: Option<String>,
+    pub created_at: i64,
+}
+
+/// Data structure for inserting a new time entry.
+pub struct NewTimeEntry {
+    pub id: String,
+    pub node_id: String,
+    pub user_id: String,
+    pub minutes: i64,
+    pub entry_date: String,
+    pub note: Option<String>,
+}
+
+/// Insert a new time entry.
+pub async fn insert<'e, E>(executor: E, entry: &NewTimeEntry) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query(
+        "INSERT INTO time_entries (id, node_id, user_id, minutes, entry_date, note, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&entry.id)
+    .bind(&entry.node_id)
+    .bind(&entry.user_id)
+    .bind(entry.minutes)
+    .bind(&entry.entry_date)
+    .bind(&entry.note)
+    .bind(now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Re-insert a time entry removed with its node, unless its author has since been deleted.
+pub async fn insert_restored<'e, E>(executor: E, entry: &TimeEntry) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO time_entries (id, node_id, user_id, minutes, entry_date, note, created_at) SELECT ?, ?, ?, ?, ?, ?, ? WHERE EXISTS (SELECT 1 FROM users WHERE id = ?)",
+    )
+    .bind(&entry.id)
+    .bind(&entry.node_id)
+    .bind(&entry.user_id)
+    .bind(entry.minutes)
+    .bind(&entry.entry_date)
+    .bind(&entry.note)
+    .bind(entry.created_at)
+    .bind(&entry.user_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Find a time entry by ID.
+pub async fn find_by_id(pool: &sqlx::SqlitePool, id: &str) -> Result<Option<TimeEntry>, sqlx::Error> {
+    sqlx::query_as::<_, TimeEntry>(
+        "SELECT id, node_id, user_id, minutes, entry_date, note, created_at FROM time_entries WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// List time entries for a node, most recent work first.
+pub async fn find_by_node(pool: &sqlx::SqlitePool, node_id: &str) -> Result<Vec<TimeEntry>, sqlx::Error> {
+    sqlx::query_as::<_, TimeEntry>(
+        "SELECT id, node_id, user_id, minutes, entry_date, note, created_at FROM time_entries WHERE node_id = ? ORDER BY entry_date DESC, created_at DESC",
+    )
+    .bind(node_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Delete a time entry by ID.
+pub async fn delete(pool: &sqlx::SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM time_entries WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Total minutes logged on a project's nodes.
+pub async fn total_minutes_for_project(pool: &sqlx::SqlitePool, project_id: &str) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT COALESCE(SUM(te.minutes), 0) FROM time_entries te INNER JOIN nodes n ON n.id = te.node_id WHERE n.project_id = ?",
+    )
+    .bind(project_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Minutes logged on a project's nodes whose status is `status_id` (e.g. done work, to compare
+/// against the estimates of finished nodes).
+pub async fn total_minutes_for_project_status(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    status_id: &str,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT COALESCE(SUM(te.minutes), 0) FROM time_entries te INNER JOIN nodes n ON n.id = te.node_id WHERE n.project_id = ? AND n.status_id = ?",
+    )
+    .bind(project_id)
+    .bind(status_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// A user's running timer.
+#[derive(Clone, Debug, FromRow, serde::Serialize)]
+pub struct RunningTimer {
+    pub user_id: String,
+    pub node_id: String,
+    pub started_at: i64,
+}
+
+/// The user's running timer, if any.
+pub async fn find_running_timer<'e, E>(executor: E, user_id: &str) -> Result<Option<RunningTimer>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_as::<_, RunningTimer>(
+        "SELECT user_id, node_id, started_at FROM running_timers WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Start a timer for the user on a node. Fails on the primary key if one is already running.
+pub async fn start_timer<'e, E>(
+    executor: E,
+    user_id: &str,
+    node_id: &str,
+    started_at: i64,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("INSERT INTO running_timers (user_id, node_id, started_at) VALUES (?, ?, ?)")
+        .bind(user_id)
+        .bind(node_id)
+        .bind(started_at)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Remove the user's running timer.
+pub async fn delete_timer<'e, E>(executor: E, user_id: &str) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("DELETE FROM running_timers WHERE user_id = ?")
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Number of time entries a user has logged (anywhere).
+pub async fn count_by_user(pool: &sqlx::SqlitePool, user_id: &str) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM time_entries WHERE user_id = ?")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+}
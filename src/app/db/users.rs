@@ -1,7 +1,7 @@
 use sqlx::FromRow;
 use time::OffsetDateTime;
 
-use crate::app::domain::{Email, HashedPassword, OrganizationId, ProfileImageUrl, UserId};
+use crate::app::domain::{DigestFrequency, Email, HashedPassword, OrganizationId, ProfileImageUrl, UserId};
 
 /// Database row for users table.
 #[derive(Debug, FromRow)]
@@ -20,6 +20,8 @@ pub struct User {
     pub email_notifications: i32,
     pub theme_mode: String,
     pub language: String,
+    pub digest_frequency: String,
+    pub digest_last_sent_at: Option<i64>,
 }
 
 /// Data structure for inserting a new user.
@@ -38,7 +40,7 @@ pub async fn find_by_email(
     email: &Email,
 ) -> Result<Option<User>, sqlx::Error> {
     sqlx::query_as::<_, User>(
-        "SELECT id, email, password_hash, created_at, updated_at, email_verified_at, organization_id, COALESCE(first_name, '') AS first_name, COALESCE(last_name, '') AS last_name, profile_image_url, bio, COALESCE(email_notifications, 1) AS email_notifications, COALESCE(theme_mode, 'light') AS theme_mode, COALESCE(language, 'en-US') AS language FROM users WHERE email = ?",
+        "SELECT id, email, password_hash, created_at, updated_at, email_verified_at, organization_id, COALESCE(first_name, '') AS first_name, COALESCE(last_name, '') AS last_name, profile_image_url, bio, COALESCE(email_notifications, 1) AS email_notifications, COALESCE(theme_mode, 'light') AS theme_mode, COALESCE(language, 'en-US') AS language, digest_frequency, digest_last_sent_at FROM users WHERE email = ?",
     )
     .bind(email.as_str())
     .fetch_optional(pool)
@@ -54,7 +56,7 @@ where
     E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
 {
     sqlx::query_as::<_, User>(
-        "SELECT id, email, password_hash, created_at, updated_at, email_verified_at, organization_id, COALESCE(first_name, '') AS first_name, COALESCE(last_name, '') AS last_name, profile_image_url, bio, COALESCE(email_notifications, 1) AS email_notifications, COALESCE(theme_mode, 'light') AS theme_mode, COALESCE(language, 'en-US') AS language FROM users WHERE id = ?",
+        "SELECT id, email, password_hash, created_at, updated_at, email_verified_at, organization_id, COALESCE(first_name, '') AS first_name, COALESCE(last_name, '') AS last_name, profile_image_url, bio, COALESCE(email_notifications, 1) AS email_notifications, COALESCE(theme_mode, 'light') AS theme_mode, COALESCE(language, 'en-US') AS language, digest_frequency, digest_last_sent_at FROM users WHERE id = ?",
     )
         .bind(user_id.as_str())
         .fetch_optional(executor)
@@ -146,15 +148,17 @@ pub async fn update_preferences<'e, E>(
     email_notifications: bool,
     theme_mode: &str,
     language: &str,
+    digest_frequency: &str,
 ) -> Result<(), sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
 {
     let now = OffsetDateTime::now_utc().unix_timestamp();
-    sqlx::query("UPDATE users SET email_notifications = ?, theme_mode = ?, language = ?, updated_at = ? WHERE id = ?")
+    sqlx::query("UPDATE users SET email_notifications = ?, theme_mode = ?, language = ?, digest_frequency = ?, updated_at = ? WHERE id = ?")
         .bind(if email_notifications { 1 } else { 0 })
         .bind(theme_mode)
         .bind(language)
+        .bind(digest_frequency)
         .bind(now)
         .bind(user_id.as_str())
         .execute(executor)
@@ -162,6 +166,39 @@ where
     Ok(())
 }
 
+/// Verified users with notifications on whose digest period has elapsed at `now`.
+pub async fn find_digest_due(
+    pool: &sqlx::SqlitePool,
+    now: i64,
+) -> Result<Vec<User>, sqlx::Error> {
+    sqlx::query_as::<_, User>(
+        "SELECT id, email, password_hash, created_at, updated_at, email_verified_at, organization_id, COALESCE(first_name, '') AS first_name, COALESCE(last_name, '') AS last_name, profile_image_url, bio, COALESCE(email_notifications, 1) AS email_notifications, COALESCE(theme_mode, 'light') AS theme_mode, COALESCE(language, 'en-US') AS language, digest_frequency, digest_last_sent_at FROM users WHERE email_verified_at IS NOT NULL AND COALESCE(email_notifications, 1) = 1 AND ((digest_frequency = 'daily' AND COALESCE(digest_last_sent_at, 0) <= ? - ?) OR (digest_frequency = 'weekly' AND COALESCE(digest_last_sent_at, 0) <= ? - ?)) ORDER BY id",
+    )
+    .bind(now)
+    .bind(DigestFrequency::Daily.period_secs())
+    .bind(now)
+    .bind(DigestFrequency::Weekly.period_secs())
+    .fetch_all(pool)
+    .await
+}
+
+/// Record that a digest went out to the user at `now`.
+pub async fn mark_digest_sent<'e, E>(
+    executor: E,
+    user_id: &str,
+    now: i64,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("UPDATE users SET digest_last_sent_at = ? WHERE id = ?")
+        .bind(now)
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
 /// Delete a user by ID. Cascades to sessions, organization_members, etc.
 pub async fn delete<'e, E>(executor: E, user_id: &UserId) -> Result<(), sqlx::Error>
 where
@@ -239,6 +276,40 @@ where
     Ok(())
 }
 
+/// After a user leaves `organization_id`, point them at their oldest remaining membership if that
+/// org was their current one. Users with no other membership are left as they are.
+pub async fn switch_organization_after_leaving<'e, E>(
+    executor: E,
+    user_id: &str,
+    organization_id: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    sqlx::query(
+        "UPDATE users SET organization_id = (SELECT om.organization_id FROM organization_members om WHERE om.user_id = users.id ORDER BY om.created_at LIMIT 1), updated_at = ? \
+         WHERE id = ? AND organization_id = ? AND EXISTS (SELECT 1 FROM organization_members om WHERE om.user_id = users.id)",
+    )
+    .bind(now)
+    .bind(user_id)
+    .bind(organization_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// IDs of users whose current organization is `organization_id`.
+pub async fn list_ids_in_organization<'e, E>(executor: E, organization_id: &str) -> Result<Vec<String>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_scalar("SELECT id FROM users WHERE organization_id = ?")
+        .bind(organization_id)
+        .fetch_all(executor)
+        .await
+}
+
 /// Insert a new user into the database.
 pub async fn insert<'e, E>(
     executor: E,
@@ -0,0 +1,197 @@
+//! TXT record lookups for verifying that an organization controls an email domain. Handlers go
+//! through [`TxtResolver`] on `AppState`, so tests can answer with fixed records instead of DNS.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::net::UdpSocket;
+
+/// How long to wait for the nameserver before giving up.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const TYPE_TXT: u16 = 16;
+const CLASS_IN: u16 = 1;
+const RCODE_NXDOMAIN: u16 = 3;
+
+/// Looks up TXT records. Swappable per environment.
+#[async_trait::async_trait]
+pub trait TxtResolver: Send + Sync {
+    /// The TXT strings published at `name`; empty when there are none.
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, DnsError>;
+}
+
+/// Errors that can occur during a lookup.
+#[derive(Debug, thiserror::Error)]
+pub enum DnsError {
+    #[error("Invalid name: {0}")]
+    InvalidName(String),
+    #[error("Network error: {0}")]
+    Network(String),
+    #[error("Nameserver did not answer in time")]
+    Timeout,
+    #[error("Malformed response")]
+    Malformed,
+    #[error("Nameserver returned error code {0}")]
+    Server(u16),
+}
+
+/// Sends a single recursive TXT query over UDP to the configured nameserver.
+#[derive(Debug)]
+pub struct UdpTxtResolver {
+    server: String,
+}
+
+impl UdpTxtResolver {
+    pub fn new(server: impl Into<String>) -> Self {
+        Self { server: server.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl TxtResolver for UdpTxtResolver {
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, DnsError> {
+        let id: u16 = rand_core::RngCore::next_u32(&mut rand_core::OsRng) as u16;
+        let query = encode_query(id, name)?;
+        let exchange = async {
+            let socket = UdpSocket::bind("0.0.0.0:0").await?;
+            socket.connect(&self.server).await?;
+            socket.send(&query).await?;
+            let mut buf = vec![0u8; 4096];
+            let len = socket.recv(&mut buf).await?;
+            buf.truncate(len);
+            Ok::<_, std::io::Error>(buf)
+        };
+        let response = tokio::time::timeout(QUERY_TIMEOUT, exchange)
+            .await
+            .map_err(|_| DnsError::Timeout)?
+            .map_err(|e| DnsError::Network(e.to_string()))?;
+        parse_txt_response(id, &response)
+    }
+}
+
+/// The resolver for this environment.
+pub fn from_config(config: &crate::app::config::Config) -> Arc<dyn TxtResolver> {
+    Arc::new(UdpTxtResolver::new(config.dns_server.clone()))
+}
+
+/// Build a recursive TXT query for `name`.
+fn encode_query(id: u16, name: &str) -> Result<Vec<u8>, DnsError> {
+    let mut out = Vec::with_capacity(name.len() + 18);
+    out.extend_from_slice(&id.to_be_bytes());
+    out.extend_from_slice(&0x0100u16.to_be_bytes()); // recursion desired
+    out.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]); // one question
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(DnsError::InvalidName(name.to_string()));
+        }
+        out.push(label.len() as u8);
+        out.extend_from_slice(label.as_bytes());
+    }
+    out.push(0);
+    out.extend_from_slice(&TYPE_TXT.to_be_bytes());
+    out.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(out)
+}
+
+fn read_u16(buf: &[u8], at: usize) -> Result<u16, DnsError> {
+    buf.get(at..at + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .ok_or(DnsError::Malformed)
+}
+
+/// Offset just past the (possibly compressed) name starting at `at`.
+fn skip_name(buf: &[u8], mut at: usize) -> Result<usize, DnsError> {
+    loop {
+        let len = *buf.get(at).ok_or(DnsError::Malformed)?;
+        match len {
+            0 => return Ok(at + 1),
+            l if l & 0xC0 == 0xC0 => return Ok(at + 2),
+            l => at += 1 + l as usize,
+        }
+    }
+}
+
+/// Pull the TXT strings out of a response to query `id`. Each record's character-strings are
+/// joined, as long values are split into 255-byte chunks.
+fn parse_txt_response(id: u16, buf: &[u8]) -> Result<Vec<String>, DnsError> {
+    if read_u16(buf, 0)? != id {
+        return Err(DnsError::Malformed);
+    }
+    let rcode = read_u16(buf, 2)? & 0x000F;
+    if rcode == RCODE_NXDOMAIN {
+        return Ok(Vec::new());
+    }
+    if rcode != 0 {
+        return Err(DnsError::Server(rcode));
+    }
+    let questions = read_u16(buf, 4)?;
+    let answers = read_u16(buf, 6)?;
+    let mut at = 12;
+    for _ in 0..questions {
+        at = skip_name(buf, at)? + 4;
+    }
+    let mut records = Vec::new();
+    for _ in 0..answers {
+        at = skip_name(buf, at)?;
+        let rtype = read_u16(buf, at)?;
+        let rdlen = read_u16(buf, at + 8)? as usize;
+        let rdata = buf.get(at + 10..at + 10 + rdlen).ok_or(DnsError::Malformed)?;
+        at += 10 + rdlen;
+        if rtype != TYPE_TXT {
+            continue;
+        }
+        let mut value = Vec::new();
+        let mut i = 0;
+        while i < rdata.len() {
+            let len = rdata[i] as usize;
+            value.extend_from_slice(rdata.get(i + 1..i + 1 + len).ok_or(DnsError::Malformed)?);
+            i += 1 + len;
+        }
+        records.push(String::from_utf8_lossy(&value).into_owned());
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A response to `query` carrying one TXT answer made of `chunks`, pointing back at the question name.
+    fn response_for(query: &[u8], chunks: &[&str]) -> Vec<u8> {
+        let mut out = query.to_vec();
+        out[2] = 0x81;
+        out[3] = 0x80;
+        out[7] = 1; // one answer
+        out.extend_from_slice(&[0xC0, 12]);
+        out.extend_from_slice(&TYPE_TXT.to_be_bytes());
+        out.extend_from_slice(&CLASS_IN.to_be_bytes());
+        out.extend_from_slice(&300u32.to_be_bytes());
+        let rdata: Vec<u8> = chunks.iter().flat_map(|c| std::iter::once(c.len() as u8).chain(c.bytes())).collect();
+        out.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        out.extend_from_slice(&rdata);
+        out
+    }
+
+    #[test]
+    fn encodes_the_question_as_labels() {
+        let query = encode_query(0xBEEF, "_verify.example.com").unwrap();
+        assert_eq!(&query[..2], &[0xBE, 0xEF]);
+        assert_eq!(&query[12..], b"\x07_verify\x07example\x03com\x00\x00\x10\x00\x01");
+        assert!(encode_query(1, "bad..name").is_err());
+    }
+
+    #[test]
+    fn joins_chunked_txt_strings() {
+        let query = encode_query(7, "example.com").unwrap();
+        let response = response_for(&query, &["token=", "abc123"]);
+        assert_eq!(parse_txt_response(7, &response).unwrap(), vec!["token=abc123".to_string()]);
+        assert!(parse_txt_response(8, &response).is_err(), "id must match");
+    }
+
+    #[test]
+    fn nxdomain_means_no_records() {
+        let mut response = encode_query(9, "missing.example.com").unwrap();
+        response[3] = 0x83;
+        assert!(parse_txt_response(9, &response).unwrap().is_empty());
+    }
+}
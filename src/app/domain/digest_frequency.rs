@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+/// How often a user receives the project digest email. Digests are opt-in, so the default is Off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DigestFrequency {
+    #[default]
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    /// Seconds between digests, or None when digests are off.
+    pub fn period_secs(self) -> Option<i64> {
+        match self {
+            Self::Off => None,
+            Self::Daily => Some(24 * 60 * 60),
+            Self::Weekly => Some(7 * 24 * 60 * 60),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn parses_lowercase_values() {
+        assert_eq!(DigestFrequency::from_str("weekly").unwrap(), DigestFrequency::Weekly);
+        assert_eq!(DigestFrequency::from_str("off").unwrap(), DigestFrequency::Off);
+        assert!(DigestFrequency::from_str("monthly").is_err());
+    }
+
+    #[test]
+    fn off_has_no_period() {
+        assert_eq!(DigestFrequency::Off.period_secs(), None);
+        assert_eq!(DigestFrequency::Daily.period_secs(), Some(86_400));
+    }
+}
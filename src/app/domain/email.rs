@@ -31,6 +31,11 @@ impl Email {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// The part after the `@`, e.g. "example.com".
+    pub fn domain(&self) -> &str {
+        self.0.rsplit_once('@').map_or("", |(_, domain)| domain)
+    }
 }
 
 #[cfg(test)]
@@ -61,4 +66,10 @@ mod tests {
         let result = Email::new(long_email);
         assert!(result.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn domain_is_the_part_after_the_at() {
+        let email = Email::new("Ada@Mail.Example.com".to_string()).unwrap();
+        assert_eq!(email.domain(), "mail.example.com");
+    }
+}
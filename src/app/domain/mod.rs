@@ -1,16 +1,20 @@
+pub mod digest_frequency;
 pub mod email;
 pub mod organization_id;
 pub mod organization_role;
 pub mod project_view_mode;
 pub mod password;
 pub mod profile_image_url;
+pub mod signup_mode;
 pub mod validation_helpers;
 pub mod user_id;
 
+pub use digest_frequency::DigestFrequency;
 pub use email::Email;
 pub use organization_id::OrganizationId;
 pub use organization_role::OrganizationRole;
 pub use password::{HashedPassword, Password};
 pub use project_view_mode::ProjectViewMode;
 pub use profile_image_url::ProfileImageUrl;
+pub use signup_mode::SignupMode;
 pub use user_id::UserId;
\ No newline at end of file
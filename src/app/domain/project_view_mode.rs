@@ -8,6 +8,8 @@ use strum_macros::{Display, EnumString};
 pub enum ProjectViewMode {
     Graph,
     List,
+    Board,
+    Timeline,
 }
 
 impl Default for ProjectViewMode {
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+/// Who may create a new account on this instance. Allowed email domains apply on top of any mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SignupMode {
+    /// Anyone can sign up.
+    #[default]
+    Open,
+    /// Only people holding an organization invite or invite link can sign up.
+    InviteOnly,
+    /// No new accounts; existing users can still log in.
+    Closed,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn parses_snake_case_values() {
+        assert_eq!(SignupMode::from_str("invite_only").unwrap(), SignupMode::InviteOnly);
+        assert_eq!(SignupMode::from_str("closed").unwrap(), SignupMode::Closed);
+        assert_eq!(SignupMode::InviteOnly.to_string(), "invite_only");
+        assert!(SignupMode::from_str("invite-only").is_err());
+    }
+}
@@ -29,3 +29,14 @@ pub fn check_image_extension(t: &str) -> Result<(), &'static str> {
         Ok(())
     }
 }
+
+/// Parse a strict calendar date in `YYYY-MM-DD` form (the `time` crate here has no parsing feature).
+pub fn parse_iso_date(value: &str) -> Option<time::Date> {
+    let mut parts = value.split('-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() || year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let month = time::Month::try_from(month.parse::<u8>().ok()?).ok()?;
+    time::Date::from_calendar_date(year.parse().ok()?, month, day.parse().ok()?).ok()
+}
@@ -18,6 +18,9 @@ pub enum AppError {
     /// Not found errors (404 Not Found) - resource not found
     NotFound(String),
 
+    /// Upload too large (413 Payload Too Large)
+    PayloadTooLarge(String),
+
     /// Database errors (500 Internal Server Error)
     Database(SqlxError),
 
@@ -37,6 +40,7 @@ impl IntoResponse for AppError {
             AppError::Validation(msg) => (StatusCode::BAD_REQUEST, msg),
             AppError::Auth(msg) => (StatusCode::BAD_REQUEST, msg),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            AppError::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg),
             AppError::Database(err) => {
                 tracing::error!(%err, "database error");
                 (
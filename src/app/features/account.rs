@@ -1,3 +1,4 @@
+use std::str::FromStr;
 use std::sync::LazyLock;
 
 use askama::Template;
@@ -8,15 +9,14 @@ use axum::{
     routing::{get, post},
     Router,
 };
-use axum_extra::extract::cookie::CookieJar;
 use regex::Regex;
 use serde::Deserialize;
 use validator::Validate;
 
 use crate::app::{
     db,
-    domain::{HashedPassword, Password, ProfileImageUrl, UserId},
-    session::{self, AuthenticatedSession},
+    domain::{DigestFrequency, HashedPassword, Password, ProfileImageUrl, UserId},
+    session::AuthenticatedSession,
     AppState, APP_NAME,
 };
 
@@ -43,9 +43,11 @@ pub struct AccountTemplate {
     pub email_notifications: bool,
     pub theme_mode: String,
     pub language: String,
+    pub digest_frequency: String,
     pub error: String,
     pub success: String,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
 }
 
 /// Change password form data.
@@ -98,6 +100,8 @@ pub struct UpdatePreferencesForm {
     pub email_notifications: Option<String>,
     pub theme_mode: String,
     pub language: String,
+    /// Digest cadence ("off", "daily", "weekly"); absent means off.
+    pub digest_frequency: Option<String>,
 }
 
 fn error_redirect(msg: &str) -> Redirect {
@@ -140,9 +144,11 @@ pub async fn show_account(
         email_notifications: user.email_notifications != 0,
         theme_mode: user.theme_mode.clone(),
         language: user.language.clone(),
+        digest_frequency: user.digest_frequency.clone(),
         error: query.error.unwrap_or_default(),
         success: query.success.unwrap_or_default(),
         current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
     };
 
     Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
@@ -269,6 +275,13 @@ pub async fn update_preferences(
     let email_notifications = matches!(form.email_notifications.as_deref(), Some("1"));
     let theme_mode = form.theme_mode.trim();
     let language = form.language.trim();
+    let digest_frequency = match form.digest_frequency.as_deref().map(str::trim) {
+        None | Some("") => Ok(DigestFrequency::Off),
+        Some(value) => DigestFrequency::from_str(value),
+    };
+    let Ok(digest_frequency) = digest_frequency else {
+        return error_redirect("Invalid preferences.").into_response();
+    };
     if theme_mode.is_empty()
         || !ALLOWED_THEME_MODES.contains(&theme_mode)
         || language.is_empty()
@@ -283,6 +296,7 @@ pub async fn update_preferences(
         email_notifications,
         theme_mode,
         language,
+        &digest_frequency.to_string(),
     )
     .await
     .is_err()
@@ -293,25 +307,6 @@ pub async fn update_preferences(
     Redirect::to("/app/account?success=preferences_updated").into_response()
 }
 
-/// POST /app/account/delete — Permanently delete the account.
-pub async fn delete_account(
-    AuthenticatedSession(session): AuthenticatedSession,
-    State(state): State<AppState>,
-    jar: CookieJar,
-) -> impl IntoResponse {
-    let user_id = match UserId::from_string(&session.user_id) {
-        Ok(id) => id,
-        Err(_) => return Redirect::to("/login").into_response(),
-    };
-
-    if db::users::delete(&state.db, &user_id).await.is_err() {
-        return error_redirect("Failed to delete account.").into_response();
-    }
-
-    let jar = jar.add(session::clear_session_cookie());
-    (jar, Redirect::to("/?success=account_deleted")).into_response()
-}
-
 /// Account routes.
 pub fn routes() -> Router<AppState> {
     Router::new()
@@ -319,5 +314,4 @@ pub fn routes() -> Router<AppState> {
         .route("/app/account/change-password", post(change_password))
         .route("/app/account/update-profile", post(update_profile))
         .route("/app/account/update-preferences", post(update_preferences))
-        .route("/app/account/delete", post(delete_account))
 }
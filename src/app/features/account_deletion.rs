@@ -0,0 +1,206 @@
+//! Account deletion. Projects are owned by whoever created them (`projects.user_id` cascades), so
+//! before the user row goes the projects they created — and the invites and invite links they made —
+//! are handed to another owner or admin of the same organization. Only projects in organizations
+//! nobody else can take over are deleted with the account. The sole owner of an organization that
+//! still has other members can't delete their account until someone else is made an owner.
+
+use std::collections::BTreeMap;
+
+use askama::Template;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::get,
+    Form, Router,
+};
+use axum_extra::extract::cookie::CookieJar;
+use serde::Deserialize;
+
+use crate::app::{
+    db,
+    domain::{OrganizationId, OrganizationRole, UserId},
+    session::{self, AuthenticatedSession},
+    AppState, APP_NAME,
+};
+
+/// A project that will be handed to someone else.
+pub struct TransferRow {
+    pub title: String,
+    pub org_name: String,
+    pub new_owner_email: String,
+}
+
+/// A project that will be deleted with the account.
+pub struct DeletedProjectRow {
+    pub title: String,
+    pub org_name: String,
+}
+
+/// What deleting an account will do.
+pub struct DeletionPlan {
+    /// Organizations where the user is the only owner and others are still members.
+    pub blocking_orgs: Vec<String>,
+    pub transfers: Vec<TransferRow>,
+    pub deleted_projects: Vec<DeletedProjectRow>,
+    /// Who takes over in each affected organization (None: nobody is left to).
+    successors: BTreeMap<String, Option<db::organizations::Successor>>,
+    pub time_entry_count: i64,
+    pub assigned_node_count: i64,
+}
+
+impl DeletionPlan {
+    pub fn is_blocked(&self) -> bool {
+        !self.blocking_orgs.is_empty()
+    }
+}
+
+fn parse_org_id(id: &str) -> Result<OrganizationId, sqlx::Error> {
+    OrganizationId::from_string(id).map_err(|_| sqlx::Error::Decode(format!("invalid organization id {}", id).into()))
+}
+
+/// Work out what deleting `user_id` would block on, transfer and delete.
+pub async fn plan_for(pool: &sqlx::SqlitePool, user_id: &UserId) -> Result<DeletionPlan, sqlx::Error> {
+    let memberships = db::organizations::list_memberships_for_user(pool, user_id).await?;
+    let projects = db::projects::list_created_by_user(pool, &user_id.as_str()).await?;
+
+    let mut org_names: BTreeMap<String, String> = BTreeMap::new();
+    let mut blocking_orgs = Vec::new();
+    for m in &memberships {
+        org_names.insert(m.organization_id.clone(), m.name.clone());
+        if m.role.parse::<OrganizationRole>() != Ok(OrganizationRole::Owner) {
+            continue;
+        }
+        let org_id = parse_org_id(&m.organization_id)?;
+        let owners = db::organizations::count_members_with_role(pool, &org_id, OrganizationRole::Owner).await?;
+        let members = db::organizations::count_members(pool, &org_id).await?;
+        if owners <= 1 && members > 1 {
+            blocking_orgs.push(m.name.clone());
+        }
+    }
+    // Projects can outlive the creator's membership (e.g. after they were removed from the org).
+    for p in &projects {
+        if !org_names.contains_key(&p.organization_id) {
+            let org_id = parse_org_id(&p.organization_id)?;
+            let name = db::organizations::find_by_id(pool, &org_id).await?.map(|o| o.name).unwrap_or_default();
+            org_names.insert(p.organization_id.clone(), name);
+        }
+    }
+
+    let mut successors = BTreeMap::new();
+    for org in org_names.keys() {
+        let org_id = parse_org_id(org)?;
+        successors.insert(org.clone(), db::organizations::find_successor_admin(pool, &org_id, user_id).await?);
+    }
+
+    let mut transfers = Vec::new();
+    let mut deleted_projects = Vec::new();
+    for p in projects {
+        let org_name = org_names.get(&p.organization_id).cloned().unwrap_or_default();
+        match successors.get(&p.organization_id).and_then(Option::as_ref) {
+            Some(s) => transfers.push(TransferRow { title: p.title, org_name, new_owner_email: s.email.clone() }),
+            None => deleted_projects.push(DeletedProjectRow { title: p.title, org_name }),
+        }
+    }
+
+    Ok(DeletionPlan {
+        blocking_orgs,
+        transfers,
+        deleted_projects,
+        successors,
+        time_entry_count: db::time_entries::count_by_user(pool, &user_id.as_str()).await?,
+        assigned_node_count: db::nodes::count_assigned_to_user(pool, &user_id.as_str()).await?,
+    })
+}
+
+/// Hand over projects and invites, then delete the user, in one transaction.
+async fn execute(pool: &sqlx::SqlitePool, user_id: &UserId, plan: &DeletionPlan) -> Result<(), sqlx::Error> {
+    let uid = user_id.as_str();
+    let mut tx = pool.begin().await?;
+    for (org_id, successor) in &plan.successors {
+        if let Some(s) = successor {
+            db::projects::transfer_creator(&mut *tx, org_id, &uid, &s.user_id).await?;
+            db::organization_invites::transfer_inviter(&mut *tx, org_id, &uid, &s.user_id).await?;
+            db::organization_invite_links::transfer_creator(&mut *tx, org_id, &uid, &s.user_id).await?;
+        }
+    }
+    db::users::delete(&mut *tx, user_id).await?;
+    tx.commit().await
+}
+
+/// Account deletion summary and confirmation template.
+#[derive(Template)]
+#[template(path = "account_deletion.html")]
+pub struct AccountDeletionTemplate {
+    pub app_name: &'static str,
+    pub plan: DeletionPlan,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+/// Confirmation form; the hidden `confirm_delete` must be "1".
+#[derive(Debug, Deserialize)]
+pub struct ConfirmDeleteForm {
+    pub confirm_delete: Option<String>,
+}
+
+/// GET /app/account/delete — What deleting the account will do, with the confirm button.
+pub async fn show(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+) -> Response {
+    let user_id = match UserId::from_string(&session.user_id) {
+        Ok(id) => id,
+        Err(_) => return Redirect::to("/login").into_response(),
+    };
+    let plan = match plan_for(&state.db, &user_id).await {
+        Ok(p) => p,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+    let template = AccountDeletionTemplate {
+        app_name: APP_NAME,
+        plan,
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    };
+    Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+}
+
+/// POST /app/account/delete — Permanently delete the account after handing over what others need.
+pub async fn delete_account(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<ConfirmDeleteForm>,
+) -> Response {
+    let user_id = match UserId::from_string(&session.user_id) {
+        Ok(id) => id,
+        Err(_) => return Redirect::to("/login").into_response(),
+    };
+    if form.confirm_delete.as_deref() != Some("1") {
+        return Redirect::to("/app/account/delete").into_response();
+    }
+    let plan = match plan_for(&state.db, &user_id).await {
+        Ok(p) => p,
+        Err(_) => return error_redirect("Failed to delete account."),
+    };
+    if plan.is_blocked() {
+        return Redirect::to("/app/account/delete").into_response();
+    }
+    if execute(&state.db, &user_id, &plan).await.is_err() {
+        return error_redirect("Failed to delete account.");
+    }
+
+    let jar = jar.add(session::clear_session_cookie());
+    (jar, Redirect::to("/?success=account_deleted")).into_response()
+}
+
+fn error_redirect(msg: &str) -> Response {
+    Redirect::to(&format!("/app/account?error={}", urlencoding::encode(msg))).into_response()
+}
+
+/// Account deletion routes.
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/app/account/delete", get(show).post(delete_account))
+}
@@ -0,0 +1,246 @@
+//! Instance admin pages: email outbox and signup policy. Access is limited to `Config::admin_emails`;
+//! everyone else gets 404.
+
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Form, Router,
+};
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::app::{
+    db::{self, email_outbox},
+    domain::{SignupMode, UserId},
+    features::auth::signup_policy::{self, SignupPolicy},
+    session::AuthenticatedSession,
+    AppState, APP_NAME,
+};
+
+/// Dead letters shown on the outbox page.
+const DEAD_LETTER_LIMIT: i64 = 200;
+
+/// One dead-lettered email on the outbox page.
+pub struct DeadLetterRow {
+    pub id: String,
+    pub to_email: String,
+    pub subject: String,
+    pub attempts: i64,
+    pub last_error: String,
+    pub created_at: String,
+}
+
+/// Email outbox admin template.
+#[derive(Template)]
+#[template(path = "admin_email.html")]
+pub struct AdminEmailTemplate {
+    pub app_name: &'static str,
+    pub pending_count: i64,
+    pub dead_letters: Vec<DeadLetterRow>,
+    pub success: String,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+/// Query parameters for the outbox page (success feedback).
+#[derive(Debug, Deserialize)]
+pub struct AdminEmailQuery {
+    pub success: Option<String>,
+}
+
+fn format_timestamp(ts: i64) -> String {
+    match OffsetDateTime::from_unix_timestamp(ts) {
+        Ok(dt) => format!("{} {:02}:{:02} UTC", dt.date(), dt.hour(), dt.minute()),
+        Err(_) => "—".to_string(),
+    }
+}
+
+/// Load the session user and confirm they are an instance admin. Returns their id.
+async fn require_admin(state: &AppState, user_id: &str) -> Result<UserId, Response> {
+    let not_found = || (StatusCode::NOT_FOUND, "Not found".to_string()).into_response();
+    let user_id = UserId::from_string(user_id).map_err(|_| not_found())?;
+    match db::users::find_by_id(&state.db, &user_id).await {
+        Ok(Some(user)) if state.config.is_admin_email(&user.email) => Ok(user_id),
+        Ok(_) => Err(not_found()),
+        Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response()),
+    }
+}
+
+/// GET /app/admin/email — Pending count and dead-lettered emails with a retry action.
+pub async fn show_email(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Query(query): Query<AdminEmailQuery>,
+) -> Response {
+    let user_id = match require_admin(&state, &session.user_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    let pending_count = match email_outbox::count_by_status(&state.db, email_outbox::STATUS_PENDING).await {
+        Ok(n) => n,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response(),
+    };
+    let dead = match email_outbox::find_dead(&state.db, DEAD_LETTER_LIMIT).await {
+        Ok(rows) => rows,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response(),
+    };
+    let dead_letters = dead
+        .into_iter()
+        .map(|e| DeadLetterRow {
+            id: e.id,
+            to_email: e.to_email,
+            subject: e.subject,
+            attempts: e.attempts,
+            last_error: e.last_error.unwrap_or_default(),
+            created_at: format_timestamp(e.created_at),
+        })
+        .collect();
+
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+    let template = AdminEmailTemplate {
+        app_name: APP_NAME,
+        pending_count,
+        dead_letters,
+        success: query.success.unwrap_or_default(),
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    };
+    Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+}
+
+/// POST /app/admin/email/:id/retry — Requeue a dead-lettered email with a fresh attempt budget.
+pub async fn retry_email(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Response {
+    if let Err(response) = require_admin(&state, &session.user_id).await {
+        return response;
+    }
+
+    match email_outbox::requeue_dead(&state.db, &id).await {
+        Ok(true) => Redirect::to("/app/admin/email?success=Email+requeued.").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Not found".to_string()).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response(),
+    }
+}
+
+/// Signup policy admin template.
+#[derive(Template)]
+#[template(path = "admin_signup.html")]
+pub struct AdminSignupTemplate {
+    pub app_name: &'static str,
+    pub mode: String,
+    /// Allowed domains, one per line, for the textarea.
+    pub allowed_domains: String,
+    pub overridden: bool,
+    /// What applies without an override, from the environment.
+    pub config_mode: String,
+    pub config_domains: String,
+    pub error: String,
+    pub success: String,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+/// Query parameters for the signup policy page (feedback after saving).
+#[derive(Debug, Deserialize)]
+pub struct AdminSignupQuery {
+    pub error: Option<String>,
+    pub success: Option<String>,
+}
+
+/// Form for POST /app/admin/signup.
+#[derive(Debug, Deserialize)]
+pub struct SignupPolicyForm {
+    pub mode: String,
+    #[serde(default)]
+    pub allowed_domains: String,
+}
+
+fn signup_redirect(key: &str, message: &str) -> Response {
+    Redirect::to(&format!("/app/admin/signup?{}={}", key, urlencoding::encode(message))).into_response()
+}
+
+/// GET /app/admin/signup — Who may create accounts, and the environment defaults it overrides.
+pub async fn show_signup(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Query(query): Query<AdminSignupQuery>,
+) -> Response {
+    let user_id = match require_admin(&state, &session.user_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    let policy = match SignupPolicy::load(&state.db, &state.config).await {
+        Ok(p) => p,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response(),
+    };
+    let defaults = SignupPolicy::from_config(&state.config);
+
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+    let template = AdminSignupTemplate {
+        app_name: APP_NAME,
+        mode: policy.mode.to_string(),
+        allowed_domains: policy.allowed_domains.join("\n"),
+        overridden: policy.overridden,
+        config_mode: defaults.mode.to_string(),
+        config_domains: defaults.allowed_domains.join(", "),
+        error: query.error.unwrap_or_default(),
+        success: query.success.unwrap_or_default(),
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    };
+    Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+}
+
+/// POST /app/admin/signup — Save a signup policy that takes precedence over the environment.
+pub async fn update_signup(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Form(form): Form<SignupPolicyForm>,
+) -> Response {
+    let user_id = match require_admin(&state, &session.user_id).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+    let Ok(mode) = form.mode.parse::<SignupMode>() else {
+        return signup_redirect("error", "Choose who can sign up.");
+    };
+    let allowed_domains = match signup_policy::parse_allowed_domains(&form.allowed_domains) {
+        Ok(d) => d,
+        Err(entry) => return signup_redirect("error", &format!("\"{}\" isn't a valid domain.", entry)),
+    };
+
+    match db::signup_policy::upsert(&state.db, mode, &allowed_domains, &user_id.as_str()).await {
+        Ok(()) => signup_redirect("success", "Signup policy saved."),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response(),
+    }
+}
+
+/// POST /app/admin/signup/reset — Drop the saved policy so the environment configuration applies.
+pub async fn reset_signup(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+) -> Response {
+    if let Err(response) = require_admin(&state, &session.user_id).await {
+        return response;
+    }
+    match db::signup_policy::delete(&state.db).await {
+        Ok(()) => signup_redirect("success", "Signup policy reset to the server configuration."),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response(),
+    }
+}
+
+/// Admin routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/app/admin/email", get(show_email))
+        .route("/app/admin/email/:id/retry", post(retry_email))
+        .route("/app/admin/signup", get(show_signup).post(update_signup))
+        .route("/app/admin/signup/reset", post(reset_signup))
+}
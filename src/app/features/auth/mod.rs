@@ -4,16 +4,17 @@ pub mod logout;
 pub mod verify_email;
 pub mod password_reset;
 pub mod resend_verification;
+pub mod signup_policy;
 
 use axum::Router;
 use crate::app::AppState;
 
-/// Authentication routes.
+/// Public authentication routes. Logout is mounted with the session-authenticated routes
+/// (behind CSRF protection) in `app::routes`.
 pub fn routes() -> Router<AppState> {
     Router::new()
         .merge(signup::routes())
         .merge(login::routes())
-        .merge(logout::routes())
         .merge(verify_email::routes())
         .merge(password_reset::routes())
         .merge(resend_verification::routes())
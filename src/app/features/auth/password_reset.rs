@@ -12,7 +12,7 @@ use rand_core::RngCore;
 use crate::app::{
     db,
     domain::{Email, Password, HashedPassword, UserId},
-    mail::EmailMessage,
+    mail::{self, EmailMessage},
     AppState, APP_NAME,
 };
 
@@ -125,13 +125,11 @@ pub async fn submit_forgot(
             if let Ok(()) = db::password_reset::insert_token(&state.db, &UserId::from_string(&user.id).unwrap(), &token, expires_at).await {
                 // Send email only when token was stored (link would be useless otherwise)
                 let reset_link = format!("{}/reset-password?token={}", state.config.app_url_base(), token);
-                let message = EmailMessage::new(
-                    email,
-                    "Reset your Boardtask password".to_string(),
-                    format!("Click this link to reset your password: {}", reset_link),
-                    state.config.mail_from.clone(),
-                );
-                let _ = state.mail.send(&message).await; // Ignore send errors
+                let brand = mail::org_brand(&state.db, &user.organization_id).await;
+                if let Ok(content) = mail::templates::password_reset(&brand, &reset_link) {
+                    let message = EmailMessage::from_content(email, content, state.config.mail_from.clone());
+                    let _ = state.mail.send(&message).await; // Ignore send errors
+                }
             }
         }
         _ => {
@@ -12,7 +12,7 @@ use validator::Validate;
 use crate::app::{
     db,
     domain::{Email, UserId},
-    mail::EmailMessage,
+    mail::{self, EmailMessage},
     AppState, APP_NAME,
 };
 
@@ -174,12 +174,13 @@ pub async fn submit(
                     )
                 };
 
-                let _ = state.mail.send(&EmailMessage::new(
-                    email.clone(),
-                    "Verify your email".to_string(),
-                    format!("Click here to verify your account: {}", url),
-                    state.config.mail_from.clone(),
-                )).await;
+                if let Ok(content) = mail::templates::verification(&url) {
+                    let _ = state.mail.send(&EmailMessage::from_content(
+                        email.clone(),
+                        content,
+                        state.config.mail_from.clone(),
+                    )).await;
+                }
 
                 true
             }
@@ -1,7 +1,8 @@
 use askama::Template;
 use axum::{
     extract::{Query, State},
-    response::{Html, IntoResponse, Redirect},
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
     Form, routing::get, Router,
 };
 use axum_extra::extract::cookie::CookieJar;
@@ -11,17 +12,21 @@ use validator::Validate;
 
 use crate::app::{
     db,
-    domain::{Email, Password, HashedPassword, UserId},
+    domain::{Email, Password, HashedPassword, OrganizationId, OrganizationRole, SignupMode, UserId},
     error::AppError,
-    mail::EmailMessage,
+    mail::{self, EmailMessage},
     AppState, APP_NAME,
 };
 
+use super::signup_policy::{self, InviteSource, SignupInvite, SignupPolicy};
+
 /// Query params for GET /signup (e.g. from invite flow).
 #[derive(Debug, Deserialize)]
 pub struct SignupQuery {
     pub email: Option<String>,
     pub next: Option<String>,
+    /// Invite or invite-link token, needed to sign up while signup is invite-only.
+    pub invite: Option<String>,
 }
 
 /// Safe redirect path: only allow relative paths starting with / to avoid open redirect.
@@ -52,6 +57,10 @@ pub struct SignupForm {
 
     /// Redirect path after verify (from invite flow). Optional, not validated here.
     pub next: Option<String>,
+
+    /// Invite token carried over from the invite flow. Checked against the signup policy; while signup
+    /// is invite-only the account joins the invite's organization.
+    pub invite: Option<String>,
 }
 
 /// Signup page template.
@@ -65,15 +74,24 @@ pub struct SignupTemplate {
     pub email: String,
     /// Safe next URL for hidden form field (empty if not set).
     pub next: String,
+    /// Invite token for hidden form field (empty if not set).
+    pub invite: String,
+    /// Set when the signup policy rules out signing up here; the form is hidden.
+    pub blocked_message: String,
+    /// Which email domains may sign up (empty when any may).
+    pub domain_hint: String,
 }
 
 /// Create a new user account and verification token. Returns (user_id, token) on success.
+/// With an `invite`, the account joins its organization (consuming the invite) instead of getting a
+/// personal one.
 async fn create_account(
     pool: &sqlx::SqlitePool,
     first_name: &str,
     last_name: &str,
     email: &Email,
     password: &Password,
+    invite: Option<&SignupInvite>,
 ) -> Result<(UserId, String), AppError> {
     // Check if email already exists
     if let Some(_) = db::find_by_email(pool, email).await.map_err(AppError::Database)? {
@@ -86,7 +104,10 @@ async fn create_account(
 
     // Generate user ID and Organization ID
     let user_id = UserId::new();
-    let org_id = crate::app::domain::OrganizationId::new();
+    let org_id = match invite {
+        Some(invite) => OrganizationId::from_string(&invite.organization_id).map_err(|_| AppError::Internal)?,
+        None => OrganizationId::new(),
+    };
 
     // Create new user
     let new_user = db::NewUser {
@@ -100,41 +121,66 @@ async fn create_account(
 
     let mut tx = pool.begin().await.map_err(AppError::Database)?;
 
-    // Create organization
-    let org_name = format!("{}'s Organization", email.as_str().split('@').next().unwrap_or("My"));
-    let new_org = db::organizations::NewOrganization {
-        id: org_id.clone(),
-        name: org_name.clone(),
-    };
-    db::organizations::insert(&mut *tx, &new_org).await.map_err(AppError::Database)?;
-
-    // Create default team for the org
-    let team_id = ulid::Ulid::new().to_string();
-    let new_team = db::teams::NewTeam {
-        id: team_id.clone(),
-        organization_id: org_id.as_str().to_string(),
-        name: org_name.clone(),
+    let (role, team_id) = match invite {
+        Some(invite) => {
+            let team = db::teams::find_in_org_or_default(&mut *tx, &org_id, invite.team_id.as_deref())
+                .await
+                .map_err(AppError::Database)?;
+            let role = invite.role.parse::<OrganizationRole>().unwrap_or(OrganizationRole::Member);
+            (role, team.map(|t| t.id))
+        }
+        None => {
+            // Create organization
+            let org_name = format!("{}'s Organization", email.as_str().split('@').next().unwrap_or("My"));
+            let new_org = db::organizations::NewOrganization {
+                id: org_id.clone(),
+                name: org_name.clone(),
+            };
+            db::organizations::insert(&mut *tx, &new_org).await.map_err(AppError::Database)?;
+
+            // Create default team for the org
+            let team_id = ulid::Ulid::new().to_string();
+            let new_team = db::teams::NewTeam {
+                id: team_id.clone(),
+                organization_id: org_id.as_str().to_string(),
+                name: org_name.clone(),
+            };
+            db::teams::insert(&mut *tx, &new_team).await.map_err(AppError::Database)?;
+            (OrganizationRole::Owner, Some(team_id))
+        }
     };
-    db::teams::insert(&mut *tx, &new_team).await.map_err(AppError::Database)?;
 
     // Insert user
     db::users::insert(&mut *tx, &new_user).await.map_err(AppError::Database)?;
 
     // Add user to organization
-    db::organizations::add_member(
-        &mut *tx,
-        &org_id,
-        &user_id,
-        crate::app::domain::OrganizationRole::Owner,
-    )
-    .await
-    .map_err(AppError::Database)?;
-
-    // Add user to default team
-    db::team_members::add_member(&mut *tx, &team_id, &user_id)
+    db::organizations::add_member(&mut *tx, &org_id, &user_id, role)
         .await
         .map_err(AppError::Database)?;
 
+    // Add user to the default (or invited) team
+    if let Some(team_id) = team_id {
+        db::team_members::add_member(&mut *tx, &team_id, &user_id)
+            .await
+            .map_err(AppError::Database)?;
+    }
+
+    // Joining uses up the invite, inside the same transaction as the account.
+    if let Some(invite) = invite {
+        let consumed = match &invite.source {
+            InviteSource::Email { invite_id } => db::organization_invites::delete_by_id(&mut *tx, invite_id)
+                .await
+                .map(|()| true),
+            InviteSource::Link { link_id } => db::organization_invite_links::record_use(&mut *tx, link_id).await,
+        }
+        .map_err(AppError::Database)?;
+        if !consumed {
+            return Err(AppError::Auth(
+                "This invite link is invalid, used up or has expired. Ask for a new one.".to_string(),
+            ));
+        }
+    }
+
     // Generate verification token
     let token = UserId::new().as_str();
     let expires_at = OffsetDateTime::now_utc() + Duration::hours(72);
@@ -146,10 +192,25 @@ async fn create_account(
     Ok((user_id, token))
 }
 
-/// GET /signup — Show signup form. Accepts ?email= and ?next= (e.g. from invite).
-pub async fn show(Query(query): Query<SignupQuery>) -> SignupTemplate {
+/// GET /signup — Show signup form. Accepts ?email=, ?next= and ?invite= (e.g. from invite).
+/// When the signup policy rules this visitor out, explains why instead of showing the form.
+pub async fn show(State(state): State<AppState>, Query(query): Query<SignupQuery>) -> Response {
     let email = query.email.unwrap_or_default();
     let next = safe_redirect_next(query.next);
+    let invite = query.invite.unwrap_or_default();
+    // Without the policy we can't tell who may sign up, so nobody can.
+    let Ok(policy) = SignupPolicy::load(&state.db, &state.config).await else {
+        return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response();
+    };
+    let blocked_message = match policy.mode {
+        SignupMode::Closed => signup_policy::CLOSED_MESSAGE.to_string(),
+        SignupMode::InviteOnly
+            if !signup_policy::invite_token_is_valid(&state.db, &invite, None).await.unwrap_or(false) =>
+        {
+            signup_policy::INVITE_ONLY_MESSAGE.to_string()
+        }
+        _ => String::new(),
+    };
     SignupTemplate {
         app_name: APP_NAME,
         error: String::new(),
@@ -157,7 +218,11 @@ pub async fn show(Query(query): Query<SignupQuery>) -> SignupTemplate {
         last_name: String::new(),
         email,
         next,
+        invite,
+        blocked_message,
+        domain_hint: policy.domain_message(),
     }
+    .into_response()
 }
 
 /// POST /signup — Process signup form.
@@ -165,68 +230,73 @@ pub async fn submit(
     State(state): State<AppState>,
     jar: CookieJar,
     Form(form): Form<SignupForm>,
-) -> Result<impl IntoResponse, Html<String>> {
+) -> Result<impl IntoResponse, Response> {
     let next = safe_redirect_next(form.next.clone());
-    // Validate form structure
-    if form.validate().is_err() {
+    let invite = form.invite.clone().unwrap_or_default();
+    let Ok(policy) = SignupPolicy::load(&state.db, &state.config).await else {
+        return Err((StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response());
+    };
+    // Re-render the form with the user's input and an error.
+    let form_error = |error: String| {
         let template = SignupTemplate {
             app_name: APP_NAME,
-            error: "First name and last name are required (1–100 characters each).".to_string(),
+            error,
             first_name: form.first_name.clone(),
             last_name: form.last_name.clone(),
             email: form.email.clone(),
             next: next.clone(),
+            invite: invite.clone(),
+            blocked_message: String::new(),
+            domain_hint: policy.domain_message(),
         };
-        return Err(Html(template.render().map_err(|_| "Template error".to_string())?));
+        Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+    };
+    // Validate form structure
+    if form.validate().is_err() {
+        return Err(form_error("First name and last name are required (1–100 characters each).".to_string()));
     }
 
     let first = form.first_name.trim();
     let last = form.last_name.trim();
     if first.is_empty() || last.is_empty() {
-        let template = SignupTemplate {
-            app_name: APP_NAME,
-            error: "First name and last name are required.".to_string(),
-            first_name: form.first_name.clone(),
-            last_name: form.last_name.clone(),
-            email: form.email.clone(),
-            next: next.clone(),
-        };
-        return Err(Html(template.render().map_err(|_| "Template error".to_string())?));
+        return Err(form_error("First name and last name are required.".to_string()));
     }
 
     // Parse into domain types
     let email = match Email::new(form.email.clone()) {
         Ok(email) => email,
-        Err(_) => {
-            let template = SignupTemplate {
-                app_name: APP_NAME,
-                error: "Invalid email address".to_string(),
-                first_name: form.first_name.clone(),
-                last_name: form.last_name.clone(),
-                email: form.email.clone(),
-                next: next.clone(),
-            };
-            return Err(Html(template.render().map_err(|_| "Template error".to_string())?));
-        }
+        Err(_) => return Err(form_error("Invalid email address".to_string())),
     };
 
+    let signup_invite = signup_policy::find_signup_invite(&state.db, &invite, Some(&email))
+        .await
+        .unwrap_or(None);
+    if let Some(reason) = policy.rejection(&email, signup_invite.is_some()) {
+        return Err(form_error(reason));
+    }
+    // While signup is invite-only the token is what let this account in, so the account joins the
+    // invite's organization rather than getting a personal one.
+    let joining = signup_invite.filter(|_| policy.mode == SignupMode::InviteOnly);
+
     let password = match Password::new(form.password) {
         Ok(password) => password,
-        Err(e) => {
-            let template = SignupTemplate {
-                app_name: APP_NAME,
-                error: e.message.unwrap_or_else(|| "Invalid password".into()).to_string(),
-                first_name: form.first_name.clone(),
-                last_name: form.last_name.clone(),
-                email: form.email.clone(),
-                next: next.clone(),
-            };
-            return Err(Html(template.render().map_err(|_| "Template error".to_string())?));
-        }
+        Err(e) => return Err(form_error(e.message.unwrap_or_else(|| "Invalid password".into()).to_string())),
+    };
+
+    // People from a verified company domain are offered to join that organization after verifying.
+    // Those who joined through their invite already have their organization.
+    let next = if joining.is_some() {
+        String::new()
+    } else if next.is_empty()
+        && db::organization_domains::is_verified_anywhere(&state.db, email.domain()).await.unwrap_or(false)
+    {
+        "/app/domain-join".to_string()
+    } else {
+        next.clone()
     };
 
     // Create account
-    match create_account(&state.db, first, last, &email, &password).await {
+    match create_account(&state.db, first, last, &email, &password, joining.as_ref()).await {
         Ok((_user_id, token)) => {
             let verify_path = if next.is_empty() {
                 format!("/verify-email?token={}", token)
@@ -238,12 +308,15 @@ pub async fn submit(
                 )
             };
             let url = format!("{}{}", state.config.app_url_base(), verify_path);
-            match state.mail.send(&EmailMessage::new(
-                email.clone(),
-                "Verify your email".to_string(),
-                format!("Click here to verify your account: {}", url),
-                state.config.mail_from.clone(),
-            )).await {
+            let sent = match mail::templates::verification(&url) {
+                Ok(content) => state.mail.send(&EmailMessage::from_content(
+                    email.clone(),
+                    content,
+                    state.config.mail_from.clone(),
+                )).await,
+                Err(e) => Err(e),
+            };
+            match sent {
                 Ok(()) => {
                     let redirect_to = if next.is_empty() {
                         format!("/check-email?email={}", urlencoding::encode(email.as_str()))
@@ -256,41 +329,11 @@ pub async fn submit(
                     };
                     Ok((jar, Redirect::to(&redirect_to)))
                 }
-                Err(_) => {
-                    let template = SignupTemplate {
-                        app_name: APP_NAME,
-                        error: "Failed to send verification email.".to_string(),
-                        first_name: form.first_name.clone(),
-                        last_name: form.last_name.clone(),
-                        email: form.email.clone(),
-                        next: next.clone(),
-                    };
-                    Err(Html(template.render().map_err(|_| "Template error".to_string())?))
-                }
+                Err(_) => Err(form_error("Failed to send verification email.".to_string())),
             }
         }
-        Err(AppError::Auth(msg)) => {
-            let template = SignupTemplate {
-                app_name: APP_NAME,
-                error: msg,
-                first_name: form.first_name.clone(),
-                last_name: form.last_name.clone(),
-                email: form.email.clone(),
-                next: next.clone(),
-            };
-            Err(Html(template.render().map_err(|_| "Template error".to_string())?))
-        }
-        Err(_) => {
-            let template = SignupTemplate {
-                app_name: APP_NAME,
-                error: "Internal server error".to_string(),
-                first_name: form.first_name.clone(),
-                last_name: form.last_name.clone(),
-                email: form.email.clone(),
-                next: next.clone(),
-            };
-            Err(Html(template.render().map_err(|_| "Template error".to_string())?))
-        }
+        Err(AppError::Auth(msg)) => Err(form_error(msg)),
+        Err(_) => Err(form_error("Internal server error".to_string())),
     }
 }
 
@@ -0,0 +1,208 @@
+//! Instance signup policy: whether new accounts need an invite, and which email domains they may
+//! use. `Config` provides the default; an instance admin can override it at /app/admin/signup.
+
+use crate::app::{
+    config::Config,
+    db,
+    domain::{Email, SignupMode},
+    features::organization::domains::normalize_domain,
+};
+
+/// Shown wherever an account would be created while signup is closed.
+pub const CLOSED_MESSAGE: &str =
+    "This instance isn't accepting new accounts. If you already have an account, log in instead.";
+
+/// Shown to someone signing up without an invite while signup is invite-only.
+pub const INVITE_ONLY_MESSAGE: &str =
+    "New accounts need an invitation. Ask someone in your organization to invite you, then follow the link they send.";
+
+/// The policy in effect for new accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignupPolicy {
+    pub mode: SignupMode,
+    /// Lowercase domains; empty allows any domain.
+    pub allowed_domains: Vec<String>,
+    /// Whether an admin override is in effect rather than the environment configuration.
+    pub overridden: bool,
+}
+
+impl SignupPolicy {
+    /// The policy from the environment, ignoring any admin override.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            mode: config.signup_mode,
+            allowed_domains: config.signup_allowed_domains.clone(),
+            overridden: false,
+        }
+    }
+
+    /// The admin override if one is saved, otherwise the environment configuration.
+    pub async fn load(pool: &sqlx::SqlitePool, config: &Config) -> Result<Self, sqlx::Error> {
+        let Some(row) = db::signup_policy::find(pool).await? else {
+            return Ok(Self::from_config(config));
+        };
+        Ok(Self {
+            // The table CHECK keeps mode valid; fall back to the safest mode if it ever isn't.
+            mode: row.mode.parse().unwrap_or(SignupMode::Closed),
+            allowed_domains: row
+                .allowed_domains
+                .split(',')
+                .filter(|d| !d.is_empty())
+                .map(str::to_string)
+                .collect(),
+            overridden: true,
+        })
+    }
+
+    /// Whether an address at `domain` may get an account.
+    pub fn allows_domain(&self, domain: &str) -> bool {
+        self.allowed_domains.is_empty() || self.allowed_domains.iter().any(|d| d.eq_ignore_ascii_case(domain))
+    }
+
+    /// Why `email` can't get an account, or None if it can. `invited` is whether they hold a valid
+    /// invite for that address. Allowed domains apply to invited people too.
+    pub fn rejection(&self, email: &Email, invited: bool) -> Option<String> {
+        match self.mode {
+            SignupMode::Closed => return Some(CLOSED_MESSAGE.to_string()),
+            SignupMode::InviteOnly if !invited => return Some(INVITE_ONLY_MESSAGE.to_string()),
+            _ => {}
+        }
+        if !self.allows_domain(email.domain()) {
+            return Some(self.domain_message());
+        }
+        None
+    }
+
+    /// Explains which addresses may sign up. Empty when any domain is allowed.
+    pub fn domain_message(&self) -> String {
+        if self.allowed_domains.is_empty() {
+            return String::new();
+        }
+        format!(
+            "New accounts are limited to email addresses at {}.",
+            self.allowed_domains.join(", ")
+        )
+    }
+}
+
+/// The organization a signup token invites someone into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignupInvite {
+    pub organization_id: String,
+    pub team_id: Option<String>,
+    pub role: String,
+    pub source: InviteSource,
+}
+
+/// Where a [`SignupInvite`] came from; joining through it consumes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InviteSource {
+    /// An emailed invite, deleted once accepted.
+    Email { invite_id: String },
+    /// An invite link, which records one use per person who joins.
+    Link { link_id: String },
+}
+
+/// The invite behind `token`: an emailed invite (addressed to `email`, when given) or a usable
+/// invite link. Expired and used-up invites don't count.
+pub async fn find_signup_invite(
+    pool: &sqlx::SqlitePool,
+    token: &str,
+    email: Option<&Email>,
+) -> Result<Option<SignupInvite>, sqlx::Error> {
+    if token.is_empty() {
+        return Ok(None);
+    }
+    if let Some(invite) = db::organization_invites::find_by_token(pool, token).await? {
+        if !email.is_none_or(|e| invite.email.eq_ignore_ascii_case(e.as_str())) {
+            return Ok(None);
+        }
+        return Ok(Some(SignupInvite {
+            organization_id: invite.organization_id,
+            team_id: invite.team_id,
+            role: invite.role,
+            source: InviteSource::Email { invite_id: invite.id },
+        }));
+    }
+    Ok(db::organization_invite_links::find_usable_by_token(pool, token).await?.map(|link| SignupInvite {
+        organization_id: link.organization_id,
+        team_id: link.team_id,
+        role: link.role,
+        source: InviteSource::Link { link_id: link.id },
+    }))
+}
+
+/// Whether `token` lets someone sign up; see [`find_signup_invite`].
+pub async fn invite_token_is_valid(
+    pool: &sqlx::SqlitePool,
+    token: &str,
+    email: Option<&Email>,
+) -> Result<bool, sqlx::Error> {
+    Ok(find_signup_invite(pool, token, email).await?.is_some())
+}
+
+/// Parse the admin form's domain list (comma, space or newline separated, duplicates dropped).
+/// Returns the first entry that isn't a domain as the error.
+pub fn parse_allowed_domains(input: &str) -> Result<Vec<String>, String> {
+    let mut domains: Vec<String> = Vec::new();
+    for entry in input.split(|c: char| c == ',' || c.is_whitespace()).filter(|s| !s.is_empty()) {
+        let domain = normalize_domain(entry).ok_or_else(|| entry.to_string())?;
+        if !domains.contains(&domain) {
+            domains.push(domain);
+        }
+    }
+    Ok(domains)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(mode: SignupMode, domains: &[&str]) -> SignupPolicy {
+        SignupPolicy {
+            mode,
+            allowed_domains: domains.iter().map(|d| d.to_string()).collect(),
+            overridden: false,
+        }
+    }
+
+    fn email(s: &str) -> Email {
+        Email::new(s.to_string()).unwrap()
+    }
+
+    #[test]
+    fn open_policy_accepts_anyone() {
+        assert_eq!(policy(SignupMode::Open, &[]).rejection(&email("a@example.com"), false), None);
+    }
+
+    #[test]
+    fn invite_only_requires_an_invite() {
+        let p = policy(SignupMode::InviteOnly, &[]);
+        assert_eq!(p.rejection(&email("a@example.com"), false).as_deref(), Some(INVITE_ONLY_MESSAGE));
+        assert_eq!(p.rejection(&email("a@example.com"), true), None);
+    }
+
+    #[test]
+    fn closed_rejects_invited_people_too() {
+        let p = policy(SignupMode::Closed, &[]);
+        assert_eq!(p.rejection(&email("a@example.com"), true).as_deref(), Some(CLOSED_MESSAGE));
+    }
+
+    #[test]
+    fn allowed_domains_apply_to_everyone() {
+        let p = policy(SignupMode::Open, &["acme.com"]);
+        assert_eq!(p.rejection(&email("Jo@ACME.com"), false), None);
+        let rejected = p.rejection(&email("jo@other.com"), true).unwrap();
+        assert!(rejected.contains("acme.com"));
+    }
+
+    #[test]
+    fn parses_admin_domain_list() {
+        assert_eq!(
+            parse_allowed_domains("acme.com, @Example.org\nacme.com").unwrap(),
+            vec!["acme.com".to_string(), "example.org".to_string()]
+        );
+        assert_eq!(parse_allowed_domains("acme.com nope").unwrap_err(), "nope");
+        assert!(parse_allowed_domains("  ").unwrap().is_empty());
+    }
+}
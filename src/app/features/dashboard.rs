@@ -1,28 +1,82 @@
+use std::collections::{HashMap, HashSet};
+
 use askama::Template;
 use axum::{
     extract::State,
+    http::StatusCode,
     response::{Html, IntoResponse, Redirect},
     routing::get,
     Router,
 };
+use time::OffsetDateTime;
 
 use crate::app::{
     db,
     domain::UserId,
+    features::projects::{format::format_estimated_minutes, progress},
     session::AuthenticatedSession,
+    tenant,
     AppState, APP_NAME,
 };
 
+/// How many nodes the "Recently updated" list shows.
+const RECENT_LIMIT: i64 = 10;
+
+/// A node assigned to the current user.
+pub struct WorkItem {
+    pub node_id: String,
+    pub title: String,
+    pub project_id: String,
+    pub project_title: String,
+    pub estimated_display: String,
+    pub blocked: bool,
+}
+
+/// Assigned nodes with one status, in the org's status order.
+pub struct StatusGroup {
+    pub name: String,
+    pub items: Vec<WorkItem>,
+}
+
+/// A recently changed node in one of the user's projects.
+pub struct RecentItem {
+    pub title: String,
+    pub project_id: String,
+    pub project_title: String,
+    pub status_name: String,
+    pub updated_display: String,
+}
+
 /// Dashboard page template.
 #[derive(Template)]
 #[template(path = "dashboard.html")]
 pub struct DashboardTemplate {
     pub app_name: &'static str,
     pub display_name: String,
+    pub status_groups: Vec<StatusGroup>,
+    /// Assigned nodes that are not done.
+    pub open_count: usize,
+    pub blocked_count: usize,
+    /// Sum of estimates on assigned nodes that are not done.
+    pub remaining_display: String,
+    pub recent: Vec<RecentItem>,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
 }
 
-/// GET /app — Show dashboard. Requires a valid session; redirects to /login if unauthenticated.
+/// "just now", "5 min ago", "3 h ago", "2 d ago".
+fn format_ago(now: i64, ts: i64) -> String {
+    let secs = (now - ts).max(0);
+    match secs {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{} min ago", secs / 60),
+        3600..=86_399 => format!("{} h ago", secs / 3600),
+        _ => format!("{} d ago", secs / 86_400),
+    }
+}
+
+/// GET /app — Personal work hub: nodes assigned to the user across the org's projects, grouped by
+/// status, and recent changes in projects on the user's teams. Redirects to /login if unauthenticated.
 pub async fn show(
     AuthenticatedSession(session): AuthenticatedSession,
     State(state): State<AppState>,
@@ -35,6 +89,87 @@ pub async fn show(
         Ok(Some(u)) => u,
         _ => return Redirect::to("/login").into_response(),
     };
+    if tenant::require_org_member(&state.db, &session.user_id, &session.organization_id)
+        .await
+        .is_err()
+    {
+        return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response();
+    }
+
+    let (assigned, recent_nodes, projects, task_statuses) = match tokio::try_join!(
+        db::nodes::find_assigned_in_org(&state.db, &session.organization_id, &session.user_id),
+        db::nodes::find_recently_updated_for_team_member(
+            &state.db,
+            &session.organization_id,
+            &session.user_id,
+            RECENT_LIMIT,
+        ),
+        db::projects::list_for_org(&state.db, &session.organization_id),
+        db::task_statuses::get_all_task_statuses(&state.db),
+    ) {
+        Ok(t) => t,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+
+    let project_titles: HashMap<&str, &str> =
+        projects.iter().map(|p| (p.id.as_str(), p.title.as_str())).collect();
+    let status_names: HashMap<&str, &str> =
+        task_statuses.iter().map(|s| (s.id.as_str(), s.name.as_str())).collect();
+
+    // Blocked state needs each project's whole graph, so load only projects with assigned work.
+    let project_ids: HashSet<&str> = assigned.iter().map(|n| n.project_id.as_str()).collect();
+    let mut blocked: HashSet<String> = HashSet::new();
+    for project_id in project_ids {
+        let (nodes, edges) = match tokio::try_join!(
+            db::nodes::find_by_project(&state.db, project_id),
+            db::node_edges::find_by_project(&state.db, project_id),
+        ) {
+            Ok(t) => t,
+            Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+        };
+        blocked.extend(progress::blocked_node_ids(&nodes, &edges).into_iter().map(String::from));
+    }
+
+    let open: Vec<&db::nodes::Node> = assigned
+        .iter()
+        .filter(|n| n.status_id != db::task_statuses::DONE_STATUS_ID)
+        .collect();
+    let open_count = open.len();
+    let blocked_count = open.iter().filter(|n| blocked.contains(&n.id)).count();
+    let remaining_minutes: i64 = open.iter().filter_map(|n| n.estimated_minutes).sum();
+
+    let status_groups = task_statuses
+        .iter()
+        .map(|s| StatusGroup {
+            name: s.name.clone(),
+            items: assigned
+                .iter()
+                .filter(|n| n.status_id == s.id)
+                .map(|n| WorkItem {
+                    node_id: n.id.clone(),
+                    title: n.title.clone(),
+                    project_id: n.project_id.clone(),
+                    project_title: project_titles.get(n.project_id.as_str()).copied().unwrap_or_default().to_string(),
+                    estimated_display: n.estimated_minutes.map(format_estimated_minutes).unwrap_or_default(),
+                    blocked: blocked.contains(&n.id),
+                })
+                .collect(),
+        })
+        .filter(|g: &StatusGroup| !g.items.is_empty())
+        .collect();
+
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let recent = recent_nodes
+        .iter()
+        .map(|n| RecentItem {
+            title: n.title.clone(),
+            project_id: n.project_id.clone(),
+            project_title: project_titles.get(n.project_id.as_str()).copied().unwrap_or_default().to_string(),
+            status_name: status_names.get(n.status_id.as_str()).copied().unwrap_or_default().to_string(),
+            updated_display: format_ago(now, n.updated_at.unwrap_or(n.created_at)),
+        })
+        .collect();
+
     let display_name = db::users::display_name(&user);
     let current_user_avatar_url =
         db::users::profile_image_url_for(&state.db, &user_id)
@@ -42,7 +177,13 @@ pub async fn show(
     let template = DashboardTemplate {
         app_name: APP_NAME,
         display_name,
+        status_groups,
+        open_count,
+        blocked_count,
+        remaining_display: format_estimated_minutes(remaining_minutes),
+        recent,
         current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
     };
     Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
 }
@@ -51,4 +192,18 @@ pub async fn show(
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/app", get(show))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_ago;
+
+    #[test]
+    fn format_ago_picks_largest_unit() {
+        assert_eq!(format_ago(1_000, 990), "just now");
+        assert_eq!(format_ago(1_000, 1_010), "just now");
+        assert_eq!(format_ago(10_000, 10_000 - 5 * 60), "5 min ago");
+        assert_eq!(format_ago(100_000, 100_000 - 3 * 3600), "3 h ago");
+        assert_eq!(format_ago(1_000_000, 1_000_000 - 2 * 86_400), "2 d ago");
+    }
+}
@@ -0,0 +1,142 @@
+//! Scheduled digest email: per project the user's team works on, what got done since the last
+//! digest, what is blocked, what is assigned to them, and the estimated work left.
+//!
+//! Users opt in with a daily or weekly frequency; turning off email notifications stops digests too.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::time::Duration;
+
+use time::OffsetDateTime;
+
+use crate::app::{
+    db::{self, task_statuses},
+    domain::{DigestFrequency, Email},
+    features::projects::{format::format_estimated_minutes, progress},
+    mail::{self, templates, EmailMessage},
+    AppState,
+};
+
+/// How often the scheduler looks for users whose digest is due.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Send every digest due at `now`. Returns how many were sent.
+/// A failure for one user is logged and does not stop the others.
+pub async fn send_due(state: &AppState, now: i64) -> Result<usize, sqlx::Error> {
+    let users = db::users::find_digest_due(&state.db, now).await?;
+    let mut sent = 0;
+
+    for user in users {
+        match send_to(state, &user, now).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            Err(e) => tracing::warn!(error = %e, user_id = %user.id, "Failed to send digest"),
+        }
+    }
+
+    Ok(sent)
+}
+
+/// Check for due digests forever. Spawn once at startup.
+pub async fn run_scheduler(state: AppState) {
+    loop {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        if let Err(e) = send_due(&state, now).await {
+            tracing::error!(error = %e, "Digest scheduler failed to load due users");
+        }
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
+
+/// Compose and send one user's digest. Returns false when there was nothing to send.
+/// Snapshots only advance once the email has gone out, so a failed send is retried with the same
+/// baseline.
+async fn send_to(state: &AppState, user: &db::User, now: i64) -> Result<bool, sqlx::Error> {
+    let frequency = DigestFrequency::from_str(&user.digest_frequency).unwrap_or_default();
+    let Some(period) = frequency.period_secs() else {
+        return Ok(false);
+    };
+    let since = user.digest_last_sent_at.unwrap_or(now - period);
+
+    let projects = db::projects::list_for_team_member(&state.db, &user.id).await?;
+    let mut sections = Vec::with_capacity(projects.len());
+    let mut snapshots = Vec::with_capacity(projects.len());
+    for project in &projects {
+        let (section, blocked_ids) = project_section(state, user, project, since).await?;
+        sections.push(section);
+        snapshots.push((project.id.as_str(), blocked_ids));
+    }
+
+    if !sections.is_empty() {
+        let Ok(to) = Email::new(user.email.clone()) else {
+            return Ok(false);
+        };
+        let brand = mail::org_brand(&state.db, &user.organization_id).await;
+        let content = match templates::digest(&brand, &frequency.to_string(), &sections) {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to render digest email");
+                return Ok(false);
+            }
+        };
+        let message = EmailMessage::from_content(to, content, state.config.mail_from.clone());
+        if let Err(e) = state.mail.send(&message).await {
+            tracing::warn!(error = %e, user_id = %user.id, "Failed to send digest email");
+            return Ok(false);
+        }
+    }
+
+    // Advance the clock even when there were no projects so an empty user isn't rechecked hourly.
+    let mut tx = state.db.begin().await?;
+    for (project_id, blocked_ids) in &snapshots {
+        db::digest_snapshots::upsert(&mut tx, &user.id, project_id, blocked_ids, now).await?;
+    }
+    db::users::mark_digest_sent(&mut *tx, &user.id, now).await?;
+    tx.commit().await?;
+    Ok(!sections.is_empty())
+}
+
+/// Stats for one project, plus the blocked node IDs to record once the digest is sent.
+async fn project_section(
+    state: &AppState,
+    user: &db::User,
+    project: &db::projects::Project,
+    since: i64,
+) -> Result<(templates::DigestProject, Vec<String>), sqlx::Error> {
+    let nodes = db::nodes::find_by_project(&state.db, &project.id).await?;
+    let edges = db::node_edges::find_by_project(&state.db, &project.id).await?;
+
+    let blocked_ids = progress::blocked_node_ids(&nodes, &edges);
+    // Without a previous digest there is no baseline, so nothing counts as newly blocked.
+    let previous: HashSet<String> = db::digest_snapshots::find_blocked_node_ids(&state.db, &user.id, &project.id)
+        .await?
+        .map(|ids| ids.into_iter().collect())
+        .unwrap_or_else(|| blocked_ids.iter().map(|id| id.to_string()).collect());
+    let newly_blocked = blocked_ids.iter().filter(|id| !previous.contains(**id)).count() as i64;
+    let completed = db::nodes::count_completed_since(&state.db, &project.id, since).await?;
+
+    let is_done = |n: &db::nodes::Node| n.status_id == task_statuses::DONE_STATUS_ID;
+    let assigned = nodes
+        .iter()
+        .filter(|n| !is_done(n) && n.assigned_user_id.as_deref() == Some(user.id.as_str()))
+        .map(|n| n.title.clone())
+        .collect();
+    let remaining: i64 = nodes
+        .iter()
+        .filter(|n| !is_done(n))
+        .filter_map(|n| n.estimated_minutes)
+        .sum();
+
+    let section = templates::DigestProject {
+        title: project.title.clone(),
+        url: format!("{}/app/projects/{}", state.config.app_url_base(), project.id),
+        completed,
+        blocked: blocked_ids.len() as i64,
+        newly_blocked,
+        assigned,
+        remaining: format_estimated_minutes(remaining),
+    };
+    let mut blocked_ids: Vec<String> = blocked_ids.into_iter().map(String::from).collect();
+    blocked_ids.sort();
+    Ok((section, blocked_ids))
+}
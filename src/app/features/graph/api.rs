@@ -10,9 +10,14 @@ pub fn routes() -> Router<AppState> {
         .merge(crate::app::features::graph::get_task_statuses::routes())
         .merge(crate::app::features::graph::get_project_members::routes())
         .merge(crate::app::features::graph::slots::routes())
+        .merge(crate::app::features::graph::labels::routes())
+        .merge(crate::app::features::graph::time_entries::routes())
+        .merge(crate::app::features::graph::checklist::routes())
+        .merge(crate::app::features::graph::attachments::routes())
         .merge(crate::app::features::graph::create_node::routes())
         .merge(crate::app::features::graph::update_node::routes())
         .merge(crate::app::features::graph::delete_node::routes())
+        .merge(crate::app::features::graph::restore_node::routes())
         .merge(crate::app::features::graph::create_edge::routes())
         .merge(crate::app::features::graph::delete_edge::routes())
         .merge(crate::app::features::graph::insert_between::routes())
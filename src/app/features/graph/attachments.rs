@@ -0,0 +1,288 @@
+use axum::{
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+
+use crate::app::{
+    db,
+    error::AppError,
+    session::ApiAuthenticatedSession,
+    storage::{self, BlobStorage, StorageError},
+    AppState,
+};
+
+/// Multipart field carrying the uploaded file.
+const FILE_FIELD: &str = "file";
+
+/// Longest stored filename, in characters.
+const MAX_FILENAME_CHARS: usize = 255;
+
+/// Path parameters for a single attachment.
+#[derive(Debug, Deserialize)]
+pub struct AttachmentPathParams {
+    pub project_id: String,
+    pub id: String,
+    pub attachment_id: String,
+}
+
+/// Response for listing a node's attachments.
+#[derive(Debug, Serialize)]
+pub struct AttachmentsResponse {
+    pub attachments: Vec<db::attachments::Attachment>,
+}
+
+/// Load an attachment of an accessible node.
+async fn load_attachment(
+    pool: &sqlx::SqlitePool,
+    params: &AttachmentPathParams,
+    user_id: &str,
+) -> Result<db::attachments::Attachment, AppError> {
+    let (_, node) = super::helpers::load_project_node(pool, &params.project_id, &params.id, user_id).await?;
+    db::attachments::find_by_id(pool, &params.attachment_id)
+        .await?
+        .filter(|a| a.node_id == node.id)
+        .ok_or_else(|| AppError::NotFound("Attachment not found".to_string()))
+}
+
+fn storage_error(e: StorageError) -> AppError {
+    tracing::error!(error = %e, "attachment storage error");
+    AppError::Internal
+}
+
+/// The client's filename reduced to something safe to store and echo back: no directories,
+/// control characters or quotes.
+fn clean_filename(raw: Option<&str>) -> String {
+    let base = raw.unwrap_or_default().rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(MAX_FILENAME_CHARS)
+        .collect();
+    let cleaned = cleaned.trim();
+    if cleaned.is_empty() || cleaned == "." || cleaned == ".." {
+        "attachment".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Content-Disposition for a download: inline for types browsers render safely, otherwise a
+/// forced download. Carries an ASCII fallback name and the exact UTF-8 name.
+fn content_disposition(filename: &str, content_type: &str) -> String {
+    let kind = if storage::is_inline_safe(content_type) { "inline" } else { "attachment" };
+    let ascii: String = filename
+        .chars()
+        .map(|c| if c.is_ascii() && c != '\\' { c } else { '_' })
+        .collect();
+    format!(
+        "{}; filename=\"{}\"; filename*=UTF-8''{}",
+        kind,
+        ascii,
+        urlencoding::encode(filename)
+    )
+}
+
+/// Remove stored blobs that no attachment or pending node deletion references any more. Run by
+/// the background purge jobs, never in a request.
+///
+/// Each blob is removed under the exclusive [`storage::BlobLock`]: its row is deleted only if it is
+/// still orphaned, and the bytes only once the row is gone, so an upload of the same bytes either
+/// committed first (and the row stays) or waits and stores the bytes again. If the bytes can't be
+/// removed the row is put back for the next sweep. Failures are logged and skipped.
+pub async fn remove_orphaned_blobs(
+    pool: &sqlx::SqlitePool,
+    storage: &dyn BlobStorage,
+    lock: &storage::BlobLock,
+) -> Result<u64, sqlx::Error> {
+    let mut removed = 0;
+    for (hash, size_bytes) in db::attachments::find_orphaned_blobs(pool).await? {
+        let _exclusive = lock.write().await;
+        if !db::attachments::delete_blob_if_orphaned(pool, &hash).await? {
+            continue;
+        }
+        if let Err(e) = storage.delete(&hash).await {
+            tracing::warn!(error = %e, hash, "Failed to remove orphaned attachment blob");
+            db::attachments::insert_blob(pool, &hash, size_bytes).await?;
+            continue;
+        }
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// [`remove_orphaned_blobs`] for the background jobs, where a failed sweep is only logged.
+pub async fn sweep_orphaned_blobs(state: &AppState) {
+    if let Err(e) = remove_orphaned_blobs(&state.db, state.storage.as_ref(), &state.blob_lock).await {
+        tracing::warn!(error = %e, "Failed to sweep orphaned attachment blobs");
+    }
+}
+
+/// GET /api/projects/:project_id/nodes/:id/attachments — A node's attachments.
+pub async fn list_attachments(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<super::types::NodePathParams>,
+) -> Result<Json<AttachmentsResponse>, AppError> {
+    let (_, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.id, &session.user_id).await?;
+    let attachments = db::attachments::find_by_node(&state.db, &node.id).await?;
+    Ok(Json(AttachmentsResponse { attachments }))
+}
+
+/// POST /api/projects/:project_id/nodes/:id/attachments — Upload a file (multipart field `file`).
+///
+/// The size limit is enforced while reading, and the stored MIME type comes from the file's
+/// contents, not what the client declared.
+pub async fn upload_attachment(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<super::types::NodePathParams>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<db::attachments::Attachment>), AppError> {
+    let (_, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.id, &session.user_id).await?;
+    let max_bytes = state.config.attachment_max_bytes;
+    let too_large = || AppError::PayloadTooLarge(format!("Attachments can be at most {} bytes", max_bytes));
+    let invalid = |_| AppError::Validation("Invalid upload".to_string());
+
+    let (filename, bytes) = loop {
+        let Some(mut field) = multipart.next_field().await.map_err(invalid)? else {
+            return Err(AppError::Validation("No file uploaded".to_string()));
+        };
+        if field.name() != Some(FILE_FIELD) {
+            continue;
+        }
+        let filename = clean_filename(field.file_name());
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(invalid)? {
+            if bytes.len() + chunk.len() > max_bytes {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        break (filename, bytes);
+    };
+    if bytes.is_empty() {
+        return Err(AppError::Validation("File is empty".to_string()));
+    }
+
+    let content_hash = storage::content_key(&bytes);
+    let size_bytes = bytes.len() as i64;
+    let attachment = db::attachments::NewAttachment {
+        id: Ulid::new().to_string(),
+        node_id: node.id,
+        content_hash: content_hash.clone(),
+        filename,
+        content_type: storage::sniff_content_type(&bytes).to_string(),
+        size_bytes,
+        uploaded_by_user_id: Some(session.user_id),
+    };
+
+    // Rows first, then bytes, all under the shared blob lock: the sweep can't remove the blob
+    // between this upload recording it and storing its bytes.
+    let _shared = state.blob_lock.read().await;
+    let mut tx = state.db.begin().await?;
+    db::attachments::insert_blob(&mut *tx, &content_hash, size_bytes).await?;
+    db::attachments::insert(&mut *tx, &attachment).await?;
+    tx.commit().await?;
+
+    if let Err(e) = state.storage.put(&content_hash, &bytes).await {
+        // The blob row is left for the background sweep.
+        db::attachments::delete(&state.db, &attachment.id).await?;
+        return Err(storage_error(e));
+    }
+
+    let created = db::attachments::find_by_id(&state.db, &attachment.id)
+        .await?
+        .ok_or(AppError::Internal)?;
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// GET /api/projects/:project_id/nodes/:id/attachments/:attachment_id — Download an attachment.
+pub async fn download_attachment(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<AttachmentPathParams>,
+) -> Result<Response, AppError> {
+    let attachment = load_attachment(&state.db, &params, &session.user_id).await?;
+    let bytes = state
+        .storage
+        .get(&attachment.content_hash)
+        .await
+        .map_err(storage_error)?
+        .ok_or_else(|| {
+            tracing::error!(hash = %attachment.content_hash, "attachment blob missing from storage");
+            AppError::NotFound("Attachment not found".to_string())
+        })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type.clone()),
+            (
+                header::CONTENT_DISPOSITION,
+                content_disposition(&attachment.filename, &attachment.content_type),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            (header::CACHE_CONTROL, "private, max-age=0".to_string()),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// DELETE /api/projects/:project_id/nodes/:id/attachments/:attachment_id — Delete an attachment.
+pub async fn delete_attachment(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<AttachmentPathParams>,
+) -> Result<StatusCode, AppError> {
+    let attachment = load_attachment(&state.db, &params, &session.user_id).await?;
+    db::attachments::delete(&state.db, &attachment.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Attachment routes. Uploads bypass the default body limit; the handler enforces
+/// `attachment_max_bytes` itself.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/projects/:project_id/nodes/:id/attachments",
+            get(list_attachments)
+                .post(upload_attachment)
+                .layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/api/projects/:project_id/nodes/:id/attachments/:attachment_id",
+            get(download_attachment).delete(delete_attachment),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filenames_lose_directories_and_unsafe_characters() {
+        assert_eq!(clean_filename(Some("../../etc/passwd")), "passwd");
+        assert_eq!(clean_filename(Some("C:\\Users\\me\\spec.pdf")), "spec.pdf");
+        assert_eq!(clean_filename(Some("a\"b\r\n.txt")), "ab.txt");
+        assert_eq!(clean_filename(Some("..")), "attachment");
+        assert_eq!(clean_filename(None), "attachment");
+    }
+
+    #[test]
+    fn only_safe_types_are_served_inline() {
+        assert_eq!(
+            content_disposition("shot.png", "image/png"),
+            "inline; filename=\"shot.png\"; filename*=UTF-8''shot.png"
+        );
+        assert_eq!(
+            content_disposition("notes é.html", "text/plain; charset=utf-8"),
+            "attachment; filename=\"notes _.html\"; filename*=UTF-8''notes%20%C3%A9.html"
+        );
+    }
+}
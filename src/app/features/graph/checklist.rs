@@ -0,0 +1,216 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, patch},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use validator::Validate;
+
+use crate::app::{
+    db,
+    error::AppError,
+    session::ApiAuthenticatedSession,
+    tenant,
+    AppState,
+};
+
+/// Upper bound on items per node; anything longer belongs in separate nodes.
+pub const MAX_ITEMS_PER_NODE: i64 = 200;
+
+/// Longest item text, in characters.
+pub const MAX_TEXT_CHARS: u64 = 500;
+
+/// Path parameters for a single checklist item.
+#[derive(Debug, Deserialize)]
+pub struct ChecklistItemPathParams {
+    pub project_id: String,
+    pub id: String,
+    pub item_id: String,
+}
+
+/// A node's checklist with its progress.
+#[derive(Debug, Serialize)]
+pub struct ChecklistResponse {
+    pub done: usize,
+    pub total: usize,
+    pub items: Vec<db::checklist_items::ChecklistItem>,
+}
+
+/// Request body for adding a checklist item. It goes to the end unless `sort_order` is given.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateChecklistItemRequest {
+    #[validate(length(min = 1, max = MAX_TEXT_CHARS))]
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+    pub assigned_user_id: Option<String>,
+    pub sort_order: Option<i64>,
+}
+
+/// Request body for updating a checklist item.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateChecklistItemRequest {
+    #[validate(length(min = 1, max = MAX_TEXT_CHARS))]
+    pub text: Option<String>,
+    pub done: Option<bool>,
+    /// Absent = leave unchanged, null or "" = clear, non-empty string = set.
+    #[serde(default, deserialize_with = "super::update_node::deserialize_optional_option")]
+    pub assigned_user_id: Option<Option<String>>,
+    pub sort_order: Option<i64>,
+}
+
+/// Trimmed item text, rejecting text that is only whitespace.
+fn clean_text(text: &str) -> Result<String, AppError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(AppError::Validation("Checklist item text is required".to_string()));
+    }
+    Ok(text.to_string())
+}
+
+/// GET /api/projects/:project_id/nodes/:id/checklist — A node's checklist items in order.
+pub async fn list_items(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<super::types::NodePathParams>,
+) -> Result<Json<ChecklistResponse>, AppError> {
+    let (_, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.id, &session.user_id).await?;
+    let items = db::checklist_items::find_by_node(&state.db, &node.id).await?;
+    Ok(Json(ChecklistResponse {
+        done: items.iter().filter(|i| i.done).count(),
+        total: items.len(),
+        items,
+    }))
+}
+
+/// POST /api/projects/:project_id/nodes/:id/checklist — Add an item to a node's checklist.
+pub async fn create_item(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<super::types::NodePathParams>,
+    Json(request): Json<CreateChecklistItemRequest>,
+) -> Result<(StatusCode, Json<db::checklist_items::ChecklistItem>), AppError> {
+    request
+        .validate()
+        .map_err(|_| AppError::Validation("Invalid input".to_string()))?;
+    let text = clean_text(&request.text)?;
+
+    let (project, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.id, &session.user_id).await?;
+
+    let assigned_user_id = request.assigned_user_id.filter(|uid| !uid.is_empty());
+    if let Some(ref uid) = assigned_user_id {
+        tenant::require_org_member(&state.db, uid, &project.organization_id)
+            .await
+            .map_err(|_| AppError::Validation("Invalid assigned_user_id".to_string()))?;
+    }
+
+    if db::checklist_items::count_by_node(&state.db, &node.id).await? >= MAX_ITEMS_PER_NODE {
+        return Err(AppError::Validation(format!(
+            "A checklist can have at most {} items",
+            MAX_ITEMS_PER_NODE
+        )));
+    }
+
+    let sort_order = match request.sort_order {
+        Some(order) => order,
+        None => db::checklist_items::next_sort_order(&state.db, &node.id).await?,
+    };
+    let item = db::checklist_items::NewChecklistItem {
+        id: Ulid::new().to_string(),
+        node_id: node.id,
+        text,
+        done: request.done,
+        assigned_user_id,
+        sort_order,
+    };
+    db::checklist_items::insert(&state.db, &item).await?;
+
+    let created = db::checklist_items::find_by_id(&state.db, &item.id)
+        .await?
+        .ok_or(AppError::Internal)?;
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// PATCH /api/projects/:project_id/nodes/:id/checklist/:item_id — Edit, tick off, assign or move an item.
+pub async fn update_item(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<ChecklistItemPathParams>,
+    Json(request): Json<UpdateChecklistItemRequest>,
+) -> Result<Json<db::checklist_items::ChecklistItem>, AppError> {
+    request
+        .validate()
+        .map_err(|_| AppError::Validation("Invalid input".to_string()))?;
+
+    let (project, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.id, &session.user_id).await?;
+    let item = db::checklist_items::find_by_id(&state.db, &params.item_id)
+        .await?
+        .filter(|i| i.node_id == node.id)
+        .ok_or_else(|| AppError::NotFound("Checklist item not found".to_string()))?;
+
+    let text = match request.text.as_deref() {
+        Some(text) => clean_text(text)?,
+        None => item.text.clone(),
+    };
+    let assigned_user_id = match &request.assigned_user_id {
+        None => item.assigned_user_id.as_deref(),
+        Some(uid) => match uid.as_deref().filter(|uid| !uid.is_empty()) {
+            None => None,
+            Some(uid) => {
+                tenant::require_org_member(&state.db, uid, &project.organization_id)
+                    .await
+                    .map_err(|_| AppError::Validation("Invalid assigned_user_id".to_string()))?;
+                Some(uid)
+            }
+        },
+    };
+
+    db::checklist_items::update(
+        &state.db,
+        &item.id,
+        &text,
+        request.done.unwrap_or(item.done),
+        assigned_user_id,
+        request.sort_order.unwrap_or(item.sort_order),
+    )
+    .await?;
+
+    let updated = db::checklist_items::find_by_id(&state.db, &item.id)
+        .await?
+        .ok_or(AppError::Internal)?;
+
+    Ok(Json(updated))
+}
+
+/// DELETE /api/projects/:project_id/nodes/:id/checklist/:item_id — Remove an item.
+pub async fn delete_item(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<ChecklistItemPathParams>,
+) -> Result<StatusCode, AppError> {
+    let (_, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.id, &session.user_id).await?;
+    let item = db::checklist_items::find_by_id(&state.db, &params.item_id)
+        .await?
+        .filter(|i| i.node_id == node.id)
+        .ok_or_else(|| AppError::NotFound("Checklist item not found".to_string()))?;
+
+    db::checklist_items::delete(&state.db, &item.id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Checklist routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/projects/:project_id/nodes/:id/checklist",
+            get(list_items).post(create_item),
+        )
+        .route(
+            "/api/projects/:project_id/nodes/:id/checklist/:item_id",
+            patch(update_item).delete(delete_item),
+        )
+}
@@ -30,6 +30,9 @@ pub struct CreateNodeRequest {
     pub assigned_user_id: Option<String>,
     #[validate(custom(function = "crate::app::features::graph::helpers::validate_estimated_minutes"))]
     pub estimated_minutes: Option<i64>,
+    /// Labels of the project's organization to put on the node.
+    #[serde(default)]
+    pub label_ids: Vec<String>,
 }
 
 /// Response for a created node.
@@ -47,6 +50,7 @@ pub struct NodeResponse {
     pub slot_id: Option<String>,
     pub parent_id: Option<String>,
     pub assigned_user_id: Option<String>,
+    pub label_ids: Vec<String>,
 }
 
 /// Validates create-node request (sync rules + DB-backed node_type_id, status_id, slot_id, parent_id, assigned_user_id).
@@ -145,6 +149,7 @@ pub async fn create_node(
     let node_id = Ulid::new().to_string();
     let (node_type_id, status_id, slot_id, parent_id, assigned_user_id) =
         validate_create_node_request(&request, &state.db, &project_id, &project.organization_id, &node_id).await?;
+    let label_ids = super::labels::validate_label_ids(&state.db, &project.organization_id, &request.label_ids).await?;
 
     let new_node = db::nodes::NewNode {
         id: node_id.clone(),
@@ -160,10 +165,16 @@ pub async fn create_node(
     };
 
     db::nodes::insert(&state.db, &new_node).await?;
+    if !label_ids.is_empty() {
+        db::labels::set_for_node(&state.db, &node_id, &label_ids).await?;
+    }
 
     let node = db::nodes::find_by_id(&state.db, &node_id)
         .await?
         .ok_or_else(|| AppError::Internal)?;
+    let label_ids = db::labels::find_ids_for_node(&state.db, &node.id).await?;
+
+    super::notify::node_changed(&state, &session.user_id, &project, &node, None).await;
 
     let response = NodeResponse {
         id: node.id,
@@ -178,6 +189,7 @@ pub async fn create_node(
         slot_id: node.slot_id,
         parent_id: node.parent_id,
         assigned_user_id: node.assigned_user_id,
+        label_ids,
     };
 
     Ok((StatusCode::CREATED, Json(response)))
@@ -28,7 +28,7 @@ pub async fn delete_edge(
     Json(request): Json<CreateEdgeRequest>,
 ) -> Result<StatusCode, AppError> {
     // Validate org membership on every write
-    let _project = super::helpers::ensure_project_accessible(&state.db, &project_id, &session.user_id).await?;
+    let project = super::helpers::ensure_project_accessible(&state.db, &project_id, &session.user_id).await?;
 
     // Validate both nodes exist and belong to the project
     let parent_node = db::nodes::find_by_id(&state.db, &request.parent_id)
@@ -47,9 +47,14 @@ pub async fn delete_edge(
         return Err(AppError::NotFound("Child node not found".to_string()));
     }
 
+    // Removing a dependency can unblock the child; snapshot first so its assignee can be told.
+    let blocked_before = super::notify::blocked_snapshot(&state, &project.id).await?;
+
     // Delete the edge (idempotent - succeeds even if edge doesn't exist)
     db::node_edges::delete(&state.db, &request.parent_id, &request.child_id).await?;
 
+    super::notify::nodes_unblocked(&state, &session.user_id, &project, &blocked_before).await;
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -4,6 +4,9 @@ use axum::{
     routing::delete,
     Router,
 };
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use ulid::Ulid;
 
 use crate::app::{
     db,
@@ -12,6 +15,44 @@ use crate::app::{
     AppState,
 };
 
+/// How long a deletion can be undone (see [`super::restore_node`]).
+pub const UNDO_WINDOW_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Everything a node deletion removed or rewired, stored as JSON in `node_deletions.snapshot`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeletedNodeSnapshot {
+    pub node: db::nodes::Node,
+    pub parent_ids: Vec<String>,
+    pub child_ids: Vec<String>,
+    /// Nodes whose parent_id (group) was this node.
+    pub group_member_ids: Vec<String>,
+    /// Parent → child edges added to bridge the gap; only ones that did not already exist.
+    pub bypass_edges: Vec<BypassEdge>,
+    /// Removed by the ON DELETE CASCADE on time_entries.
+    pub time_entries: Vec<db::time_entries::TimeEntry>,
+    /// Removed by the ON DELETE CASCADE on node_labels. Absent in snapshots from before labels.
+    #[serde(default)]
+    pub label_ids: Vec<String>,
+    /// Removed by the ON DELETE CASCADE on node_checklist_items. Absent in snapshots from before checklists.
+    #[serde(default)]
+    pub checklist_items: Vec<db::checklist_items::ChecklistItem>,
+    /// Removed by the ON DELETE CASCADE on node_attachments. Their blobs stay in storage while the
+    /// deletion record holds them (`node_deletion_blobs`). Absent in snapshots from before attachments.
+    #[serde(default)]
+    pub attachments: Vec<db::attachments::Attachment>,
+}
+
+/// An edge a deletion added between the node's parents and children.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BypassEdge {
+    pub parent_id: String,
+    pub child_id: String,
+    /// The edge's created_at, so restore leaves it alone if it was removed and re-added since.
+    /// Absent in snapshots from before it was recorded (stored as a `[parent_id, child_id]` pair).
+    #[serde(default)]
+    pub created_at: Option<i64>,
+}
+
 /// DELETE /api/projects/:project_id/nodes/:id — Delete a node.
 pub async fn delete_node(
     ApiAuthenticatedSession(session): ApiAuthenticatedSession,
@@ -19,7 +60,7 @@ pub async fn delete_node(
     Path(params): Path<super::types::NodePathParams>,
 ) -> Result<StatusCode, AppError> {
     // Validate org membership on every write
-    let _project = super::helpers::ensure_project_accessible(
+    let project = super::helpers::ensure_project_accessible(
         &state.db,
         &params.project_id,
         &session.user_id,
@@ -41,11 +82,20 @@ pub async fn delete_node(
         db::node_edges::find_parents_of(&state.db, &node.id).await?;
     let children =
         db::node_edges::find_children_of(&state.db, &node.id).await?;
+    let group_member_ids = db::nodes::find_child_ids_by_parent(&state.db, &node.id).await?;
+    let time_entries = db::time_entries::find_by_node(&state.db, &node.id).await?;
+    let label_ids = db::labels::find_ids_for_node(&state.db, &node.id).await?;
+    let checklist_items = db::checklist_items::find_by_node(&state.db, &node.id).await?;
+    let attachments = db::attachments::find_by_node(&state.db, &node.id).await?;
+    // Removing a blocker can unblock its children; remember what was blocked so their assignees can be told.
+    let blocked_before = super::notify::blocked_snapshot(&state, &project.id).await?;
 
     // Transactionally: rewire edges between parents and children, clear parent_id on children, then delete the node.
     let mut tx = state.db.begin().await.map_err(AppError::Database)?;
 
     // For each parent/child pair, create an edge parent -> child, skipping self-loops.
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let mut bypass_edges = Vec::new();
     for parent_id in &parents {
         for child_id in &children {
             if parent_id == child_id {
@@ -57,9 +107,16 @@ pub async fn delete_node(
                 child_id: child_id.clone(),
             };
 
-            db::node_edges::insert_if_not_exists(&mut *tx, &edge)
+            if db::node_edges::insert_if_not_exists_at(&mut *tx, &edge, now)
                 .await
-                .map_err(AppError::Database)?;
+                .map_err(AppError::Database)?
+            {
+                bypass_edges.push(BypassEdge {
+                    parent_id: parent_id.clone(),
+                    child_id: child_id.clone(),
+                    created_at: Some(now),
+                });
+            }
         }
     }
 
@@ -72,8 +129,39 @@ pub async fn delete_node(
         .await
         .map_err(AppError::Database)?;
 
+    let snapshot = DeletedNodeSnapshot {
+        node,
+        parent_ids: parents,
+        child_ids: children,
+        group_member_ids,
+        bypass_edges,
+        time_entries,
+        label_ids,
+        checklist_items,
+        attachments,
+    };
+    let deletion = db::node_deletions::NodeDeletion {
+        id: Ulid::new().to_string(),
+        project_id: params.project_id.clone(),
+        node_id: snapshot.node.id.clone(),
+        deleted_by: Some(session.user_id.clone()),
+        snapshot: serde_json::to_string(&snapshot).map_err(|_| AppError::Internal)?,
+        created_at: now,
+    };
+    db::node_deletions::insert(&mut *tx, &deletion).await?;
+    for attachment in &snapshot.attachments {
+        db::attachments::insert_deletion_hold(&mut *tx, &deletion.id, &attachment.content_hash).await?;
+    }
+
     tx.commit().await.map_err(AppError::Database)?;
 
+    super::notify::nodes_unblocked(&state, &session.user_id, &project, &blocked_before).await;
+
+    // Undo records past the window are dead weight; drop them opportunistically.
+    if let Err(e) = db::node_deletions::delete_older_than(&state.db, deletion.created_at - UNDO_WINDOW_SECS).await {
+        tracing::warn!(error = %e, "Failed to prune expired node deletion records");
+    }
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -81,4 +169,16 @@ pub async fn delete_node(
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/api/projects/:project_id/nodes/:id", delete(delete_node))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bypass_edges_from_older_snapshots_read_as_pairs() {
+        let edge: BypassEdge = serde_json::from_str(r#"["parent", "child"]"#).unwrap();
+        assert_eq!((edge.parent_id.as_str(), edge.child_id.as_str()), ("parent", "child"));
+        assert_eq!(edge.created_at, None);
+    }
+}
@@ -1,5 +1,7 @@
+use std::collections::HashSet;
+
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     routing::get,
     Json, Router,
 };
@@ -8,31 +10,69 @@ use serde::Serialize;
 use crate::app::{
     db,
     error::AppError,
+    features::projects::filter::{self, NodeFilter, NodeFilterQuery},
     session::ApiAuthenticatedSession,
     AppState,
 };
 
-/// Response for the full graph (nodes + edges).
+/// Response for the project graph (nodes + edges).
 #[derive(Debug, Serialize)]
 pub struct GraphResponse {
     pub nodes: Vec<db::nodes::Node>,
     pub edges: Vec<db::node_edges::NodeEdge>,
+    /// Every label in the project's organization, so clients can show and offer them.
+    pub labels: Vec<db::labels::Label>,
+    /// Which returned nodes carry which labels.
+    pub node_labels: Vec<db::labels::NodeLabel>,
+    /// Checklist done/total counts for returned nodes that have a checklist.
+    pub checklist_progress: Vec<db::checklist_items::ChecklistProgress>,
+    /// Present when a filter is applied: the nodes that matched it, as opposed to neighbors
+    /// and groups included for context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_node_ids: Option<Vec<String>>,
 }
 
-/// GET /api/projects/:project_id/graph — Get full project graph.
+/// GET /api/projects/:project_id/graph — Get the project graph, optionally filtered and sorted
+/// (see [`filter`] for the query parameters).
 pub async fn get_graph(
     ApiAuthenticatedSession(session): ApiAuthenticatedSession,
     State(state): State<AppState>,
     Path(project_id): Path<String>,
+    Query(query): Query<NodeFilterQuery>,
 ) -> Result<Json<GraphResponse>, AppError> {
+    let node_filter = NodeFilter::from_query(&query, &session.user_id).map_err(AppError::Validation)?;
+
     // Validate org membership (scoped read)
-    super::helpers::ensure_project_accessible(&state.db, &project_id, &session.user_id).await?;
+    let project = super::helpers::ensure_project_accessible(&state.db, &project_id, &session.user_id).await?;
 
     // Fetch nodes and edges
     let nodes = db::nodes::find_by_project(&state.db, &project_id).await?;
     let edges = db::node_edges::find_by_project(&state.db, &project_id).await?;
+    let node_labels = db::labels::find_node_labels_by_project(&state.db, &project_id).await?;
+    let labels = db::labels::find_by_organization(&state.db, &project.organization_id).await?;
+    let checklist_progress = db::checklist_items::progress_by_project(&state.db, &project_id).await?;
+    let statuses = db::task_statuses::get_all_task_statuses(&state.db).await?;
+
+    let active = node_filter.is_active();
+    let graph = filter::filter_graph(nodes, edges, &db::labels::by_node(&node_labels), &node_filter, &statuses);
+    let kept: HashSet<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+    let node_labels = node_labels
+        .into_iter()
+        .filter(|nl| kept.contains(nl.node_id.as_str()))
+        .collect();
+    let checklist_progress = checklist_progress
+        .into_iter()
+        .filter(|p| kept.contains(p.node_id.as_str()))
+        .collect();
 
-    Ok(Json(GraphResponse { nodes, edges }))
+    Ok(Json(GraphResponse {
+        nodes: graph.nodes,
+        edges: graph.edges,
+        labels,
+        node_labels,
+        checklist_progress,
+        matched_node_ids: active.then_some(graph.matched_ids),
+    }))
 }
 
 /// Graph routes.
@@ -41,4 +41,19 @@ pub async fn ensure_project_accessible(
         })?;
 
     Ok(project)
-}
\ No newline at end of file
+}
+
+/// Load a node after checking project access; 404 if it belongs to another project.
+pub(crate) async fn load_project_node(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    node_id: &str,
+    user_id: &str,
+) -> Result<(db::projects::Project, db::nodes::Node), AppError> {
+    let project = ensure_project_accessible(pool, project_id, user_id).await?;
+    let node = db::nodes::find_by_id(pool, node_id)
+        .await?
+        .filter(|n| n.project_id == project_id)
+        .ok_or_else(|| AppError::NotFound("Node not found".to_string()))?;
+    Ok((project, node))
+}
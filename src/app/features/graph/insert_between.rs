@@ -196,6 +196,8 @@ pub async fn insert_between(
         .await?
         .ok_or_else(|| AppError::Internal)?;
 
+    super::notify::node_changed(&state, &session.user_id, &project, &node, None).await;
+
     let response = crate::app::features::graph::create_node::NodeResponse {
         id: node.id,
         project_id: node.project_id,
@@ -209,6 +211,8 @@ pub async fn insert_between(
         slot_id: node.slot_id,
         parent_id: node.parent_id,
         assigned_user_id: node.assigned_user_id,
+        // A node inserted between two others starts without labels.
+        label_ids: Vec::new(),
     };
 
     Ok((StatusCode::CREATED, Json(response)))
@@ -0,0 +1,215 @@
+//! Organization labels. Every project in an organization shares its labels; they are managed from
+//! any of its projects and put on nodes through the create/update node requests (`label_ids`).
+
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, patch},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use validator::Validate;
+
+use crate::app::{db, error::AppError, session::ApiAuthenticatedSession, AppState};
+
+/// Path parameters for label endpoints with ID.
+#[derive(Debug, Deserialize)]
+pub struct LabelPathParams {
+    pub project_id: String,
+    pub id: String,
+}
+
+/// Response for listing labels.
+#[derive(Debug, Serialize)]
+pub struct LabelsResponse {
+    pub labels: Vec<db::labels::Label>,
+}
+
+/// Request body for creating a label.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateLabelRequest {
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    /// `#rrggbb`.
+    pub color: String,
+}
+
+/// Request body for updating a label. Omitted fields are left unchanged.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateLabelRequest {
+    #[validate(length(min = 1, max = 50))]
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+/// Lowercase `#rrggbb`, or None when `input` isn't a hex color in that form.
+pub fn normalize_color(input: &str) -> Option<String> {
+    let hex = input.trim().strip_prefix('#')?;
+    (hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())).then(|| format!("#{}", hex.to_lowercase()))
+}
+
+/// Check that every ID names a label of the organization; returns them without duplicates.
+pub async fn validate_label_ids(
+    pool: &sqlx::SqlitePool,
+    organization_id: &str,
+    label_ids: &[String],
+) -> Result<Vec<String>, AppError> {
+    let org_labels: HashSet<String> = db::labels::find_by_organization(pool, organization_id)
+        .await?
+        .into_iter()
+        .map(|l| l.id)
+        .collect();
+    let mut ids: Vec<String> = Vec::new();
+    for id in label_ids {
+        if !org_labels.contains(id) {
+            return Err(AppError::Validation("Invalid label_ids".to_string()));
+        }
+        if !ids.contains(id) {
+            ids.push(id.clone());
+        }
+    }
+    Ok(ids)
+}
+
+fn trimmed_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Label name is required".to_string()));
+    }
+    Ok(name.to_string())
+}
+
+fn is_duplicate_name(labels: &[db::labels::Label], name: &str, except_id: Option<&str>) -> bool {
+    labels
+        .iter()
+        .any(|l| Some(l.id.as_str()) != except_id && l.name.to_lowercase() == name.to_lowercase())
+}
+
+/// Load a label, treating labels of other organizations as missing.
+async fn find_org_label(
+    pool: &sqlx::SqlitePool,
+    organization_id: &str,
+    id: &str,
+) -> Result<db::labels::Label, AppError> {
+    db::labels::find_by_id(pool, id)
+        .await?
+        .filter(|l| l.organization_id == organization_id)
+        .ok_or_else(|| AppError::NotFound("Label not found".to_string()))
+}
+
+/// GET /api/projects/:project_id/labels — List the labels of the project's organization.
+pub async fn list_labels(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<LabelsResponse>, AppError> {
+    let project = super::helpers::ensure_project_accessible(&state.db, &project_id, &session.user_id).await?;
+    let labels = db::labels::find_by_organization(&state.db, &project.organization_id).await?;
+    Ok(Json(LabelsResponse { labels }))
+}
+
+/// POST /api/projects/:project_id/labels — Create a label in the project's organization.
+pub async fn create_label(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(request): Json<CreateLabelRequest>,
+) -> Result<(StatusCode, Json<db::labels::Label>), AppError> {
+    request
+        .validate()
+        .map_err(|_| AppError::Validation("Invalid input".to_string()))?;
+    let name = trimmed_name(&request.name)?;
+    let color = normalize_color(&request.color)
+        .ok_or_else(|| AppError::Validation("Invalid color".to_string()))?;
+
+    let project = super::helpers::ensure_project_accessible(&state.db, &project_id, &session.user_id).await?;
+
+    let existing = db::labels::find_by_organization(&state.db, &project.organization_id).await?;
+    if is_duplicate_name(&existing, &name, None) {
+        return Err(AppError::Validation("Duplicate label name".to_string()));
+    }
+
+    let label = db::labels::NewLabel {
+        id: Ulid::new().to_string(),
+        organization_id: project.organization_id.clone(),
+        name,
+        color,
+    };
+    db::labels::insert(&state.db, &label).await?;
+
+    let created = db::labels::find_by_id(&state.db, &label.id)
+        .await?
+        .ok_or(AppError::Internal)?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// PATCH /api/projects/:project_id/labels/:id — Rename or recolor a label.
+pub async fn update_label(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<LabelPathParams>,
+    Json(request): Json<UpdateLabelRequest>,
+) -> Result<Json<db::labels::Label>, AppError> {
+    request
+        .validate()
+        .map_err(|_| AppError::Validation("Invalid input".to_string()))?;
+
+    let project = super::helpers::ensure_project_accessible(&state.db, &params.project_id, &session.user_id).await?;
+    let label = find_org_label(&state.db, &project.organization_id, &params.id).await?;
+
+    let name = match &request.name {
+        Some(n) => trimmed_name(n)?,
+        None => label.name.clone(),
+    };
+    let color = match &request.color {
+        Some(c) => normalize_color(c).ok_or_else(|| AppError::Validation("Invalid color".to_string()))?,
+        None => label.color.clone(),
+    };
+    let existing = db::labels::find_by_organization(&state.db, &project.organization_id).await?;
+    if is_duplicate_name(&existing, &name, Some(&label.id)) {
+        return Err(AppError::Validation("Duplicate label name".to_string()));
+    }
+
+    db::labels::update(&state.db, &label.id, &name, &color).await?;
+
+    let updated = db::labels::find_by_id(&state.db, &label.id)
+        .await?
+        .ok_or(AppError::Internal)?;
+    Ok(Json(updated))
+}
+
+/// DELETE /api/projects/:project_id/labels/:id — Delete a label, removing it from every node.
+pub async fn delete_label(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<LabelPathParams>,
+) -> Result<StatusCode, AppError> {
+    let project = super::helpers::ensure_project_accessible(&state.db, &params.project_id, &session.user_id).await?;
+    let label = find_org_label(&state.db, &project.organization_id, &params.id).await?;
+    db::labels::delete(&state.db, &label.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Label routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/projects/:project_id/labels", get(list_labels).post(create_label))
+        .route("/api/projects/:project_id/labels/:id", patch(update_label).delete(delete_label))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_must_be_six_digit_hex() {
+        assert_eq!(normalize_color(" #A1B2C3 ").as_deref(), Some("#a1b2c3"));
+        assert_eq!(normalize_color("a1b2c3"), None);
+        assert_eq!(normalize_color("#abc"), None);
+        assert_eq!(normalize_color("#ggggggg"), None);
+        assert_eq!(normalize_color("#12345g"), None);
+    }
+}
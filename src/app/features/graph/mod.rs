@@ -1,17 +1,23 @@
 mod defaults;
 pub mod api;
+pub mod attachments;
+pub mod checklist;
 pub mod create_node;
 pub mod update_node;
 pub mod delete_node;
+pub mod restore_node;
 pub mod get_graph;
 pub mod create_edge;
 pub mod delete_edge;
 pub mod insert_between;
+pub mod labels;
 pub mod helpers;
+pub mod notify;
 pub mod types;
 pub mod get_node_types;
 pub mod get_project_members;
 pub mod get_task_statuses;
 pub mod slots;
+pub mod time_entries;
 
 pub use defaults::sync_system_node_types;
\ No newline at end of file
@@ -0,0 +1,180 @@
+//! Email notifications for graph writes: assignment, new work in an owned slot, and unblocked nodes.
+//!
+//! Recipients opt out via `users.email_notifications`, and the actor never hears about their own
+//! change. Delivery is best-effort: failures are logged and never fail the write that triggered them.
+
+use std::collections::HashSet;
+
+use crate::app::{
+    db,
+    domain::{Email, UserId},
+    features::projects::progress,
+    mail::{self, templates, EmailMessage},
+    AppState,
+};
+
+/// One notification: subject line, headline, and a sentence of detail.
+struct Notice {
+    subject: String,
+    headline: String,
+    detail: String,
+}
+
+/// Notify about a created or updated node. `previous` is the node before the write (None on create).
+/// The assignee hears about a new assignment; the slot owner hears about a node entering their slot
+/// unless they were just told about it as assignee.
+pub async fn node_changed(
+    state: &AppState,
+    actor_id: &str,
+    project: &db::projects::Project,
+    node: &db::nodes::Node,
+    previous: Option<&db::nodes::Node>,
+) {
+    let brand = mail::org_brand(&state.db, &project.organization_id).await;
+    let url = project_url(state, &project.id);
+
+    let newly_assigned = node
+        .assigned_user_id
+        .as_deref()
+        .filter(|uid| previous.and_then(|p| p.assigned_user_id.as_deref()) != Some(*uid));
+    if let Some(assignee) = newly_assigned {
+        let notice = Notice {
+            subject: format!("You were assigned \"{}\"", node.title),
+            headline: "You have a new assignment".to_string(),
+            detail: format!("You've been assigned \"{}\" in {}.", node.title, project.title),
+        };
+        send(state, actor_id, assignee, &brand, &url, notice).await;
+    }
+
+    let entered_slot = node
+        .slot_id
+        .as_deref()
+        .filter(|sid| previous.and_then(|p| p.slot_id.as_deref()) != Some(*sid));
+    let Some(slot_id) = entered_slot else {
+        return;
+    };
+    let slot = match db::project_slots::find_by_id(&state.db, slot_id).await {
+        Ok(Some(s)) => s,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load slot for notification");
+            return;
+        }
+    };
+    let Some(owner) = slot.assigned_user_id.as_deref() else {
+        return;
+    };
+    if newly_assigned == Some(owner) {
+        return;
+    }
+    let notice = Notice {
+        subject: format!("New node in {}: \"{}\"", slot.name, node.title),
+        headline: format!("New work in {}", slot.name),
+        detail: format!("\"{}\" was added to your slot {} in {}.", node.title, slot.name, project.title),
+    };
+    send(state, actor_id, owner, &brand, &url, notice).await;
+}
+
+/// Snapshot of blocked node IDs, taken before a write that may unblock work.
+pub async fn blocked_snapshot(
+    state: &AppState,
+    project_id: &str,
+) -> Result<HashSet<String>, sqlx::Error> {
+    let (nodes, edges) = load_graph(state, project_id).await?;
+    Ok(progress::blocked_node_ids(&nodes, &edges)
+        .into_iter()
+        .map(String::from)
+        .collect())
+}
+
+/// Notify assignees of nodes that were blocked in `before` and no longer are.
+pub async fn nodes_unblocked(
+    state: &AppState,
+    actor_id: &str,
+    project: &db::projects::Project,
+    before: &HashSet<String>,
+) {
+    if before.is_empty() {
+        return;
+    }
+    let (nodes, edges) = match load_graph(state, &project.id).await {
+        Ok(pair) => pair,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load graph for unblocked notifications");
+            return;
+        }
+    };
+    let still_blocked = progress::blocked_node_ids(&nodes, &edges);
+    let brand = mail::org_brand(&state.db, &project.organization_id).await;
+    let url = project_url(state, &project.id);
+
+    for node in &nodes {
+        if !before.contains(&node.id) || still_blocked.contains(node.id.as_str()) {
+            continue;
+        }
+        let Some(assignee) = node.assigned_user_id.as_deref() else {
+            continue;
+        };
+        let notice = Notice {
+            subject: format!("Ready to start: \"{}\"", node.title),
+            headline: "Your work is unblocked".to_string(),
+            detail: format!("\"{}\" in {} is no longer blocked.", node.title, project.title),
+        };
+        send(state, actor_id, assignee, &brand, &url, notice).await;
+    }
+}
+
+async fn load_graph(
+    state: &AppState,
+    project_id: &str,
+) -> Result<(Vec<db::nodes::Node>, Vec<db::node_edges::NodeEdge>), sqlx::Error> {
+    let nodes = db::nodes::find_by_project(&state.db, project_id).await?;
+    let edges = db::node_edges::find_by_project(&state.db, project_id).await?;
+    Ok((nodes, edges))
+}
+
+fn project_url(state: &AppState, project_id: &str) -> String {
+    format!("{}/app/projects/{}", state.config.app_url_base(), project_id)
+}
+
+/// Send to one user unless they are the actor or have turned notifications off.
+async fn send(
+    state: &AppState,
+    actor_id: &str,
+    recipient_id: &str,
+    brand: &str,
+    url: &str,
+    notice: Notice,
+) {
+    if recipient_id == actor_id {
+        return;
+    }
+    let Ok(user_id) = UserId::from_string(recipient_id) else {
+        return;
+    };
+    let user = match db::users::find_by_id(&state.db, &user_id).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load notification recipient");
+            return;
+        }
+    };
+    if user.email_notifications == 0 {
+        return;
+    }
+    let Ok(to) = Email::new(user.email) else {
+        return;
+    };
+    let content = match templates::notification(brand, notice.subject, &notice.headline, &notice.detail, url) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to render notification email");
+            return;
+        }
+    };
+    let message = EmailMessage::from_content(to, content, state.config.mail_from.clone());
+    if let Err(e) = state.mail.send(&message).await {
+        tracing::warn!(error = %e, recipient = %recipient_id, "Failed to send notification email");
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Path, State},
+    routing::post,
+    Json, Router,
+};
+use time::OffsetDateTime;
+
+use crate::app::{
+    db,
+    error::AppError,
+    session::ApiAuthenticatedSession,
+    tenant,
+    AppState,
+};
+
+use super::delete_node::{DeletedNodeSnapshot, UNDO_WINDOW_SECS};
+
+/// POST /api/projects/:project_id/nodes/:id/restore — Undo the node's most recent deletion.
+///
+/// Puts the node back with its original edges and group members and removes the bypass edges the
+/// deletion added, unless they were removed and re-added since. References to things deleted since (other nodes, the slot, labels, a departed
+/// assignee on the node or its checklist) are dropped rather than failing the restore.
+pub async fn restore_node(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<super::types::NodePathParams>,
+) -> Result<Json<db::nodes::Node>, AppError> {
+    let project = super::helpers::ensure_project_accessible(
+        &state.db,
+        &params.project_id,
+        &session.user_id,
+    )
+    .await?;
+
+    // Read the undo record inside the transaction so a concurrent restore cannot apply it twice.
+    let mut tx = state.db.begin().await?;
+
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let deletion = db::node_deletions::find_latest_for_node(&mut *tx, &params.project_id, &params.id)
+        .await?
+        .filter(|d| d.created_at >= now - UNDO_WINDOW_SECS)
+        .ok_or_else(|| AppError::NotFound("Nothing to restore".to_string()))?;
+    let snapshot: DeletedNodeSnapshot =
+        serde_json::from_str(&deletion.snapshot).map_err(|_| AppError::Internal)?;
+
+    if db::nodes::find_by_id(&mut *tx, &snapshot.node.id).await?.is_some() {
+        return Err(AppError::Validation("Node already exists".to_string()));
+    }
+
+    let (nodes, slots) = tokio::try_join!(
+        db::nodes::find_by_project(&state.db, &params.project_id),
+        db::project_slots::find_by_project(&state.db, &params.project_id),
+    )?;
+    let existing: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+
+    let mut node = snapshot.node.clone();
+    node.parent_id = node.parent_id.filter(|id| existing.contains(id.as_str()));
+    node.slot_id = node.slot_id.filter(|id| slots.iter().any(|s| &s.id == id));
+    if let Some(uid) = node.assigned_user_id.clone() {
+        if tenant::require_org_member(&state.db, &uid, &project.organization_id).await.is_err() {
+            node.assigned_user_id = None;
+        }
+    }
+
+    db::nodes::insert_restored(&mut *tx, &node).await?;
+
+    // Only remove bypass edges that are still the ones the deletion added; older snapshots didn't
+    // record created_at, so theirs are removed by endpoints.
+    for edge in &snapshot.bypass_edges {
+        match edge.created_at {
+            Some(created_at) => {
+                db::node_edges::delete_if_created_at(&mut *tx, &edge.parent_id, &edge.child_id, created_at).await?
+            }
+            None => db::node_edges::delete_with_executor(&mut *tx, &edge.parent_id, &edge.child_id).await?,
+        }
+    }
+    let original_edges = snapshot
+        .parent_ids
+        .iter()
+        .map(|p| (p.clone(), node.id.clone()))
+        .chain(snapshot.child_ids.iter().map(|c| (node.id.clone(), c.clone())));
+    for (parent_id, child_id) in original_edges {
+        let other = if parent_id == node.id { &child_id } else { &parent_id };
+        if !existing.contains(other.as_str()) {
+            continue;
+        }
+        let edge = db::node_edges::NewNodeEdge { parent_id, child_id };
+        db::node_edges::insert_if_not_exists(&mut *tx, &edge).await?;
+    }
+
+    for member_id in &snapshot.group_member_ids {
+        if existing.contains(member_id.as_str()) {
+            db::nodes::set_parent_if_unset(&mut *tx, member_id, &node.id).await?;
+        }
+    }
+
+    for entry in &snapshot.time_entries {
+        db::time_entries::insert_restored(&mut *tx, entry).await?;
+    }
+
+    // Labels deleted since are skipped by add_to_node.
+    for label_id in &snapshot.label_ids {
+        db::labels::add_to_node(&mut *tx, &node.id, label_id).await?;
+    }
+
+    for item in &snapshot.checklist_items {
+        let mut item = item.clone();
+        if let Some(uid) = item.assigned_user_id.clone() {
+            if tenant::require_org_member(&state.db, &uid, &project.organization_id).await.is_err() {
+                item.assigned_user_id = None;
+            }
+        }
+        db::checklist_items::insert_restored(&mut *tx, &item).await?;
+    }
+
+    // A blob can only be gone if something else removed it; skip rather than fail.
+    for attachment in &snapshot.attachments {
+        if db::attachments::blob_exists(&mut *tx, &attachment.content_hash).await? {
+            db::attachments::insert_restored(&mut *tx, attachment).await?;
+        }
+    }
+
+    db::node_deletions::delete(&mut *tx, &deletion.id).await?;
+    tx.commit().await?;
+
+    let restored = db::nodes::find_by_id(&state.db, &node.id)
+        .await?
+        .ok_or(AppError::Internal)?;
+    Ok(Json(restored))
+}
+
+/// Node restore routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/projects/:project_id/nodes/:id/restore", post(restore_node))
+}
@@ -0,0 +1,287 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use ulid::Ulid;
+use validator::Validate;
+
+use crate::app::{
+    db,
+    domain::validation_helpers::parse_iso_date,
+    error::AppError,
+    session::ApiAuthenticatedSession,
+    AppState,
+};
+
+/// Path parameters for per-node time endpoints.
+#[derive(Debug, Deserialize)]
+pub struct NodeTimePathParams {
+    pub project_id: String,
+    pub node_id: String,
+}
+
+/// Path parameters for a single time entry.
+#[derive(Debug, Deserialize)]
+pub struct TimeEntryPathParams {
+    pub project_id: String,
+    pub node_id: String,
+    pub entry_id: String,
+}
+
+/// Actual time spent on a node next to its estimate.
+#[derive(Debug, Serialize)]
+pub struct NodeTimeResponse {
+    pub estimated_minutes: Option<i64>,
+    pub actual_minutes: i64,
+    pub over_estimate: bool,
+    pub entries: Vec<db::time_entries::TimeEntry>,
+}
+
+/// Request body for logging time. `date` is YYYY-MM-DD and defaults to today (UTC).
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateTimeEntryRequest {
+    #[validate(range(min = 1, max = 1_000_000_000))]
+    pub minutes: i64,
+    pub date: Option<String>,
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+/// The caller's running timer, if any.
+#[derive(Debug, Serialize)]
+pub struct TimerResponse {
+    pub timer: Option<RunningTimer>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunningTimer {
+    pub node_id: String,
+    pub project_id: String,
+    pub title: String,
+    pub started_at: i64,
+    pub elapsed_seconds: i64,
+}
+
+/// Whole minutes for a timer run, rounded up so a short run still counts.
+fn timer_minutes(elapsed_seconds: i64) -> i64 {
+    ((elapsed_seconds + 59) / 60).max(1)
+}
+
+/// GET /api/projects/:project_id/nodes/:node_id/time-entries — Time logged on a node.
+pub async fn list_time_entries(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<NodeTimePathParams>,
+) -> Result<Json<NodeTimeResponse>, AppError> {
+    let (_, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.node_id, &session.user_id).await?;
+    let entries = db::time_entries::find_by_node(&state.db, &node.id).await?;
+    let actual_minutes: i64 = entries.iter().map(|e| e.minutes).sum();
+    Ok(Json(NodeTimeResponse {
+        estimated_minutes: node.estimated_minutes,
+        actual_minutes,
+        over_estimate: node.estimated_minutes.is_some_and(|est| actual_minutes > est),
+        entries,
+    }))
+}
+
+/// POST /api/projects/:project_id/nodes/:node_id/time-entries — Log time on a node.
+pub async fn create_time_entry(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<NodeTimePathParams>,
+    Json(request): Json<CreateTimeEntryRequest>,
+) -> Result<(StatusCode, Json<db::time_entries::TimeEntry>), AppError> {
+    request
+        .validate()
+        .map_err(|_| AppError::Validation("Invalid input".to_string()))?;
+
+    let (_, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.node_id, &session.user_id).await?;
+
+    let entry_date = match request.date.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(value) => parse_iso_date(value)
+            .ok_or_else(|| AppError::Validation("Invalid date".to_string()))?,
+        None => OffsetDateTime::now_utc().date(),
+    };
+    let note = request
+        .note
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+
+    let entry = db::time_entries::NewTimeEntry {
+        id: Ulid::new().to_string(),
+        node_id: node.id,
+        user_id: session.user_id,
+        minutes: request.minutes,
+        entry_date: entry_date.to_string(),
+        note,
+    };
+    db::time_entries::insert(&state.db, &entry).await?;
+
+    let created = db::time_entries::find_by_id(&state.db, &entry.id)
+        .await?
+        .ok_or(AppError::Internal)?;
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// DELETE /api/projects/:project_id/nodes/:node_id/time-entries/:entry_id — Delete one of your own entries.
+pub async fn delete_time_entry(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<TimeEntryPathParams>,
+) -> Result<StatusCode, AppError> {
+    let (_, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.node_id, &session.user_id).await?;
+
+    let entry = db::time_entries::find_by_id(&state.db, &params.entry_id)
+        .await?
+        .filter(|e| e.node_id == node.id && e.user_id == session.user_id)
+        .ok_or_else(|| AppError::NotFound("Time entry not found".to_string()))?;
+
+    db::time_entries::delete(&state.db, &entry.id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Describe the caller's running timer, or None if nothing is running.
+async fn running_timer(
+    pool: &sqlx::SqlitePool,
+    user_id: &str,
+) -> Result<Option<RunningTimer>, AppError> {
+    let Some(timer) = db::time_entries::find_running_timer(pool, user_id).await? else {
+        return Ok(None);
+    };
+    let node = db::nodes::find_by_id(pool, &timer.node_id)
+        .await?
+        .ok_or(AppError::Internal)?;
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    Ok(Some(RunningTimer {
+        node_id: node.id,
+        project_id: node.project_id,
+        title: node.title,
+        started_at: timer.started_at,
+        elapsed_seconds: (now - timer.started_at).max(0),
+    }))
+}
+
+/// POST /api/projects/:project_id/nodes/:node_id/timer — Start a timer on a node.
+/// Only one timer runs per user; stop the current one first.
+pub async fn start_timer(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(params): Path<NodeTimePathParams>,
+) -> Result<(StatusCode, Json<TimerResponse>), AppError> {
+    let (_, node) = super::helpers::load_project_node(&state.db, &params.project_id, &params.node_id, &session.user_id).await?;
+
+    if db::time_entries::find_running_timer(&state.db, &session.user_id)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::Validation("A timer is already running".to_string()));
+    }
+
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    // A concurrent start can slip past the check above; the primary key catches it.
+    match db::time_entries::start_timer(&state.db, &session.user_id, &node.id, now).await {
+        Ok(()) => {}
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return Err(AppError::Validation("A timer is already running".to_string()));
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    let timer = running_timer(&state.db, &session.user_id).await?;
+    Ok((StatusCode::CREATED, Json(TimerResponse { timer })))
+}
+
+/// GET /api/timer — The caller's running timer.
+pub async fn get_timer(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+) -> Result<Json<TimerResponse>, AppError> {
+    let timer = running_timer(&state.db, &session.user_id).await?;
+    Ok(Json(TimerResponse { timer }))
+}
+
+/// POST /api/timer/stop — Stop the running timer and log its time as an entry dated today.
+pub async fn stop_timer(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<db::time_entries::TimeEntry>), AppError> {
+    let timer = db::time_entries::find_running_timer(&state.db, &session.user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No timer running".to_string()))?;
+    let node = db::nodes::find_by_id(&state.db, &timer.node_id)
+        .await?
+        .ok_or(AppError::Internal)?;
+
+    // Access may have been lost while the timer ran; drop it without logging.
+    if let Err(e) =
+        super::helpers::ensure_project_accessible(&state.db, &node.project_id, &session.user_id).await
+    {
+        db::time_entries::delete_timer(&state.db, &session.user_id).await?;
+        return Err(e);
+    }
+
+    let now = OffsetDateTime::now_utc();
+    let entry = db::time_entries::NewTimeEntry {
+        id: Ulid::new().to_string(),
+        node_id: node.id,
+        user_id: session.user_id.clone(),
+        minutes: timer_minutes(now.unix_timestamp() - timer.started_at),
+        entry_date: now.date().to_string(),
+        note: None,
+    };
+
+    let mut tx = state.db.begin().await?;
+    db::time_entries::insert(&mut *tx, &entry).await?;
+    db::time_entries::delete_timer(&mut *tx, &session.user_id).await?;
+    tx.commit().await?;
+
+    let created = db::time_entries::find_by_id(&state.db, &entry.id)
+        .await?
+        .ok_or(AppError::Internal)?;
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// Time tracking routes.
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/projects/:project_id/nodes/:node_id/time-entries",
+            get(list_time_entries).post(create_time_entry),
+        )
+        .route(
+            "/api/projects/:project_id/nodes/:node_id/time-entries/:entry_id",
+            delete(delete_time_entry),
+        )
+        .route("/api/projects/:project_id/nodes/:node_id/timer", post(start_timer))
+        .route("/api/timer", get(get_timer))
+        .route("/api/timer/stop", post(stop_timer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_strict_calendar_dates() {
+        assert_eq!(parse_iso_date("2026-02-28").unwrap().to_string(), "2026-02-28");
+        assert!(parse_iso_date("2026-02-30").is_none());
+        assert!(parse_iso_date("2026-2-3").is_none());
+        assert!(parse_iso_date("2026-02-03T00:00").is_none());
+        assert!(parse_iso_date("yesterday").is_none());
+    }
+
+    #[test]
+    fn timer_rounds_up_to_whole_minutes() {
+        assert_eq!(timer_minutes(0), 1);
+        assert_eq!(timer_minutes(60), 1);
+        assert_eq!(timer_minutes(61), 2);
+        assert_eq!(timer_minutes(3600), 60);
+    }
+}
@@ -21,7 +21,7 @@ use crate::app::{
 
 /// Deserializes a JSON value so that missing key => None, present null => Some(None), present value => Some(Some(v)).
 /// Required to distinguish "omit field" (leave unchanged) from "field: null" (clear estimate).
-fn deserialize_optional_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+pub(super) fn deserialize_optional_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
 where
     T: Deserialize<'de>,
     D: Deserializer<'de>,
@@ -51,6 +51,8 @@ pub struct UpdateNodeRequest {
     #[serde(default, deserialize_with = "deserialize_optional_option")]
     #[validate(custom(function = "crate::app::features::graph::helpers::validate_estimated_minutes"))]
     pub estimated_minutes: Option<Option<i64>>,
+    /// Omit = unchanged, otherwise replaces the node's labels ([] removes them all).
+    pub label_ids: Option<Vec<String>>,
 }
 
 /// Response for an updated node.
@@ -68,6 +70,7 @@ pub struct NodeResponse {
     pub slot_id: Option<String>,
     pub parent_id: Option<String>,
     pub assigned_user_id: Option<String>,
+    pub label_ids: Vec<String>,
 }
 
 /// Validates update-node request (sync rules + DB-backed node_type_id, status_id, slot_id, parent_id, assigned_user_id when provided).
@@ -177,11 +180,22 @@ pub async fn update_node(
         &node.id,
     )
     .await?;
+    let label_ids = match &request.label_ids {
+        Some(ids) => Some(super::labels::validate_label_ids(&state.db, &project.organization_id, ids).await?),
+        None => None,
+    };
 
     let title = request.title.as_deref().unwrap_or(&node.title);
-    let description = request.description.or(node.description);
+    let description = request.description.clone().or_else(|| node.description.clone());
     let estimated_minutes = request.estimated_minutes.unwrap_or(node.estimated_minutes);
 
+    // Status changes can unblock dependents; remember what was blocked so their assignees can be told.
+    let blocked_before = if status_id != node.status_id {
+        super::notify::blocked_snapshot(&state, &project.id).await?
+    } else {
+        Default::default()
+    };
+
     db::nodes::update(
         &state.db,
         &node.id,
@@ -195,11 +209,18 @@ pub async fn update_node(
         assigned_user_id.as_deref(),
     )
     .await?;
+    if let Some(label_ids) = &label_ids {
+        db::labels::set_for_node(&state.db, &node.id, label_ids).await?;
+    }
 
     // Fetch the updated node for response
     let updated_node = db::nodes::find_by_id(&state.db, &node.id)
         .await?
         .ok_or_else(|| AppError::Internal)?;
+    let label_ids = db::labels::find_ids_for_node(&state.db, &node.id).await?;
+
+    super::notify::node_changed(&state, &session.user_id, &project, &updated_node, Some(&node)).await;
+    super::notify::nodes_unblocked(&state, &session.user_id, &project, &blocked_before).await;
 
     let response = NodeResponse {
         id: updated_node.id,
@@ -214,6 +235,7 @@ pub async fn update_node(
         slot_id: updated_node.slot_id,
         parent_id: updated_node.parent_id,
         assigned_user_id: updated_node.assigned_user_id,
+        label_ids,
     };
 
     Ok((StatusCode::OK, Json(response)))
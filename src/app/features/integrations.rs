@@ -22,6 +22,7 @@ pub struct IntegrationsTemplate {
     pub app_name: &'static str,
     pub integrations: Vec<db::integrations::Integration>,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
 }
 
 /// GET /app/integrations — List allowed integrations with "coming soon" message (org-scoped).
@@ -52,6 +53,7 @@ pub async fn show(
         app_name: APP_NAME,
         integrations,
         current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
     };
 
     match template.render() {
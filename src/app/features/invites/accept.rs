@@ -12,6 +12,7 @@ use time::{Duration, OffsetDateTime};
 use crate::app::{
     db,
     domain::{Email, OrganizationId, OrganizationRole, UserId},
+    features::auth::signup_policy::SignupPolicy,
     session::{self, AuthenticatedSession},
     AppState, APP_NAME,
 };
@@ -34,6 +35,8 @@ pub struct AcceptInviteTemplate {
     pub new_user_email: String,
     pub new_user_signup_url: String,
     pub new_user_login_url: String,
+    /// Set when the signup policy won't let this new user create an account.
+    pub new_user_signup_blocked: String,
     /// Set when existing user should log in.
     pub existing_org_name: String,
     pub existing_login_url: String,
@@ -47,6 +50,7 @@ pub enum AcceptInviteState {
         email: String,
         signup_url: String,
         login_url: String,
+        signup_blocked: String,
     },
     ExistingUser {
         org_name: String,
@@ -64,6 +68,7 @@ impl AcceptInviteTemplate {
                 new_user_email: String::new(),
                 new_user_signup_url: String::new(),
                 new_user_login_url: String::new(),
+                new_user_signup_blocked: String::new(),
                 existing_org_name: String::new(),
                 existing_login_url: String::new(),
             },
@@ -72,6 +77,7 @@ impl AcceptInviteTemplate {
                 email,
                 signup_url,
                 login_url,
+                signup_blocked,
             } => AcceptInviteTemplate {
                 app_name,
                 invalid_message: String::new(),
@@ -79,6 +85,7 @@ impl AcceptInviteTemplate {
                 new_user_email: email,
                 new_user_signup_url: signup_url,
                 new_user_login_url: login_url,
+                new_user_signup_blocked: signup_blocked,
                 existing_org_name: String::new(),
                 existing_login_url: String::new(),
             },
@@ -92,6 +99,7 @@ impl AcceptInviteTemplate {
                 new_user_email: String::new(),
                 new_user_signup_url: String::new(),
                 new_user_login_url: String::new(),
+                new_user_signup_blocked: String::new(),
                 existing_org_name: org_name,
                 existing_login_url: login_url,
             },
@@ -178,15 +186,24 @@ pub async fn show(
             urlencoding::encode(&format!("/accept-invite/confirm?token={}", urlencoding::encode(&token)))
         );
         let signup_url = format!(
-            "/signup?email={}&next={}",
+            "/signup?email={}&invite={}&next={}",
             urlencoding::encode(&invite.email),
+            urlencoding::encode(&token),
             urlencoding::encode(&format!("/accept-invite/confirm?token={}", urlencoding::encode(&token)))
         );
+        // The invite itself satisfies invite-only signup, but not a closed instance or a domain
+        // the instance doesn't allow.
+        let policy = match SignupPolicy::load(&state.db, &state.config).await {
+            Ok(p) => p,
+            Err(_) => return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+        };
+        let signup_blocked = policy.rejection(&email_for_lookup, true).unwrap_or_default();
         AcceptInviteState::NewUser {
             org_name: org.name,
             email: invite.email,
             signup_url,
             login_url,
+            signup_blocked,
         }
     };
 
@@ -249,9 +266,9 @@ pub async fn confirm_existing_user(
             return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, "Failed to add to organization".to_string()).into_response();
         }
     }
-    // Add user to org's default team (idempotent)
-    if let Ok(Some(default_team)) = db::teams::find_default_for_org(&mut *tx, &org_id).await {
-        if db::team_members::add_member(&mut *tx, &default_team.id, &user_id).await.is_err() {
+    // Add user to the invite's team, or the org's default team (idempotent)
+    if let Ok(Some(team)) = db::teams::find_in_org_or_default(&mut *tx, &org_id, invite.team_id.as_deref()).await {
+        if db::team_members::add_member(&mut *tx, &team.id, &user_id).await.is_err() {
             return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, "Failed to add to team".to_string()).into_response();
         }
     }
@@ -0,0 +1,216 @@
+//! Joining an organization through a reusable invite link. Unlike emailed invites the link isn't
+//! tied to an address: anyone who signs in (or signs up) with it joins, until it runs out of uses
+//! or expires. The link page is public; joining itself is a signed-in form post, so a stray GET
+//! (an `<img>` on another site) can't add anyone to an organization.
+
+use askama::Template;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::get,
+    Form, Router,
+};
+use axum_extra::extract::cookie::CookieJar;
+use serde::Deserialize;
+use time::{Duration, OffsetDateTime};
+
+use crate::app::{
+    db,
+    domain::{OrganizationId, OrganizationRole, SignupMode, UserId},
+    features::auth::signup_policy::SignupPolicy,
+    session::{self, AuthenticatedSession},
+    AppState, APP_NAME,
+};
+
+/// Query for GET /join and GET /join/confirm.
+#[derive(Debug, Deserialize)]
+pub struct JoinQuery {
+    pub token: Option<String>,
+}
+
+/// Join form posted from the confirm page.
+#[derive(Debug, Deserialize)]
+pub struct JoinForm {
+    pub token: String,
+}
+
+/// Join page: either why the link doesn't work, or the org and where to sign in.
+#[derive(Template)]
+#[template(path = "join_invite.html")]
+pub struct JoinInviteTemplate {
+    pub app_name: &'static str,
+    pub invalid_message: String,
+    pub org_name: String,
+    /// Empty when the signup policy doesn't let new people create an account.
+    pub signup_url: String,
+    pub login_url: String,
+}
+
+/// Confirm page shown to a signed-in user before they join.
+#[derive(Template)]
+#[template(path = "join_confirm.html")]
+pub struct JoinConfirmTemplate {
+    pub app_name: &'static str,
+    pub org_name: String,
+    pub token: String,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+fn back_to_link(token: &str) -> Response {
+    Redirect::to(&format!("/join?token={}", urlencoding::encode(token))).into_response()
+}
+
+fn invalid(message: &str) -> Response {
+    let tmpl = JoinInviteTemplate {
+        app_name: APP_NAME,
+        invalid_message: message.to_string(),
+        org_name: String::new(),
+        signup_url: String::new(),
+        login_url: String::new(),
+    };
+    Html(tmpl.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+}
+
+/// GET /join — Show which organization the link joins, with sign-up and log-in links.
+pub async fn show(State(state): State<AppState>, Query(query): Query<JoinQuery>) -> Response {
+    let token = match query.token {
+        Some(t) if !t.is_empty() => t,
+        _ => return invalid("Invalid or missing invite link."),
+    };
+    let link = match db::organization_invite_links::find_usable_by_token(&state.db, &token).await {
+        Ok(Some(l)) => l,
+        Ok(None) => return invalid("This invite link is invalid, used up or has expired. Ask for a new one."),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let Ok(org_id) = OrganizationId::from_string(&link.organization_id) else {
+        return invalid("This invite link is no longer valid.");
+    };
+    let org = match db::organizations::find_by_id(&state.db, &org_id).await {
+        Ok(Some(o)) => o,
+        Ok(None) => return invalid("This invite link is no longer valid."),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+
+    let policy = match SignupPolicy::load(&state.db, &state.config).await {
+        Ok(p) => p,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+
+    let next = urlencoding::encode(&format!("/join/confirm?token={}", urlencoding::encode(&token))).into_owned();
+    let signup_url = if policy.mode == SignupMode::Closed {
+        String::new()
+    } else {
+        format!("/signup?invite={}&next={}", urlencoding::encode(&token), next)
+    };
+    let tmpl = JoinInviteTemplate {
+        app_name: APP_NAME,
+        invalid_message: String::new(),
+        org_name: org.name,
+        signup_url,
+        login_url: format!("/login?next={}", next),
+    };
+    Html(tmpl.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+}
+
+/// GET /join/confirm — After login, ask the user to confirm joining the link's organization. Requires auth.
+pub async fn confirm_page(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Query(query): Query<JoinQuery>,
+) -> Response {
+    let token = match query.token {
+        Some(t) if !t.is_empty() => t,
+        _ => return Redirect::to("/app").into_response(),
+    };
+    let link = match db::organization_invite_links::find_usable_by_token(&state.db, &token).await {
+        Ok(Some(l)) => l,
+        Ok(None) => return back_to_link(&token),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let (Ok(org_id), Ok(user_id)) = (
+        OrganizationId::from_string(&link.organization_id),
+        UserId::from_string(&session.user_id),
+    ) else {
+        return Redirect::to("/app").into_response();
+    };
+    let org = match db::organizations::find_by_id(&state.db, &org_id).await {
+        Ok(Some(o)) => o,
+        Ok(None) => return back_to_link(&token),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+    let template = JoinConfirmTemplate {
+        app_name: APP_NAME,
+        org_name: org.name,
+        token,
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    };
+    Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+}
+
+/// POST /join/confirm — Join the link's organization and switch to it. Existing members just switch
+/// without using up the link. Requires auth.
+pub async fn confirm(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<JoinForm>,
+) -> Response {
+    let token = form.token;
+    if token.is_empty() {
+        return Redirect::to("/app").into_response();
+    }
+    let link = match db::organization_invite_links::find_usable_by_token(&state.db, &token).await {
+        Ok(Some(l)) => l,
+        Ok(None) => return back_to_link(&token),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let (Ok(org_id), Ok(user_id)) = (
+        OrganizationId::from_string(&link.organization_id),
+        UserId::from_string(&session.user_id),
+    ) else {
+        return Redirect::to("/app").into_response();
+    };
+    let role = link.role.parse::<OrganizationRole>().unwrap_or(OrganizationRole::Member);
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        if !db::organizations::is_member(&mut *tx, &org_id, &user_id).await? {
+            if !db::organization_invite_links::record_use(&mut *tx, &link.id).await? {
+                return Ok(None);
+            }
+            db::organizations::add_member(&mut *tx, &org_id, &user_id, role).await?;
+            if let Some(team) = db::teams::find_in_org_or_default(&mut *tx, &org_id, link.team_id.as_deref()).await? {
+                db::team_members::add_member(&mut *tx, &team.id, &user_id).await?;
+            }
+        }
+        db::users::update_organization_id(&mut *tx, &user_id, &org_id).await?;
+        // Rotate the session with the org switch, as accepting an emailed invite does.
+        db::sessions::delete(&mut *tx, &session.id).await?;
+        let expires_at = OffsetDateTime::now_utc() + Duration::days(30);
+        let new_session_id = db::sessions::create(&mut *tx, &user_id, &org_id, expires_at).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(Some(new_session_id))
+    }
+    .await;
+    match result {
+        Ok(Some(new_session_id)) => {
+            let jar = jar.add(session::session_cookie(new_session_id));
+            (jar, Redirect::to("/app")).into_response()
+        }
+        Ok(None) => back_to_link(&token),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/join", get(show))
+}
+
+/// Signed-in join routes; mounted behind the CSRF middleware.
+pub fn confirm_routes() -> Router<AppState> {
+    Router::new().route("/join/confirm", get(confirm_page).post(confirm))
+}
@@ -1,4 +1,5 @@
 mod accept;
+mod join;
 
 use axum::Router;
 
@@ -6,5 +7,12 @@ use crate::app::AppState;
 
 /// Public invite acceptance routes (no auth required for GET).
 pub fn routes() -> Router<AppState> {
-    Router::new().merge(accept::routes())
+    Router::new()
+        .merge(accept::routes())
+        .merge(join::routes())
+}
+
+/// Invite routes that act for a signed-in user; mounted behind the CSRF middleware.
+pub fn authenticated_routes() -> Router<AppState> {
+    join::confirm_routes()
 }
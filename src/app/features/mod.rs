@@ -1,10 +1,15 @@
 pub mod account;
+pub mod account_deletion;
+pub mod admin;
 pub mod auth;
 pub mod dashboard;
+pub mod digest;
 pub mod not_found;
 pub mod graph;
 pub mod integrations;
 pub mod invites;
 pub mod organization;
 pub mod projects;
-pub mod teams;
\ No newline at end of file
+pub mod search;
+pub mod teams;
+pub mod workload;
\ No newline at end of file
@@ -12,6 +12,7 @@ use serde_json::json;
 pub struct NotFoundTemplate {
     pub app_name: &'static str,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
 }
 
 /// Fallback handler for unmatched routes. Returns HTML 404 page for browser
@@ -35,6 +36,7 @@ pub async fn handler(req: Request) -> impl IntoResponse {
         let template = NotFoundTemplate {
             app_name: crate::app::APP_NAME,
             current_user_avatar_url: String::new(),
+            csrf_token: String::new(),
         };
         let html = template
             .render()
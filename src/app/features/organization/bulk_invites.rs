@@ -0,0 +1,183 @@
+//! Inviting many people at once from a pasted list or an uploaded CSV (`email,role,team`, one per
+//! line). Every row becomes an ordinary emailed invite; rows that can't be sent are reported back
+//! without stopping the rest.
+
+use std::collections::HashSet;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+    routing::post,
+    Form, Router,
+};
+use serde::Deserialize;
+
+use crate::app::{
+    db,
+    domain::{Email, OrganizationId, OrganizationRole, UserId},
+    session::AuthenticatedSession,
+    tenant,
+    AppState,
+};
+
+use super::settings::{can_invite, send_invite};
+
+/// Most rows accepted in one submission.
+const MAX_ROWS: usize = 200;
+
+/// Bulk invite form: the list, and the role for rows that don't name one.
+#[derive(Debug, Deserialize)]
+pub struct BulkInviteForm {
+    pub entries: String,
+    pub default_role: String,
+}
+
+/// One parsed row.
+#[derive(Debug, PartialEq)]
+pub struct BulkInviteRow {
+    pub email: String,
+    pub role: OrganizationRole,
+    /// Team name as written; resolved against the org's teams later.
+    pub team: Option<String>,
+}
+
+/// Parse `email[,role[,team]]` lines. Blank lines and an `email,...` header are skipped; later
+/// duplicates of an address are dropped. Lines that don't parse come back as messages.
+pub fn parse_bulk_invites(text: &str, default_role: OrganizationRole) -> (Vec<BulkInviteRow>, Vec<String>) {
+    let mut rows = Vec::new();
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+    for line in text.lines() {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let email = fields[0];
+        if email.is_empty() || email.eq_ignore_ascii_case("email") {
+            continue;
+        }
+        let Ok(parsed) = Email::new(email.to_string()) else {
+            problems.push(format!("{} (invalid email)", email));
+            continue;
+        };
+        let role = match fields.get(1).copied().filter(|r| !r.is_empty()) {
+            None => default_role,
+            Some(r) => match r.to_lowercase().parse::<OrganizationRole>() {
+                Ok(role) => role,
+                Err(_) => {
+                    problems.push(format!("{} (unknown role \"{}\")", email, r));
+                    continue;
+                }
+            },
+        };
+        let team = fields.get(2).filter(|t| !t.is_empty()).map(|t| t.to_string());
+        let email = parsed.as_str().to_string();
+        if seen.insert(email.clone()) {
+            rows.push(BulkInviteRow { email, role, team });
+        }
+    }
+    (rows, problems)
+}
+
+fn bulk_redirect(sent: usize, problems: &[String]) -> Response {
+    let mut url = format!(
+        "/app/settings/organization?success={}",
+        urlencoding::encode(&format!("Sent {} invite{}.", sent, if sent == 1 { "" } else { "s" }))
+    );
+    if !problems.is_empty() {
+        let msg = format!("Not invited: {}.", problems.join("; "));
+        url.push_str(&format!("&error={}", urlencoding::encode(&msg)));
+    }
+    Redirect::to(&url).into_response()
+}
+
+/// POST /app/settings/organization/invite/bulk — Send an invite for every row (owners/admins only).
+pub async fn create_bulk_invites(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Form(form): Form<BulkInviteForm>,
+) -> Response {
+    let role = match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(r) if can_invite(r) => r,
+        _ => return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response(),
+    };
+    let Ok(default_role) = form.default_role.parse::<OrganizationRole>() else {
+        return super::settings::invite_redirect_error("Invalid role.").into_response();
+    };
+    let (Ok(org_id), Ok(user_id)) = (
+        OrganizationId::from_string(&session.organization_id),
+        UserId::from_string(&session.user_id),
+    ) else {
+        return super::settings::invite_redirect_error("Invalid organization.").into_response();
+    };
+    let (org, teams) = match tokio::try_join!(
+        db::organizations::find_by_id(&state.db, &org_id),
+        db::teams::find_by_organization(&state.db, &session.organization_id),
+    ) {
+        Ok((Some(org), teams)) => (org, teams),
+        _ => return super::settings::invite_redirect_error("Organization not found.").into_response(),
+    };
+
+    let (rows, mut problems) = parse_bulk_invites(&form.entries, default_role);
+    if rows.is_empty() && problems.is_empty() {
+        return super::settings::invite_redirect_error("Add at least one email address.").into_response();
+    }
+    if rows.len() > MAX_ROWS {
+        return super::settings::invite_redirect_error(&format!("Invite at most {} people at a time.", MAX_ROWS))
+            .into_response();
+    }
+
+    let mut sent = 0;
+    for row in rows {
+        if row.role == OrganizationRole::Owner && role != OrganizationRole::Owner {
+            problems.push(format!("{} (only owners can invite owners)", row.email));
+            continue;
+        }
+        let team_id = match &row.team {
+            None => None,
+            Some(name) => match teams.iter().find(|t| t.name.eq_ignore_ascii_case(name)) {
+                Some(team) => Some(team.id.clone()),
+                None => {
+                    problems.push(format!("{} (no team named \"{}\")", row.email, name));
+                    continue;
+                }
+            },
+        };
+        let Ok(email) = Email::new(row.email.clone()) else {
+            continue;
+        };
+        match send_invite(&state, &org, &user_id, email, row.role, team_id).await {
+            Ok(_) => sent += 1,
+            Err(msg) => problems.push(format!("{} ({})", row.email, msg.trim_end_matches('.'))),
+        }
+    }
+    bulk_redirect(sent, &problems)
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/app/settings/organization/invite/bulk", post(create_bulk_invites))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_lists_and_csv_rows() {
+        let text = "email,role,team\nada@example.com\n\n grace@example.com , admin , Design \nlin@example.com,,Ops\n";
+        let (rows, problems) = parse_bulk_invites(text, OrganizationRole::Member);
+        assert!(problems.is_empty());
+        assert_eq!(rows, vec![
+            BulkInviteRow { email: "ada@example.com".into(), role: OrganizationRole::Member, team: None },
+            BulkInviteRow { email: "grace@example.com".into(), role: OrganizationRole::Admin, team: Some("Design".into()) },
+            BulkInviteRow { email: "lin@example.com".into(), role: OrganizationRole::Member, team: Some("Ops".into()) },
+        ]);
+    }
+
+    #[test]
+    fn reports_bad_rows_and_drops_duplicates() {
+        let text = "not-an-email\nada@example.com,boss\nada@example.com,viewer\nADA@example.com";
+        let (rows, problems) = parse_bulk_invites(text, OrganizationRole::Viewer);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].role, OrganizationRole::Viewer);
+        assert_eq!(problems.len(), 2);
+    }
+}
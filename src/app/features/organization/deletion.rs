@@ -0,0 +1,194 @@
+//! Organization deletion. An owner schedules it by typing the organization's name; for
+//! `Config::org_deletion_grace_days` any owner can cancel, after which the purge job removes the
+//! organization with its projects, teams and memberships. Members keep their accounts and move to
+//! another organization they belong to, or to a fresh personal one.
+
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+    Form, Router,
+};
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::app::{
+    db,
+    domain::{OrganizationId, OrganizationRole, UserId},
+    features::graph,
+    session::AuthenticatedSession,
+    tenant,
+    AppState,
+};
+
+use super::settings::owner_zone_redirect;
+
+/// How often the purge job looks for organizations past their grace period.
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Deletion form: the organization's name, typed to confirm.
+#[derive(Debug, Deserialize)]
+pub struct DeleteOrganizationForm {
+    pub confirm_name: String,
+}
+
+/// When a scheduled deletion becomes due.
+pub fn deletion_due_at(requested_at: i64, grace_days: u32) -> i64 {
+    requested_at + i64::from(grace_days) * SECS_PER_DAY
+}
+
+/// The session's organization if the session user owns it; `Err` is the response to send instead.
+async fn owned_org(
+    state: &AppState,
+    session: &db::sessions::Session,
+) -> Result<(OrganizationId, db::organizations::Organization), Response> {
+    match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(OrganizationRole::Owner) => {}
+        _ => return Err((StatusCode::NOT_FOUND, "Not found".to_string()).into_response()),
+    }
+    let org_id = OrganizationId::from_string(&session.organization_id)
+        .map_err(|_| owner_zone_redirect("error", "Invalid organization."))?;
+    match db::organizations::find_by_id(&state.db, &org_id).await {
+        Ok(Some(org)) => Ok((org_id, org)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Not found".to_string()).into_response()),
+        Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()),
+    }
+}
+
+/// POST /app/settings/organization/delete — Schedule the organization for deletion (owners only).
+pub async fn schedule(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Form(form): Form<DeleteOrganizationForm>,
+) -> Response {
+    let (org_id, org) = match owned_org(&state, &session).await {
+        Ok(found) => found,
+        Err(response) => return response,
+    };
+    if form.confirm_name.trim() != org.name {
+        return owner_zone_redirect("error", "Type the organization's name exactly to confirm.");
+    }
+    if org.deletion_requested_at.is_some() {
+        return owner_zone_redirect("success", "Deletion is already scheduled.");
+    }
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    match db::organizations::set_deletion_requested_at(&state.db, &org_id, Some(now)).await {
+        Ok(()) => owner_zone_redirect(
+            "success",
+            &format!(
+                "The organization will be deleted in {} days. You can cancel until then.",
+                state.config.org_deletion_grace_days
+            ),
+        ),
+        Err(_) => owner_zone_redirect("error", "Failed to schedule deletion."),
+    }
+}
+
+/// POST /app/settings/organization/delete/cancel — Cancel a scheduled deletion (owners only).
+pub async fn cancel(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+) -> Response {
+    let (org_id, _) = match owned_org(&state, &session).await {
+        Ok(found) => found,
+        Err(response) => return response,
+    };
+    match db::organizations::set_deletion_requested_at(&state.db, &org_id, None).await {
+        Ok(()) => owner_zone_redirect("success", "Deletion cancelled."),
+        Err(_) => owner_zone_redirect("error", "Failed to cancel deletion."),
+    }
+}
+
+/// Give a user left without any organization a personal one, the same shape signup creates.
+pub(super) async fn create_personal_org(
+    conn: &mut sqlx::SqliteConnection,
+    user_id: &UserId,
+) -> Result<(), sqlx::Error> {
+    let Some(user) = db::users::find_by_id(&mut *conn, user_id).await? else {
+        return Ok(());
+    };
+    let org_id = OrganizationId::new();
+    let org_name = format!("{}'s Organization", user.email.split('@').next().unwrap_or("My"));
+    db::organizations::insert(&mut *conn, &db::organizations::NewOrganization {
+        id: org_id.clone(),
+        name: org_name.clone(),
+    })
+    .await?;
+    let team_id = ulid::Ulid::new().to_string();
+    db::teams::insert(&mut *conn, &db::teams::NewTeam {
+        id: team_id.clone(),
+        organization_id: org_id.as_str(),
+        name: org_name,
+    })
+    .await?;
+    db::organizations::add_member(&mut *conn, &org_id, user_id, OrganizationRole::Owner).await?;
+    db::team_members::add_member(&mut *conn, &team_id, user_id).await?;
+    db::users::update_organization_id(&mut *conn, user_id, &org_id).await?;
+    Ok(())
+}
+
+/// Delete an organization and everything in it, in one transaction.
+pub async fn purge_organization(pool: &sqlx::SqlitePool, org_id: &OrganizationId) -> Result<(), sqlx::Error> {
+    let org = org_id.as_str();
+    let mut tx = pool.begin().await?;
+    db::sessions::delete_for_org(&mut *tx, &org).await?;
+    db::organizations::remove_all_members(&mut *tx, org_id).await?;
+    for user_id in db::users::list_ids_in_organization(&mut *tx, &org).await? {
+        db::users::switch_organization_after_leaving(&mut *tx, &user_id, &org).await?;
+    }
+    // Whoever had nowhere else to go still points at the org.
+    for user_id in db::users::list_ids_in_organization(&mut *tx, &org).await? {
+        if let Ok(user_id) = UserId::from_string(&user_id) {
+            create_personal_org(&mut tx, &user_id).await?;
+        }
+    }
+    db::projects::delete_all_for_org(&mut *tx, &org).await?;
+    db::task_statuses::delete_for_org(&mut *tx, &org).await?;
+    db::organizations::delete(&mut *tx, org_id).await?;
+    tx.commit().await
+}
+
+/// Delete organizations whose deletion was requested more than `grace_days` before `now`.
+/// One that fails to purge is logged and retried on the next run; the rest still go.
+/// Returns how many were deleted.
+pub async fn purge_due(pool: &sqlx::SqlitePool, grace_days: u32, now: i64) -> Result<u64, sqlx::Error> {
+    let cutoff = now - i64::from(grace_days) * SECS_PER_DAY;
+    let mut purged = 0;
+    for id in db::organizations::list_ids_due_for_deletion(pool, cutoff).await? {
+        let Ok(org_id) = OrganizationId::from_string(&id) else {
+            continue;
+        };
+        match purge_organization(pool, &org_id).await {
+            Ok(()) => purged += 1,
+            Err(e) => tracing::error!(organization_id = %id, error = %e, "Failed to purge organization"),
+        }
+    }
+    Ok(purged)
+}
+
+/// Delete organizations past their grace period. Spawn once at startup.
+pub async fn run_scheduler(state: AppState) {
+    loop {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        match purge_due(&state.db, state.config.org_deletion_grace_days, now).await {
+            Ok(0) => {}
+            Ok(purged) => {
+                tracing::info!(purged, "Deleted organizations past their grace period");
+                graph::attachments::sweep_orphaned_blobs(&state).await;
+            }
+            Err(e) => tracing::error!(error = %e, "Organization purge failed"),
+        }
+        tokio::time::sleep(PURGE_INTERVAL).await;
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/app/settings/organization/delete", post(schedule))
+        .route("/app/settings/organization/delete/cancel", post(cancel))
+}
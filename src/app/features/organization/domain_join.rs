@@ -0,0 +1,134 @@
+//! Offering people from a verified domain a place in its organization. Signup sends new users here
+//! when their address matches; joining is always their choice.
+
+use askama::Template;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::get,
+    Form, Router,
+};
+use axum_extra::extract::cookie::CookieJar;
+use serde::Deserialize;
+use time::{Duration, OffsetDateTime};
+
+use crate::app::{
+    db,
+    domain::{Email, OrganizationId, OrganizationRole, UserId},
+    session::{self, AuthenticatedSession},
+    AppState, APP_NAME,
+};
+
+/// Join form: the verified domain claim being accepted.
+#[derive(Debug, Deserialize)]
+pub struct DomainJoinForm {
+    pub domain_id: String,
+}
+
+/// Offer page.
+#[derive(Template)]
+#[template(path = "domain_join.html")]
+pub struct DomainJoinTemplate {
+    pub app_name: &'static str,
+    pub org_name: String,
+    pub domain: String,
+    pub role_display: String,
+    pub domain_id: String,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+/// The user and the verified-domain organization they could join, if any.
+async fn offer_for(
+    pool: &sqlx::SqlitePool,
+    user_id: &UserId,
+) -> Result<Option<(String, db::organization_domains::DomainOffer)>, sqlx::Error> {
+    let Some(user) = db::users::find_by_id(pool, user_id).await? else {
+        return Ok(None);
+    };
+    let Ok(email) = Email::new(user.email) else {
+        return Ok(None);
+    };
+    let domain = email.domain().to_string();
+    let offer = db::organization_domains::find_offer_for_user(pool, &domain, &user_id.as_str()).await?;
+    Ok(offer.map(|o| (domain, o)))
+}
+
+/// GET /app/domain-join — Offer to join the organization that verified the user's email domain.
+pub async fn show(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+) -> Response {
+    let Ok(user_id) = UserId::from_string(&session.user_id) else {
+        return Redirect::to("/app").into_response();
+    };
+    let (domain, offer) = match offer_for(&state.db, &user_id).await {
+        Ok(Some(found)) => found,
+        Ok(None) => return Redirect::to("/app").into_response(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+    let role_display = match offer.default_role.parse::<OrganizationRole>() {
+        Ok(OrganizationRole::Admin) => "an admin",
+        Ok(OrganizationRole::Viewer) => "a viewer",
+        _ => "a member",
+    };
+    let template = DomainJoinTemplate {
+        app_name: APP_NAME,
+        org_name: offer.organization_name,
+        domain,
+        role_display: role_display.to_string(),
+        domain_id: offer.domain_id,
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    };
+    Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+}
+
+/// POST /app/domain-join — Join the offered organization and switch to it.
+pub async fn join(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    jar: CookieJar,
+    Form(form): Form<DomainJoinForm>,
+) -> Response {
+    let Ok(user_id) = UserId::from_string(&session.user_id) else {
+        return Redirect::to("/app").into_response();
+    };
+    let offer = match offer_for(&state.db, &user_id).await {
+        Ok(Some((_, offer))) if offer.domain_id == form.domain_id => offer,
+        Ok(_) => return Redirect::to("/app").into_response(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let Ok(org_id) = OrganizationId::from_string(&offer.organization_id) else {
+        return Redirect::to("/app").into_response();
+    };
+    let role = offer.default_role.parse::<OrganizationRole>().unwrap_or(OrganizationRole::Member);
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        db::organizations::add_member(&mut *tx, &org_id, &user_id, role).await?;
+        if let Some(team) = db::teams::find_in_org_or_default(&mut *tx, &org_id, offer.team_id.as_deref()).await? {
+            db::team_members::add_member(&mut *tx, &team.id, &user_id).await?;
+        }
+        db::users::update_organization_id(&mut *tx, &user_id, &org_id).await?;
+        db::sessions::delete(&mut *tx, &session.id).await?;
+        let expires_at = OffsetDateTime::now_utc() + Duration::days(30);
+        let new_session_id = db::sessions::create(&mut *tx, &user_id, &org_id, expires_at).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(new_session_id)
+    }
+    .await;
+    match result {
+        Ok(new_session_id) => {
+            let jar = jar.add(session::session_cookie(new_session_id));
+            (jar, Redirect::to("/app")).into_response()
+        }
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/app/domain-join", get(show).post(join))
+}
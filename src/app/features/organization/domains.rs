@@ -0,0 +1,204 @@
+//! Verified email domains. An admin claims a domain and publishes the TXT record shown on the
+//! settings page; once the record resolves, people signing up with an address at that domain are
+//! offered to join (see [`super::domain_join`]).
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+    routing::post,
+    Form, Router,
+};
+use serde::Deserialize;
+
+use crate::app::{
+    db,
+    domain::OrganizationRole,
+    session::AuthenticatedSession,
+    tenant,
+    AppState,
+};
+
+use super::settings::{can_invite, generate_invite_token};
+
+/// Host the verification record is published under, below the claimed domain.
+pub const VERIFICATION_RECORD_PREFIX: &str = "_boardtask-verification";
+/// Prefix of the verification record's value; the claim's token follows it.
+pub const VERIFICATION_VALUE_PREFIX: &str = "boardtask-verification=";
+
+/// Claim form. An empty `team_id` means the org's default team.
+#[derive(Debug, Deserialize)]
+pub struct ClaimDomainForm {
+    pub domain: String,
+    pub default_role: String,
+    #[serde(default)]
+    pub team_id: String,
+}
+
+fn domains_redirect(key: &str, msg: &str) -> Response {
+    Redirect::to(&format!(
+        "/app/settings/organization?{}={}#domains",
+        key,
+        urlencoding::encode(msg)
+    ))
+    .into_response()
+}
+
+/// Lowercase `input` and strip a leading `@`; None unless it looks like a registrable host name.
+pub fn normalize_domain(input: &str) -> Option<String> {
+    let domain = input.trim().trim_start_matches('@').trim_end_matches('.').to_lowercase();
+    let labels: Vec<&str> = domain.split('.').collect();
+    let valid = domain.len() <= 253
+        && labels.len() >= 2
+        && labels.iter().all(|l| {
+            !l.is_empty()
+                && l.len() <= 63
+                && !l.starts_with('-')
+                && !l.ends_with('-')
+                && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    valid.then_some(domain)
+}
+
+/// Where the verification record for `domain` lives.
+pub fn verification_record_name(domain: &str) -> String {
+    format!("{}.{}", VERIFICATION_RECORD_PREFIX, domain)
+}
+
+/// The value the verification record must hold.
+pub fn verification_record_value(token: &str) -> String {
+    format!("{}{}", VERIFICATION_VALUE_PREFIX, token)
+}
+
+async fn require_admin(state: &AppState, session: &db::sessions::Session) -> Result<(), Response> {
+    match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(r) if can_invite(r) => Ok(()),
+        _ => Err((StatusCode::NOT_FOUND, "Not found".to_string()).into_response()),
+    }
+}
+
+/// POST /app/settings/organization/domains — Claim a domain (owners/admins only). It does nothing
+/// until verified.
+pub async fn claim(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Form(form): Form<ClaimDomainForm>,
+) -> Response {
+    if let Err(response) = require_admin(&state, &session).await {
+        return response;
+    }
+    let Some(domain) = normalize_domain(&form.domain) else {
+        return domains_redirect("error", "Enter a domain like example.com.");
+    };
+    let default_role = match form.default_role.parse::<OrganizationRole>() {
+        Ok(OrganizationRole::Owner) => return domains_redirect("error", "Domain members can't join as owners."),
+        Ok(r) => r,
+        Err(_) => return domains_redirect("error", "Invalid role."),
+    };
+    let team_id = if form.team_id.is_empty() {
+        None
+    } else {
+        match db::teams::find_by_id(&state.db, &form.team_id).await {
+            Ok(Some(team)) if team.organization_id == session.organization_id => Some(team.id),
+            Ok(_) => return domains_redirect("error", "Team not found."),
+            Err(_) => return domains_redirect("error", "Failed to add domain."),
+        }
+    };
+    match db::organization_domains::is_verified_anywhere(&state.db, &domain).await {
+        Ok(false) => {}
+        Ok(true) => return domains_redirect("error", "That domain is already verified by an organization."),
+        Err(_) => return domains_redirect("error", "Failed to add domain."),
+    }
+
+    let claim = db::organization_domains::NewOrganizationDomain {
+        id: ulid::Ulid::new().to_string(),
+        organization_id: session.organization_id.clone(),
+        domain,
+        verification_token: generate_invite_token(),
+        default_role,
+        team_id,
+    };
+    match db::organization_domains::insert(&state.db, &claim).await {
+        Ok(()) => domains_redirect("success", "Domain added. Publish the TXT record below, then verify."),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            domains_redirect("error", "You have already added that domain.")
+        }
+        Err(_) => domains_redirect("error", "Failed to add domain."),
+    }
+}
+
+/// POST /app/settings/organization/domains/:id/verify — Look up the TXT record and verify the claim.
+pub async fn verify(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(domain_id): Path<String>,
+) -> Response {
+    if let Err(response) = require_admin(&state, &session).await {
+        return response;
+    }
+    let claim = match db::organization_domains::find_for_org(&state.db, &domain_id, &session.organization_id).await {
+        Ok(Some(c)) => c,
+        Ok(None) => return domains_redirect("error", "Domain not found."),
+        Err(_) => return domains_redirect("error", "Failed to verify domain."),
+    };
+    if claim.verified_at.is_some() {
+        return domains_redirect("success", "Domain already verified.");
+    }
+
+    let expected = verification_record_value(&claim.verification_token);
+    let records = match state.dns.lookup_txt(&verification_record_name(&claim.domain)).await {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!(domain = %claim.domain, error = %e, "Domain verification lookup failed");
+            return domains_redirect("error", "We couldn't look up the TXT record. Try again in a few minutes.");
+        }
+    };
+    if !records.iter().any(|r| r.trim() == expected) {
+        return domains_redirect("error", "The TXT record wasn't found yet. DNS changes can take a while to appear.");
+    }
+    match db::organization_domains::mark_verified(&state.db, &claim.id).await {
+        Ok(()) => domains_redirect("success", &format!("{} is verified.", claim.domain)),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            domains_redirect("error", "That domain is already verified by an organization.")
+        }
+        Err(_) => domains_redirect("error", "Failed to verify domain."),
+    }
+}
+
+/// POST /app/settings/organization/domains/:id/remove — Drop a claim; existing members stay.
+pub async fn remove(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(domain_id): Path<String>,
+) -> Response {
+    if let Err(response) = require_admin(&state, &session).await {
+        return response;
+    }
+    match db::organization_domains::delete(&state.db, &domain_id, &session.organization_id).await {
+        Ok(true) => domains_redirect("success", "Domain removed."),
+        Ok(false) => domains_redirect("error", "Domain not found."),
+        Err(_) => domains_redirect("error", "Failed to remove domain."),
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/app/settings/organization/domains", post(claim))
+        .route("/app/settings/organization/domains/:id/verify", post(verify))
+        .route("/app/settings/organization/domains/:id/remove", post(remove))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_domains_and_rejects_junk() {
+        assert_eq!(normalize_domain(" @Example.COM "), Some("example.com".to_string()));
+        assert_eq!(normalize_domain("mail.example.co.uk."), Some("mail.example.co.uk".to_string()));
+        assert_eq!(normalize_domain("localhost"), None);
+        assert_eq!(normalize_domain("exa mple.com"), None);
+        assert_eq!(normalize_domain("-bad.com"), None);
+        assert_eq!(normalize_domain("a..com"), None);
+    }
+}
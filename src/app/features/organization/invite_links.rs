@@ -0,0 +1,112 @@
+//! Reusable invite links. Admins create a link with a role, an optional team, a use limit and an
+//! expiry, and share it however they like; see `features::invites::join` for the other end.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+    Form, Router,
+};
+use serde::Deserialize;
+use time::{Duration, OffsetDateTime};
+
+use crate::app::{
+    db,
+    domain::OrganizationRole,
+    session::AuthenticatedSession,
+    tenant,
+    AppState,
+};
+
+use super::settings::{can_invite, generate_invite_token, invite_redirect_error, invite_redirect_success};
+
+/// Upper bounds for the create form.
+const MAX_USES: i64 = 1000;
+const MAX_DAYS: i64 = 90;
+
+/// Create form. An empty `team_id` means the org's default team.
+#[derive(Debug, Deserialize)]
+pub struct CreateInviteLinkForm {
+    pub role: String,
+    #[serde(default)]
+    pub team_id: String,
+    pub max_uses: i64,
+    pub expires_in_days: i64,
+}
+
+async fn require_inviter(state: &AppState, session: &db::sessions::Session) -> Result<(), Response> {
+    match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(r) if can_invite(r) => Ok(()),
+        _ => Err((StatusCode::NOT_FOUND, "Not found".to_string()).into_response()),
+    }
+}
+
+/// POST /app/settings/organization/invite-links — Create a link (owners/admins only).
+pub async fn create_link(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Form(form): Form<CreateInviteLinkForm>,
+) -> Response {
+    if let Err(response) = require_inviter(&state, &session).await {
+        return response;
+    }
+    let role = match form.role.parse::<OrganizationRole>() {
+        Ok(OrganizationRole::Owner) => return invite_redirect_error("Links can't make people owners.").into_response(),
+        Ok(r) => r,
+        Err(_) => return invite_redirect_error("Invalid role.").into_response(),
+    };
+    if !(1..=MAX_USES).contains(&form.max_uses) {
+        return invite_redirect_error(&format!("Uses must be between 1 and {}.", MAX_USES)).into_response();
+    }
+    if !(1..=MAX_DAYS).contains(&form.expires_in_days) {
+        return invite_redirect_error(&format!("Expiry must be between 1 and {} days.", MAX_DAYS)).into_response();
+    }
+    let team_id = if form.team_id.is_empty() {
+        None
+    } else {
+        match db::teams::find_by_id(&state.db, &form.team_id).await {
+            Ok(Some(team)) if team.organization_id == session.organization_id => Some(team.id),
+            Ok(_) => return invite_redirect_error("Team not found.").into_response(),
+            Err(_) => return invite_redirect_error("Failed to create link.").into_response(),
+        }
+    };
+
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let link = db::organization_invite_links::NewInviteLink {
+        id: ulid::Ulid::new().to_string(),
+        organization_id: session.organization_id.clone(),
+        token: generate_invite_token(),
+        role,
+        team_id,
+        max_uses: form.max_uses,
+        created_by_user_id: session.user_id.clone(),
+        expires_at: now + Duration::days(form.expires_in_days).whole_seconds(),
+    };
+    match db::organization_invite_links::insert(&state.db, &link).await {
+        Ok(()) => invite_redirect_success("Invite link created.").into_response(),
+        Err(_) => invite_redirect_error("Failed to create link.").into_response(),
+    }
+}
+
+/// POST /app/settings/organization/invite-links/:id/revoke — Stop a link from working.
+pub async fn revoke_link(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(link_id): Path<String>,
+) -> Response {
+    if let Err(response) = require_inviter(&state, &session).await {
+        return response;
+    }
+    match db::organization_invite_links::delete(&state.db, &link_id, &session.organization_id).await {
+        Ok(true) => invite_redirect_success("Invite link revoked.").into_response(),
+        Ok(false) => invite_redirect_error("Invite link not found.").into_response(),
+        Err(_) => invite_redirect_error("Failed to revoke link.").into_response(),
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/app/settings/organization/invite-links", post(create_link))
+        .route("/app/settings/organization/invite-links/:id/revoke", post(revoke_link))
+}
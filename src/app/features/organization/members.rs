@@ -0,0 +1,197 @@
+//! Changing member roles and removing members. Owners can do anything except leave the org without
+//! an owner; admins can manage everyone but owners, and can't hand out the owner role.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+    routing::post,
+    Form, Router,
+};
+use serde::Deserialize;
+
+use crate::app::{
+    db,
+    domain::{OrganizationId, OrganizationRole, UserId},
+    session::AuthenticatedSession,
+    tenant,
+    AppState,
+};
+
+/// Role change form.
+#[derive(Debug, Deserialize)]
+pub struct RoleForm {
+    pub role: String,
+}
+
+fn members_redirect(key: &str, msg: &str) -> Response {
+    Redirect::to(&format!(
+        "/app/settings/organization?{}={}#members",
+        key,
+        urlencoding::encode(msg)
+    ))
+    .into_response()
+}
+
+/// Whether `actor` may manage a member whose role is `target` (admins can't touch owners).
+pub(super) fn can_manage_member(actor: OrganizationRole, target: OrganizationRole) -> bool {
+    match actor {
+        OrganizationRole::Owner => true,
+        OrganizationRole::Admin => target != OrganizationRole::Owner,
+        OrganizationRole::Member | OrganizationRole::Viewer => false,
+    }
+}
+
+/// Check a role change (`new_role`) or removal (`None`) against the safeguards. `owner_count` is the
+/// org's current number of owners.
+fn check_member_change(
+    actor: OrganizationRole,
+    target: OrganizationRole,
+    new_role: Option<OrganizationRole>,
+    owner_count: i64,
+) -> Result<(), &'static str> {
+    if !can_manage_member(actor, target) || (actor != OrganizationRole::Owner && new_role == Some(OrganizationRole::Owner)) {
+        return Err("Only owners can change who is an owner.");
+    }
+    if target == OrganizationRole::Owner && new_role != Some(OrganizationRole::Owner) && owner_count <= 1 {
+        return Err("The organization needs at least one owner.");
+    }
+    Ok(())
+}
+
+/// The session's org, role and the target member's role; `Err` is the response to send instead.
+async fn load_roles(
+    state: &AppState,
+    session: &db::sessions::Session,
+    target_user_id: &str,
+) -> Result<(OrganizationId, OrganizationRole, UserId, OrganizationRole), Response> {
+    let actor_role = match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(r) if matches!(r, OrganizationRole::Owner | OrganizationRole::Admin) => r,
+        _ => return Err((StatusCode::NOT_FOUND, "Not found".to_string()).into_response()),
+    };
+    let org_id = OrganizationId::from_string(&session.organization_id)
+        .map_err(|_| members_redirect("error", "Invalid organization."))?;
+    let target_id =
+        UserId::from_string(target_user_id).map_err(|_| members_redirect("error", "Member not found."))?;
+    let target_role = match db::organizations::find_member_role(&state.db, &org_id, &target_id).await {
+        Ok(Some(r)) => r,
+        Ok(None) => return Err(members_redirect("error", "Member not found.")),
+        Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()),
+    };
+    Ok((org_id, actor_role, target_id, target_role))
+}
+
+/// POST /app/settings/organization/members/:user_id/role — Change a member's role.
+pub async fn change_role(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Form(form): Form<RoleForm>,
+) -> Response {
+    let (org_id, actor_role, target_id, target_role) = match load_roles(&state, &session, &user_id).await {
+        Ok(roles) => roles,
+        Err(response) => return response,
+    };
+    let new_role = match form.role.parse::<OrganizationRole>() {
+        Ok(r) => r,
+        Err(_) => return members_redirect("error", "Invalid role."),
+    };
+    if new_role == target_role {
+        return members_redirect("success", "Role unchanged.");
+    }
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let owners =
+            db::organizations::count_members_with_role(&mut *tx, &org_id, OrganizationRole::Owner).await?;
+        if let Err(msg) = check_member_change(actor_role, target_role, Some(new_role), owners) {
+            return Ok(Err(msg));
+        }
+        db::organizations::update_member_role(&mut *tx, &org_id, &target_id, new_role).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(Ok(()))
+    }
+    .await;
+    match result {
+        Ok(Ok(())) => members_redirect("success", "Role updated."),
+        Ok(Err(msg)) => members_redirect("error", msg),
+        Err(_) => members_redirect("error", "Failed to update role."),
+    }
+}
+
+/// POST /app/settings/organization/members/:user_id/remove — Remove a member from the organization.
+///
+/// Also takes them off the org's teams, unassigns their nodes, slots and checklist items, signs out their sessions in
+/// this org and, if it was their current org, moves them to another one they belong to.
+pub async fn remove_member(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+) -> Response {
+    let (org_id, actor_role, target_id, target_role) = match load_roles(&state, &session, &user_id).await {
+        Ok(roles) => roles,
+        Err(response) => return response,
+    };
+    if target_id.as_str() == session.user_id {
+        return members_redirect("error", "You can't remove yourself.");
+    }
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let owners =
+            db::organizations::count_members_with_role(&mut *tx, &org_id, OrganizationRole::Owner).await?;
+        if let Err(msg) = check_member_change(actor_role, target_role, None, owners) {
+            return Ok(Err(msg));
+        }
+        let (org, target) = (org_id.as_str(), target_id.as_str());
+        db::organizations::remove_member(&mut *tx, &org_id, &target_id).await?;
+        db::team_members::remove_from_org_teams(&mut *tx, &org, &target).await?;
+        db::nodes::unassign_user_in_org(&mut *tx, &org, &target).await?;
+        db::project_slots::unassign_user_in_org(&mut *tx, &org, &target).await?;
+        db::checklist_items::unassign_user_in_org(&mut *tx, &org, &target).await?;
+        db::sessions::delete_for_user_in_org(&mut *tx, &target, &org).await?;
+        db::users::switch_organization_after_leaving(&mut *tx, &target, &org).await?;
+        // Nowhere else to go: give them a fresh personal organization, as a purge does.
+        let user = db::users::find_by_id(&mut *tx, &target_id).await?;
+        if user.is_some_and(|u| u.organization_id == org) {
+            super::deletion::create_personal_org(&mut tx, &target_id).await?;
+        }
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(Ok(()))
+    }
+    .await;
+    match result {
+        Ok(Ok(())) => members_redirect("success", "Member removed."),
+        Ok(Err(msg)) => members_redirect("error", msg),
+        Err(_) => members_redirect("error", "Failed to remove member."),
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/app/settings/organization/members/:user_id/role", post(change_role))
+        .route("/app/settings/organization/members/:user_id/remove", post(remove_member))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use OrganizationRole::*;
+
+    #[test]
+    fn admins_cannot_touch_owners_or_make_new_ones() {
+        assert!(check_member_change(Admin, Owner, Some(Member), 2).is_err());
+        assert!(check_member_change(Admin, Owner, None, 2).is_err());
+        assert!(check_member_change(Admin, Member, Some(Owner), 1).is_err());
+        assert!(check_member_change(Admin, Member, Some(Admin), 1).is_ok());
+        assert!(check_member_change(Admin, Admin, None, 1).is_ok());
+    }
+
+    #[test]
+    fn the_last_owner_stays_an_owner() {
+        assert!(check_member_change(Owner, Owner, Some(Admin), 1).is_err());
+        assert!(check_member_change(Owner, Owner, None, 1).is_err());
+        assert!(check_member_change(Owner, Owner, Some(Admin), 2).is_ok());
+        assert!(check_member_change(Owner, Member, Some(Owner), 1).is_ok());
+    }
+}
@@ -1,10 +1,25 @@
+mod bulk_invites;
+pub mod deletion;
+mod domain_join;
+pub mod domains;
+mod invite_links;
+mod members;
+mod ownership;
 mod settings;
 
 use axum::Router;
 
 use crate::app::AppState;
 
-/// Organization settings and invite routes.
+/// Organization settings, member management, ownership and invite routes.
 pub fn routes() -> Router<AppState> {
-    Router::new().merge(settings::routes())
+    Router::new()
+        .merge(settings::routes())
+        .merge(members::routes())
+        .merge(ownership::routes())
+        .merge(deletion::routes())
+        .merge(bulk_invites::routes())
+        .merge(invite_links::routes())
+        .merge(domains::routes())
+        .merge(domain_join::routes())
 }
@@ -0,0 +1,268 @@
+//! Handing an organization to another member. The owner picks a member, who gets an emailed link;
+//! ownership only moves when the recipient, signed in as themselves, confirms it. The previous
+//! owner stays on as an admin.
+
+use askama::Template;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Form, Router,
+};
+use serde::Deserialize;
+use time::{Duration, OffsetDateTime};
+
+use crate::app::{
+    db,
+    domain::{Email, OrganizationId, OrganizationRole, UserId},
+    mail,
+    session::AuthenticatedSession,
+    tenant,
+    AppState, APP_NAME,
+};
+
+use super::settings::owner_zone_redirect;
+
+/// How long the recipient has to confirm.
+const TRANSFER_VALID_DAYS: i64 = 7;
+
+fn invalid_link() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        "This ownership transfer link is invalid or has expired.".to_string(),
+    )
+        .into_response()
+}
+
+/// Transfer form: the member who should become owner.
+#[derive(Debug, Deserialize)]
+pub struct TransferForm {
+    pub to_user_id: String,
+}
+
+/// Token carried by the confirmation link and form.
+#[derive(Debug, Deserialize)]
+pub struct TransferTokenQuery {
+    pub token: String,
+}
+
+/// Recipient's confirmation page.
+#[derive(Template)]
+#[template(path = "ownership_transfer_accept.html")]
+pub struct OwnershipTransferAcceptTemplate {
+    pub app_name: &'static str,
+    pub org_name: String,
+    pub from_email: String,
+    pub token: String,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+/// POST /app/settings/organization/transfer — Offer ownership to another member (owners only).
+/// Replaces any pending offer.
+pub async fn start_transfer(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Form(form): Form<TransferForm>,
+) -> Response {
+    match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(OrganizationRole::Owner) => {}
+        _ => return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response(),
+    }
+    let (Ok(org_id), Ok(sender_id)) = (
+        OrganizationId::from_string(&session.organization_id),
+        UserId::from_string(&session.user_id),
+    ) else {
+        return owner_zone_redirect("error", "Invalid organization.");
+    };
+    let recipient = match UserId::from_string(&form.to_user_id) {
+        Ok(id) if id.as_str() != session.user_id => id,
+        _ => return owner_zone_redirect("error", "Choose another member of the organization."),
+    };
+    match db::organizations::find_member_role(&state.db, &org_id, &recipient).await {
+        Ok(Some(OrganizationRole::Owner)) => {
+            return owner_zone_redirect("error", "That member is already an owner.")
+        }
+        Ok(Some(_)) => {}
+        Ok(None) => return owner_zone_redirect("error", "Choose another member of the organization."),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    }
+    let (org, recipient_user, sender) = match tokio::try_join!(
+        db::organizations::find_by_id(&state.db, &org_id),
+        db::users::find_by_id(&state.db, &recipient),
+        db::users::find_by_id(&state.db, &sender_id),
+    ) {
+        Ok((Some(o), Some(r), Some(s))) => (o, r, s),
+        Ok(_) => return owner_zone_redirect("error", "Choose another member of the organization."),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let Ok(recipient_email) = Email::new(recipient_user.email.clone()) else {
+        return owner_zone_redirect("error", "That member has no usable email address.");
+    };
+
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let transfer = db::organization_ownership_transfers::OwnershipTransfer {
+        id: ulid::Ulid::new().to_string(),
+        organization_id: org.id.clone(),
+        from_user_id: session.user_id.clone(),
+        to_user_id: recipient.as_str().to_string(),
+        token: super::settings::generate_invite_token(),
+        expires_at: now + Duration::days(TRANSFER_VALID_DAYS).whole_seconds(),
+        created_at: now,
+    };
+    let saved = async {
+        let mut tx = state.db.begin().await?;
+        db::organization_ownership_transfers::delete_for_org(&mut *tx, &org.id).await?;
+        db::organization_ownership_transfers::insert(&mut *tx, &transfer).await?;
+        tx.commit().await
+    }
+    .await;
+    if saved.is_err() {
+        return owner_zone_redirect("error", "Failed to start the transfer.");
+    }
+
+    let url = format!(
+        "{}/app/ownership-transfer?token={}",
+        state.config.app_url_base(),
+        urlencoding::encode(&transfer.token)
+    );
+    let headline = format!("{} wants to make you the owner of {}", sender.email, org.name);
+    let detail = format!(
+        "Sign in as {} and open the link to accept. It expires in {} days.",
+        recipient_user.email, TRANSFER_VALID_DAYS
+    );
+    let sent = match mail::templates::notification(&org.name, headline.clone(), &headline, &detail, &url) {
+        Ok(content) => {
+            let msg = mail::EmailMessage::from_content(recipient_email, content, state.config.mail_from.clone());
+            state.mail.send(&msg).await
+        }
+        Err(e) => Err(e),
+    };
+    if sent.is_err() {
+        return owner_zone_redirect("error", "Transfer created but we couldn't send the email. Please try again.");
+    }
+    owner_zone_redirect(
+        "success",
+        &format!("Ownership offered to {}. It moves once they confirm.", recipient_user.email),
+    )
+}
+
+/// POST /app/settings/organization/transfer/cancel — Withdraw the pending offer (owners only).
+pub async fn cancel_transfer(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+) -> Response {
+    match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(OrganizationRole::Owner) => {}
+        _ => return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response(),
+    }
+    match db::organization_ownership_transfers::delete_for_org(&state.db, &session.organization_id).await {
+        Ok(()) => owner_zone_redirect("success", "Ownership transfer cancelled."),
+        Err(_) => owner_zone_redirect("error", "Failed to cancel the transfer."),
+    }
+}
+
+/// The transfer behind `token` if it is live and addressed to `user_id`.
+async fn live_transfer_for(
+    pool: &sqlx::SqlitePool,
+    token: &str,
+    user_id: &str,
+) -> Result<Option<db::organization_ownership_transfers::OwnershipTransfer>, sqlx::Error> {
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    Ok(db::organization_ownership_transfers::find_by_token(pool, token)
+        .await?
+        .filter(|t| t.to_user_id == user_id && t.expires_at > now))
+}
+
+/// GET /app/ownership-transfer?token= — Recipient's confirmation page.
+pub async fn show_accept(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Query(query): Query<TransferTokenQuery>,
+) -> Response {
+    let transfer = match live_transfer_for(&state.db, &query.token, &session.user_id).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return invalid_link(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let (Ok(org_id), Ok(from_id), Ok(user_id)) = (
+        OrganizationId::from_string(&transfer.organization_id),
+        UserId::from_string(&transfer.from_user_id),
+        UserId::from_string(&session.user_id),
+    ) else {
+        return invalid_link();
+    };
+    let (org, from) = match tokio::try_join!(
+        db::organizations::find_by_id(&state.db, &org_id),
+        db::users::find_by_id(&state.db, &from_id),
+    ) {
+        Ok((Some(o), Some(f))) => (o, f),
+        Ok(_) => return invalid_link(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+    let template = OwnershipTransferAcceptTemplate {
+        app_name: APP_NAME,
+        org_name: org.name,
+        from_email: from.email,
+        token: transfer.token,
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    };
+    Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+}
+
+/// POST /app/ownership-transfer/accept — Recipient confirms: they become owner, the sender an admin.
+pub async fn accept(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Form(form): Form<TransferTokenQuery>,
+) -> Response {
+    let transfer = match live_transfer_for(&state.db, &form.token, &session.user_id).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return invalid_link(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let (Ok(org_id), Ok(from_id), Ok(to_id)) = (
+        OrganizationId::from_string(&transfer.organization_id),
+        UserId::from_string(&transfer.from_user_id),
+        UserId::from_string(&transfer.to_user_id),
+    ) else {
+        return invalid_link();
+    };
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        let from_role = db::organizations::find_member_role(&mut *tx, &org_id, &from_id).await?;
+        let to_role = db::organizations::find_member_role(&mut *tx, &org_id, &to_id).await?;
+        // The offer lapses if the sender is no longer an owner or the recipient has left.
+        if from_role != Some(OrganizationRole::Owner) || to_role.is_none() {
+            db::organization_ownership_transfers::delete_for_org(&mut *tx, &transfer.organization_id).await?;
+            tx.commit().await?;
+            return Ok(false);
+        }
+        db::organizations::update_member_role(&mut *tx, &org_id, &to_id, OrganizationRole::Owner).await?;
+        db::organizations::update_member_role(&mut *tx, &org_id, &from_id, OrganizationRole::Admin).await?;
+        db::organization_ownership_transfers::delete_for_org(&mut *tx, &transfer.organization_id).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(true)
+    }
+    .await;
+    match result {
+        Ok(true) if session.organization_id == transfer.organization_id => {
+            owner_zone_redirect("success", "You are now the owner of this organization.")
+        }
+        Ok(true) => Redirect::to("/app").into_response(),
+        Ok(false) => invalid_link(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/app/settings/organization/transfer", post(start_transfer))
+        .route("/app/settings/organization/transfer/cancel", post(cancel_transfer))
+        .route("/app/ownership-transfer", get(show_accept))
+        .route("/app/ownership-transfer/accept", post(accept))
+}
@@ -14,6 +14,7 @@ use validator::Validate;
 use crate::app::{
     db,
     domain::{Email, OrganizationId, OrganizationRole, UserId},
+    mail,
     session::AuthenticatedSession,
     tenant,
     AppState, APP_NAME,
@@ -21,9 +22,15 @@ use crate::app::{
 
 /// One row for the members table on the org settings page.
 pub struct MemberRow {
+    pub user_id: String,
     pub display_name: String,
     pub email: String,
+    pub role: String,
     pub role_display: String,
+    /// The viewer may change this member's role.
+    pub can_edit: bool,
+    /// The viewer may remove this member (never themselves).
+    pub can_remove: bool,
     pub avatar_url: String,
     pub initials: String,
 }
@@ -37,6 +44,40 @@ pub struct PendingInviteRow {
     pub expires_in: String,
 }
 
+/// One active invite link.
+pub struct InviteLinkRow {
+    pub id: String,
+    pub url: String,
+    pub role_display: String,
+    pub team_name: String,
+    pub uses: String,
+    pub expires_in: String,
+}
+
+/// One claimed email domain.
+pub struct DomainRow {
+    pub id: String,
+    pub domain: String,
+    pub verified: bool,
+    /// TXT record to publish while unverified.
+    pub record_name: String,
+    pub record_value: String,
+    pub role_display: String,
+    pub team_name: String,
+}
+
+/// A team option for the invite link form.
+pub struct TeamOption {
+    pub id: String,
+    pub name: String,
+}
+
+/// A member the owner can hand the organization to.
+pub struct TransferCandidate {
+    pub user_id: String,
+    pub label: String,
+}
+
 /// Human-readable role label for the UI.
 fn role_display(role: &str) -> String {
     match role.to_lowercase().as_str() {
@@ -105,10 +146,23 @@ pub struct OrganizationSettingsTemplate {
     pub org_name: String,
     pub members: Vec<MemberRow>,
     pub members_total: usize,
+    /// Roles the viewer can hand out (owners only for owners).
+    pub assignable_roles: Vec<String>,
     pub pending_invites: Vec<PendingInviteRow>,
+    pub invite_links: Vec<InviteLinkRow>,
+    pub domains: Vec<DomainRow>,
+    pub teams: Vec<TeamOption>,
+    /// The viewer owns the org and sees the ownership and deletion controls.
+    pub is_owner: bool,
+    pub transfer_candidates: Vec<TransferCandidate>,
+    /// Recipient of the pending ownership transfer, empty if none.
+    pub pending_transfer_email: String,
+    /// Day the scheduled deletion happens, empty if none is scheduled.
+    pub deletion_date: String,
     pub error: String,
     pub success: String,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
 }
 
 /// Invite form data from HTTP request.
@@ -128,7 +182,7 @@ pub struct OrganizationSettingsQuery {
     pub success: Option<String>,
 }
 
-fn can_invite(role: OrganizationRole) -> bool {
+pub(super) fn can_invite(role: OrganizationRole) -> bool {
     matches!(role, OrganizationRole::Owner | OrganizationRole::Admin)
 }
 
@@ -162,16 +216,42 @@ pub async fn show(
         Ok(m) => m,
         Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response(),
     };
+    let is_owner = role == OrganizationRole::Owner;
+    let transfer_candidates: Vec<TransferCandidate> = members
+        .iter()
+        .filter(|m| is_owner && m.user_id != session.user_id && m.role != OrganizationRole::Owner.to_string())
+        .map(|m| TransferCandidate { user_id: m.user_id.clone(), label: m.email.clone() })
+        .collect();
+    let pending_transfer_email = match db::organization_ownership_transfers::find_for_org(&state.db, &org.id).await {
+        Ok(Some(t)) => members.iter().find(|m| m.user_id == t.to_user_id).map(|m| m.email.clone()).unwrap_or_default(),
+        Ok(None) => String::new(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response(),
+    };
+    let deletion_date = org
+        .deletion_requested_at
+        .map(|at| super::deletion::deletion_due_at(at, state.config.org_deletion_grace_days))
+        .and_then(|at| OffsetDateTime::from_unix_timestamp(at).ok())
+        .map(|t| t.date().to_string())
+        .unwrap_or_default();
     let members: Vec<MemberRow> = members
         .into_iter()
         .map(|m| {
             let display_name = db::display_name_from_parts(&m.first_name, &m.last_name);
             let initials = initials_from_name(&display_name, &m.email);
             let avatar_url = m.profile_image_url.unwrap_or_default();
+            let can_edit = m
+                .role
+                .parse::<OrganizationRole>()
+                .map(|target| super::members::can_manage_member(role, target))
+                .unwrap_or(false);
             MemberRow {
+                can_remove: can_edit && m.user_id != session.user_id,
+                can_edit,
+                user_id: m.user_id,
                 display_name,
                 email: m.email,
                 role_display: role_display(&m.role),
+                role: m.role,
                 avatar_url,
                 initials,
             }
@@ -193,6 +273,47 @@ pub async fn show(
         })
         .collect();
 
+    let (links, claimed_domains, teams) = match tokio::try_join!(
+        db::organization_invite_links::list_active_for_org(&state.db, &org.id),
+        db::organization_domains::list_for_org(&state.db, &org.id),
+        db::teams::find_by_organization(&state.db, &org.id),
+    ) {
+        Ok(found) => found,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()).into_response(),
+    };
+    let default_team = teams.first().map(|t| t.name.clone()).unwrap_or_default();
+    let team_name = |team_id: &Option<String>| {
+        team_id
+            .as_ref()
+            .and_then(|id| teams.iter().find(|t| &t.id == id))
+            .map(|t| t.name.clone())
+            .unwrap_or_else(|| default_team.clone())
+    };
+    let invite_links: Vec<InviteLinkRow> = links
+        .into_iter()
+        .map(|l| InviteLinkRow {
+            url: format!("{}/join?token={}", state.config.app_url_base(), l.token),
+            role_display: role_display(&l.role),
+            team_name: team_name(&l.team_id),
+            uses: format!("{} / {}", l.use_count, l.max_uses),
+            expires_in: format_expires_in(l.expires_at),
+            id: l.id,
+        })
+        .collect();
+    let domains: Vec<DomainRow> = claimed_domains
+        .into_iter()
+        .map(|d| DomainRow {
+            verified: d.verified_at.is_some(),
+            record_name: super::domains::verification_record_name(&d.domain),
+            record_value: super::domains::verification_record_value(&d.verification_token),
+            role_display: role_display(&d.default_role),
+            team_name: team_name(&d.team_id),
+            id: d.id,
+            domain: d.domain,
+        })
+        .collect();
+    let teams: Vec<TeamOption> = teams.into_iter().map(|t| TeamOption { id: t.id, name: t.name }).collect();
+
     let user_id = match UserId::from_string(&session.user_id) {
         Ok(id) => id,
         Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid session".to_string()).into_response(),
@@ -206,24 +327,47 @@ pub async fn show(
         org_name: org.name.clone(),
         members,
         members_total,
+        assignable_roles: [OrganizationRole::Owner, OrganizationRole::Admin, OrganizationRole::Member, OrganizationRole::Viewer]
+            .into_iter()
+            .filter(|r| role == OrganizationRole::Owner || *r != OrganizationRole::Owner)
+            .map(|r| r.to_string())
+            .collect(),
         pending_invites,
+        invite_links,
+        domains,
+        teams,
+        is_owner,
+        transfer_candidates,
+        pending_transfer_email,
+        deletion_date,
         error: query.error.unwrap_or_default(),
         success: query.success.unwrap_or_default(),
         current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
     };
     Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
 }
 
-fn invite_redirect_error(msg: &str) -> Redirect {
+/// Back to the settings page's owner zone (ownership transfer and deletion) with a message.
+pub(super) fn owner_zone_redirect(key: &str, msg: &str) -> Response {
+    Redirect::to(&format!(
+        "/app/settings/organization?{}={}#owner-zone",
+        key,
+        urlencoding::encode(msg)
+    ))
+    .into_response()
+}
+
+pub(super) fn invite_redirect_error(msg: &str) -> Redirect {
     Redirect::to(&format!("/app/settings/organization?error={}", urlencoding::encode(msg)))
 }
 
-fn invite_redirect_success(msg: &str) -> Redirect {
+pub(super) fn invite_redirect_success(msg: &str) -> Redirect {
     Redirect::to(&format!("/app/settings/organization?success={}", urlencoding::encode(msg)))
 }
 
 /// Generate a high-entropy invite token (64 hex chars = 32 bytes).
-fn generate_invite_token() -> String {
+pub(super) fn generate_invite_token() -> String {
     let mut bytes = [0u8; 32];
     rand_core::OsRng.fill_bytes(&mut bytes);
     hex::encode(bytes)
@@ -252,7 +396,6 @@ pub async fn create_invite(
         Ok(e) => e,
         Err(_) => return invite_redirect_error("Invalid email address.").into_response(),
     };
-    let email_str = email.as_str().to_string();
 
     let invite_role = match form.role.parse::<OrganizationRole>() {
         Ok(r) => r,
@@ -269,11 +412,36 @@ pub async fn create_invite(
         Err(_) => return invite_redirect_error("Invalid session.").into_response(),
     };
 
+    let org = match db::organizations::find_by_id(&state.db, &org_id).await {
+        Ok(Some(o)) => o,
+        _ => return invite_redirect_error("Organization not found.").into_response(),
+    };
+
+    match send_invite(&state, &org, &user_id, email, invite_role, None).await {
+        Ok(true) => invite_redirect_success("Previous invite cancelled; new invite sent.").into_response(),
+        Ok(false) => invite_redirect_success("Invite sent.").into_response(),
+        Err(msg) => invite_redirect_error(msg).into_response(),
+    }
+}
+
+/// Invite `email` to `org` and email them the link. A pending invite for the same address is
+/// replaced; `Ok(true)` means that happened. `Err` is a message for the settings page.
+pub(super) async fn send_invite(
+    state: &AppState,
+    org: &db::organizations::Organization,
+    invited_by: &UserId,
+    email: Email,
+    role: OrganizationRole,
+    team_id: Option<String>,
+) -> Result<bool, &'static str> {
+    let org_id = OrganizationId::from_string(&org.id).map_err(|_| "Invalid organization.")?;
+    let email_str = email.as_str().to_string();
+
     // Already a member?
     if let Ok(Some(existing_user)) = db::find_by_email(&state.db, &email).await {
         let existing_id = UserId::from_string(&existing_user.id).unwrap_or_else(|_| UserId::new());
         if db::organizations::is_member(&state.db, &org_id, &existing_id).await.unwrap_or(false) {
-            return invite_redirect_error("That email is already a member of this organization.").into_response();
+            return Err("That email is already a member of this organization.");
         }
     }
 
@@ -287,7 +455,7 @@ pub async fn create_invite(
             .await
             .is_err()
         {
-            return invite_redirect_error("Failed to update invite.").into_response();
+            return Err("Failed to update invite.");
         }
         resend = true;
     }
@@ -299,17 +467,18 @@ pub async fn create_invite(
 
     let invite = db::organization_invites::NewOrganizationInvite {
         id: id.to_string(),
-        organization_id: org_id.clone(),
-        email: email_str.clone(),
-        role: invite_role,
-        invited_by_user_id: user_id,
+        organization_id: org_id,
+        email: email_str,
+        role,
+        invited_by_user_id: invited_by.clone(),
         token: token.clone(),
         expires_at,
         created_at: now,
+        team_id,
     };
 
     if db::organization_invites::insert(&state.db, &invite).await.is_err() {
-        return invite_redirect_error("Failed to create invite.").into_response();
+        return Err("Failed to create invite.");
     }
 
     let invite_url = format!(
@@ -317,32 +486,17 @@ pub async fn create_invite(
         state.config.app_url_base(),
         urlencoding::encode(&token)
     );
-    let org = match db::organizations::find_by_id(&state.db, &org_id).await {
-        Ok(Some(o)) => o,
-        _ => return invite_redirect_error("Organization not found.").into_response(),
+    let sent = match mail::templates::invite(&org.name, &invite_url) {
+        Ok(content) => {
+            let msg = mail::EmailMessage::from_content(email, content, state.config.mail_from.clone());
+            state.mail.send(&msg).await
+        }
+        Err(e) => Err(e),
     };
-    let body = format!(
-        "You've been invited to join {} on {}. Click here to accept: {}",
-        org.name,
-        APP_NAME,
-        invite_url
-    );
-    let msg = crate::app::mail::EmailMessage::new(
-        email,
-        format!("You're invited to join {}", org.name),
-        body,
-        state.config.mail_from.clone(),
-    );
-    if state.mail.send(&msg).await.is_err() {
-        return invite_redirect_error("Invite created but we couldn't send the email. Please try again.").into_response();
+    if sent.is_err() {
+        return Err("Invite created but we couldn't send the email. Please try again.");
     }
-
-    let success_msg = if resend {
-        "Previous invite cancelled; new invite sent."
-    } else {
-        "Invite sent."
-    };
-    invite_redirect_success(success_msg).into_response()
+    Ok(resend)
 }
 
 /// POST /app/settings/organization/invite/:id/revoke — Revoke a pending invite.
@@ -427,6 +581,7 @@ pub async fn resend_invite(
         token: token.clone(),
         expires_at,
         created_at: now,
+        team_id: invite.team_id.clone(),
     };
 
     if db::organization_invites::insert(&state.db, &new_invite).await.is_err() {
@@ -442,19 +597,14 @@ pub async fn resend_invite(
         state.config.app_url_base(),
         urlencoding::encode(&token)
     );
-    let body = format!(
-        "You've been invited to join {} on {}. Click here to accept: {}",
-        org.name,
-        APP_NAME,
-        invite_url
-    );
-    let msg = crate::app::mail::EmailMessage::new(
-        email,
-        format!("You're invited to join {}", org.name),
-        body,
-        state.config.mail_from.clone(),
-    );
-    if state.mail.send(&msg).await.is_err() {
+    let sent = match mail::templates::invite(&org.name, &invite_url) {
+        Ok(content) => {
+            let msg = mail::EmailMessage::from_content(email, content, state.config.mail_from.clone());
+            state.mail.send(&msg).await
+        }
+        Err(e) => Err(e),
+    };
+    if sent.is_err() {
         return invite_redirect_error("Invite resent but email could not be sent. Please try again.").into_response();
     }
     invite_redirect_success("Invitation resent.").into_response()
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+
+use crate::app::{
+    db,
+    domain::{OrganizationId, UserId},
+    session::AuthenticatedSession,
+    tenant,
+    AppState, APP_NAME,
+};
+
+use super::{
+    filter::{NodeFilter, NodeFilterQuery},
+    format, helpers, progress,
+};
+
+/// How board rows are split.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Swimlanes {
+    #[default]
+    None,
+    Slot,
+    Assignee,
+}
+
+/// Board-specific query parameters (filters come from [`NodeFilterQuery`]).
+#[derive(Debug, Default, Deserialize)]
+pub struct BoardQuery {
+    #[serde(default)]
+    pub lanes: Swimlanes,
+}
+
+/// One card on the board.
+pub struct BoardCard {
+    pub id: String,
+    pub title: String,
+    pub node_type_name: String,
+    pub assignee_name: String,
+    pub estimated_display: String,
+    pub blocked: bool,
+}
+
+/// A status column header.
+pub struct BoardColumn {
+    pub status_id: String,
+    pub name: String,
+    pub count: usize,
+}
+
+/// One horizontal lane; `cells` lines up with the board's columns.
+pub struct BoardLane {
+    pub name: String,
+    pub cells: Vec<Vec<BoardCard>>,
+}
+
+/// Project board template.
+#[derive(Template)]
+#[template(path = "projects_board.html")]
+pub struct ProjectBoardTemplate {
+    pub app_name: &'static str,
+    pub project: db::projects::Project,
+    pub columns: Vec<BoardColumn>,
+    pub lanes: Vec<BoardLane>,
+    pub lanes_mode: &'static str,
+    pub is_empty: bool,
+    pub filter_active: bool,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+/// GET /app/projects/:id/board — Kanban board of task nodes in status columns, optionally in
+/// swimlanes by slot or assignee. Accepts the same filters as the list view.
+pub async fn board(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(filter_query): Query<NodeFilterQuery>,
+    Query(board_query): Query<BoardQuery>,
+) -> impl IntoResponse {
+    let node_filter = match NodeFilter::from_query(&filter_query, &session.user_id) {
+        Ok(f) => f,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
+    let project = match helpers::load_project(&state.db, &id).await {
+        Ok(p) => p,
+        Err((status, msg)) => return (status, msg).into_response(),
+    };
+    if tenant::require_org_member(&state.db, &session.user_id, &project.organization_id)
+        .await
+        .is_err()
+    {
+        return (StatusCode::NOT_FOUND, "Project not found").into_response();
+    }
+    let Ok(org_id) = OrganizationId::from_string(&project.organization_id) else {
+        return (StatusCode::NOT_FOUND, "Project not found").into_response();
+    };
+
+    let (nodes, edges, node_labels, node_types, task_statuses, slots, members) = match tokio::try_join!(
+        db::nodes::find_by_project(&state.db, &id),
+        db::node_edges::find_by_project(&state.db, &id),
+        db::labels::find_node_labels_by_project(&state.db, &id),
+        db::node_types::get_all_systems(&state.db),
+        db::task_statuses::get_all_task_statuses(&state.db),
+        db::project_slots::find_by_project(&state.db, &id),
+        db::organizations::list_members_with_email(&state.db, &org_id),
+    ) {
+        Ok(t) => t,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+
+    let blocked = progress::blocked_node_ids(&nodes, &edges);
+    let labels_by_node = db::labels::by_node(&node_labels);
+    let mut task_nodes: Vec<&db::nodes::Node> = helpers::task_nodes_from_nodes(&nodes)
+        .into_iter()
+        .filter(|n| node_filter.matches(n, &blocked, &labels_by_node))
+        .collect();
+    node_filter.sort(&mut task_nodes, &task_statuses);
+
+    let type_names: HashMap<&str, &str> =
+        node_types.iter().map(|t| (t.id.as_str(), t.name.as_str())).collect();
+    let member_names: HashMap<&str, String> = members
+        .iter()
+        .map(|m| {
+            let name = db::users::display_name_from_parts(&m.first_name, &m.last_name);
+            (m.user_id.as_str(), if name.is_empty() { m.email.clone() } else { name })
+        })
+        .collect();
+    let column_index: HashMap<&str, usize> = task_statuses
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.id.as_str(), i))
+        .collect();
+
+    // Lane keys in display order, with a trailing lane for nodes without a slot/assignee.
+    let lane_keys: Vec<(Option<String>, String)> = match board_query.lanes {
+        Swimlanes::None => vec![(None, String::new())],
+        Swimlanes::Slot => slots
+            .iter()
+            .map(|s| (Some(s.id.clone()), s.name.clone()))
+            .chain(std::iter::once((None, "No slot".to_string())))
+            .collect(),
+        Swimlanes::Assignee => members
+            .iter()
+            .map(|m| (Some(m.user_id.clone()), member_names[m.user_id.as_str()].clone()))
+            .chain(std::iter::once((None, "Unassigned".to_string())))
+            .collect(),
+    };
+    let lane_index: HashMap<Option<&str>, usize> = lane_keys
+        .iter()
+        .enumerate()
+        .map(|(i, (k, _))| (k.as_deref(), i))
+        .collect();
+
+    let mut cells: Vec<Vec<Vec<BoardCard>>> = lane_keys
+        .iter()
+        .map(|_| task_statuses.iter().map(|_| Vec::new()).collect())
+        .collect();
+    let mut counts = vec![0usize; task_statuses.len()];
+
+    for n in task_nodes {
+        let Some(&col) = column_index.get(n.status_id.as_str()) else {
+            continue;
+        };
+        let lane_key = match board_query.lanes {
+            Swimlanes::None => None,
+            Swimlanes::Slot => n.slot_id.as_deref(),
+            Swimlanes::Assignee => n.assigned_user_id.as_deref(),
+        };
+        // Nodes pointing at a slot or user that no longer exists fall into the catch-all lane.
+        let lane = lane_index
+            .get(&lane_key)
+            .copied()
+            .unwrap_or(lane_keys.len() - 1);
+        counts[col] += 1;
+        cells[lane][col].push(BoardCard {
+            id: n.id.clone(),
+            title: n.title.clone(),
+            node_type_name: type_names.get(n.node_type_id.as_str()).copied().unwrap_or("Task").to_string(),
+            assignee_name: n
+                .assigned_user_id
+                .as_deref()
+                .and_then(|uid| member_names.get(uid).cloned())
+                .unwrap_or_default(),
+            estimated_display: n
+                .estimated_minutes
+                .map(format::format_estimated_minutes)
+                .unwrap_or_default(),
+            blocked: blocked.contains(n.id.as_str()),
+        });
+    }
+
+    let is_empty = counts.iter().all(|c| *c == 0);
+    let columns = task_statuses
+        .iter()
+        .zip(counts)
+        .map(|(s, count)| BoardColumn {
+            status_id: s.id.clone(),
+            name: s.name.clone(),
+            count,
+        })
+        .collect();
+    // Hide empty swimlanes except the only one.
+    let lanes: Vec<BoardLane> = lane_keys
+        .into_iter()
+        .zip(cells)
+        .map(|((_, name), cells)| BoardLane { name, cells })
+        .filter(|l| board_query.lanes == Swimlanes::None || l.cells.iter().any(|c| !c.is_empty()))
+        .collect();
+
+    let user_id = match UserId::from_string(&session.user_id) {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid session".to_string()).into_response(),
+    };
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+
+    ProjectBoardTemplate {
+        app_name: APP_NAME,
+        project,
+        columns,
+        lanes,
+        lanes_mode: match board_query.lanes {
+            Swimlanes::None => "none",
+            Swimlanes::Slot => "slot",
+            Swimlanes::Assignee => "assignee",
+        },
+        is_empty,
+        filter_active: node_filter.is_active(),
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    }
+    .into_response()
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/app/projects/:id/board", get(board))
+}
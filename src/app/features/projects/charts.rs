@@ -0,0 +1,368 @@
+//! Burndown and burnup charts drawn server-side from [`super::snapshots`]. Burndown plots the
+//! estimate left against a straight ideal line to zero; burnup plots task scope against done tasks.
+
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use time::{Date, OffsetDateTime};
+
+use crate::app::{
+    db,
+    domain::{validation_helpers::parse_iso_date, UserId},
+    error::AppError,
+    features::graph,
+    session::{ApiAuthenticatedSession, AuthenticatedSession},
+    tenant,
+    AppState, APP_NAME,
+};
+
+use super::{format, helpers};
+
+/// Range shown when the request names none: the last 30 days including today.
+const DEFAULT_RANGE_DAYS: i64 = 30;
+/// Longest range accepted, in days.
+const MAX_RANGE_DAYS: i64 = 366;
+const CHART_WIDTH: i64 = 720;
+const CHART_HEIGHT: i64 = 260;
+const PLOT_LEFT: i64 = 72;
+const PLOT_RIGHT: i64 = 16;
+const PLOT_TOP: i64 = 16;
+const PLOT_BOTTOM: i64 = 32;
+/// Roughly how many date labels fit on the x axis.
+const MAX_X_LABELS: i64 = 6;
+
+/// Query parameters for both chart endpoints; dates are YYYY-MM-DD (UTC), inclusive.
+#[derive(Debug, Default, Deserialize)]
+pub struct ChartRangeQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// Resolve the requested range, defaulting to the last [`DEFAULT_RANGE_DAYS`] days up to today.
+fn resolve_range(query: &ChartRangeQuery, today: Date) -> Result<(Date, Date), AppError> {
+    let parse = |value: &Option<String>| -> Result<Option<Date>, AppError> {
+        match value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            Some(v) => parse_iso_date(v)
+                .map(Some)
+                .ok_or_else(|| AppError::Validation("Invalid date".to_string())),
+            None => Ok(None),
+        }
+    };
+    let to = parse(&query.to)?.unwrap_or(today);
+    let from = parse(&query.from)?
+        .unwrap_or_else(|| to - time::Duration::days(DEFAULT_RANGE_DAYS - 1));
+    let days = (to - from).whole_days() + 1;
+    if !(1..=MAX_RANGE_DAYS).contains(&days) {
+        return Err(AppError::Validation("Invalid date range".to_string()));
+    }
+    Ok((from, to))
+}
+
+#[derive(Serialize)]
+pub struct SnapshotsResponse {
+    pub from: String,
+    pub to: String,
+    /// Days with a snapshot, oldest first. Days before the project existed or the job ran are absent.
+    pub snapshots: Vec<db::project_snapshots::ProjectSnapshot>,
+}
+
+/// GET /api/projects/:project_id/snapshots — Daily snapshots in a date range.
+pub async fn get_snapshots(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(query): Query<ChartRangeQuery>,
+) -> Result<Json<SnapshotsResponse>, AppError> {
+    graph::helpers::ensure_project_accessible(&state.db, &project_id, &session.user_id).await?;
+    let (from, to) = resolve_range(&query, OffsetDateTime::now_utc().date())?;
+    let (from, to) = (from.to_string(), to.to_string());
+    let snapshots = db::project_snapshots::find_in_range(&state.db, &project_id, &from, &to).await?;
+    Ok(Json(SnapshotsResponse { from, to, snapshots }))
+}
+
+/// One line on a chart, as SVG polyline points.
+pub struct ChartSeries {
+    pub name: &'static str,
+    pub color: &'static str,
+    pub dashed: bool,
+    pub points: String,
+}
+
+/// An axis label with its pixel position (x for dates, y for values).
+pub struct ChartTick {
+    pub pos: i64,
+    pub label: String,
+}
+
+/// A rendered chart's geometry.
+pub struct Chart {
+    pub width: i64,
+    pub height: i64,
+    pub plot_left: i64,
+    pub plot_right: i64,
+    pub plot_bottom: i64,
+    pub series: Vec<ChartSeries>,
+    pub x_ticks: Vec<ChartTick>,
+    pub y_ticks: Vec<ChartTick>,
+}
+
+/// Values of one series by day.
+struct SeriesData {
+    name: &'static str,
+    color: &'static str,
+    dashed: bool,
+    values: Vec<(Date, i64)>,
+}
+
+/// Lay out `series` over the days `from..=to`, scaling y to the largest value.
+fn build_chart(from: Date, to: Date, series: Vec<SeriesData>, format_value: fn(i64) -> String) -> Chart {
+    let span = (to - from).whole_days().max(1);
+    let plot_width = CHART_WIDTH - PLOT_LEFT - PLOT_RIGHT;
+    let plot_height = CHART_HEIGHT - PLOT_TOP - PLOT_BOTTOM;
+    let max_value = series
+        .iter()
+        .flat_map(|s| s.values.iter().map(|(_, v)| *v))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let x_of = |date: Date| PLOT_LEFT + (date - from).whole_days() * plot_width / span;
+    let y_of = |value: i64| PLOT_TOP + plot_height - value * plot_height / max_value;
+
+    let series = series
+        .into_iter()
+        .map(|s| ChartSeries {
+            name: s.name,
+            color: s.color,
+            dashed: s.dashed,
+            points: s
+                .values
+                .iter()
+                .map(|(date, value)| format!("{},{}", x_of(*date), y_of(*value)))
+                .collect::<Vec<_>>()
+                .join(" "),
+        })
+        .collect();
+
+    let step = ((span + 1) + MAX_X_LABELS - 1) / MAX_X_LABELS;
+    let x_ticks = (0..=span)
+        .step_by(step.max(1) as usize)
+        .map(|d| {
+            let date = from + time::Duration::days(d);
+            ChartTick { pos: x_of(date), label: format!("{:02}-{:02}", u8::from(date.month()), date.day()) }
+        })
+        .collect();
+    let y_ticks = [0, max_value / 2, max_value]
+        .into_iter()
+        .map(|v| ChartTick { pos: y_of(v), label: format_value(v) })
+        .collect();
+
+    Chart {
+        width: CHART_WIDTH,
+        height: CHART_HEIGHT,
+        plot_left: PLOT_LEFT,
+        plot_right: CHART_WIDTH - PLOT_RIGHT,
+        plot_bottom: CHART_HEIGHT - PLOT_BOTTOM,
+        series,
+        x_ticks,
+        y_ticks,
+    }
+}
+
+fn format_minutes_axis(minutes: i64) -> String {
+    if minutes == 0 {
+        "0".to_string()
+    } else {
+        format::format_estimated_minutes(minutes)
+    }
+}
+
+fn format_count_axis(count: i64) -> String {
+    count.to_string()
+}
+
+/// Estimate left per day, plus an ideal line from the first snapshot's value down to zero at `to`.
+fn burndown(from: Date, to: Date, snapshots: &[(Date, &db::project_snapshots::ProjectSnapshot)]) -> Chart {
+    let remaining: Vec<(Date, i64)> =
+        snapshots.iter().map(|(d, s)| (*d, s.estimated_remaining_minutes)).collect();
+    let ideal = remaining
+        .first()
+        .map(|(start, value)| vec![(*start, *value), (to, 0)])
+        .unwrap_or_default();
+    build_chart(
+        from,
+        to,
+        vec![
+            SeriesData { name: "Ideal", color: "#9ca3af", dashed: true, values: ideal },
+            SeriesData { name: "Remaining", color: "#3b82f6", dashed: false, values: remaining },
+        ],
+        format_minutes_axis,
+    )
+}
+
+/// Total tasks (scope) and done tasks per day.
+fn burnup(from: Date, to: Date, snapshots: &[(Date, &db::project_snapshots::ProjectSnapshot)]) -> Chart {
+    let scope = snapshots
+        .iter()
+        .map(|(d, s)| (*d, s.todo_count + s.in_progress_count + s.done_count))
+        .collect();
+    let done = snapshots.iter().map(|(d, s)| (*d, s.done_count)).collect();
+    build_chart(
+        from,
+        to,
+        vec![
+            SeriesData { name: "Scope", color: "#9ca3af", dashed: false, values: scope },
+            SeriesData { name: "Done", color: "#10b981", dashed: false, values: done },
+        ],
+        format_count_axis,
+    )
+}
+
+/// Project charts template.
+#[derive(Template)]
+#[template(path = "projects_charts.html")]
+pub struct ProjectChartsTemplate {
+    pub app_name: &'static str,
+    pub project: db::projects::Project,
+    pub from: String,
+    pub to: String,
+    pub burndown: Chart,
+    pub burnup: Chart,
+    pub snapshot_count: usize,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+/// GET /app/projects/:id/charts — Burndown and burnup charts over a date range.
+pub async fn charts(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ChartRangeQuery>,
+) -> impl IntoResponse {
+    let project = match helpers::load_project(&state.db, &id).await {
+        Ok(p) => p,
+        Err((status, msg)) => return (status, msg).into_response(),
+    };
+    if tenant::require_org_member(&state.db, &session.user_id, &project.organization_id)
+        .await
+        .is_err()
+    {
+        return (StatusCode::NOT_FOUND, "Project not found").into_response();
+    }
+
+    let (from, to) = match resolve_range(&query, OffsetDateTime::now_utc().date()) {
+        Ok(range) => range,
+        Err(_) => return (StatusCode::BAD_REQUEST, "Invalid date range").into_response(),
+    };
+    let rows = match db::project_snapshots::find_in_range(
+        &state.db,
+        &id,
+        &from.to_string(),
+        &to.to_string(),
+    )
+    .await
+    {
+        Ok(rows) => rows,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let snapshots: Vec<_> = rows
+        .iter()
+        .filter_map(|s| Some((parse_iso_date(&s.snapshot_date)?, s)))
+        .collect();
+
+    let user_id = match UserId::from_string(&session.user_id) {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid session".to_string()).into_response(),
+    };
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+
+    ProjectChartsTemplate {
+        app_name: APP_NAME,
+        project,
+        from: from.to_string(),
+        to: to.to_string(),
+        burndown: burndown(from, to, &snapshots),
+        burnup: burnup(from, to, &snapshots),
+        snapshot_count: snapshots.len(),
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    }
+    .into_response()
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/app/projects/:id/charts", get(charts))
+}
+
+pub fn api_routes() -> Router<AppState> {
+    Router::new().route("/api/projects/:project_id/snapshots", get(get_snapshots))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Date {
+        parse_iso_date(s).unwrap()
+    }
+
+    fn snapshot(day: &str, todo: i64, done: i64, remaining: i64) -> db::project_snapshots::ProjectSnapshot {
+        db::project_snapshots::ProjectSnapshot {
+            snapshot_date: day.to_string(),
+            todo_count: todo,
+            in_progress_count: 0,
+            done_count: done,
+            blocked_count: 0,
+            estimated_remaining_minutes: remaining,
+        }
+    }
+
+    #[test]
+    fn range_defaults_to_last_thirty_days_and_rejects_bad_input() {
+        let today = date("2026-10-18");
+        let (from, to) = resolve_range(&ChartRangeQuery::default(), today).unwrap();
+        assert_eq!((from.to_string(), to.to_string()), ("2026-09-19".to_string(), "2026-10-18".to_string()));
+
+        let reversed = ChartRangeQuery { from: Some("2026-10-10".into()), to: Some("2026-10-01".into()) };
+        assert!(resolve_range(&reversed, today).is_err());
+        let too_long = ChartRangeQuery { from: Some("2024-01-01".into()), to: None };
+        assert!(resolve_range(&too_long, today).is_err());
+        let garbage = ChartRangeQuery { from: Some("last week".into()), to: None };
+        assert!(resolve_range(&garbage, today).is_err());
+    }
+
+    #[test]
+    fn burndown_scales_to_the_range_and_draws_ideal_to_zero() {
+        let rows = [snapshot("2026-10-01", 2, 0, 600), snapshot("2026-10-11", 1, 1, 300)];
+        let snapshots: Vec<_> = rows.iter().map(|s| (date(&s.snapshot_date), s)).collect();
+        let chart = burndown(date("2026-10-01"), date("2026-10-21"), &snapshots);
+
+        let plot_width = CHART_WIDTH - PLOT_LEFT - PLOT_RIGHT;
+        let bottom = CHART_HEIGHT - PLOT_BOTTOM;
+        let remaining = &chart.series[1];
+        assert_eq!(remaining.name, "Remaining");
+        assert_eq!(
+            remaining.points,
+            format!("{},{} {},{}", PLOT_LEFT, PLOT_TOP, PLOT_LEFT + plot_width / 2, PLOT_TOP + (bottom - PLOT_TOP) / 2)
+        );
+        let ideal = &chart.series[0];
+        assert!(ideal.dashed);
+        assert!(ideal.points.ends_with(&format!("{},{}", PLOT_LEFT + plot_width, bottom)));
+        assert_eq!(chart.y_ticks.last().unwrap().label, "10 h");
+    }
+
+    #[test]
+    fn burnup_plots_scope_and_done_counts() {
+        let rows = [snapshot("2026-10-01", 3, 1, 0)];
+        let snapshots: Vec<_> = rows.iter().map(|s| (date(&s.snapshot_date), s)).collect();
+        let chart = burnup(date("2026-10-01"), date("2026-10-02"), &snapshots);
+        assert_eq!(chart.series[0].points, format!("{},{}", PLOT_LEFT, PLOT_TOP));
+        assert_eq!(chart.y_ticks.last().unwrap().label, "4");
+    }
+}
@@ -35,6 +35,7 @@ pub struct CreateProjectTemplate {
     pub teams: Vec<db::teams::Team>,
     pub selected_team_id: String,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
 }
 
 /// GET /app/projects/new — Show project creation form.
@@ -66,6 +67,7 @@ pub async fn show_form(
         teams,
         selected_team_id,
         current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
     }
     .into_response()
 }
@@ -92,6 +94,7 @@ pub async fn create(
             teams,
             selected_team_id,
             current_user_avatar_url,
+            csrf_token: session.csrf_token.clone(),
         };
         return Html(
             template
@@ -124,6 +127,7 @@ pub async fn create(
             teams,
             selected_team_id: String::new(),
             current_user_avatar_url,
+            csrf_token: session.csrf_token.clone(),
         };
         return Html(
             template
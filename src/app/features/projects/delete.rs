@@ -5,6 +5,7 @@ use axum::{
     routing::post,
     Router,
 };
+use time::OffsetDateTime;
 
 use crate::app::{
     db,
@@ -13,7 +14,8 @@ use crate::app::{
     AppState,
 };
 
-/// DELETE /api/projects/:id — Delete a project (tenant-scoped).
+/// POST /api/projects/:id/delete — Move a project to the trash (tenant-scoped). It can be restored
+/// until the purge job removes it; see [`super::trash`].
 pub async fn delete(
     AuthenticatedSession(session): AuthenticatedSession,
     State(state): State<AppState>,
@@ -27,33 +29,15 @@ pub async fn delete(
         return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response();
     }
 
-    // Check if project exists and belongs to user's org
-    let project_exists = match db::projects::find_by_id_and_org(&state.db, &project_id, &session.organization_id).await {
-        Ok(Some(_)) => true,
-        Ok(None) => false,
-        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
-    };
-
-    if !project_exists {
-        return (StatusCode::NOT_FOUND, "Project not found".to_string()).into_response();
-    }
-
-    // Delete the project
-    match db::projects::delete_by_id_and_org(&state.db, &project_id, &session.organization_id).await {
-        Ok(true) => {
-            // Success - redirect back to projects list
-            Redirect::to("/app/projects").into_response()
-        }
-        Ok(false) => {
-            // Project was not found or didn't belong to org (shouldn't happen due to check above)
-            (StatusCode::NOT_FOUND, "Project not found".to_string()).into_response()
-        }
-        Err(_) => {
-            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
-        }
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    match db::projects::move_to_trash(&state.db, &project_id, &session.organization_id, now).await {
+        Ok(true) => Redirect::to("/app/projects").into_response(),
+        // Missing, in another org, or already trashed
+        Ok(false) => (StatusCode::NOT_FOUND, "Project not found".to_string()).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
     }
 }
 
 pub fn routes() -> Router<AppState> {
     Router::new().route("/api/projects/:id/delete", post(delete))
-}
\ No newline at end of file
+}
@@ -15,8 +15,8 @@ use crate::app::{
     error::AppError,
     features::graph,
     features::projects::import_export::{
-        ProjectExport, ProjectExportEdge, ProjectExportNode, ProjectExportProject,
-        ProjectExportSlot, EXPORT_VERSION,
+        ProjectExport, ProjectExportChecklistItem, ProjectExportEdge, ProjectExportLabel, ProjectExportNode,
+        ProjectExportProject, ProjectExportSlot, EXPORT_VERSION,
     },
     session::ApiAuthenticatedSession,
     AppState,
@@ -40,11 +40,15 @@ pub async fn export_project(
 ) -> Result<Response, AppError> {
     let project = graph::helpers::ensure_project_accessible(&state.db, &project_id, &session.user_id).await?;
 
-    let (slots, nodes, edges) = tokio::try_join!(
+    let (slots, nodes, edges, node_labels, org_labels, checklist_items) = tokio::try_join!(
         db::project_slots::find_by_project(&state.db, &project_id),
         db::nodes::find_by_project(&state.db, &project_id),
         db::node_edges::find_by_project(&state.db, &project_id),
+        db::labels::find_node_labels_by_project(&state.db, &project_id),
+        db::labels::find_by_organization(&state.db, &project.organization_id),
+        db::checklist_items::find_by_project(&state.db, &project_id),
     )?;
+    let labels_by_node = db::labels::by_node(&node_labels);
 
     let exported_at = OffsetDateTime::now_utc().format(&time::format_description::well_known::Rfc3339).ok().map(String::from);
 
@@ -65,6 +69,19 @@ pub async fn export_project(
         nodes: nodes
             .into_iter()
             .map(|n| ProjectExportNode {
+                label_ids: labels_by_node
+                    .get(n.id.as_str())
+                    .map(|ids| ids.iter().map(|id| id.to_string()).collect())
+                    .unwrap_or_default(),
+                checklist: checklist_items
+                    .iter()
+                    .filter(|c| c.node_id == n.id)
+                    .map(|c| ProjectExportChecklistItem {
+                        text: c.text.clone(),
+                        done: c.done,
+                        assigned_user_id: c.assigned_user_id.clone(),
+                    })
+                    .collect(),
                 id: n.id,
                 node_type_id: n.node_type_id,
                 status_id: n.status_id,
@@ -83,6 +100,16 @@ pub async fn export_project(
                 child_id: e.child_id,
             })
             .collect(),
+        // Only the labels this project's nodes use.
+        labels: org_labels
+            .into_iter()
+            .filter(|l| node_labels.iter().any(|nl| nl.label_id == l.id))
+            .map(|l| ProjectExportLabel {
+                id: l.id,
+                name: l.name,
+                color: l.color,
+            })
+            .collect(),
     };
 
     let body = serde_json::to_vec(&payload).map_err(|_| AppError::Internal)?;
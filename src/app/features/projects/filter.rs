@@ -0,0 +1,376 @@
+//! Node filtering and sorting shared by the graph API and the list view (computed in code from
+//! nodes + edges, like [`super::progress`]).
+//!
+//! Query parameters (all optional; list values are comma-separated):
+//! `status`, `node_type`, `slot`, `label` (label IDs; any of them), `assignee` (user IDs or `me`),
+//! `unassigned`, `blocked`,
+//! `q` (case-insensitive title/description match), `sort` (`created`, `updated`, `title`,
+//! `status`, `estimate`; prefix `-` for descending) and, for the graph only, `neighbors`.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use serde::Deserialize;
+use strum_macros::{Display, EnumString};
+
+use super::progress;
+use crate::app::db::{node_edges, nodes, task_statuses};
+
+/// Raw filter query parameters, as they appear in the URL.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct NodeFilterQuery {
+    pub status: Option<String>,
+    pub node_type: Option<String>,
+    pub slot: Option<String>,
+    pub label: Option<String>,
+    pub assignee: Option<String>,
+    pub unassigned: Option<String>,
+    pub blocked: Option<String>,
+    pub q: Option<String>,
+    pub sort: Option<String>,
+    pub neighbors: Option<String>,
+}
+
+/// Sort key for filtered nodes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum SortKey {
+    #[default]
+    Created,
+    Updated,
+    Title,
+    Status,
+    Estimate,
+}
+
+/// Parsed, validated filter.
+#[derive(Debug, Default)]
+pub struct NodeFilter {
+    pub statuses: Vec<String>,
+    pub node_types: Vec<String>,
+    pub slots: Vec<String>,
+    /// Matches nodes carrying any of these labels.
+    pub labels: Vec<String>,
+    /// Matches nodes assigned to any of these users. Combined with `unassigned` as "either".
+    pub assignees: Vec<String>,
+    pub unassigned: bool,
+    pub blocked_only: bool,
+    /// Lowercased text to find in title or description.
+    pub text: Option<String>,
+    pub sort: SortKey,
+    pub descending: bool,
+    /// Graph only: also return direct dependency parents/children of matches.
+    pub include_neighbors: bool,
+}
+
+impl NodeFilter {
+    /// Parse query parameters. `current_user_id` replaces `me` in `assignee`.
+    pub fn from_query(query: &NodeFilterQuery, current_user_id: &str) -> Result<Self, String> {
+        let (sort, descending) = match query.sort.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            None => (SortKey::default(), false),
+            Some(s) => {
+                let (key, descending) = match s.strip_prefix('-') {
+                    Some(rest) => (rest, true),
+                    None => (s, false),
+                };
+                let key = SortKey::from_str(key).map_err(|_| format!("Invalid sort: {}", s))?;
+                (key, descending)
+            }
+        };
+
+        Ok(Self {
+            statuses: split_list(query.status.as_deref()),
+            node_types: split_list(query.node_type.as_deref()),
+            slots: split_list(query.slot.as_deref()),
+            labels: split_list(query.label.as_deref()),
+            assignees: split_list(query.assignee.as_deref())
+                .into_iter()
+                .map(|a| if a == "me" { current_user_id.to_string() } else { a })
+                .collect(),
+            unassigned: parse_flag("unassigned", query.unassigned.as_deref())?,
+            blocked_only: parse_flag("blocked", query.blocked.as_deref())?,
+            text: query
+                .q
+                .as_deref()
+                .map(str::trim)
+                .filter(|q| !q.is_empty())
+                .map(str::to_lowercase),
+            sort,
+            descending,
+            include_neighbors: parse_flag("neighbors", query.neighbors.as_deref())?,
+        })
+    }
+
+    /// True when any condition narrows the result (sorting alone does not).
+    pub fn is_active(&self) -> bool {
+        !self.statuses.is_empty()
+            || !self.node_types.is_empty()
+            || !self.slots.is_empty()
+            || !self.labels.is_empty()
+            || !self.assignees.is_empty()
+            || self.unassigned
+            || self.blocked_only
+            || self.text.is_some()
+    }
+
+    /// Whether one node passes every condition. `blocked` comes from [`progress::blocked_node_ids`],
+    /// `node_labels` from [`crate::app::db::labels::by_node`].
+    pub fn matches(
+        &self,
+        node: &nodes::Node,
+        blocked: &HashSet<&str>,
+        node_labels: &HashMap<&str, Vec<&str>>,
+    ) -> bool {
+        if !self.statuses.is_empty() && !self.statuses.contains(&node.status_id) {
+            return false;
+        }
+        if !self.node_types.is_empty() && !self.node_types.contains(&node.node_type_id) {
+            return false;
+        }
+        if !self.slots.is_empty() && !node.slot_id.as_ref().is_some_and(|s| self.slots.contains(s)) {
+            return false;
+        }
+        if !self.labels.is_empty() {
+            let on_node = node_labels.get(node.id.as_str());
+            if !on_node.is_some_and(|ids| ids.iter().any(|id| self.labels.iter().any(|l| l == id))) {
+                return false;
+            }
+        }
+        if !self.assignees.is_empty() || self.unassigned {
+            let ok = match &node.assigned_user_id {
+                Some(uid) => self.assignees.contains(uid),
+                None => self.unassigned,
+            };
+            if !ok {
+                return false;
+            }
+        }
+        if self.blocked_only && !blocked.contains(node.id.as_str()) {
+            return false;
+        }
+        if let Some(text) = &self.text {
+            let in_title = node.title.to_lowercase().contains(text.as_str());
+            let in_description = node
+                .description
+                .as_deref()
+                .is_some_and(|d| d.to_lowercase().contains(text.as_str()));
+            if !in_title && !in_description {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Sort nodes in place. Nodes without an estimate or update time sort last either way.
+    pub fn sort<N: Borrow<nodes::Node>>(&self, nodes: &mut [N], statuses: &[task_statuses::TaskStatus]) {
+        let status_order: HashMap<&str, i64> =
+            statuses.iter().map(|s| (s.id.as_str(), s.sort_order)).collect();
+        let descending = self.descending;
+        let directed = |o: Ordering| if descending { o.reverse() } else { o };
+        let missing_last = |a: Option<i64>, b: Option<i64>| match (a, b) {
+            (Some(a), Some(b)) => directed(a.cmp(&b)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+
+        nodes.sort_by(|a, b| {
+            let (a, b) = (a.borrow(), b.borrow());
+            let primary = match self.sort {
+                SortKey::Created => directed(a.created_at.cmp(&b.created_at)),
+                SortKey::Updated => missing_last(a.updated_at, b.updated_at),
+                SortKey::Title => directed(a.title.to_lowercase().cmp(&b.title.to_lowercase())),
+                SortKey::Status => directed(
+                    status_order
+                        .get(a.status_id.as_str())
+                        .cmp(&status_order.get(b.status_id.as_str())),
+                ),
+                SortKey::Estimate => missing_last(a.estimated_minutes, b.estimated_minutes),
+            };
+            primary.then_with(|| a.created_at.cmp(&b.created_at))
+        });
+    }
+}
+
+/// Result of filtering a graph.
+pub struct FilteredGraph {
+    pub nodes: Vec<nodes::Node>,
+    pub edges: Vec<node_edges::NodeEdge>,
+    /// IDs that matched the filter itself (the rest are neighbors or containing groups).
+    pub matched_ids: Vec<String>,
+}
+
+/// Filter and sort a project graph. With `include_neighbors`, direct dependency parents and
+/// children of matches are kept so the result stays connected; groups containing any kept node
+/// are always kept so nodes render inside them. Edges are kept when both ends are.
+pub fn filter_graph(
+    nodes: Vec<nodes::Node>,
+    edges: Vec<node_edges::NodeEdge>,
+    node_labels: &HashMap<&str, Vec<&str>>,
+    filter: &NodeFilter,
+    statuses: &[task_statuses::TaskStatus],
+) -> FilteredGraph {
+    let (matched_ids, keep): (Vec<String>, HashSet<String>) = {
+        let blocked = progress::blocked_node_ids(&nodes, &edges);
+        let matched: Vec<String> = nodes
+            .iter()
+            .filter(|n| filter.matches(n, &blocked, node_labels))
+            .map(|n| n.id.clone())
+            .collect();
+        let mut keep: HashSet<String> = matched.iter().cloned().collect();
+
+        if filter.include_neighbors {
+            let matched_set: HashSet<&str> = matched.iter().map(String::as_str).collect();
+            for e in &edges {
+                if matched_set.contains(e.parent_id.as_str()) {
+                    keep.insert(e.child_id.clone());
+                }
+                if matched_set.contains(e.child_id.as_str()) {
+                    keep.insert(e.parent_id.clone());
+                }
+            }
+        }
+
+        let parent_by_id: HashMap<&str, &str> = nodes
+            .iter()
+            .filter_map(|n| n.parent_id.as_deref().map(|p| (n.id.as_str(), p)))
+            .collect();
+        let mut groups = Vec::new();
+        for id in &keep {
+            let mut current = id.as_str();
+            while let Some(parent) = parent_by_id.get(current) {
+                if keep.contains(*parent) || groups.contains(parent) {
+                    break;
+                }
+                groups.push(*parent);
+                current = parent;
+            }
+        }
+        let groups: Vec<String> = groups.into_iter().map(String::from).collect();
+        keep.extend(groups);
+        (matched, keep)
+    };
+
+    let mut nodes: Vec<nodes::Node> = nodes.into_iter().filter(|n| keep.contains(&n.id)).collect();
+    filter.sort(&mut nodes, statuses);
+    let edges = edges
+        .into_iter()
+        .filter(|e| keep.contains(&e.parent_id) && keep.contains(&e.child_id))
+        .collect();
+
+    FilteredGraph { nodes, edges, matched_ids }
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn parse_flag(name: &str, value: Option<&str>) -> Result<bool, String> {
+    match value.map(str::trim) {
+        None | Some("") | Some("0") | Some("false") => Ok(false),
+        Some("1") | Some("true") => Ok(true),
+        Some(other) => Err(format!("Invalid {}: {}", name, other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, title: &str, assigned_user_id: Option<&str>) -> nodes::Node {
+        nodes::Node {
+            id: id.to_string(),
+            project_id: "project".to_string(),
+            node_type_id: "type".to_string(),
+            status_id: task_statuses::TODO_STATUS_ID.to_string(),
+            title: title.to_string(),
+            description: None,
+            created_at: 0,
+            updated_at: None,
+            estimated_minutes: None,
+            slot_id: None,
+            parent_id: None,
+            assigned_user_id: assigned_user_id.map(String::from),
+            completed_at: None,
+        }
+    }
+
+    fn parse(query: NodeFilterQuery) -> NodeFilter {
+        NodeFilter::from_query(&query, "user-1").unwrap()
+    }
+
+    #[test]
+    fn assignee_me_and_unassigned_match_either() {
+        let filter = parse(NodeFilterQuery {
+            assignee: Some("me".to_string()),
+            unassigned: Some("true".to_string()),
+            ..Default::default()
+        });
+        let blocked = HashSet::new();
+        let labels = HashMap::new();
+        assert!(filter.matches(&node("a", "", Some("user-1")), &blocked, &labels));
+        assert!(filter.matches(&node("b", "", None), &blocked, &labels));
+        assert!(!filter.matches(&node("c", "", Some("user-2")), &blocked, &labels));
+    }
+
+    #[test]
+    fn label_filter_matches_any_listed_label() {
+        let filter = parse(NodeFilterQuery { label: Some("bug, ux".to_string()), ..Default::default() });
+        let blocked = HashSet::new();
+        let labels = HashMap::from([("a", vec!["ux"]), ("b", vec!["docs"])]);
+        assert!(filter.is_active());
+        assert!(filter.matches(&node("a", "", None), &blocked, &labels));
+        assert!(!filter.matches(&node("b", "", None), &blocked, &labels));
+        assert!(!filter.matches(&node("c", "", None), &blocked, &labels));
+    }
+
+    #[test]
+    fn sort_prefix_minus_is_descending() {
+        let filter = parse(NodeFilterQuery { sort: Some("-title".to_string()), ..Default::default() });
+        let mut nodes = vec![node("a", "apple", None), node("b", "Banana", None)];
+        filter.sort(&mut nodes, &[]);
+        assert_eq!(nodes[0].title, "Banana");
+        assert!(!filter.is_active());
+    }
+
+    #[test]
+    fn rejects_unknown_sort_and_flag_values() {
+        let bad_sort = NodeFilterQuery { sort: Some("priority".to_string()), ..Default::default() };
+        assert!(NodeFilter::from_query(&bad_sort, "u").is_err());
+        let bad_flag = NodeFilterQuery { blocked: Some("yes".to_string()), ..Default::default() };
+        assert!(NodeFilter::from_query(&bad_flag, "u").is_err());
+    }
+
+    #[test]
+    fn neighbors_and_groups_are_kept() {
+        let mut child = node("c", "child", None);
+        child.parent_id = Some("g".to_string());
+        let nodes = vec![node("a", "upstream", None), child, node("g", "group", None), node("x", "other", None)];
+        let edges = vec![node_edges::NodeEdge {
+            parent_id: "a".to_string(),
+            child_id: "c".to_string(),
+            created_at: 0,
+        }];
+        let filter = parse(NodeFilterQuery {
+            q: Some("child".to_string()),
+            neighbors: Some("1".to_string()),
+            ..Default::default()
+        });
+
+        let graph = filter_graph(nodes, edges, &HashMap::new(), &filter, &[]);
+
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids.len(), 3);
+        assert!(!ids.contains(&"x"));
+        assert_eq!(graph.matched_ids, vec!["c".to_string()]);
+        assert_eq!(graph.edges.len(), 1);
+    }
+}
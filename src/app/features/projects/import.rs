@@ -12,12 +12,16 @@ use crate::app::{
     db,
     domain::OrganizationId,
     error::AppError,
-    features::projects::import_export::{EXPORT_VERSION, ProjectExportEdge, ProjectExportNode, ProjectExportProject, ProjectExportSlot},
+    features::graph::{checklist::{MAX_ITEMS_PER_NODE, MAX_TEXT_CHARS}, labels::normalize_color},
+    features::projects::import_export::{EXPORT_VERSION, ProjectExportEdge, ProjectExportLabel, ProjectExportNode, ProjectExportProject, ProjectExportSlot},
     session::ApiAuthenticatedSession,
     tenant,
     AppState,
 };
 
+/// Color for imported labels whose color isn't a valid `#rrggbb`.
+const DEFAULT_LABEL_COLOR: &str = "#94a3b8";
+
 /// Request body for import: optional team_id plus export payload.
 #[derive(Debug, Deserialize)]
 pub struct ImportRequest {
@@ -34,6 +38,8 @@ pub struct ImportRequest {
     pub nodes: Vec<ProjectExportNode>,
     #[serde(default)]
     pub edges: Vec<ProjectExportEdge>,
+    #[serde(default)]
+    pub labels: Vec<ProjectExportLabel>,
 }
 
 /// Return indices into nodes so that for every edge (parent, child), parent's index is before child's.
@@ -146,6 +152,29 @@ pub async fn import_project(
         db::project_slots::insert(&mut *tx, &new_slot).await?;
     }
 
+    // Map exported label id -> the org's label of the same name, creating labels it lacks.
+    let mut org_labels = db::labels::find_by_organization(&mut *tx, &session.organization_id).await?;
+    let mut label_map: HashMap<String, String> = HashMap::new();
+    for l in &body.labels {
+        let name = l.name.trim();
+        if name.is_empty() || name.chars().count() > 50 {
+            continue;
+        }
+        if let Some(existing) = org_labels.iter().find(|o| o.name.to_lowercase() == name.to_lowercase()) {
+            label_map.insert(l.id.clone(), existing.id.clone());
+            continue;
+        }
+        let new_label = db::labels::NewLabel {
+            id: ulid::Ulid::new().to_string(),
+            organization_id: session.organization_id.clone(),
+            name: name.to_string(),
+            color: normalize_color(&l.color).unwrap_or_else(|| DEFAULT_LABEL_COLOR.to_string()),
+        };
+        db::labels::insert(&mut *tx, &new_label).await?;
+        label_map.insert(l.id.clone(), new_label.id.clone());
+        org_labels = db::labels::find_by_organization(&mut *tx, &session.organization_id).await?;
+    }
+
     // Map old node id -> new node id; insert nodes in topological order (parents before children)
     let mut node_map: HashMap<String, String> = HashMap::new();
     let node_order = topological_node_order(&body.nodes, &body.edges);
@@ -158,7 +187,7 @@ pub async fn import_project(
         let parent_id = n.parent_id.as_ref().and_then(|id| node_map.get(id).cloned());
 
         let new_node = db::nodes::NewNode {
-            id: new_id,
+            id: new_id.clone(),
             project_id: new_project_id.clone(),
             node_type_id: n.node_type_id.clone(),
             status_id: n.status_id.clone(),
@@ -170,6 +199,21 @@ pub async fn import_project(
             assigned_user_id: None,
         };
         db::nodes::insert(&mut *tx, &new_node).await?;
+        for label_id in n.label_ids.iter().filter_map(|id| label_map.get(id)) {
+            db::labels::add_to_node(&mut *tx, &new_id, label_id).await?;
+        }
+        let checklist = n.checklist.iter().filter(|c| !c.text.trim().is_empty());
+        for (sort_order, c) in (0_i64..).zip(checklist.take(MAX_ITEMS_PER_NODE as usize)) {
+            let item = db::checklist_items::NewChecklistItem {
+                id: ulid::Ulid::new().to_string(),
+                node_id: new_id.clone(),
+                text: c.text.trim().chars().take(MAX_TEXT_CHARS as usize).collect(),
+                done: c.done,
+                assigned_user_id: None,
+                sort_order,
+            };
+            db::checklist_items::insert(&mut *tx, &item).await?;
+        }
     }
 
     // Insert edges (both endpoints must be in node_map)
@@ -18,6 +18,8 @@ pub struct ProjectExport {
     pub nodes: Vec<ProjectExportNode>,
     #[serde(default)]
     pub edges: Vec<ProjectExportEdge>,
+    #[serde(default)]
+    pub labels: Vec<ProjectExportLabel>,
 }
 
 /// Project metadata (title only; id/org/team assigned on import).
@@ -47,6 +49,22 @@ pub struct ProjectExportNode {
     pub parent_id: Option<String>,
     #[serde(default)]
     pub assigned_user_id: Option<String>,
+    /// Ids of labels in the same payload.
+    #[serde(default)]
+    pub label_ids: Vec<String>,
+    /// Checklist items in order.
+    #[serde(default)]
+    pub checklist: Vec<ProjectExportChecklistItem>,
+}
+
+/// Checklist item in export. The assignee is informational; import leaves items unassigned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectExportChecklistItem {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+    #[serde(default)]
+    pub assigned_user_id: Option<String>,
 }
 
 /// Edge in export; parent_id and child_id refer to node ids in the same payload.
@@ -55,3 +73,12 @@ pub struct ProjectExportEdge {
     pub parent_id: String,
     pub child_id: String,
 }
+
+/// Label used by the exported nodes; id used only for mapping. On import it is matched to the
+/// target organization's label of the same name, or created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectExportLabel {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+}
@@ -40,12 +40,13 @@ pub struct ProjectRow {
 }
 
 impl ProjectRow {
-    /// URL to open this project (graph or list based on default_view_mode).
+    /// URL to open this project (graph, list, board or timeline based on default_view_mode).
     pub fn project_url(&self) -> String {
-        if self.default_view_mode == "list" {
-            format!("/app/projects/{}/list", self.id)
-        } else {
-            format!("/app/projects/{}", self.id)
+        match self.default_view_mode.as_str() {
+            "list" => format!("/app/projects/{}/list", self.id),
+            "board" => format!("/app/projects/{}/board", self.id),
+            "timeline" => format!("/app/projects/{}/timeline", self.id),
+            _ => format!("/app/projects/{}", self.id),
         }
     }
 }
@@ -57,6 +58,7 @@ pub struct ProjectsListTemplate {
     pub app_name: &'static str,
     pub projects: Vec<ProjectRow>,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
     pub total_tasks: i64,
     pub total_blockers: i64,
     pub contributor_count: i64,
@@ -170,6 +172,7 @@ pub async fn list(
         app_name: APP_NAME,
         projects,
         current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
         total_tasks,
         total_blockers,
         contributor_count,
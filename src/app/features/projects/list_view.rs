@@ -1,6 +1,6 @@
 use askama::Template;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     routing::get,
@@ -15,7 +15,10 @@ use crate::app::{
     AppState, APP_NAME,
 };
 
-use super::{format, helpers};
+use super::{
+    filter::{NodeFilter, NodeFilterQuery},
+    format, helpers, progress,
+};
 
 /// One task row for the list view (node plus resolved display names and formatted estimate).
 #[derive(Clone)]
@@ -25,6 +28,9 @@ pub struct TaskRow {
     pub status_name: String,
     pub slot_name: String,
     pub estimated_display: String,
+    /// Checklist progress as "done/total", or "—" when the node has no checklist.
+    pub checklist_display: String,
+    pub labels: Vec<db::labels::Label>,
 }
 
 /// Project list view template (tasks only, no group nodes).
@@ -35,15 +41,28 @@ pub struct ProjectListViewTemplate {
     pub project: db::projects::Project,
     pub task_rows: Vec<TaskRow>,
     pub task_rows_json: String,
+    pub task_statuses: Vec<db::task_statuses::TaskStatus>,
+    /// The organization's labels, for the label filter.
+    pub labels: Vec<db::labels::Label>,
+    /// Current filter/sort parameters, to keep the filter form populated.
+    pub filter: NodeFilterQuery,
+    pub filter_active: bool,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
 }
 
-/// GET /app/projects/:id/list — List view of project tasks.
+/// GET /app/projects/:id/list — List view of project tasks, optionally filtered and sorted
+/// (see [`super::filter`] for the query parameters).
 pub async fn list_view(
     AuthenticatedSession(session): AuthenticatedSession,
     State(state): State<AppState>,
     Path(id): Path<String>,
+    Query(query): Query<NodeFilterQuery>,
 ) -> impl IntoResponse {
+    let node_filter = match NodeFilter::from_query(&query, &session.user_id) {
+        Ok(f) => f,
+        Err(msg) => return (StatusCode::BAD_REQUEST, msg).into_response(),
+    };
     let project = match helpers::load_project(&state.db, &id).await {
         Ok(p) => p,
         Err((status, msg)) => return (status, msg).into_response(),
@@ -55,17 +74,27 @@ pub async fn list_view(
         return (StatusCode::NOT_FOUND, "Project not found").into_response();
     }
 
-    let (nodes, node_types, task_statuses, slots) = match tokio::try_join!(
+    let (nodes, edges, node_labels, labels, node_types, task_statuses, slots, checklist_progress) = match tokio::try_join!(
         db::nodes::find_by_project(&state.db, &id),
+        db::node_edges::find_by_project(&state.db, &id),
+        db::labels::find_node_labels_by_project(&state.db, &id),
+        db::labels::find_by_organization(&state.db, &project.organization_id),
         db::node_types::get_all_systems(&state.db),
         db::task_statuses::get_all_task_statuses(&state.db),
         db::project_slots::find_by_project(&state.db, &id),
+        db::checklist_items::progress_by_project(&state.db, &id),
     ) {
         Ok(t) => t,
         Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
     };
 
-    let task_nodes = helpers::task_nodes_from_nodes(&nodes);
+    let blocked = progress::blocked_node_ids(&nodes, &edges);
+    let labels_by_node = db::labels::by_node(&node_labels);
+    let mut task_nodes: Vec<&db::nodes::Node> = helpers::task_nodes_from_nodes(&nodes)
+        .into_iter()
+        .filter(|n| node_filter.matches(n, &blocked, &labels_by_node))
+        .collect();
+    node_filter.sort(&mut task_nodes, &task_statuses);
 
     let type_by_id: std::collections::HashMap<&str, &db::node_types::NodeType> = node_types
         .iter()
@@ -77,6 +106,10 @@ pub async fn list_view(
         .iter()
         .map(|s| (s.id.as_str(), s))
         .collect();
+    let label_by_id: std::collections::HashMap<&str, &db::labels::Label> =
+        labels.iter().map(|l| (l.id.as_str(), l)).collect();
+    let checklist_by_node: std::collections::HashMap<&str, &db::checklist_items::ChecklistProgress> =
+        checklist_progress.iter().map(|p| (p.node_id.as_str(), p)).collect();
 
     let task_rows: Vec<TaskRow> = task_nodes
         .into_iter()
@@ -103,12 +136,24 @@ pub async fn list_view(
                 .estimated_minutes
                 .map(format::format_estimated_minutes)
                 .unwrap_or_else(|| "—".to_string());
+            let checklist_display = checklist_by_node
+                .get(n.id.as_str())
+                .map(|p| format!("{}/{}", p.done, p.total))
+                .unwrap_or_else(|| "—".to_string());
+            let labels = labels_by_node
+                .get(n.id.as_str())
+                .into_iter()
+                .flatten()
+                .filter_map(|label_id| label_by_id.get(label_id).map(|l| (*l).clone()))
+                .collect();
             TaskRow {
                 node,
                 node_type_name,
                 status_name,
                 slot_name,
                 estimated_display,
+                checklist_display,
+                labels,
             }
         })
         .collect();
@@ -133,7 +178,12 @@ pub async fn list_view(
         project,
         task_rows,
         task_rows_json,
+        task_statuses,
+        labels,
+        filter: query,
+        filter_active: node_filter.is_active(),
         current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
     }
     .into_response()
 }
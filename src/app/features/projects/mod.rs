@@ -1,34 +1,48 @@
+mod board;
+mod charts;
 mod create;
 mod delete;
 mod export;
-mod format;
+pub(crate) mod filter;
+pub(crate) mod format;
 mod helpers;
 mod import;
 mod import_export;
 mod list;
 mod list_view;
-mod progress;
+pub(crate) mod progress;
+mod schedule;
 mod show;
+pub mod snapshots;
+mod timeline;
+pub mod trash;
 mod update_settings;
 
 use axum::Router;
 
 use crate::app::AppState;
 
-/// Projects routes (list, create, show, list_view).
+/// Projects routes (list, create, show, list_view, board, timeline, charts, archive).
 pub fn routes() -> Router<AppState> {
     Router::new()
         .merge(list::routes())
         .merge(create::routes())
         .merge(show::routes())
         .merge(list_view::routes())
+        .merge(board::routes())
+        .merge(timeline::routes())
+        .merge(charts::routes())
+        .merge(trash::routes())
 }
 
-/// API routes for projects (export, import, delete, update settings) under /api/projects/...
+/// API routes for projects (export, import, delete, archive/trash, update settings, timeline, snapshots) under /api/projects/...
 pub fn api_routes() -> Router<AppState> {
     Router::new()
         .merge(export::routes())
         .merge(import::routes())
         .merge(delete::routes())
         .merge(update_settings::routes())
+        .merge(timeline::api_routes())
+        .merge(charts::api_routes())
+        .merge(trash::api_routes())
 }
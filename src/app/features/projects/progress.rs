@@ -4,13 +4,12 @@ use std::collections::{HashMap, HashSet};
 
 use crate::app::db::{node_edges, nodes, task_statuses};
 
-/// Counts nodes that are blocked (dependent on an incomplete parent).
+/// IDs of nodes that are blocked (dependent on an incomplete parent).
 /// Only dependency edges (node_edges) count; group containment (parent_id) does not block.
-/// Returns (blocked_count, blocked_todo_count, blocked_in_progress_count).
-pub fn count_blocked(
-    nodes: &[nodes::Node],
+pub fn blocked_node_ids<'a>(
+    nodes: &'a [nodes::Node],
     edges: &[node_edges::NodeEdge],
-) -> (i64, i64, i64) {
+) -> HashSet<&'a str> {
     let group_ids: HashSet<&str> = nodes
         .iter()
         .filter_map(|n| n.parent_id.as_deref())
@@ -48,12 +47,27 @@ pub fn count_blocked(
     };
     let is_blocked = |id: &str| !is_root(id) && !is_done(id) && has_blocking_parent(id);
 
+    nodes
+        .iter()
+        .map(|n| n.id.as_str())
+        .filter(|id| is_blocked(id))
+        .collect()
+}
+
+/// Counts nodes that are blocked (see [`blocked_node_ids`]).
+/// Returns (blocked_count, blocked_todo_count, blocked_in_progress_count).
+pub fn count_blocked(
+    nodes: &[nodes::Node],
+    edges: &[node_edges::NodeEdge],
+) -> (i64, i64, i64) {
+    let blocked = blocked_node_ids(nodes, edges);
+
     let mut blocked_count: i64 = 0;
     let mut blocked_todo_count: i64 = 0;
     let mut blocked_in_progress_count: i64 = 0;
 
     for n in nodes {
-        if !is_blocked(n.id.as_str()) {
+        if !blocked.contains(n.id.as_str()) {
             continue;
         }
         blocked_count += 1;
@@ -90,6 +104,7 @@ mod tests {
             slot_id: None,
             parent_id: parent_id.map(String::from),
             assigned_user_id: None,
+            completed_at: None,
         }
     }
 
@@ -0,0 +1,291 @@
+//! Timeline scheduling for a project (computed in code from nodes + edges, like [`super::progress`]).
+//!
+//! Task nodes are placed in topological order of their dependency edges. Lanes are people: one per
+//! slot assignee, holding every slot they are assigned to, plus one "Unassigned" lane for slots
+//! without an assignee and nodes without a slot. A node starts once every dependency parent has
+//! finished and once its worker's previous node has finished: an assignee works through the nodes
+//! of all their slots in order, and an unassigned slot stands in for one person. Nodes without a
+//! slot are not serialized. Durations come from `estimated_minutes`; unestimated and done nodes
+//! take no time. Times are minutes of work from the start of the plan.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::app::db::{node_edges, nodes, project_slots, task_statuses};
+
+/// Name of the lane for unassigned slots and nodes without a slot.
+pub const UNASSIGNED_LANE_NAME: &str = "Unassigned";
+
+/// One row group on the timeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lane {
+    /// None for the "Unassigned" lane.
+    pub assigned_user_id: Option<String>,
+    /// Slots whose nodes land in this lane, in slot order.
+    pub slot_ids: Vec<String>,
+    /// The lane's slot names, or [`UNASSIGNED_LANE_NAME`].
+    pub name: String,
+}
+
+/// Who works through a node: serializes the nodes that share one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Worker<'a> {
+    Assignee(&'a str),
+    /// A slot nobody is assigned to.
+    Slot(&'a str),
+}
+
+/// One scheduled node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledNode {
+    pub node_id: String,
+    pub title: String,
+    pub lane: usize,
+    pub start_minutes: i64,
+    pub end_minutes: i64,
+    pub estimated: bool,
+    pub done: bool,
+}
+
+/// The full schedule: lanes in display order and nodes in topological order.
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    pub lanes: Vec<Lane>,
+    pub items: Vec<ScheduledNode>,
+    /// When the last node finishes.
+    pub total_minutes: i64,
+}
+
+/// Schedule task nodes (groups are left out, as are edges from groups, which don't block).
+/// Nodes caught in a dependency cycle are placed after everything else, in creation order.
+pub fn schedule(
+    nodes: &[nodes::Node],
+    edges: &[node_edges::NodeEdge],
+    slots: &[project_slots::ProjectSlot],
+) -> Schedule {
+    let tasks = super::helpers::task_nodes_from_nodes(nodes);
+    let task_ids: HashSet<&str> = tasks.iter().map(|n| n.id.as_str()).collect();
+
+    let mut lanes: Vec<Lane> = Vec::new();
+    let mut slot_names: Vec<Vec<&str>> = Vec::new();
+    let mut lane_by_assignee: HashMap<&str, usize> = HashMap::new();
+    let mut unassigned_slots = Vec::new();
+    for s in slots {
+        let Some(uid) = s.assigned_user_id.as_deref() else {
+            unassigned_slots.push(s.id.clone());
+            continue;
+        };
+        let lane = *lane_by_assignee.entry(uid).or_insert_with(|| {
+            lanes.push(Lane { assigned_user_id: Some(uid.to_string()), slot_ids: Vec::new(), name: String::new() });
+            slot_names.push(Vec::new());
+            lanes.len() - 1
+        });
+        lanes[lane].slot_ids.push(s.id.clone());
+        slot_names[lane].push(&s.name);
+    }
+    for (lane, names) in lanes.iter_mut().zip(slot_names) {
+        lane.name = names.join(", ");
+    }
+    let unassigned_lane = lanes.len();
+    lanes.push(Lane {
+        assigned_user_id: None,
+        slot_ids: unassigned_slots,
+        name: UNASSIGNED_LANE_NAME.to_string(),
+    });
+    let slot_by_id: HashMap<&str, &project_slots::ProjectSlot> = slots.iter().map(|s| (s.id.as_str(), s)).collect();
+
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for e in edges {
+        let (p, c) = (e.parent_id.as_str(), e.child_id.as_str());
+        if !task_ids.contains(p) || !task_ids.contains(c) {
+            continue;
+        }
+        parents.entry(c).or_default().push(p);
+        children.entry(p).or_default().push(c);
+    }
+
+    // Kahn's algorithm; ready nodes are taken in creation order so the plan is stable.
+    let position: HashMap<&str, usize> = tasks.iter().enumerate().map(|(i, n)| (n.id.as_str(), i)).collect();
+    let mut in_degree: HashMap<&str, usize> = tasks
+        .iter()
+        .map(|n| (n.id.as_str(), parents.get(n.id.as_str()).map_or(0, Vec::len)))
+        .collect();
+    let mut ready: BTreeMap<usize, &str> = in_degree
+        .iter()
+        .filter(|(_, d)| **d == 0)
+        .map(|(id, _)| (position[id], *id))
+        .collect();
+    let mut order: Vec<&str> = Vec::with_capacity(tasks.len());
+    while let Some((_, id)) = ready.pop_first() {
+        order.push(id);
+        for child in children.get(id).into_iter().flatten() {
+            let d = in_degree.get_mut(child).expect("child is a task");
+            *d -= 1;
+            if *d == 0 {
+                ready.insert(position[child], child);
+            }
+        }
+    }
+    let ordered: HashSet<&str> = order.iter().copied().collect();
+    order.extend(tasks.iter().map(|n| n.id.as_str()).filter(|id| !ordered.contains(id)));
+
+    let by_id: HashMap<&str, &nodes::Node> = tasks.iter().map(|n| (n.id.as_str(), *n)).collect();
+    let mut end_by_id: HashMap<&str, i64> = HashMap::new();
+    let mut worker_free_at: HashMap<Worker, i64> = HashMap::new();
+    let mut items = Vec::with_capacity(order.len());
+
+    for id in order {
+        let node = by_id[id];
+        let slot = node.slot_id.as_deref().and_then(|s| slot_by_id.get(s));
+        let worker = slot.map(|s| match s.assigned_user_id.as_deref() {
+            Some(uid) => Worker::Assignee(uid),
+            None => Worker::Slot(&s.id),
+        });
+        let lane = match worker {
+            Some(Worker::Assignee(uid)) => lane_by_assignee[uid],
+            _ => unassigned_lane,
+        };
+        let deps_done = parents
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter_map(|p| end_by_id.get(p))
+            .copied()
+            .max()
+            .unwrap_or(0);
+        let free_at = worker.and_then(|w| worker_free_at.get(&w).copied()).unwrap_or(0);
+        let start = deps_done.max(free_at);
+        let done = node.status_id == task_statuses::DONE_STATUS_ID;
+        let duration = if done { 0 } else { node.estimated_minutes.unwrap_or(0).max(0) };
+        let end = start + duration;
+        if let Some(w) = worker {
+            worker_free_at.insert(w, end);
+        }
+        end_by_id.insert(id, end);
+        items.push(ScheduledNode {
+            node_id: node.id.clone(),
+            title: node.title.clone(),
+            lane,
+            start_minutes: start,
+            end_minutes: end,
+            estimated: node.estimated_minutes.is_some(),
+            done,
+        });
+    }
+
+    let total_minutes = items.iter().map(|i| i.end_minutes).max().unwrap_or(0);
+    Schedule { lanes, items, total_minutes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::db::{node_edges, nodes, project_slots, task_statuses};
+
+    fn node(id: &str, minutes: Option<i64>, slot_id: Option<&str>) -> nodes::Node {
+        nodes::Node {
+            id: id.to_string(),
+            project_id: "project".to_string(),
+            node_type_id: "type".to_string(),
+            status_id: task_statuses::TODO_STATUS_ID.to_string(),
+            title: id.to_uppercase(),
+            description: None,
+            created_at: 0,
+            updated_at: None,
+            estimated_minutes: minutes,
+            slot_id: slot_id.map(String::from),
+            parent_id: None,
+            assigned_user_id: None,
+            completed_at: None,
+        }
+    }
+
+    fn edge(parent_id: &str, child_id: &str) -> node_edges::NodeEdge {
+        node_edges::NodeEdge {
+            parent_id: parent_id.to_string(),
+            child_id: child_id.to_string(),
+            created_at: 0,
+        }
+    }
+
+    fn slot(id: &str, assigned_user_id: Option<&str>) -> project_slots::ProjectSlot {
+        project_slots::ProjectSlot {
+            id: id.to_string(),
+            project_id: "project".to_string(),
+            name: id.to_string(),
+            sort_order: 0,
+            created_at: 0,
+            assigned_user_id: assigned_user_id.map(String::from),
+        }
+    }
+
+    fn span(s: &Schedule, id: &str) -> (i64, i64) {
+        let item = s.items.iter().find(|i| i.node_id == id).unwrap();
+        (item.start_minutes, item.end_minutes)
+    }
+
+    #[test]
+    fn dependencies_run_in_sequence() {
+        let nodes = vec![node("b", Some(30), None), node("a", Some(60), None)];
+        let s = schedule(&nodes, &[edge("a", "b")], &[]);
+        assert_eq!(s.items[0].node_id, "a");
+        assert_eq!(span(&s, "a"), (0, 60));
+        assert_eq!(span(&s, "b"), (60, 90));
+        assert_eq!(s.total_minutes, 90);
+    }
+
+    #[test]
+    fn slot_serializes_independent_work_but_no_slot_does_not() {
+        let nodes = vec![
+            node("a", Some(60), Some("dev")),
+            node("b", Some(60), Some("dev")),
+            node("c", Some(60), None),
+            node("d", Some(60), None),
+        ];
+        let s = schedule(&nodes, &[], &[slot("dev", None)]);
+        assert_eq!(span(&s, "b"), (60, 120));
+        assert_eq!(span(&s, "c"), (0, 60));
+        assert_eq!(span(&s, "d"), (0, 60));
+        assert_eq!(s.lanes.len(), 1);
+        assert_eq!(s.lanes[0].name, UNASSIGNED_LANE_NAME);
+        assert_eq!(s.lanes[0].slot_ids, vec!["dev".to_string()]);
+    }
+
+    #[test]
+    fn slots_sharing_an_assignee_share_one_lane_and_one_queue() {
+        let nodes = vec![
+            node("a", Some(60), Some("backend")),
+            node("b", Some(30), Some("review")),
+            node("c", Some(45), Some("docs")),
+        ];
+        let slots = [slot("backend", Some("ann")), slot("review", Some("ann")), slot("docs", Some("bo"))];
+        let s = schedule(&nodes, &[], &slots);
+        assert_eq!(s.lanes.len(), 3);
+        assert_eq!(s.lanes[0].assigned_user_id.as_deref(), Some("ann"));
+        assert_eq!(s.lanes[0].name, "backend, review");
+        assert_eq!(s.lanes[1].name, "docs");
+        assert_eq!(s.lanes[2].name, UNASSIGNED_LANE_NAME);
+        let lane = |id: &str| s.items.iter().find(|i| i.node_id == id).unwrap().lane;
+        assert_eq!((lane("a"), lane("b"), lane("c")), (0, 0, 1));
+        // Ann does her backend node before her review node; Bo works in parallel.
+        assert_eq!(span(&s, "b"), (60, 90));
+        assert_eq!(span(&s, "c"), (0, 45));
+    }
+
+    #[test]
+    fn done_and_unestimated_take_no_time() {
+        let mut done = node("a", Some(120), None);
+        done.status_id = task_statuses::DONE_STATUS_ID.to_string();
+        let nodes = vec![done, node("b", None, None), node("c", Some(15), None)];
+        let s = schedule(&nodes, &[edge("a", "b"), edge("b", "c")], &[]);
+        assert_eq!(span(&s, "c"), (0, 15));
+        assert!(!s.items[1].estimated);
+    }
+
+    #[test]
+    fn cycles_do_not_drop_nodes() {
+        let nodes = vec![node("a", Some(10), None), node("b", Some(10), None)];
+        let s = schedule(&nodes, &[edge("a", "b"), edge("b", "a")], &[]);
+        assert_eq!(s.items.len(), 2);
+    }
+}
@@ -27,6 +27,11 @@ pub struct ProjectShowTemplate {
     pub completed_count: i64,
     pub blocked_count: i64,
     pub estimated_left_display: String,
+    /// Total time logged on the project's nodes.
+    pub time_spent_display: String,
+    /// Estimate vs. logged time for finished tasks, e.g. "Done: 6 h estimated, 7 h 30 min spent".
+    pub done_comparison_display: String,
+    pub csrf_token: String,
 }
 
 /// GET /app/projects/:id — Show project detail.
@@ -46,11 +51,17 @@ pub async fn show(
         return (StatusCode::NOT_FOUND, "Project not found").into_response();
     }
 
-    let (nodes, edges) = match tokio::try_join!(
+    let (nodes, edges, time_spent_minutes, done_spent_minutes) = match tokio::try_join!(
         db::nodes::find_by_project(&state.db, &id),
         db::node_edges::find_by_project(&state.db, &id),
+        db::time_entries::total_minutes_for_project(&state.db, &id),
+        db::time_entries::total_minutes_for_project_status(
+            &state.db,
+            &id,
+            db::task_statuses::DONE_STATUS_ID,
+        ),
     ) {
-        Ok((n, e)) => (n, e),
+        Ok(results) => results,
         Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
     };
 
@@ -82,6 +93,12 @@ pub async fn show(
 
     let estimated_left_display =
         format::format_estimated_minutes(estimated_left_minutes);
+    let time_spent_display = format::format_estimated_minutes(time_spent_minutes);
+    let done_comparison_display = format!(
+        "Done: {} estimated, {} spent",
+        format::format_estimated_minutes(estimated_completed_minutes),
+        format::format_estimated_minutes(done_spent_minutes),
+    );
 
     ProjectShowTemplate {
         app_name: APP_NAME,
@@ -91,6 +108,9 @@ pub async fn show(
         completed_count,
         blocked_count,
         estimated_left_display,
+        time_spent_display,
+        done_comparison_display,
+        csrf_token: session.csrf_token,
     }
     .into_response()
 }
@@ -0,0 +1,79 @@
+//! Daily project snapshots: the same task counts the project page shows, stored once per UTC day
+//! so [`super::charts`] can draw trends.
+
+use std::time::Duration;
+
+use time::{Date, OffsetDateTime};
+
+use crate::app::db::{self, task_statuses};
+
+use super::{helpers, progress};
+
+/// How often today's snapshots are refreshed. The last refresh of a day is the one that sticks.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Task counts and estimate left for one project, as of `date`.
+pub fn snapshot_of(
+    nodes: &[db::nodes::Node],
+    edges: &[db::node_edges::NodeEdge],
+    date: Date,
+) -> db::project_snapshots::ProjectSnapshot {
+    let task_nodes = helpers::task_nodes_from_nodes(nodes);
+    let count = |status: &str| task_nodes.iter().filter(|n| n.status_id == status).count() as i64;
+    let (blocked_count, _, _) = progress::count_blocked(nodes, edges);
+    let estimated_remaining_minutes = task_nodes
+        .iter()
+        .filter(|n| n.status_id != task_statuses::DONE_STATUS_ID)
+        .filter_map(|n| n.estimated_minutes)
+        .sum();
+
+    db::project_snapshots::ProjectSnapshot {
+        snapshot_date: date.to_string(),
+        todo_count: count(task_statuses::TODO_STATUS_ID),
+        in_progress_count: count(task_statuses::IN_PROGRESS_STATUS_ID),
+        done_count: count(task_statuses::DONE_STATUS_ID),
+        blocked_count,
+        estimated_remaining_minutes,
+    }
+}
+
+/// Record `date`'s snapshot for every project. Returns how many were written.
+/// A failure for one project is logged and does not stop the others.
+pub async fn record_all(pool: &sqlx::SqlitePool, date: Date) -> Result<usize, sqlx::Error> {
+    let project_ids = db::projects::list_all_ids(pool).await?;
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let mut recorded = 0;
+
+    for project_id in project_ids {
+        match record_project(pool, &project_id, date, now).await {
+            Ok(()) => recorded += 1,
+            Err(e) => tracing::warn!(error = %e, project_id = %project_id, "Failed to record project snapshot"),
+        }
+    }
+
+    Ok(recorded)
+}
+
+async fn record_project(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    date: Date,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    let (nodes, edges) = tokio::try_join!(
+        db::nodes::find_by_project(pool, project_id),
+        db::node_edges::find_by_project(pool, project_id),
+    )?;
+    db::project_snapshots::upsert(pool, project_id, &snapshot_of(&nodes, &edges, date), now).await
+}
+
+/// Refresh today's snapshots forever. Spawn once at startup.
+pub async fn run_scheduler(pool: sqlx::SqlitePool) {
+    loop {
+        let today = OffsetDateTime::now_utc().date();
+        if let Err(e) = record_all(&pool, today).await {
+            tracing::error!(error = %e, "Snapshot job failed to list projects");
+        }
+        tokio::time::sleep(REFRESH_INTERVAL).await;
+    }
+}
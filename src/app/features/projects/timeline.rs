@@ -0,0 +1,442 @@
+//! Timeline (Gantt) view: task nodes laid out on a time axis by [`super::schedule`], one lane per
+//! slot assignee. The HTML page draws the SVG server-side; the JSON endpoint returns the same layout.
+
+use std::collections::{HashMap, HashSet};
+
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use time::{Date, OffsetDateTime, Weekday};
+
+use crate::app::{
+    db,
+    domain::{OrganizationId, UserId},
+    error::AppError,
+    features::graph,
+    session::{ApiAuthenticatedSession, AuthenticatedSession},
+    tenant,
+    AppState, APP_NAME,
+};
+
+use super::{format, helpers, progress, schedule};
+
+/// Minutes in one working day; the time axis is marked in working days of this length.
+const WORKDAY_MINUTES: i64 = 480;
+/// Zero-length bars (done or unestimated nodes) are drawn and packed as if they took this long.
+const MIN_BAR_MINUTES: i64 = 30;
+const PX_PER_HOUR: i64 = 24;
+const LABEL_WIDTH: i64 = 180;
+const AXIS_HEIGHT: i64 = 28;
+const ROW_HEIGHT: i64 = 32;
+const BAR_HEIGHT: i64 = 22;
+
+/// Schedule plus per-item rows so overlapping bars in the same lane don't cover each other.
+struct Layout {
+    schedule: schedule::Schedule,
+    /// Row within its lane, per item.
+    rows: Vec<usize>,
+    /// Number of rows per lane (at least one).
+    lane_rows: Vec<usize>,
+    /// Assignee display name per lane (empty when the slot has nobody).
+    lane_assignees: Vec<String>,
+    blocked: HashSet<String>,
+    /// Dependency edges between scheduled items, as item indices.
+    links: Vec<(usize, usize)>,
+}
+
+async fn load_layout(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    org_id: &OrganizationId,
+) -> Result<Layout, sqlx::Error> {
+    let (nodes, edges, slots, members) = tokio::try_join!(
+        db::nodes::find_by_project(pool, project_id),
+        db::node_edges::find_by_project(pool, project_id),
+        db::project_slots::find_by_project(pool, project_id),
+        db::organizations::list_members_with_email(pool, org_id),
+    )?;
+
+    let schedule = schedule::schedule(&nodes, &edges, &slots);
+    let blocked = progress::blocked_node_ids(&nodes, &edges)
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    let member_names: HashMap<&str, String> = members
+        .iter()
+        .map(|m| {
+            let name = db::users::display_name_from_parts(&m.first_name, &m.last_name);
+            (m.user_id.as_str(), if name.is_empty() { m.email.clone() } else { name })
+        })
+        .collect();
+    let lane_assignees = schedule
+        .lanes
+        .iter()
+        .map(|l| {
+            l.assigned_user_id
+                .as_deref()
+                .and_then(|uid| member_names.get(uid).cloned())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    // First-fit packing: each item takes the first row in its lane that is free by its start.
+    let mut row_ends: Vec<Vec<i64>> = vec![Vec::new(); schedule.lanes.len()];
+    let mut rows = Vec::with_capacity(schedule.items.len());
+    for item in &schedule.items {
+        let end = item.end_minutes.max(item.start_minutes + MIN_BAR_MINUTES);
+        let lane_rows = &mut row_ends[item.lane];
+        let row = match lane_rows.iter().position(|free_at| *free_at <= item.start_minutes) {
+            Some(r) => {
+                lane_rows[r] = end;
+                r
+            }
+            None => {
+                lane_rows.push(end);
+                lane_rows.len() - 1
+            }
+        };
+        rows.push(row);
+    }
+    let lane_rows = row_ends.iter().map(|r| r.len().max(1)).collect();
+
+    let index: HashMap<&str, usize> = schedule
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| (item.node_id.as_str(), i))
+        .collect();
+    let links = edges
+        .iter()
+        .filter_map(|e| Some((*index.get(e.parent_id.as_str())?, *index.get(e.child_id.as_str())?)))
+        .collect();
+
+    Ok(Layout { schedule, rows, lane_rows, lane_assignees, blocked, links })
+}
+
+#[derive(Serialize)]
+pub struct TimelineLaneResponse {
+    pub assigned_user_id: Option<String>,
+    pub slot_ids: Vec<String>,
+    pub name: String,
+    pub assignee_name: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TimelineItemResponse {
+    pub node_id: String,
+    pub title: String,
+    pub lane_index: usize,
+    pub row: usize,
+    pub start_minutes: i64,
+    pub end_minutes: i64,
+    pub estimated: bool,
+    pub done: bool,
+    pub blocked: bool,
+}
+
+#[derive(Serialize)]
+pub struct TimelineResponse {
+    pub lanes: Vec<TimelineLaneResponse>,
+    /// Items in topological order; times are working minutes from the start of the plan.
+    pub items: Vec<TimelineItemResponse>,
+    pub total_minutes: i64,
+}
+
+/// GET /api/projects/:project_id/timeline — Scheduled timeline layout as JSON.
+pub async fn get_timeline(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<TimelineResponse>, AppError> {
+    let project = graph::helpers::ensure_project_accessible(&state.db, &project_id, &session.user_id).await?;
+    let org_id = OrganizationId::from_string(&project.organization_id)
+        .map_err(|_| AppError::NotFound("Project not found".to_string()))?;
+    let layout = load_layout(&state.db, &project_id, &org_id).await?;
+
+    let lanes = layout
+        .schedule
+        .lanes
+        .into_iter()
+        .zip(layout.lane_assignees)
+        .map(|(l, assignee)| TimelineLaneResponse {
+            assigned_user_id: l.assigned_user_id,
+            slot_ids: l.slot_ids,
+            name: l.name,
+            assignee_name: (!assignee.is_empty()).then_some(assignee),
+        })
+        .collect();
+    let items = layout
+        .schedule
+        .items
+        .into_iter()
+        .zip(layout.rows)
+        .map(|(item, row)| TimelineItemResponse {
+            blocked: layout.blocked.contains(&item.node_id),
+            node_id: item.node_id,
+            title: item.title,
+            lane_index: item.lane,
+            row,
+            start_minutes: item.start_minutes,
+            end_minutes: item.end_minutes,
+            estimated: item.estimated,
+            done: item.done,
+        })
+        .collect();
+
+    Ok(Json(TimelineResponse {
+        lanes,
+        items,
+        total_minutes: layout.schedule.total_minutes,
+    }))
+}
+
+/// A lane band in the SVG (pixel geometry).
+pub struct TimelineLane {
+    pub name: String,
+    pub assignee_name: String,
+    pub y: i64,
+    pub height: i64,
+    pub label_y: i64,
+}
+
+/// A node bar in the SVG (pixel geometry).
+pub struct TimelineBar {
+    pub node_id: String,
+    pub title: String,
+    pub tooltip: String,
+    pub x: i64,
+    pub y: i64,
+    pub width: i64,
+    pub height: i64,
+    pub text_x: i64,
+    pub text_y: i64,
+    pub estimated: bool,
+    pub done: bool,
+    pub blocked: bool,
+}
+
+/// A dependency connector from the end of one bar to the start of another.
+pub struct TimelineLink {
+    pub x1: i64,
+    pub y1: i64,
+    pub x2: i64,
+    pub y2: i64,
+}
+
+/// A day marker on the time axis.
+pub struct TimelineTick {
+    pub x: i64,
+    pub label: String,
+}
+
+/// Project timeline template.
+#[derive(Template)]
+#[template(path = "projects_timeline.html")]
+pub struct ProjectTimelineTemplate {
+    pub app_name: &'static str,
+    pub project: db::projects::Project,
+    pub width: i64,
+    pub height: i64,
+    pub lanes: Vec<TimelineLane>,
+    pub bars: Vec<TimelineBar>,
+    pub links: Vec<TimelineLink>,
+    pub ticks: Vec<TimelineTick>,
+    pub total_display: String,
+    pub unestimated_count: usize,
+    pub is_empty: bool,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+fn minutes_to_x(minutes: i64) -> i64 {
+    LABEL_WIDTH + minutes * PX_PER_HOUR / 60
+}
+
+/// Calendar dates of the first `count` working days (Monday to Friday) on or after `start`.
+fn working_days(start: Date, count: usize) -> Vec<Date> {
+    let is_weekend = |d: &Date| matches!(d.weekday(), Weekday::Saturday | Weekday::Sunday);
+    std::iter::successors(Some(start), |d| d.next_day())
+        .filter(|d| !is_weekend(d))
+        .take(count)
+        .collect()
+}
+
+/// Axis label for a working day, e.g. "Mon 10-19".
+fn day_label(date: Date) -> String {
+    let weekday = date.weekday().to_string();
+    format!("{} {:02}-{:02}", &weekday[..3], u8::from(date.month()), date.day())
+}
+
+/// GET /app/projects/:id/timeline — Timeline (Gantt) view of the project's task nodes.
+pub async fn timeline(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let project = match helpers::load_project(&state.db, &id).await {
+        Ok(p) => p,
+        Err((status, msg)) => return (status, msg).into_response(),
+    };
+    if tenant::require_org_member(&state.db, &session.user_id, &project.organization_id)
+        .await
+        .is_err()
+    {
+        return (StatusCode::NOT_FOUND, "Project not found").into_response();
+    }
+    let Ok(org_id) = OrganizationId::from_string(&project.organization_id) else {
+        return (StatusCode::NOT_FOUND, "Project not found").into_response();
+    };
+    let layout = match load_layout(&state.db, &id, &org_id).await {
+        Ok(l) => l,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+
+    let mut lane_top = Vec::with_capacity(layout.lane_rows.len());
+    let mut y = AXIS_HEIGHT;
+    let lanes: Vec<TimelineLane> = layout
+        .schedule
+        .lanes
+        .iter()
+        .zip(&layout.lane_rows)
+        .zip(&layout.lane_assignees)
+        .map(|((lane, rows), assignee)| {
+            let height = *rows as i64 * ROW_HEIGHT;
+            lane_top.push(y);
+            let band = TimelineLane {
+                name: lane.name.clone(),
+                assignee_name: assignee.clone(),
+                y,
+                height,
+                label_y: y + ROW_HEIGHT / 2 + 4,
+            };
+            y += height;
+            band
+        })
+        .collect();
+    let height = y;
+
+    let bars: Vec<TimelineBar> = layout
+        .schedule
+        .items
+        .iter()
+        .zip(&layout.rows)
+        .map(|(item, row)| {
+            let x = minutes_to_x(item.start_minutes);
+            let bar_y = lane_top[item.lane] + *row as i64 * ROW_HEIGHT + (ROW_HEIGHT - BAR_HEIGHT) / 2;
+            let minutes = item.end_minutes - item.start_minutes;
+            let tooltip = if item.done {
+                format!("{} (done)", item.title)
+            } else if item.estimated {
+                format!("{} ({})", item.title, format::format_estimated_minutes(minutes))
+            } else {
+                format!("{} (no estimate)", item.title)
+            };
+            TimelineBar {
+                node_id: item.node_id.clone(),
+                title: item.title.clone(),
+                tooltip,
+                x,
+                y: bar_y,
+                width: minutes.max(MIN_BAR_MINUTES) * PX_PER_HOUR / 60,
+                height: BAR_HEIGHT,
+                text_x: x + 4,
+                text_y: bar_y + BAR_HEIGHT / 2 + 4,
+                estimated: item.estimated,
+                done: item.done,
+                blocked: layout.blocked.contains(&item.node_id),
+            }
+        })
+        .collect();
+
+    let links = layout
+        .links
+        .iter()
+        .map(|(from, to)| {
+            let (a, b) = (&bars[*from], &bars[*to]);
+            TimelineLink {
+                x1: a.x + a.width,
+                y1: a.y + BAR_HEIGHT / 2,
+                x2: b.x,
+                y2: b.y + BAR_HEIGHT / 2,
+            }
+        })
+        .collect();
+
+    let axis_end = bars.iter().map(|b| b.x + b.width).max().unwrap_or(LABEL_WIDTH);
+    let days = ((layout.schedule.total_minutes + WORKDAY_MINUTES - 1) / WORKDAY_MINUTES).max(1);
+    // The plan starts on the day the project was created and only advances on working days.
+    let start = OffsetDateTime::from_unix_timestamp(project.created_at)
+        .map(|t| t.date())
+        .unwrap_or_else(|_| OffsetDateTime::now_utc().date());
+    let ticks = working_days(start, days as usize)
+        .into_iter()
+        .zip(0..)
+        .map(|(date, d)| TimelineTick {
+            x: minutes_to_x(d * WORKDAY_MINUTES),
+            label: day_label(date),
+        })
+        .collect();
+    let width = axis_end.max(minutes_to_x(days * WORKDAY_MINUTES)) + 16;
+
+    let user_id = match UserId::from_string(&session.user_id) {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid session".to_string()).into_response(),
+    };
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+
+    ProjectTimelineTemplate {
+        app_name: APP_NAME,
+        project,
+        width,
+        height,
+        unestimated_count: bars.iter().filter(|b| !b.estimated && !b.done).count(),
+        is_empty: bars.is_empty(),
+        lanes,
+        bars,
+        links,
+        ticks,
+        total_display: format::format_estimated_minutes(layout.schedule.total_minutes),
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    }
+    .into_response()
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/app/projects/:id/timeline", get(timeline))
+}
+
+pub fn api_routes() -> Router<AppState> {
+    Router::new().route("/api/projects/:project_id/timeline", get(get_timeline))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Month;
+
+    fn date(month: Month, day: u8) -> Date {
+        Date::from_calendar_date(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn working_days_skip_weekends() {
+        // 2026-10-16 is a Friday.
+        let days = working_days(date(Month::October, 16), 3);
+        assert_eq!(days, vec![date(Month::October, 16), date(Month::October, 19), date(Month::October, 20)]);
+    }
+
+    #[test]
+    fn plan_started_on_a_weekend_begins_on_monday() {
+        let days = working_days(date(Month::October, 17), 1);
+        assert_eq!(days, vec![date(Month::October, 19)]);
+        assert_eq!(day_label(days[0]), "Mon 10-19");
+    }
+}
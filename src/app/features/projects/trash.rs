@@ -0,0 +1,262 @@
+//! Archive and trash. Archived projects drop off `/app/projects` but stay readable; trashed
+//! projects (see [`super::delete`]) are unreachable until restored, and the purge job deletes them
+//! for good once they have been in the trash longer than `Config::trash_retention_days`, along with
+//! attachment blobs nothing else uses.
+
+use std::time::Duration;
+
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect},
+    routing::{get, post},
+    Router,
+};
+use time::OffsetDateTime;
+
+use crate::app::{
+    db,
+    domain::{OrganizationRole, UserId},
+    features::graph,
+    session::AuthenticatedSession,
+    tenant,
+    AppState, APP_NAME,
+};
+
+/// How often the purge job looks for expired trash.
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Permanently delete projects trashed more than `retention_days` before `now`. Returns how many.
+pub async fn purge_expired(
+    pool: &sqlx::SqlitePool,
+    retention_days: u32,
+    now: i64,
+) -> Result<u64, sqlx::Error> {
+    let cutoff = now - i64::from(retention_days) * SECS_PER_DAY;
+    db::projects::purge_trashed_before(pool, cutoff).await
+}
+
+/// Purge expired trash forever. Spawn once at startup.
+pub async fn run_purge_scheduler(state: AppState) {
+    loop {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        match purge_expired(&state.db, state.config.trash_retention_days, now).await {
+            Ok(0) => {}
+            Ok(purged) => tracing::info!(purged, "Purged expired projects from the trash"),
+            Err(e) => tracing::error!(error = %e, "Trash purge failed"),
+        }
+        // Attachment blobs freed by anything since the last run: deleted attachments, purged
+        // projects and expired node deletion records.
+        graph::attachments::sweep_orphaned_blobs(&state).await;
+        tokio::time::sleep(PURGE_INTERVAL).await;
+    }
+}
+
+/// One archived project.
+pub struct ArchivedRow {
+    pub id: String,
+    pub title: String,
+    pub archived_display: String,
+}
+
+/// One trashed project with its remaining restore window.
+pub struct TrashedRow {
+    pub id: String,
+    pub title: String,
+    pub deleted_display: String,
+    pub days_left: i64,
+}
+
+/// Archive and trash template.
+#[derive(Template)]
+#[template(path = "projects_archive.html")]
+pub struct ProjectsArchiveTemplate {
+    pub app_name: &'static str,
+    pub archived: Vec<ArchivedRow>,
+    pub trashed: Vec<TrashedRow>,
+    pub retention_days: u32,
+    pub can_purge: bool,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+fn format_day(timestamp: i64) -> String {
+    OffsetDateTime::from_unix_timestamp(timestamp)
+        .map(|t| t.date().to_string())
+        .unwrap_or_default()
+}
+
+/// Whole days until a project trashed at `deleted_at` is purged (0 = on the next run).
+fn days_until_purge(deleted_at: i64, retention_days: u32, now: i64) -> i64 {
+    let purge_at = deleted_at + i64::from(retention_days) * SECS_PER_DAY;
+    ((purge_at - now + SECS_PER_DAY - 1) / SECS_PER_DAY).max(0)
+}
+
+/// GET /app/projects/archive — Archived and trashed projects in the session's organization.
+pub async fn archive_page(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let role = match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(r) => r,
+        Err(_) => return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response(),
+    };
+
+    let (archived, trashed) = match tokio::try_join!(
+        db::projects::list_archived_for_org(&state.db, &session.organization_id),
+        db::projects::list_trashed_for_org(&state.db, &session.organization_id),
+    ) {
+        Ok(lists) => lists,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+
+    let retention_days = state.config.trash_retention_days;
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let archived = archived
+        .into_iter()
+        .map(|p| ArchivedRow {
+            archived_display: format_day(p.archived_at.unwrap_or(p.created_at)),
+            id: p.id,
+            title: p.title,
+        })
+        .collect();
+    let trashed = trashed
+        .into_iter()
+        .map(|p| {
+            let deleted_at = p.deleted_at.unwrap_or(now);
+            TrashedRow {
+                deleted_display: format_day(deleted_at),
+                days_left: days_until_purge(deleted_at, retention_days, now),
+                id: p.id,
+                title: p.title,
+            }
+        })
+        .collect();
+
+    let user_id = match UserId::from_string(&session.user_id) {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid session".to_string()).into_response(),
+    };
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+
+    ProjectsArchiveTemplate {
+        app_name: APP_NAME,
+        archived,
+        trashed,
+        retention_days,
+        can_purge: matches!(role, OrganizationRole::Owner | OrganizationRole::Admin),
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    }
+    .into_response()
+}
+
+/// Turn a "did it match" result into the redirect or 404 these form posts answer with.
+fn respond(result: Result<bool, sqlx::Error>, redirect_to: &str) -> axum::response::Response {
+    match result {
+        Ok(true) => Redirect::to(redirect_to).into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Project not found".to_string()).into_response(),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    }
+}
+
+/// POST /api/projects/:id/archive — Hide a project from the projects list.
+pub async fn archive(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> impl IntoResponse {
+    if tenant::require_org_member(&state.db, &session.user_id, &session.organization_id)
+        .await
+        .is_err()
+    {
+        return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response();
+    }
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let result =
+        db::projects::set_archived_at(&state.db, &project_id, &session.organization_id, Some(now)).await;
+    respond(result, "/app/projects")
+}
+
+/// POST /api/projects/:id/unarchive — Put an archived project back on the projects list.
+pub async fn unarchive(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> impl IntoResponse {
+    if tenant::require_org_member(&state.db, &session.user_id, &session.organization_id)
+        .await
+        .is_err()
+    {
+        return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response();
+    }
+    let result =
+        db::projects::set_archived_at(&state.db, &project_id, &session.organization_id, None).await;
+    respond(result, &format!("/app/projects/{}", project_id))
+}
+
+/// POST /api/projects/:id/restore — Take a project out of the trash.
+pub async fn restore(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> impl IntoResponse {
+    if tenant::require_org_member(&state.db, &session.user_id, &session.organization_id)
+        .await
+        .is_err()
+    {
+        return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response();
+    }
+    let result =
+        db::projects::restore_from_trash(&state.db, &project_id, &session.organization_id).await;
+    respond(result, "/app/projects/archive")
+}
+
+/// POST /api/projects/:id/purge — Permanently delete a trashed project. Owners and admins only.
+pub async fn purge(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> impl IntoResponse {
+    match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(OrganizationRole::Owner | OrganizationRole::Admin) => {}
+        _ => return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response(),
+    }
+    match db::projects::find_trashed_by_id_and_org(&state.db, &project_id, &session.organization_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return (StatusCode::NOT_FOUND, "Project not found".to_string()).into_response(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    }
+    let result =
+        db::projects::delete_by_id_and_org(&state.db, &project_id, &session.organization_id).await;
+    respond(result, "/app/projects/archive")
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/app/projects/archive", get(archive_page))
+}
+
+pub fn api_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/projects/:id/archive", post(archive))
+        .route("/api/projects/:id/unarchive", post(unarchive))
+        .route("/api/projects/:id/restore", post(restore))
+        .route("/api/projects/:id/purge", post(purge))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_until_purge_counts_partial_days_and_stops_at_zero() {
+        let now = 100 * SECS_PER_DAY;
+        assert_eq!(days_until_purge(now, 30, now), 30);
+        assert_eq!(days_until_purge(now - SECS_PER_DAY / 2, 30, now), 30);
+        assert_eq!(days_until_purge(now - 30 * SECS_PER_DAY + 1, 30, now), 1);
+        assert_eq!(days_until_purge(now - 31 * SECS_PER_DAY, 30, now), 0);
+    }
+}
@@ -0,0 +1,142 @@
+//! GET /api/search?q= — Full-text search over nodes in the current organisation.
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{
+    db::{self, node_search},
+    error::AppError,
+    session::ApiAuthenticatedSession,
+    tenant, AppState,
+};
+
+/// Maximum results returned per search.
+const RESULT_LIMIT: i64 = 25;
+
+/// Longest accepted query, in characters.
+const MAX_QUERY_LEN: usize = 200;
+
+/// Query string for search.
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+/// One search result.
+#[derive(Debug, Serialize)]
+pub struct SearchResultItem {
+    pub node_id: String,
+    pub title: String,
+    pub project_id: String,
+    pub project_title: String,
+    pub status_id: String,
+    pub status_name: String,
+    /// HTML-escaped excerpt with matched terms wrapped in `<mark>`.
+    pub snippet_html: String,
+}
+
+/// Response for search.
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub results: Vec<SearchResultItem>,
+}
+
+/// GET /api/search?q= — Search node titles and descriptions across the organisation's projects.
+pub async fn search(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<SearchResponse>, AppError> {
+    let q = query.q.trim();
+    if q.chars().count() > MAX_QUERY_LEN {
+        return Err(AppError::Validation("Search query is too long".to_string()));
+    }
+    tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await?;
+
+    let Some(fts_query) = to_fts_query(q) else {
+        return Ok(Json(SearchResponse { results: Vec::new() }));
+    };
+    let hits = db::node_search::search_in_org(&state.db, &session.organization_id, &fts_query, RESULT_LIMIT).await?;
+    let results = hits
+        .into_iter()
+        .map(|h| SearchResultItem {
+            snippet_html: highlight(&h.snippet),
+            node_id: h.node_id,
+            title: h.title,
+            project_id: h.project_id,
+            project_title: h.project_title,
+            status_id: h.status_id,
+            status_name: h.status_name,
+        })
+        .collect();
+
+    Ok(Json(SearchResponse { results }))
+}
+
+/// Turn free text into an FTS5 query: every word must match, the last one as a prefix
+/// so results update while typing. Words are quoted so FTS5 operators in user input are literal.
+/// Returns None when there is nothing to search for.
+fn to_fts_query(q: &str) -> Option<String> {
+    let terms: Vec<String> = q
+        .split_whitespace()
+        .map(|w| w.replace('"', ""))
+        .filter(|w| !w.is_empty())
+        .map(|w| format!("\"{}\"", w))
+        .collect();
+    let (last, rest) = terms.split_last()?;
+    let mut query = rest.join(" ");
+    if !query.is_empty() {
+        query.push(' ');
+    }
+    query.push_str(last);
+    query.push('*');
+    Some(query)
+}
+
+/// Escape a snippet for HTML and turn the FTS match markers into `<mark>` tags.
+fn highlight(snippet: &str) -> String {
+    let mut out = String::with_capacity(snippet.len() + 16);
+    for c in snippet.chars() {
+        match c {
+            node_search::MATCH_START => out.push_str("<mark>"),
+            node_search::MATCH_END => out.push_str("</mark>"),
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/api/search", get(search))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fts_query_quotes_words_and_prefixes_last() {
+        assert_eq!(to_fts_query("write docs").as_deref(), Some("\"write\" \"docs\"*"));
+        assert_eq!(to_fts_query("a\"b OR"), Some("\"ab\" \"OR\"*".to_string()));
+        assert_eq!(to_fts_query("   "), None);
+        assert_eq!(to_fts_query("\"\""), None);
+    }
+
+    #[test]
+    fn highlight_escapes_and_marks() {
+        assert_eq!(
+            highlight("<b>\u{2}fix\u{3}</b> & more"),
+            "&lt;b&gt;<mark>fix</mark>&lt;/b&gt; &amp; more"
+        );
+    }
+}
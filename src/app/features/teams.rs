@@ -1,19 +1,25 @@
 use askama::Template;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::{Html, IntoResponse},
-    routing::get,
-    Router,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Form, Router,
 };
+use serde::Deserialize;
+use ulid::Ulid;
 
 use crate::app::{
     db,
+    domain::{OrganizationId, OrganizationRole, UserId},
     session::AuthenticatedSession,
     tenant,
     AppState, APP_NAME,
 };
 
+/// Longest team name accepted.
+const MAX_TEAM_NAME_LEN: usize = 100;
+
 /// One row for the teams list table.
 pub(crate) struct TeamRow {
     pub id: String,
@@ -28,11 +34,16 @@ pub(crate) struct TeamRow {
 pub(crate) struct TeamsListTemplate {
     pub app_name: &'static str,
     pub teams: Vec<TeamRow>,
+    pub can_manage: bool,
+    pub error: String,
+    pub success: String,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
 }
 
 /// One row for the team members table on the team detail page.
 pub(crate) struct TeamMemberRow {
+    pub user_id: String,
     pub display_name: String,
     pub email: String,
     pub avatar_url: String,
@@ -44,11 +55,71 @@ pub(crate) struct TeamMemberRow {
 #[template(path = "teams_show.html")]
 pub(crate) struct TeamsShowTemplate {
     pub app_name: &'static str,
+    pub team_id: String,
     pub team_name: String,
     pub project_count: i64,
+    pub projects: Vec<TeamProjectRow>,
     pub members: Vec<TeamMemberRow>,
     pub members_total: usize,
+    /// Org members not yet on the team (add-member picker; admins only).
+    pub addable_members: Vec<UserOption>,
+    /// The org's other teams (delete reassignment and project move targets).
+    pub other_teams: Vec<TeamOption>,
+    pub can_manage: bool,
+    pub error: String,
+    pub success: String,
     pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+/// A project on the team detail page.
+pub(crate) struct TeamProjectRow {
+    pub id: String,
+    pub title: String,
+    pub archived: bool,
+}
+
+/// An org member that can be added to the team.
+pub(crate) struct UserOption {
+    pub user_id: String,
+    pub label: String,
+}
+
+/// Another team of the org, for reassignment selects.
+pub(crate) struct TeamOption {
+    pub id: String,
+    pub name: String,
+}
+
+/// Query parameters for the teams pages (error/success feedback).
+#[derive(Debug, Deserialize)]
+pub(crate) struct TeamsPageQuery {
+    pub error: Option<String>,
+    pub success: Option<String>,
+}
+
+/// Create or rename form.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TeamNameForm {
+    pub name: String,
+}
+
+/// Delete form: where the team's projects go.
+#[derive(Debug, Deserialize)]
+pub(crate) struct DeleteTeamForm {
+    pub reassign_to_team_id: String,
+}
+
+/// Add-member form.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AddMemberForm {
+    pub user_id: String,
+}
+
+/// Move-project form.
+#[derive(Debug, Deserialize)]
+pub(crate) struct MoveProjectForm {
+    pub to_team_id: String,
 }
 
 fn initials_from_name(name: &str, email: &str) -> String {
@@ -72,13 +143,12 @@ fn initials_from_name(name: &str, email: &str) -> String {
 pub(crate) async fn list(
     AuthenticatedSession(session): AuthenticatedSession,
     State(state): State<AppState>,
+    Query(query): Query<TeamsPageQuery>,
 ) -> impl IntoResponse {
-    if tenant::require_org_member(&state.db, &session.user_id, &session.organization_id)
-        .await
-        .is_err()
-    {
-        return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response();
-    }
+    let role = match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(r) => r,
+        Err(_) => return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response(),
+    };
 
     let db_teams = match db::teams::find_by_organization(&state.db, &session.organization_id).await
     {
@@ -114,7 +184,11 @@ pub(crate) async fn list(
     let template = TeamsListTemplate {
         app_name: APP_NAME,
         teams,
+        can_manage: is_admin(role),
+        error: query.error.unwrap_or_default(),
+        success: query.success.unwrap_or_default(),
         current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
     };
     Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
 }
@@ -124,6 +198,7 @@ pub(crate) async fn show(
     AuthenticatedSession(session): AuthenticatedSession,
     State(state): State<AppState>,
     Path(team_id): Path<String>,
+    Query(query): Query<TeamsPageQuery>,
 ) -> impl IntoResponse {
     let user_id = match crate::app::domain::UserId::from_string(&session.user_id) {
         Ok(id) => id,
@@ -138,12 +213,11 @@ pub(crate) async fn show(
         Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
     };
 
-    if tenant::require_org_member(&state.db, &session.user_id, &team.organization_id)
-        .await
-        .is_err()
-    {
-        return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response();
-    }
+    let role = match tenant::require_org_member(&state.db, &session.user_id, &team.organization_id).await {
+        Ok(r) => r,
+        Err(_) => return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response(),
+    };
+    let can_manage = is_admin(role);
 
     let current_user_avatar_url =
         db::users::profile_image_url_for(&state.db, &user_id).await;
@@ -169,6 +243,7 @@ pub(crate) async fn show(
             let initials = initials_from_name(&display_name, &m.email);
             let avatar_url = m.profile_image_url.unwrap_or_default();
             TeamMemberRow {
+                user_id: m.user_id,
                 display_name,
                 email: m.email,
                 avatar_url,
@@ -178,19 +253,292 @@ pub(crate) async fn show(
         .collect();
     let members_total = members.len();
 
+    let projects = match db::projects::list_by_team(&state.db, &team_id).await {
+        Ok(p) => p
+            .into_iter()
+            .map(|p| TeamProjectRow {
+                archived: p.archived_at.is_some(),
+                id: p.id,
+                title: p.title,
+            })
+            .collect(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+
+    let (addable_members, other_teams) = if can_manage {
+        let org_id = match OrganizationId::from_string(&team.organization_id) {
+            Ok(id) => id,
+            Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid organization".to_string()).into_response(),
+        };
+        let (org_members, org_teams) = match tokio::try_join!(
+            db::organizations::list_members_with_email(&state.db, &org_id),
+            db::teams::find_by_organization(&state.db, &team.organization_id),
+        ) {
+            Ok(lists) => lists,
+            Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+        };
+        let addable = org_members
+            .into_iter()
+            .filter(|m| !members.iter().any(|row| row.user_id == m.user_id))
+            .map(|m| {
+                let name = db::users::display_name_from_parts(&m.first_name, &m.last_name);
+                let label = if name.is_empty() { m.email } else { format!("{} ({})", name, m.email) };
+                UserOption { user_id: m.user_id, label }
+            })
+            .collect();
+        let others = org_teams
+            .into_iter()
+            .filter(|t| t.id != team.id)
+            .map(|t| TeamOption { id: t.id, name: t.name })
+            .collect();
+        (addable, others)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
     let template = TeamsShowTemplate {
         app_name: APP_NAME,
+        team_id: team.id,
         team_name: team.name,
         project_count,
+        projects,
         members,
         members_total,
+        addable_members,
+        other_teams,
+        can_manage,
+        error: query.error.unwrap_or_default(),
+        success: query.success.unwrap_or_default(),
         current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
     };
     Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
 }
 
+fn is_admin(role: OrganizationRole) -> bool {
+    matches!(role, OrganizationRole::Owner | OrganizationRole::Admin)
+}
+
+fn not_found() -> Response {
+    (StatusCode::NOT_FOUND, "Not found".to_string()).into_response()
+}
+
+fn teams_redirect(key: &str, msg: &str) -> Response {
+    Redirect::to(&format!("/app/teams?{}={}", key, urlencoding::encode(msg))).into_response()
+}
+
+fn team_redirect(team_id: &str, key: &str, msg: &str) -> Response {
+    Redirect::to(&format!("/app/teams/{}?{}={}", team_id, key, urlencoding::encode(msg))).into_response()
+}
+
+/// Load a team the session user administers; anything else is a 404 so other orgs' teams don't leak.
+async fn team_for_admin(
+    state: &AppState,
+    session: &db::sessions::Session,
+    team_id: &str,
+) -> Result<db::teams::Team, Response> {
+    let team = match db::teams::find_by_id(&state.db, team_id).await {
+        Ok(Some(t)) if t.organization_id == session.organization_id => t,
+        Ok(_) => return Err(not_found()),
+        Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()),
+    };
+    match tenant::require_org_member(&state.db, &session.user_id, &team.organization_id).await {
+        Ok(role) if is_admin(role) => Ok(team),
+        _ => Err(not_found()),
+    }
+}
+
+/// Trimmed team name, or the message to show when it is unusable. Names are unique per org,
+/// ignoring case; `except_team_id` is the team being renamed.
+fn validate_team_name(
+    name: &str,
+    existing: &[db::teams::Team],
+    except_team_id: Option<&str>,
+) -> Result<String, &'static str> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Team name is required.");
+    }
+    if name.chars().count() > MAX_TEAM_NAME_LEN {
+        return Err("Team name is too long.");
+    }
+    let taken = existing
+        .iter()
+        .any(|t| Some(t.id.as_str()) != except_team_id && t.name.to_lowercase() == name.to_lowercase());
+    if taken {
+        return Err("A team with that name already exists.");
+    }
+    Ok(name.to_string())
+}
+
+/// POST /app/teams — Create a team (owners/admins only).
+pub(crate) async fn create(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Form(form): Form<TeamNameForm>,
+) -> Response {
+    match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(role) if is_admin(role) => {}
+        _ => return not_found(),
+    }
+    let existing = match db::teams::find_by_organization(&state.db, &session.organization_id).await {
+        Ok(t) => t,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let name = match validate_team_name(&form.name, &existing, None) {
+        Ok(n) => n,
+        Err(msg) => return teams_redirect("error", msg),
+    };
+
+    let team = db::teams::NewTeam {
+        id: Ulid::new().to_string(),
+        organization_id: session.organization_id.clone(),
+        name,
+    };
+    if db::teams::insert(&state.db, &team).await.is_err() {
+        return teams_redirect("error", "Failed to create team.");
+    }
+    team_redirect(&team.id, "success", "Team created.")
+}
+
+/// POST /app/teams/:team_id/rename — Rename a team (owners/admins only).
+pub(crate) async fn rename(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(team_id): Path<String>,
+    Form(form): Form<TeamNameForm>,
+) -> Response {
+    let team = match team_for_admin(&state, &session, &team_id).await {
+        Ok(t) => t,
+        Err(response) => return response,
+    };
+    let existing = match db::teams::find_by_organization(&state.db, &team.organization_id).await {
+        Ok(t) => t,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let name = match validate_team_name(&form.name, &existing, Some(&team.id)) {
+        Ok(n) => n,
+        Err(msg) => return team_redirect(&team.id, "error", msg),
+    };
+    match db::teams::rename(&state.db, &team.id, &team.organization_id, &name).await {
+        Ok(_) => team_redirect(&team.id, "success", "Team renamed."),
+        Err(_) => team_redirect(&team.id, "error", "Failed to rename team."),
+    }
+}
+
+/// POST /app/teams/:team_id/delete — Delete a team, moving its projects to another team of the org
+/// (owners/admins only). The last team can't be deleted since new projects need one.
+pub(crate) async fn delete(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(team_id): Path<String>,
+    Form(form): Form<DeleteTeamForm>,
+) -> Response {
+    let team = match team_for_admin(&state, &session, &team_id).await {
+        Ok(t) => t,
+        Err(response) => return response,
+    };
+    let target = match db::teams::find_by_id(&state.db, &form.reassign_to_team_id).await {
+        Ok(Some(t)) if t.organization_id == team.organization_id && t.id != team.id => t,
+        Ok(_) => return team_redirect(&team.id, "error", "Choose another team to take over this team's projects."),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+
+    let result = async {
+        let mut tx = state.db.begin().await?;
+        db::projects::reassign_team(&mut *tx, &team.id, &target.id).await?;
+        db::teams::delete(&mut *tx, &team.id).await?;
+        tx.commit().await
+    }
+    .await;
+    match result {
+        Ok(()) => teams_redirect(
+            "success",
+            &format!("Team deleted. Its projects moved to {}.", target.name),
+        ),
+        Err(_) => team_redirect(&team.id, "error", "Failed to delete team."),
+    }
+}
+
+/// POST /app/teams/:team_id/members — Add an org member to the team (owners/admins only).
+pub(crate) async fn add_member(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path(team_id): Path<String>,
+    Form(form): Form<AddMemberForm>,
+) -> Response {
+    let team = match team_for_admin(&state, &session, &team_id).await {
+        Ok(t) => t,
+        Err(response) => return response,
+    };
+    let user_id = match UserId::from_string(&form.user_id) {
+        Ok(id) => id,
+        Err(_) => return team_redirect(&team.id, "error", "Choose a member of your organization."),
+    };
+    if tenant::require_org_member(&state.db, &user_id.as_str(), &team.organization_id)
+        .await
+        .is_err()
+    {
+        return team_redirect(&team.id, "error", "Choose a member of your organization.");
+    }
+    match db::team_members::add_member(&state.db, &team.id, &user_id).await {
+        Ok(()) => team_redirect(&team.id, "success", "Member added."),
+        Err(_) => team_redirect(&team.id, "error", "Failed to add member."),
+    }
+}
+
+/// POST /app/teams/:team_id/members/:user_id/remove — Take a user off the team (owners/admins only).
+pub(crate) async fn remove_member(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path((team_id, user_id)): Path<(String, String)>,
+) -> Response {
+    let team = match team_for_admin(&state, &session, &team_id).await {
+        Ok(t) => t,
+        Err(response) => return response,
+    };
+    match db::team_members::remove_member(&state.db, &team.id, &user_id).await {
+        Ok(true) => team_redirect(&team.id, "success", "Member removed."),
+        Ok(false) => team_redirect(&team.id, "error", "That user is not on this team."),
+        Err(_) => team_redirect(&team.id, "error", "Failed to remove member."),
+    }
+}
+
+/// POST /app/teams/:team_id/projects/:project_id/move — Move one of the team's projects to another
+/// team of the org (owners/admins only).
+pub(crate) async fn move_project(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Path((team_id, project_id)): Path<(String, String)>,
+    Form(form): Form<MoveProjectForm>,
+) -> Response {
+    let team = match team_for_admin(&state, &session, &team_id).await {
+        Ok(t) => t,
+        Err(response) => return response,
+    };
+    match db::projects::find_by_id_and_org(&state.db, &project_id, &team.organization_id).await {
+        Ok(Some(p)) if p.team_id.as_deref() == Some(team.id.as_str()) => {}
+        Ok(_) => return not_found(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    }
+    let target = match db::teams::find_by_id(&state.db, &form.to_team_id).await {
+        Ok(Some(t)) if t.organization_id == team.organization_id && t.id != team.id => t,
+        Ok(_) => return team_redirect(&team.id, "error", "Choose another team."),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    match db::projects::set_team(&state.db, &project_id, &team.organization_id, &target.id).await {
+        Ok(_) => team_redirect(&team.id, "success", &format!("Project moved to {}.", target.name)),
+        Err(_) => team_redirect(&team.id, "error", "Failed to move project."),
+    }
+}
+
 pub(crate) fn routes() -> Router<AppState> {
     Router::new()
-        .route("/app/teams", get(list))
+        .route("/app/teams", get(list).post(create))
         .route("/app/teams/:team_id", get(show))
+        .route("/app/teams/:team_id/rename", post(rename))
+        .route("/app/teams/:team_id/delete", post(delete))
+        .route("/app/teams/:team_id/members", post(add_member))
+        .route("/app/teams/:team_id/members/:user_id/remove", post(remove_member))
+        .route("/app/teams/:team_id/projects/:project_id/move", post(move_project))
 }
@@ -0,0 +1,367 @@
+//! Workload report: open estimated work per assignee across the organisation's projects, against
+//! each member's weekly capacity (stored per team membership).
+//!
+//! - GET /app/workload?team_id= — HTML report (whole organisation, or one team's members)
+//! - GET /api/workload?team_id= — the same report as JSON
+//! - PATCH /api/teams/:team_id/members/:user_id — set a member's weekly capacity (owners/admins)
+
+use std::collections::HashMap;
+
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse},
+    routing::{get, patch},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{
+    db,
+    domain::{OrganizationId, OrganizationRole, UserId},
+    error::AppError,
+    features::projects::format::format_estimated_minutes,
+    session::{ApiAuthenticatedSession, AuthenticatedSession},
+    tenant, AppState, APP_NAME,
+};
+
+/// Upper bound for a weekly capacity: every minute of the week.
+const MAX_WEEKLY_CAPACITY_MINUTES: i64 = 7 * 24 * 60;
+
+/// Query string for the report.
+#[derive(Debug, Default, Deserialize)]
+pub struct WorkloadQuery {
+    /// Limit the report to this team's members (and use their capacity on that team).
+    pub team_id: Option<String>,
+}
+
+/// One member's load.
+#[derive(Debug, Serialize)]
+pub struct WorkloadMember {
+    pub user_id: String,
+    pub name: String,
+    pub email: String,
+    pub open_count: i64,
+    pub open_minutes: i64,
+    /// None when the member is on no team in the organisation.
+    pub weekly_capacity_minutes: Option<i64>,
+    /// Open minutes as a percentage of capacity; None without a (non-zero) capacity.
+    pub utilization_percent: Option<i64>,
+    pub over_capacity: bool,
+}
+
+/// Response for the workload report.
+#[derive(Debug, Serialize)]
+pub struct WorkloadResponse {
+    pub team_id: Option<String>,
+    /// Most loaded first.
+    pub members: Vec<WorkloadMember>,
+    pub unassigned_count: i64,
+    pub unassigned_minutes: i64,
+}
+
+fn member_load(
+    user_id: String,
+    name: String,
+    email: String,
+    load: Option<&db::nodes::AssigneeLoad>,
+    weekly_capacity_minutes: Option<i64>,
+) -> WorkloadMember {
+    let open_count = load.map_or(0, |l| l.open_count);
+    let open_minutes = load.map_or(0, |l| l.open_minutes);
+    WorkloadMember {
+        user_id,
+        name,
+        email,
+        open_count,
+        open_minutes,
+        weekly_capacity_minutes,
+        utilization_percent: weekly_capacity_minutes
+            .filter(|c| *c > 0)
+            .map(|c| open_minutes * 100 / c),
+        over_capacity: weekly_capacity_minutes.is_some_and(|c| open_minutes > c),
+    }
+}
+
+fn display_name_or_email(first_name: &str, last_name: &str, email: &str) -> String {
+    let name = db::users::display_name_from_parts(first_name, last_name);
+    if name.is_empty() {
+        email.to_string()
+    } else {
+        name
+    }
+}
+
+/// Build the report for the organisation, or for one of its teams.
+async fn build_report(
+    pool: &sqlx::SqlitePool,
+    organization_id: &str,
+    team_id: Option<&str>,
+) -> Result<WorkloadResponse, AppError> {
+    let loads = db::nodes::open_load_by_assignee_in_org(
+        pool,
+        organization_id,
+        db::task_statuses::DONE_STATUS_ID,
+    )
+    .await?;
+    let load_by_user: HashMap<Option<&str>, &db::nodes::AssigneeLoad> =
+        loads.iter().map(|l| (l.user_id.as_deref(), l)).collect();
+
+    let mut members: Vec<WorkloadMember> = match team_id {
+        Some(team_id) => {
+            let team = db::teams::find_by_id(pool, team_id)
+                .await?
+                .filter(|t| t.organization_id == organization_id)
+                .ok_or_else(|| AppError::NotFound("Team not found".to_string()))?;
+            db::team_members::list_members_with_user_details(pool, &team.id)
+                .await?
+                .into_iter()
+                .map(|m| {
+                    let load = load_by_user.get(&Some(m.user_id.as_str())).copied();
+                    let name = display_name_or_email(&m.first_name, &m.last_name, &m.email);
+                    member_load(m.user_id, name, m.email, load, Some(m.weekly_capacity_minutes))
+                })
+                .collect()
+        }
+        None => {
+            let org_id = OrganizationId::from_string(organization_id)
+                .map_err(|_| AppError::NotFound("Not found".to_string()))?;
+            let capacities: HashMap<String, i64> =
+                db::team_members::list_capacity_for_org(pool, organization_id)
+                    .await?
+                    .into_iter()
+                    .map(|c| (c.user_id, c.weekly_capacity_minutes))
+                    .collect();
+            db::organizations::list_members_with_email(pool, &org_id)
+                .await?
+                .into_iter()
+                .map(|m| {
+                    let load = load_by_user.get(&Some(m.user_id.as_str())).copied();
+                    let capacity = capacities.get(&m.user_id).copied();
+                    let name = display_name_or_email(&m.first_name, &m.last_name, &m.email);
+                    member_load(m.user_id, name, m.email, load, capacity)
+                })
+                .collect()
+        }
+    };
+    members.sort_by(|a, b| b.open_minutes.cmp(&a.open_minutes).then_with(|| a.name.cmp(&b.name)));
+
+    let unassigned = load_by_user.get(&None);
+    Ok(WorkloadResponse {
+        team_id: team_id.map(String::from),
+        members,
+        unassigned_count: unassigned.map_or(0, |l| l.open_count),
+        unassigned_minutes: unassigned.map_or(0, |l| l.open_minutes),
+    })
+}
+
+/// GET /api/workload?team_id= — Workload report as JSON.
+pub async fn get_workload(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Query(query): Query<WorkloadQuery>,
+) -> Result<Json<WorkloadResponse>, AppError> {
+    tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await?;
+    let team_id = query.team_id.as_deref().filter(|t| !t.is_empty());
+    Ok(Json(build_report(&state.db, &session.organization_id, team_id).await?))
+}
+
+/// Request body for setting a member's capacity.
+#[derive(Debug, Deserialize)]
+pub struct UpdateCapacityRequest {
+    pub weekly_capacity_minutes: i64,
+}
+
+/// Response after setting a member's capacity.
+#[derive(Debug, Serialize)]
+pub struct UpdateCapacityResponse {
+    pub weekly_capacity_minutes: i64,
+}
+
+/// PATCH /api/teams/:team_id/members/:user_id — Set a member's weekly capacity on a team.
+pub async fn update_capacity(
+    ApiAuthenticatedSession(session): ApiAuthenticatedSession,
+    State(state): State<AppState>,
+    Path((team_id, user_id)): Path<(String, String)>,
+    Json(body): Json<UpdateCapacityRequest>,
+) -> Result<Json<UpdateCapacityResponse>, AppError> {
+    if !(0..=MAX_WEEKLY_CAPACITY_MINUTES).contains(&body.weekly_capacity_minutes) {
+        return Err(AppError::Validation(format!(
+            "Weekly capacity must be between 0 and {} minutes",
+            MAX_WEEKLY_CAPACITY_MINUTES
+        )));
+    }
+    let team = db::teams::find_by_id(&state.db, &team_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Team not found".to_string()))?;
+    let role = tenant::require_org_member(&state.db, &session.user_id, &team.organization_id).await?;
+    if !matches!(role, OrganizationRole::Owner | OrganizationRole::Admin) {
+        return Err(AppError::NotFound("Team not found".to_string()));
+    }
+    if !db::team_members::update_capacity(&state.db, &team.id, &user_id, body.weekly_capacity_minutes).await? {
+        return Err(AppError::NotFound("Team member not found".to_string()));
+    }
+    Ok(Json(UpdateCapacityResponse {
+        weekly_capacity_minutes: body.weekly_capacity_minutes,
+    }))
+}
+
+/// One member row in the HTML report.
+pub struct WorkloadRow {
+    pub user_id: String,
+    pub name: String,
+    pub email: String,
+    pub open_count: i64,
+    pub load_display: String,
+    pub capacity_display: String,
+    /// Capacity in hours for the edit field.
+    pub capacity_hours: String,
+    /// Bar width, capped at 100.
+    pub bar_percent: i64,
+    pub utilization_display: String,
+    pub over_capacity: bool,
+}
+
+/// Team option for the report's team picker.
+pub struct TeamOption {
+    pub id: String,
+    pub name: String,
+    pub selected: bool,
+}
+
+/// Workload report template.
+#[derive(Template)]
+#[template(path = "workload.html")]
+pub struct WorkloadTemplate {
+    pub app_name: &'static str,
+    pub teams: Vec<TeamOption>,
+    pub team_id: String,
+    pub rows: Vec<WorkloadRow>,
+    pub over_count: usize,
+    pub unassigned_count: i64,
+    pub unassigned_display: String,
+    /// Owners/admins viewing a single team can edit capacities.
+    pub can_edit_capacity: bool,
+    pub current_user_avatar_url: String,
+    pub csrf_token: String,
+}
+
+/// GET /app/workload?team_id= — Workload report page.
+pub async fn show(
+    AuthenticatedSession(session): AuthenticatedSession,
+    State(state): State<AppState>,
+    Query(query): Query<WorkloadQuery>,
+) -> impl IntoResponse {
+    let role = match tenant::require_org_member(&state.db, &session.user_id, &session.organization_id).await {
+        Ok(r) => r,
+        Err(_) => return (StatusCode::NOT_FOUND, "Not found".to_string()).into_response(),
+    };
+    let team_id = query.team_id.as_deref().filter(|t| !t.is_empty());
+    let report = match build_report(&state.db, &session.organization_id, team_id).await {
+        Ok(r) => r,
+        Err(AppError::NotFound(msg)) => return (StatusCode::NOT_FOUND, msg).into_response(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+    let teams = match db::teams::find_by_organization(&state.db, &session.organization_id).await {
+        Ok(t) => t,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response(),
+    };
+
+    let rows: Vec<WorkloadRow> = report
+        .members
+        .into_iter()
+        .map(|m| WorkloadRow {
+            load_display: format_estimated_minutes(m.open_minutes),
+            capacity_display: m
+                .weekly_capacity_minutes
+                .map(format_estimated_minutes)
+                .unwrap_or_else(|| "—".to_string()),
+            capacity_hours: m
+                .weekly_capacity_minutes
+                .map(|c| format!("{}", c as f64 / 60.0))
+                .unwrap_or_default(),
+            bar_percent: match (m.utilization_percent, m.over_capacity) {
+                (Some(p), _) => p.min(100),
+                (None, true) => 100,
+                (None, false) => 0,
+            },
+            utilization_display: m
+                .utilization_percent
+                .map(|p| format!("{}%", p))
+                .unwrap_or_else(|| "—".to_string()),
+            over_capacity: m.over_capacity,
+            user_id: m.user_id,
+            name: m.name,
+            email: m.email,
+            open_count: m.open_count,
+        })
+        .collect();
+
+    let user_id = match UserId::from_string(&session.user_id) {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid session".to_string()).into_response(),
+    };
+    let current_user_avatar_url = db::users::profile_image_url_for(&state.db, &user_id).await;
+
+    let template = WorkloadTemplate {
+        app_name: APP_NAME,
+        teams: teams
+            .into_iter()
+            .map(|t| TeamOption {
+                selected: Some(t.id.as_str()) == team_id,
+                id: t.id,
+                name: t.name,
+            })
+            .collect(),
+        team_id: team_id.unwrap_or_default().to_string(),
+        over_count: rows.iter().filter(|r| r.over_capacity).count(),
+        rows,
+        unassigned_count: report.unassigned_count,
+        unassigned_display: format_estimated_minutes(report.unassigned_minutes),
+        can_edit_capacity: team_id.is_some() && matches!(role, OrganizationRole::Owner | OrganizationRole::Admin),
+        current_user_avatar_url,
+        csrf_token: session.csrf_token.clone(),
+    };
+    Html(template.render().unwrap_or_else(|_| "Template error".to_string())).into_response()
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/app/workload", get(show))
+        .route("/api/workload", get(get_workload))
+        .route("/api/teams/:team_id/members/:user_id", patch(update_capacity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(minutes: i64) -> db::nodes::AssigneeLoad {
+        db::nodes::AssigneeLoad { user_id: Some("u".to_string()), open_count: 1, open_minutes: minutes }
+    }
+
+    fn row(minutes: Option<i64>, capacity: Option<i64>) -> WorkloadMember {
+        let l = minutes.map(load);
+        member_load("u".into(), "U".into(), "u@example.com".into(), l.as_ref(), capacity)
+    }
+
+    #[test]
+    fn flags_over_capacity_only_above_the_limit() {
+        assert!(!row(Some(2400), Some(2400)).over_capacity);
+        assert!(row(Some(2401), Some(2400)).over_capacity);
+        assert_eq!(row(Some(3600), Some(2400)).utilization_percent, Some(150));
+    }
+
+    #[test]
+    fn zero_or_missing_capacity() {
+        let zero = row(Some(60), Some(0));
+        assert!(zero.over_capacity);
+        assert_eq!(zero.utilization_percent, None);
+        let none = row(Some(60), None);
+        assert!(!none.over_capacity);
+        assert_eq!(none.utilization_percent, None);
+        let idle = row(None, Some(2400));
+        assert_eq!((idle.open_count, idle.utilization_percent), (0, Some(0)));
+    }
+}
@@ -17,6 +17,13 @@ impl EmailSender for ConsoleMailer {
             body = %message.body,
             "Email sent (console)"
         );
+        if let Some(html) = &message.html_body {
+            tracing::info!(
+                to = %message.to.as_str(),
+                html = %html,
+                "Email HTML part (console)"
+            );
+        }
         Ok(())
     }
 }
\ No newline at end of file
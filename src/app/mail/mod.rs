@@ -5,7 +5,10 @@ use std::sync::Arc;
 pub struct EmailMessage {
     pub to: crate::app::domain::Email,
     pub subject: String,
+    /// Plain-text body (always present).
     pub body: String,
+    /// HTML alternative; when set, the message is sent as multipart/alternative.
+    pub html_body: Option<String>,
     pub from: String,
 }
 
@@ -21,13 +24,40 @@ impl EmailMessage {
             to,
             subject,
             body,
+            html_body: None,
+            from: from.into(),
+        }
+    }
+
+    /// Create a multipart message from rendered template content.
+    pub fn from_content(
+        to: crate::app::domain::Email,
+        content: templates::EmailContent,
+        from: impl Into<String>,
+    ) -> Self {
+        Self {
+            to,
+            subject: content.subject,
+            body: content.text,
+            html_body: Some(content.html),
             from: from.into(),
         }
     }
 
     /// Create a new email message with custom from address.
     pub fn with_from(to: crate::app::domain::Email, subject: String, body: String, from: String) -> Self {
-        Self { to, subject, body, from }
+        Self { to, subject, body, html_body: None, from }
+    }
+}
+
+/// Organization name for the email header; falls back to the app name.
+pub async fn org_brand(pool: &sqlx::SqlitePool, organization_id: &str) -> String {
+    let Ok(org_id) = crate::app::domain::OrganizationId::from_string(organization_id) else {
+        return crate::app::APP_NAME.to_string();
+    };
+    match crate::app::db::organizations::find_by_id(pool, &org_id).await {
+        Ok(Some(org)) => org.name,
+        _ => crate::app::APP_NAME.to_string(),
     }
 }
 
@@ -46,16 +76,22 @@ pub enum EmailError {
     Smtp(String),
     #[error("Send error: {0}")]
     Send(String),
+    #[error("Template error: {0}")]
+    Template(String),
 }
 
 // Re-export implementations
 pub use console::ConsoleMailer;
+pub use outbox::OutboxMailer;
 pub use smtp::SmtpMailer;
 
 mod console;
+pub mod outbox;
 mod smtp;
+pub mod templates;
 
-/// Build the email sender from config.
+/// Build the delivery transport from config. Handlers send through [`OutboxMailer`]; this is what the
+/// outbox dispatcher delivers with.
 pub fn from_config(config: &crate::app::config::Config) -> Result<Arc<dyn EmailSender>, EmailError> {
     match config.mail_adapter.as_str() {
         "console" => Ok(Arc::new(ConsoleMailer)),
@@ -0,0 +1,137 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use time::OffsetDateTime;
+
+use super::{EmailError, EmailMessage, EmailSender};
+use crate::app::{db, domain::Email};
+
+/// Attempts before a message is dead-lettered.
+pub const MAX_ATTEMPTS: i64 = 8;
+
+/// Delay before the first retry; doubles on each further failure.
+const BASE_BACKOFF_SECS: i64 = 30;
+
+/// Upper bound on the retry delay.
+const MAX_BACKOFF_SECS: i64 = 60 * 60;
+
+/// How often the dispatcher polls for due messages.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Messages delivered per poll.
+const BATCH_SIZE: i64 = 20;
+
+/// Email sender that writes to the `email_outbox` table instead of delivering.
+/// Handlers get a fast, durable `send`; [`run_dispatcher`] does the actual delivery.
+#[derive(Debug, Clone)]
+pub struct OutboxMailer {
+    pool: SqlitePool,
+}
+
+impl OutboxMailer {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EmailSender for OutboxMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        let email = db::email_outbox::NewOutboxEmail {
+            id: ulid::Ulid::new().to_string(),
+            to_email: message.to.as_str().to_string(),
+            from_email: message.from.clone(),
+            subject: message.subject.clone(),
+            body: message.body.clone(),
+            html_body: message.html_body.clone(),
+        };
+        db::email_outbox::insert(&self.pool, &email)
+            .await
+            .map_err(|e| EmailError::Send(format!("Failed to enqueue email: {}", e)))
+    }
+}
+
+/// Delay before the next attempt after `attempts` failures (1-based), capped at an hour.
+pub fn backoff_secs(attempts: i64) -> i64 {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    BASE_BACKOFF_SECS
+        .saturating_mul(2_i64.pow(exponent))
+        .min(MAX_BACKOFF_SECS)
+}
+
+/// Deliver every message due at `now` through `transport`. Returns how many were sent.
+pub async fn dispatch_due(
+    pool: &SqlitePool,
+    transport: &dyn EmailSender,
+    now: i64,
+) -> Result<usize, sqlx::Error> {
+    let due = db::email_outbox::find_due(pool, now, BATCH_SIZE).await?;
+    let mut sent = 0;
+
+    for email in due {
+        let result = match Email::new(email.to_email.clone()) {
+            Ok(to) => {
+                let mut message = EmailMessage::with_from(
+                    to,
+                    email.subject.clone(),
+                    email.body.clone(),
+                    email.from_email.clone(),
+                );
+                message.html_body = email.html_body.clone();
+                transport.send(&message).await
+            }
+            Err(_) => Err(EmailError::Send("Invalid recipient address".to_string())),
+        };
+
+        match result {
+            Ok(()) => {
+                db::email_outbox::mark_sent(pool, &email.id, now).await?;
+                sent += 1;
+            }
+            Err(e) => {
+                let attempts = email.attempts + 1;
+                let error = e.to_string();
+                if attempts >= MAX_ATTEMPTS {
+                    tracing::error!(id = %email.id, to = %email.to_email, error = %error, "Email dead-lettered");
+                    db::email_outbox::mark_dead(pool, &email.id, &error).await?;
+                } else {
+                    tracing::warn!(id = %email.id, attempts, error = %error, "Email delivery failed; will retry");
+                    db::email_outbox::mark_retry(pool, &email.id, now.saturating_add(backoff_secs(attempts)), &error).await?;
+                }
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+/// Poll the outbox forever, delivering through `transport`. Spawn once at startup.
+pub async fn run_dispatcher(pool: SqlitePool, transport: Arc<dyn EmailSender>) {
+    loop {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        if let Err(e) = dispatch_due(&pool, transport.as_ref(), now).await {
+            tracing::error!(error = %e, "Email dispatcher failed to read outbox");
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_from_base() {
+        assert_eq!(backoff_secs(1), 30);
+        assert_eq!(backoff_secs(2), 60);
+        assert_eq!(backoff_secs(3), 120);
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        assert_eq!(backoff_secs(MAX_ATTEMPTS), 3600);
+        assert_eq!(backoff_secs(100), 3600);
+    }
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use lettre::{
-    message::{header::ContentType, Mailbox, Message},
+    message::{header::ContentType, Mailbox, Message, MultiPart},
     transport::smtp::authentication::Credentials,
     AsyncSmtpTransport, Tokio1Executor,
 };
@@ -54,13 +54,20 @@ impl EmailSender for SmtpMailer {
         let to: Mailbox = message.to.as_str().parse()
             .map_err(|e| EmailError::Config(format!("Invalid to address '{}': {}", message.to.as_str(), e)))?;
 
-        let email = Message::builder()
+        let builder = Message::builder()
             .from(from)
             .to(to)
-            .subject(&message.subject)
-            .header(ContentType::TEXT_PLAIN)
-            .body(message.body.clone())
-            .map_err(|e| EmailError::Send(format!("Failed to build email message: {}", e)))?;
+            .subject(&message.subject);
+        let email = match &message.html_body {
+            Some(html) => builder.multipart(MultiPart::alternative_plain_html(
+                message.body.clone(),
+                html.clone(),
+            )),
+            None => builder
+                .header(ContentType::TEXT_PLAIN)
+                .body(message.body.clone()),
+        }
+        .map_err(|e| EmailError::Send(format!("Failed to build email message: {}", e)))?;
 
         lettre::AsyncTransport::send(&self.transport, email).await
             .map(|_| ())
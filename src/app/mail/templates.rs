@@ -0,0 +1,193 @@
+//! Askama email templates. Each email renders an HTML part (`mail/*.html`) and a plain-text part
+//! (`mail/*.txt`) on a shared layout whose header carries the sending organization's name.
+
+use askama::Template;
+
+use super::EmailError;
+use crate::app::APP_NAME;
+
+/// Rendered subject and both bodies, ready for [`super::EmailMessage::from_content`].
+#[derive(Debug, Clone)]
+pub struct EmailContent {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+#[derive(Template)]
+#[template(path = "mail/verification.html")]
+struct VerificationHtml<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "mail/verification.txt")]
+struct VerificationText<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "mail/password_reset.html")]
+struct PasswordResetHtml<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "mail/password_reset.txt")]
+struct PasswordResetText<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "mail/invite.html")]
+struct InviteHtml<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "mail/invite.txt")]
+struct InviteText<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "mail/notification.html")]
+struct NotificationHtml<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    headline: &'a str,
+    detail: &'a str,
+    url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "mail/notification.txt")]
+struct NotificationText<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    headline: &'a str,
+    detail: &'a str,
+    url: &'a str,
+}
+
+/// One project's section of a digest email.
+#[derive(Debug, Clone)]
+pub struct DigestProject {
+    pub title: String,
+    pub url: String,
+    pub completed: i64,
+    pub blocked: i64,
+    pub newly_blocked: i64,
+    /// Titles of open nodes assigned to the recipient.
+    pub assigned: Vec<String>,
+    /// Formatted estimate of the work left (e.g. "2 h 30 min").
+    pub remaining: String,
+}
+
+#[derive(Template)]
+#[template(path = "mail/digest.html")]
+struct DigestHtml<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    headline: &'a str,
+    projects: &'a [DigestProject],
+}
+
+#[derive(Template)]
+#[template(path = "mail/digest.txt")]
+struct DigestText<'a> {
+    brand: &'a str,
+    app_name: &'a str,
+    headline: &'a str,
+    projects: &'a [DigestProject],
+}
+
+fn render_error(e: askama::Error) -> EmailError {
+    EmailError::Template(e.to_string())
+}
+
+/// Email verification link. Sent before the account belongs to anyone, so branded as the app.
+pub fn verification(url: &str) -> Result<EmailContent, EmailError> {
+    Ok(EmailContent {
+        subject: "Verify your email".to_string(),
+        html: VerificationHtml { brand: APP_NAME, app_name: APP_NAME, url }.render().map_err(render_error)?,
+        text: VerificationText { brand: APP_NAME, app_name: APP_NAME, url }.render().map_err(render_error)?,
+    })
+}
+
+/// Password reset link, branded with the user's organization.
+pub fn password_reset(brand: &str, url: &str) -> Result<EmailContent, EmailError> {
+    Ok(EmailContent {
+        subject: format!("Reset your {} password", APP_NAME),
+        html: PasswordResetHtml { brand, app_name: APP_NAME, url }.render().map_err(render_error)?,
+        text: PasswordResetText { brand, app_name: APP_NAME, url }.render().map_err(render_error)?,
+    })
+}
+
+/// Organization invite; `org_name` is both the brand and the subject.
+pub fn invite(org_name: &str, url: &str) -> Result<EmailContent, EmailError> {
+    Ok(EmailContent {
+        subject: format!("You're invited to join {}", org_name),
+        html: InviteHtml { brand: org_name, app_name: APP_NAME, url }.render().map_err(render_error)?,
+        text: InviteText { brand: org_name, app_name: APP_NAME, url }.render().map_err(render_error)?,
+    })
+}
+
+/// Activity notification (assignment, slot activity, unblocked work) linking to a project.
+pub fn notification(
+    brand: &str,
+    subject: String,
+    headline: &str,
+    detail: &str,
+    url: &str,
+) -> Result<EmailContent, EmailError> {
+    Ok(EmailContent {
+        subject,
+        html: NotificationHtml { brand, app_name: APP_NAME, headline, detail, url }.render().map_err(render_error)?,
+        text: NotificationText { brand, app_name: APP_NAME, headline, detail, url }.render().map_err(render_error)?,
+    })
+}
+
+/// Periodic summary of the recipient's projects; `period` is "daily" or "weekly".
+pub fn digest(brand: &str, period: &str, projects: &[DigestProject]) -> Result<EmailContent, EmailError> {
+    let headline = format!("Your {} digest", period);
+    Ok(EmailContent {
+        subject: format!("{} for {}", headline, brand),
+        html: DigestHtml { brand, app_name: APP_NAME, headline: &headline, projects }.render().map_err(render_error)?,
+        text: DigestText { brand, app_name: APP_NAME, headline: &headline, projects }.render().map_err(render_error)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invite_is_branded_with_org_name_in_both_parts() {
+        let content = invite("Acme & Co", "https://example.com/accept?token=abc").unwrap();
+        assert_eq!(content.subject, "You're invited to join Acme & Co");
+        assert!(content.html.contains("Acme &amp; Co"));
+        assert!(content.html.contains("on Boardtask"));
+        assert!(content.text.starts_with("Acme & Co on Boardtask"));
+        assert!(content.text.contains("https://example.com/accept?token=abc"));
+    }
+
+    #[test]
+    fn app_branded_email_omits_org_suffix() {
+        let content = verification("https://example.com/verify").unwrap();
+        assert!(content.text.starts_with("Boardtask\n"));
+        assert!(!content.html.contains(" on Boardtask"));
+    }
+}
@@ -1,4 +1,4 @@
-use axum::Router;
+use axum::{middleware, Router};
 use sqlx::SqlitePool;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -16,30 +16,47 @@ pub type ResendCooldown = Arc<std::sync::RwLock<HashMap<String, Instant>>>;
 pub struct AppState {
     pub db: SqlitePool,
     pub mail: Arc<dyn crate::app::mail::EmailSender>,
+    pub dns: Arc<dyn crate::app::dns::TxtResolver>,
+    pub storage: Arc<dyn crate::app::storage::BlobStorage>,
+    pub blob_lock: crate::app::storage::BlobLock,
     pub config: crate::app::config::Config,
     pub resend_cooldown: ResendCooldown,
 }
 
 /// App routes (auth, dashboard). Merged with site routes in main.rs.
-pub fn routes(_state: AppState) -> Router<AppState> {
-    Router::new()
-        .merge(features::auth::routes())
+/// Session-authenticated routes sit behind the CSRF middleware; public auth and invite pages do not.
+pub fn routes(state: AppState) -> Router<AppState> {
+    let authenticated = Router::new()
+        .merge(features::auth::logout::routes())
         .merge(features::dashboard::routes())
+        .merge(features::admin::routes())
         .merge(features::account::routes())
+        .merge(features::account_deletion::routes())
         .merge(features::integrations::routes())
-        .merge(features::invites::routes())
         .merge(features::organization::routes())
+        .merge(features::invites::authenticated_routes())
         .merge(features::teams::routes())
         .merge(features::projects::routes())
         .merge(features::projects::api_routes())
         .merge(features::graph::api::routes())
+        .merge(features::search::routes())
+        .merge(features::workload::routes())
+        .layer(middleware::from_fn_with_state(state, csrf::require_csrf_token));
+
+    Router::new()
+        .merge(features::auth::routes())
+        .merge(features::invites::routes())
+        .merge(authenticated)
 }
 
 pub mod config;
+pub mod csrf;
 pub mod domain;
 pub mod db;
+pub mod dns;
 pub mod single_writer;
 pub mod session;
+pub mod storage;
 pub mod tenant;
 pub mod error;
 pub mod features;
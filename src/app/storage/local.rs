@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use super::{is_valid_key, BlobStorage, StorageError};
+
+/// Keeps blobs on the local filesystem under `root/<first two key chars>/<key>`.
+#[derive(Debug)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, StorageError> {
+        if !is_valid_key(key) {
+            return Err(StorageError::InvalidKey(key.to_string()));
+        }
+        Ok(self.root.join(&key[..2]).join(key))
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStorage for LocalStorage {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        let dir = path.parent().expect("blob path has a parent");
+        tokio::fs::create_dir_all(dir).await?;
+        // Always write, even if the key exists: the copy there may be mid-removal by the orphan
+        // sweep. Write under a temporary name and rename, so a crash never leaves a truncated blob
+        // under its real key.
+        let tmp = dir.join(format!("{}.{}.tmp", key, ulid::Ulid::new()));
+        tokio::fs::write(&tmp, bytes).await?;
+        if let Err(e) = tokio::fs::rename(&tmp, &path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::storage::content_key;
+
+    #[tokio::test]
+    async fn stores_reads_and_deletes_by_content_key() {
+        let root = std::env::temp_dir().join(format!("boardtask-storage-{}", ulid::Ulid::new()));
+        let storage = LocalStorage::new(&root);
+        let bytes = b"spec v1";
+        let key = content_key(bytes);
+
+        storage.put(&key, bytes).await.unwrap();
+        // A second put rewrites the blob rather than trusting what is on disk.
+        std::fs::write(root.join(&key[..2]).join(&key), b"spec").unwrap();
+        storage.put(&key, bytes).await.unwrap();
+        assert!(root.join(&key[..2]).join(&key).is_file());
+        assert_eq!(storage.get(&key).await.unwrap().as_deref(), Some(&bytes[..]));
+
+        storage.delete(&key).await.unwrap();
+        storage.delete(&key).await.unwrap();
+        assert!(storage.get(&key).await.unwrap().is_none());
+        assert!(matches!(storage.get("../../etc/passwd").await, Err(StorageError::InvalidKey(_))));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
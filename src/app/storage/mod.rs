@@ -0,0 +1,104 @@
+//! Blob storage for node attachments. Blobs are content-addressed: the key is the SHA-256 of the
+//! bytes, so identical uploads share one stored copy. Handlers go through [`BlobStorage`] on
+//! `AppState`; which attachments point at which blob lives in the database.
+
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+
+pub use local::LocalStorage;
+
+mod local;
+
+/// Orders blob writes against the orphan sweep. Uploads hold it shared from recording a blob until
+/// its bytes are stored; the sweep holds it exclusively while it removes one, so it can never delete
+/// bytes an upload has just recorded. In-process is enough: one instance owns the database.
+pub type BlobLock = Arc<tokio::sync::RwLock<()>>;
+
+/// Stores and fetches blobs by content key. Swappable per environment.
+#[async_trait::async_trait]
+pub trait BlobStorage: Send + Sync {
+    /// Store `bytes` under `key`, replacing whatever is there. Readers never see a partial blob.
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), StorageError>;
+    /// The bytes stored under `key`, or None if there are none.
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError>;
+    /// Remove `key`. Removing a missing key is not an error.
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+}
+
+/// Errors that can occur while reading or writing blobs.
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Content key for `bytes`: lowercase hex SHA-256.
+pub fn content_key(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Whether `key` looks like a [`content_key`]; anything else is refused before touching storage.
+pub fn is_valid_key(key: &str) -> bool {
+    key.len() == 64 && key.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// MIME type from the leading bytes. The client's declared type is never trusted, so a file can
+/// only be served as an image if it actually is one.
+pub fn sniff_content_type(bytes: &[u8]) -> &'static str {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+    ];
+    if let Some((_, mime)) = SIGNATURES.iter().find(|(magic, _)| bytes.starts_with(magic)) {
+        return mime;
+    }
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+    if !bytes.contains(&0) && std::str::from_utf8(bytes).is_ok() {
+        return "text/plain; charset=utf-8";
+    }
+    "application/octet-stream"
+}
+
+/// Whether a sniffed type is safe for browsers to display inline rather than download.
+pub fn is_inline_safe(content_type: &str) -> bool {
+    matches!(content_type, "image/png" | "image/jpeg" | "image/gif" | "image/webp" | "application/pdf")
+}
+
+/// The storage backend for this environment.
+pub fn from_config(config: &crate::app::config::Config) -> Arc<dyn BlobStorage> {
+    Arc::new(LocalStorage::new(config.attachments_dir.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_keys_are_sha256_hex() {
+        let key = content_key(b"abc");
+        assert_eq!(key, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert!(is_valid_key(&key));
+        assert!(!is_valid_key("../etc/passwd"));
+        assert!(!is_valid_key(&key.to_uppercase()));
+    }
+
+    #[test]
+    fn sniffs_by_magic_bytes_not_extension() {
+        assert_eq!(sniff_content_type(b"\x89PNG\r\n\x1a\nrest"), "image/png");
+        assert_eq!(sniff_content_type(b"RIFF\0\0\0\0WEBPVP8 "), "image/webp");
+        assert_eq!(sniff_content_type(b"%PDF-1.7"), "application/pdf");
+        assert_eq!(sniff_content_type("# Spec\nnotes".as_bytes()), "text/plain; charset=utf-8");
+        assert_eq!(sniff_content_type(b"\x00\x01\x02"), "application/octet-stream");
+        assert!(!is_inline_safe(sniff_content_type(b"<svg onload=alert(1)>")));
+    }
+}
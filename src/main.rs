@@ -69,20 +69,30 @@ async fn main() {
         .await
         .expect("Failed to run seeds");
 
-    // Build the mail adapter from config
-    let mail = app::mail::from_config(&config)
+    // Build the mail transport from config; handlers enqueue into the outbox and the dispatcher delivers
+    let transport = app::mail::from_config(&config)
         .unwrap_or_else(|e| {
             tracing::error!("Failed to initialize mail adapter: {}", e);
             std::process::exit(1);
         });
+    tokio::spawn(app::mail::outbox::run_dispatcher(pool.clone(), transport));
+    let mail: std::sync::Arc<dyn app::mail::EmailSender> =
+        std::sync::Arc::new(app::mail::OutboxMailer::new(pool.clone()));
 
     // Build the application state
     let state = app::AppState {
         db: pool.clone(),
         mail,
+        dns: app::dns::from_config(&config),
+        storage: app::storage::from_config(&config),
+        blob_lock: std::sync::Arc::new(tokio::sync::RwLock::new(())),
         config,
         resend_cooldown: std::sync::Arc::new(std::sync::RwLock::new(std::collections::HashMap::new())),
     };
+    tokio::spawn(app::features::digest::run_scheduler(state.clone()));
+    tokio::spawn(app::features::projects::snapshots::run_scheduler(pool.clone()));
+    tokio::spawn(app::features::projects::trash::run_purge_scheduler(state.clone()));
+    tokio::spawn(app::features::organization::deletion::run_scheduler(state.clone()));
     let router = boardtask::create_router(state);
 
     // Start the server
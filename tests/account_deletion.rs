@@ -0,0 +1,105 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db;
+use boardtask::app::domain::{OrganizationId, OrganizationRole, UserId};
+
+async fn get(app: &axum::Router, cookie: &str, uri: &str) -> (http::StatusCode, String) {
+    let request = http::Request::builder()
+        .uri(uri)
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8_lossy(&bytes).to_string())
+}
+
+async fn confirm_delete(app: &axum::Router, pool: &sqlx::SqlitePool, cookie: &str) -> String {
+    let request = http::Request::builder()
+        .method("POST")
+        .uri("/app/account/delete")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .header("cookie", cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(pool, cookie).await)
+        .body(axum::body::Body::from("confirm_delete=1"))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::SEE_OTHER);
+    response.headers().get("location").unwrap().to_str().unwrap().to_string()
+}
+
+/// Add `email` to the org as an admin and log them in there.
+async fn join_as_admin(pool: &sqlx::SqlitePool, app: &axum::Router, org_id: &str, email: &str) -> (UserId, String) {
+    let user_id = UserId::from_string(&add_org_member(pool, org_id, email).await).unwrap();
+    let org = OrganizationId::from_string(org_id).unwrap();
+    db::organizations::update_member_role(pool, &org, &user_id, OrganizationRole::Admin).await.unwrap();
+    db::users::update_organization_id(pool, &user_id, &org).await.unwrap();
+    (user_id.clone(), login_cookie(app, email).await)
+}
+
+async fn create_project(pool: &sqlx::SqlitePool, org_id: &str, team_id: &str, user_id: &UserId, title: &str) -> String {
+    let id = ulid::Ulid::new().to_string();
+    db::projects::insert(pool, &db::projects::NewProject {
+        id: id.clone(),
+        title: title.to_string(),
+        user_id: user_id.as_str().to_string(),
+        organization_id: org_id.to_string(),
+        team_id: team_id.to_string(),
+    })
+    .await
+    .unwrap();
+    id
+}
+
+#[tokio::test]
+async fn deleting_an_admin_hands_their_projects_to_the_owner() {
+    let (owner, _project_id, pool, app, team_id) = setup_user_and_project("keeper@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let owner_id = user_id_from_cookie(&pool, &owner).await;
+    let (admin_id, admin) = join_as_admin(&pool, &app, &org_id, "departing@example.com").await;
+    let project_id = create_project(&pool, &org_id, &team_id, &admin_id, "Roadmap").await;
+
+    let (status, summary) = get(&app, &admin, "/app/account/delete").await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(summary.contains("Roadmap"));
+    assert!(summary.contains("keeper@example.com"));
+    assert!(!summary.contains("data-testid=\"deletion-blocked\""));
+
+    assert_eq!(confirm_delete(&app, &pool, &admin).await, "/?success=account_deleted");
+    assert!(db::users::find_by_id(&pool, &admin_id).await.unwrap().is_none());
+    let project = db::projects::find_by_id(&pool, &project_id).await.unwrap().expect("project survives");
+    assert_eq!(project.user_id, owner_id);
+}
+
+#[tokio::test]
+async fn sole_owner_with_members_cannot_delete_their_account() {
+    let (owner, project_id, pool, app, _) = setup_user_and_project("onlyowner@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    add_org_member(&pool, &org_id, "teammate@example.com").await;
+
+    let (_, summary) = get(&app, &owner, "/app/account/delete").await;
+    assert!(summary.contains("data-testid=\"deletion-blocked\""));
+    assert!(!summary.contains("confirm_delete"));
+
+    assert_eq!(confirm_delete(&app, &pool, &owner).await, "/app/account/delete");
+    let owner_id = UserId::from_string(&user_id_from_cookie(&pool, &owner).await).unwrap();
+    assert!(db::users::find_by_id(&pool, &owner_id).await.unwrap().is_some());
+    assert!(db::projects::find_by_id(&pool, &project_id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn solo_account_deletion_lists_and_deletes_its_projects() {
+    let (owner, project_id, pool, app, _) = setup_user_and_project("loner@example.com", "Password123").await;
+
+    let (_, summary) = get(&app, &owner, "/app/account/delete").await;
+    assert!(summary.contains("data-testid=\"deletion-deleted-projects\""));
+
+    assert_eq!(confirm_delete(&app, &pool, &owner).await, "/?success=account_deleted");
+    assert!(db::projects::find_by_id(&pool, &project_id).await.unwrap().is_none());
+}
@@ -0,0 +1,294 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::storage::{BlobLock, BlobStorage, StorageError};
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+const BOUNDARY: &str = "boardtask-test-boundary";
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR fake image";
+
+struct Response {
+    status: http::StatusCode,
+    headers: http::HeaderMap,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+async fn send(
+    app: &axum::Router,
+    pool: &sqlx::SqlitePool,
+    cookie: &str,
+    method: &str,
+    uri: &str,
+    content_type: &str,
+    body: Vec<u8>,
+) -> Response {
+    let request = http::Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("cookie", cookie)
+        .header("content-type", content_type)
+        .header("x-csrf-token", csrf_token_from_cookie(pool, cookie).await)
+        .body(axum::body::Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.into_body().collect().await.unwrap().to_bytes().to_vec();
+    Response { status, headers, body }
+}
+
+fn multipart_body(field: &str, filename: &str, declared_type: &str, bytes: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+        BOUNDARY, field, filename, declared_type
+    )
+    .into_bytes();
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+async fn upload(
+    app: &axum::Router,
+    pool: &sqlx::SqlitePool,
+    cookie: &str,
+    project_id: &str,
+    node_id: &str,
+    filename: &str,
+    bytes: &[u8],
+) -> Response {
+    send(
+        app,
+        pool,
+        cookie,
+        "POST",
+        &format!("/api/projects/{}/nodes/{}/attachments", project_id, node_id),
+        &format!("multipart/form-data; boundary={}", BOUNDARY),
+        multipart_body("file", filename, "text/plain", bytes),
+    )
+    .await
+}
+
+async fn create_node(app: &axum::Router, pool: &sqlx::SqlitePool, cookie: &str, project_id: &str, title: &str) -> String {
+    let response = send(
+        app,
+        pool,
+        cookie,
+        "POST",
+        &format!("/api/projects/{}/nodes", project_id),
+        "application/json",
+        serde_json::json!({ "node_type_id": TASK_NODE_TYPE_ID, "title": title }).to_string().into_bytes(),
+    )
+    .await;
+    assert_eq!(response.status, http::StatusCode::CREATED);
+    response.json()["id"].as_str().unwrap().to_string()
+}
+
+async fn setup(email: &str) -> (String, String, sqlx::SqlitePool, axum::Router, MemoryStorage) {
+    let (cookie, project_id, pool, _, _) = setup_user_and_project(email, "Password123").await;
+    let storage = MemoryStorage::default();
+    let app = test_router_with_storage(pool.clone(), storage.clone());
+    (cookie, project_id, pool, app, storage)
+}
+
+#[tokio::test]
+async fn attachments_upload_list_download_and_delete() {
+    let (cookie, project_id, pool, app, storage) = setup("attach@example.com").await;
+    let node = create_node(&app, &pool, &cookie, &project_id, "Design").await;
+    let other = create_node(&app, &pool, &cookie, &project_id, "Build").await;
+
+    let created = upload(&app, &pool, &cookie, &project_id, &node, "../mockup.png", PNG).await;
+    assert_eq!(created.status, http::StatusCode::CREATED);
+    let created = created.json();
+    assert_eq!(created["filename"], "mockup.png");
+    // Sniffed from the bytes, not the declared text/plain.
+    assert_eq!(created["content_type"], "image/png");
+    assert_eq!(created["size_bytes"], PNG.len());
+    let copy = upload(&app, &pool, &cookie, &project_id, &other, "copy.png", PNG).await.json();
+    assert_eq!(copy["content_hash"], created["content_hash"]);
+    assert_eq!(storage.keys(), vec![created["content_hash"].as_str().unwrap().to_string()]);
+
+    let list = send(
+        &app,
+        &pool,
+        &cookie,
+        "GET",
+        &format!("/api/projects/{}/nodes/{}/attachments", project_id, node),
+        "application/json",
+        Vec::new(),
+    )
+    .await;
+    assert_eq!(list.json()["attachments"].as_array().unwrap().len(), 1);
+
+    let uri = |node: &str, attachment: &serde_json::Value| {
+        format!("/api/projects/{}/nodes/{}/attachments/{}", project_id, node, attachment["id"].as_str().unwrap())
+    };
+    let download = send(&app, &pool, &cookie, "GET", &uri(&node, &created), "application/json", Vec::new()).await;
+    assert_eq!(download.status, http::StatusCode::OK);
+    assert_eq!(download.body, PNG);
+    assert_eq!(download.headers["content-type"], "image/png");
+    assert_eq!(download.headers["x-content-type-options"], "nosniff");
+    assert!(download.headers["content-disposition"].to_str().unwrap().starts_with("inline;"));
+    // Addressed through the wrong node, it does not exist.
+    let wrong = send(&app, &pool, &cookie, "GET", &uri(&other, &created), "application/json", Vec::new()).await;
+    assert_eq!(wrong.status, http::StatusCode::NOT_FOUND);
+
+    // The shared blob stays until the last attachment using it is gone.
+    let deleted = send(&app, &pool, &cookie, "DELETE", &uri(&node, &created), "application/json", Vec::new()).await;
+    assert_eq!(deleted.status, http::StatusCode::NO_CONTENT);
+    boardtask::app::features::graph::attachments::remove_orphaned_blobs(&pool, &storage, &BlobLock::default()).await.unwrap();
+    assert_eq!(storage.keys().len(), 1);
+    send(&app, &pool, &cookie, "DELETE", &uri(&other, &copy), "application/json", Vec::new()).await;
+    // Deleting only drops the row; the background sweep removes the bytes.
+    assert_eq!(storage.keys().len(), 1);
+    boardtask::app::features::graph::attachments::remove_orphaned_blobs(&pool, &storage, &BlobLock::default()).await.unwrap();
+    assert!(storage.keys().is_empty());
+}
+
+#[tokio::test]
+async fn attachments_enforce_size_limit_and_project_access() {
+    let (cookie, project_id, pool, app, storage) = setup("attachlimits@example.com").await;
+    let node = create_node(&app, &pool, &cookie, &project_id, "Task").await;
+    let max = boardtask::app::config::Config::for_tests().attachment_max_bytes;
+
+    let too_big = upload(&app, &pool, &cookie, &project_id, &node, "big.bin", &vec![7u8; max + 1]).await;
+    assert_eq!(too_big.status, http::StatusCode::PAYLOAD_TOO_LARGE);
+    let empty = upload(&app, &pool, &cookie, &project_id, &node, "empty.txt", b"").await;
+    assert_eq!(empty.status, http::StatusCode::BAD_REQUEST);
+    let wrong_field = send(
+        &app,
+        &pool,
+        &cookie,
+        "POST",
+        &format!("/api/projects/{}/nodes/{}/attachments", project_id, node),
+        &format!("multipart/form-data; boundary={}", BOUNDARY),
+        multipart_body("other", "notes.txt", "text/plain", b"notes"),
+    )
+    .await;
+    assert_eq!(wrong_field.status, http::StatusCode::BAD_REQUEST);
+
+    let outsider = authenticated_cookie(&pool, &app, "attachoutsider@example.com", "Password123").await;
+    let denied = upload(&app, &pool, &outsider, &project_id, &node, "notes.txt", b"notes").await;
+    assert_eq!(denied.status, http::StatusCode::NOT_FOUND);
+    assert!(storage.keys().is_empty());
+
+    let allowed = upload(&app, &pool, &cookie, &project_id, &node, "notes.html", b"<script>alert(1)</script>").await;
+    let attachment = allowed.json();
+    assert_eq!(attachment["content_type"], "text/plain; charset=utf-8");
+    let download = send(
+        &app,
+        &pool,
+        &outsider,
+        "GET",
+        &format!("/api/projects/{}/nodes/{}/attachments/{}", project_id, node, attachment["id"].as_str().unwrap()),
+        "application/json",
+        Vec::new(),
+    )
+    .await;
+    assert_eq!(download.status, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn attachments_follow_node_restore_and_are_removed_with_their_project() {
+    let (cookie, project_id, pool, app, storage) = setup("attachcleanup@example.com").await;
+    let node = create_node(&app, &pool, &cookie, &project_id, "Task").await;
+    let created = upload(&app, &pool, &cookie, &project_id, &node, "spec.pdf", b"%PDF-1.7 spec").await.json();
+    assert_eq!(created["content_type"], "application/pdf");
+
+    let node_uri = format!("/api/projects/{}/nodes/{}", project_id, node);
+    let deleted = send(&app, &pool, &cookie, "DELETE", &node_uri, "application/json", Vec::new()).await;
+    assert_eq!(deleted.status, http::StatusCode::NO_CONTENT);
+    // The undo record still needs the blob.
+    boardtask::app::features::graph::attachments::remove_orphaned_blobs(&pool, &storage, &BlobLock::default()).await.unwrap();
+    assert_eq!(storage.keys().len(), 1);
+
+    let restored = send(&app, &pool, &cookie, "POST", &format!("{}/restore", node_uri), "application/json", Vec::new()).await;
+    assert_eq!(restored.status, http::StatusCode::OK);
+    let attachments = boardtask::app::db::attachments::find_by_node(&pool, &node).await.unwrap();
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].id, created["id"].as_str().unwrap());
+
+    let org_id = boardtask::app::db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap().organization_id;
+    assert!(boardtask::app::db::projects::delete_by_id_and_org(&pool, &project_id, &org_id).await.unwrap());
+    let removed = boardtask::app::features::graph::attachments::remove_orphaned_blobs(&pool, &storage, &BlobLock::default()).await.unwrap();
+    assert_eq!(removed, 1);
+    assert!(storage.keys().is_empty());
+}
+
+/// Storage whose deletes pause until the test lets them finish, so a request can be raced against
+/// the sweep while it removes a blob.
+#[derive(Clone)]
+struct PausedDelete {
+    inner: MemoryStorage,
+    deleting: std::sync::Arc<tokio::sync::Notify>,
+    resume: std::sync::Arc<tokio::sync::Notify>,
+}
+
+#[async_trait::async_trait]
+impl BlobStorage for PausedDelete {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), StorageError> {
+        self.inner.put(key, bytes).await
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.inner.get(key).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.deleting.notify_one();
+        self.resume.notified().await;
+        self.inner.delete(key).await
+    }
+}
+
+#[tokio::test]
+async fn upload_of_a_blob_being_swept_waits_and_stays_downloadable() {
+    let (cookie, project_id, pool, _, _) = setup_user_and_project("attachrace@example.com", "Password123").await;
+    let storage = PausedDelete {
+        inner: MemoryStorage::default(),
+        deleting: Default::default(),
+        resume: Default::default(),
+    };
+    let mut state = test_state(pool.clone());
+    state.storage = std::sync::Arc::new(storage.clone());
+    let lock = state.blob_lock.clone();
+    let app = boardtask::create_router(state);
+
+    let node = create_node(&app, &pool, &cookie, &project_id, "Task").await;
+    let created = upload(&app, &pool, &cookie, &project_id, &node, "notes.txt", b"shared notes").await.json();
+    let uri = format!("/api/projects/{}/nodes/{}/attachments/{}", project_id, node, created["id"].as_str().unwrap());
+    send(&app, &pool, &cookie, "DELETE", &uri, "application/json", Vec::new()).await;
+
+    let sweep = tokio::spawn({
+        let (pool, storage) = (pool.clone(), storage.clone());
+        async move {
+            boardtask::app::features::graph::attachments::remove_orphaned_blobs(&pool, &storage, &lock).await.unwrap()
+        }
+    });
+    storage.deleting.notified().await;
+    // The same bytes are uploaded again while the sweep is removing them.
+    let reupload = tokio::spawn({
+        let (app, pool, cookie, project_id, node) = (app.clone(), pool.clone(), cookie.clone(), project_id.clone(), node.clone());
+        async move { upload(&app, &pool, &cookie, &project_id, &node, "again.txt", b"shared notes").await }
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    storage.resume.notify_one();
+
+    assert_eq!(sweep.await.unwrap(), 1);
+    let again = reupload.await.unwrap();
+    assert_eq!(again.status, http::StatusCode::CREATED);
+    let again_uri = format!("/api/projects/{}/nodes/{}/attachments/{}", project_id, node, again.json()["id"].as_str().unwrap());
+    let download = send(&app, &pool, &cookie, "GET", &again_uri, "application/json", Vec::new()).await;
+    assert_eq!(download.status, http::StatusCode::OK);
+    assert_eq!(download.body, b"shared notes");
+}
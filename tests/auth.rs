@@ -345,6 +345,7 @@ mod auth {
                 .method("POST")
                 .uri("/logout")
                 .header("cookie", format!("session_id={}", session_id))
+                .header("x-csrf-token", csrf_token_from_cookie(&pool, &format!("session_id={}", session_id)).await)
                 .body(Body::empty())
                 .unwrap();
             let logout_response = app.oneshot(logout_request).await.unwrap();
@@ -748,6 +749,7 @@ mod auth {
                 .uri("/app/account/change-password")
                 .header("content-type", "application/x-www-form-urlencoded")
                 .header("cookie", &cookie)
+                .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
                 .body(Body::from(body))
                 .unwrap();
             let response = app.oneshot(request).await.unwrap();
@@ -778,6 +780,7 @@ mod auth {
                 .uri("/app/account/change-password")
                 .header("content-type", "application/x-www-form-urlencoded")
                 .header("cookie", &cookie)
+                .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
                 .body(Body::from(body))
                 .unwrap();
             let response = app.oneshot(request).await.unwrap();
@@ -808,6 +811,7 @@ mod auth {
                 .uri("/app/account/change-password")
                 .header("content-type", "application/x-www-form-urlencoded")
                 .header("cookie", &cookie)
+                .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
                 .body(Body::from(body))
                 .unwrap();
             let response = app.oneshot(request).await.unwrap();
@@ -838,6 +842,7 @@ mod auth {
                 .uri("/app/account/change-password")
                 .header("content-type", "application/x-www-form-urlencoded")
                 .header("cookie", &cookie)
+                .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
                 .body(Body::from(body))
                 .unwrap();
             let response = app.clone().oneshot(request).await.unwrap();
@@ -908,6 +913,7 @@ mod auth {
                 .uri("/app/account/update-profile")
                 .header("content-type", "application/x-www-form-urlencoded")
                 .header("cookie", &cookie)
+                .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
                 .body(Body::from(body))
                 .unwrap();
             let response = app.oneshot(request).await.unwrap();
@@ -938,6 +944,7 @@ mod auth {
                 .uri("/app/account/update-profile")
                 .header("content-type", "application/x-www-form-urlencoded")
                 .header("cookie", &cookie)
+                .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
                 .body(Body::from(body))
                 .unwrap();
             let response = app.oneshot(request).await.unwrap();
@@ -969,6 +976,7 @@ mod auth {
                 .uri("/app/account/update-profile")
                 .header("content-type", "application/x-www-form-urlencoded")
                 .header("cookie", &cookie)
+                .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
                 .body(Body::from(body))
                 .unwrap();
             let response = app.clone().oneshot(request).await.unwrap();
@@ -0,0 +1,241 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+
+async fn send_json(
+    app: &axum::Router,
+    pool: &sqlx::SqlitePool,
+    cookie: &str,
+    method: &str,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> (http::StatusCode, serde_json::Value) {
+    let request = http::Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("cookie", cookie)
+        .header("content-type", "application/json")
+        .header("x-csrf-token", csrf_token_from_cookie(pool, cookie).await)
+        .body(axum::body::Body::from(body.map(|b| b.to_string()).unwrap_or_default()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+async fn create_node(app: &axum::Router, pool: &sqlx::SqlitePool, cookie: &str, project_id: &str, title: &str) -> String {
+    let (status, body) = send_json(
+        app,
+        pool,
+        cookie,
+        "POST",
+        &format!("/api/projects/{}/nodes", project_id),
+        Some(serde_json::json!({ "node_type_id": TASK_NODE_TYPE_ID, "title": title })),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::CREATED);
+    body["id"].as_str().unwrap().to_string()
+}
+
+async fn add_item(
+    app: &axum::Router,
+    pool: &sqlx::SqlitePool,
+    cookie: &str,
+    project_id: &str,
+    node_id: &str,
+    body: serde_json::Value,
+) -> (http::StatusCode, serde_json::Value) {
+    send_json(
+        app,
+        pool,
+        cookie,
+        "POST",
+        &format!("/api/projects/{}/nodes/{}/checklist", project_id, node_id),
+        Some(body),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn checklist_items_are_added_in_order_updated_and_deleted() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("checklist@example.com", "Password123").await;
+    ensure_graph_seeds(&pool).await;
+    let node = create_node(&app, &pool, &cookie, &project_id, "Release").await;
+    let user_id = user_id_from_cookie(&pool, &cookie).await;
+
+    let (status, first) = add_item(&app, &pool, &cookie, &project_id, &node, serde_json::json!({ "text": " Tag build " })).await;
+    assert_eq!(status, http::StatusCode::CREATED);
+    assert_eq!(first["text"], "Tag build");
+    assert_eq!(first["done"], false);
+    let (_, second) = add_item(
+        &app,
+        &pool,
+        &cookie,
+        &project_id,
+        &node,
+        serde_json::json!({ "text": "Write notes", "assigned_user_id": user_id }),
+    )
+    .await;
+    assert_eq!(second["assigned_user_id"], user_id.as_str());
+    assert!(second["sort_order"].as_i64().unwrap() > first["sort_order"].as_i64().unwrap());
+
+    let first_id = first["id"].as_str().unwrap();
+    let second_id = second["id"].as_str().unwrap();
+    let item_uri = |id: &str| format!("/api/projects/{}/nodes/{}/checklist/{}", project_id, node, id);
+
+    let (status, updated) = send_json(
+        &app,
+        &pool,
+        &cookie,
+        "PATCH",
+        &item_uri(first_id),
+        Some(serde_json::json!({ "done": true, "sort_order": 10 })),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(updated["done"], true);
+    assert_eq!(updated["text"], "Tag build");
+
+    let (_, cleared) =
+        send_json(&app, &pool, &cookie, "PATCH", &item_uri(second_id), Some(serde_json::json!({ "assigned_user_id": null }))).await;
+    assert!(cleared["assigned_user_id"].is_null());
+
+    let (status, list) =
+        send_json(&app, &pool, &cookie, "GET", &format!("/api/projects/{}/nodes/{}/checklist", project_id, node), None).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(list["done"], 1);
+    assert_eq!(list["total"], 2);
+    assert_eq!(list["items"][0]["id"], second_id);
+    assert_eq!(list["items"][1]["id"], first_id);
+
+    let (status, _) = send_json(&app, &pool, &cookie, "DELETE", &item_uri(second_id), None).await;
+    assert_eq!(status, http::StatusCode::NO_CONTENT);
+    let (status, _) = send_json(&app, &pool, &cookie, "DELETE", &item_uri(second_id), None).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn checklist_rejects_blank_text_and_outside_assignees() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("checkrules@example.com", "Password123").await;
+    ensure_graph_seeds(&pool).await;
+    let node = create_node(&app, &pool, &cookie, &project_id, "Task").await;
+    let outsider = authenticated_cookie(&pool, &app, "outsider@example.com", "Password123").await;
+    let outsider_id = user_id_from_cookie(&pool, &outsider).await;
+
+    let (status, _) = add_item(&app, &pool, &cookie, &project_id, &node, serde_json::json!({ "text": "   " })).await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    let (status, _) = add_item(&app, &pool, &cookie, &project_id, &node, serde_json::json!({ "text": "x".repeat(501) })).await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    let (status, _) = add_item(
+        &app,
+        &pool,
+        &cookie,
+        &project_id,
+        &node,
+        serde_json::json!({ "text": "Review", "assigned_user_id": outsider_id }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+
+    // Someone outside the organization cannot read or add to the checklist.
+    let (status, _) = add_item(&app, &pool, &outsider, &project_id, &node, serde_json::json!({ "text": "Sneaky" })).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn checklist_progress_shows_in_graph_and_list_view() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("checkprogress@example.com", "Password123").await;
+    ensure_graph_seeds(&pool).await;
+    let node = create_node(&app, &pool, &cookie, &project_id, "Progress task").await;
+    create_node(&app, &pool, &cookie, &project_id, "Plain task").await;
+    add_item(&app, &pool, &cookie, &project_id, &node, serde_json::json!({ "text": "One", "done": true })).await;
+    add_item(&app, &pool, &cookie, &project_id, &node, serde_json::json!({ "text": "Two" })).await;
+    add_item(&app, &pool, &cookie, &project_id, &node, serde_json::json!({ "text": "Three" })).await;
+
+    let (_, graph) = send_json(&app, &pool, &cookie, "GET", &format!("/api/projects/{}/graph", project_id), None).await;
+    assert_eq!(
+        graph["checklist_progress"],
+        serde_json::json!([{ "node_id": node, "done": 1, "total": 3 }])
+    );
+
+    let request = http::Request::builder()
+        .uri(format!("/app/projects/{}/list", project_id))
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let page = String::from_utf8_lossy(&bytes);
+    assert!(page.contains("Checklist"));
+    assert!(page.contains(">1/3<"));
+}
+
+#[tokio::test]
+async fn checklist_survives_export_import_and_node_restore() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("checkroundtrip@example.com", "Password123").await;
+    ensure_graph_seeds(&pool).await;
+    let user_id = user_id_from_cookie(&pool, &cookie).await;
+    let node = create_node(&app, &pool, &cookie, &project_id, "Task").await;
+    add_item(&app, &pool, &cookie, &project_id, &node, serde_json::json!({ "text": "First", "done": true })).await;
+    add_item(
+        &app,
+        &pool,
+        &cookie,
+        &project_id,
+        &node,
+        serde_json::json!({ "text": "Second", "assigned_user_id": user_id }),
+    )
+    .await;
+
+    let (_, export) = send_json(&app, &pool, &cookie, "GET", &format!("/api/projects/{}/export", project_id), None).await;
+    let checklist = &export["nodes"][0]["checklist"];
+    assert_eq!(checklist[0]["text"], "First");
+    assert_eq!(checklist[0]["done"], true);
+    assert_eq!(checklist[1]["text"], "Second");
+
+    let (status, _) = send_json(&app, &pool, &cookie, "POST", "/api/projects/import", Some(export)).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    let org_id = boardtask::app::db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap().organization_id;
+    let copy = boardtask::app::db::projects::list_for_org(&pool, &org_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|p| p.id != project_id)
+        .unwrap();
+    let copied_node = boardtask::app::db::nodes::find_by_project(&pool, &copy.id).await.unwrap().remove(0);
+    let imported: Vec<(String, bool, Option<String>)> =
+        boardtask::app::db::checklist_items::find_by_node(&pool, &copied_node.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.text, item.done, item.assigned_user_id))
+            .collect();
+    assert_eq!(
+        imported,
+        vec![("First".to_string(), true, None), ("Second".to_string(), false, None)]
+    );
+
+    let (status, _) =
+        send_json(&app, &pool, &cookie, "DELETE", &format!("/api/projects/{}/nodes/{}", project_id, node), None).await;
+    assert_eq!(status, http::StatusCode::NO_CONTENT);
+    let (status, _) = send_json(
+        &app,
+        &pool,
+        &cookie,
+        "POST",
+        &format!("/api/projects/{}/nodes/{}/restore", project_id, node),
+        None,
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    let restored = boardtask::app::db::checklist_items::find_by_node(&pool, &node).await.unwrap();
+    assert_eq!(restored.len(), 2);
+    assert_eq!(restored[1].assigned_user_id.as_deref(), Some(user_id.as_str()));
+}
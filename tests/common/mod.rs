@@ -12,12 +12,29 @@ pub async fn test_pool() -> SqlitePool {
 }
 
 pub fn test_router(pool: SqlitePool) -> axum::Router {
-    let state = boardtask::app::AppState {
+    create_router(test_state(pool))
+}
+
+/// App state used by test_router. Tweak fields (mail, config) and pass to create_router for special cases.
+pub fn test_state(pool: SqlitePool) -> boardtask::app::AppState {
+    boardtask::app::AppState {
         db: pool,
         mail: std::sync::Arc::new(boardtask::app::mail::ConsoleMailer),
+        dns: std::sync::Arc::new(StaticTxtResolver::default()),
+        storage: std::sync::Arc::new(MemoryStorage::default()),
+        blob_lock: std::sync::Arc::new(tokio::sync::RwLock::new(())),
         config: boardtask::app::config::Config::for_tests(),
         resend_cooldown: std::sync::Arc::new(std::sync::RwLock::new(std::collections::HashMap::new())),
-    };
+    }
+}
+
+/// Router with a custom mailer (e.g. RecordingMailer to assert on sent email).
+pub fn test_router_with_mail(
+    pool: SqlitePool,
+    mail: std::sync::Arc<dyn boardtask::app::mail::EmailSender>,
+) -> axum::Router {
+    let mut state = test_state(pool);
+    state.mail = mail;
     create_router(state)
 }
 
@@ -135,11 +152,20 @@ pub async fn user_id_from_cookie(pool: &sqlx::SqlitePool, cookie: &str) -> Strin
 pub async fn setup_user_and_project(
     email: &str,
     password: &str,
+) -> (String, String, SqlitePool, axum::Router, String) {
+    setup_user_and_project_with_mail(email, password, std::sync::Arc::new(boardtask::app::mail::ConsoleMailer)).await
+}
+
+/// Same as setup_user_and_project, but the router sends email through `mail`.
+pub async fn setup_user_and_project_with_mail(
+    email: &str,
+    password: &str,
+    mail: std::sync::Arc<dyn boardtask::app::mail::EmailSender>,
 ) -> (String, String, SqlitePool, axum::Router, String) {
     use boardtask::app::db::{self, projects};
 
     let pool = test_pool().await;
-    let app = test_router(pool.clone());
+    let app = test_router_with_mail(pool.clone(), mail);
     ensure_graph_seeds(&pool).await;
 
     let cookie = authenticated_cookie(&pool, &app, email, password).await;
@@ -310,3 +336,246 @@ pub async fn authenticated_cookie(
     let session_id = extract_session_id_from_cookie(set_cookie).unwrap();
     format!("session_id={}", session_id)
 }
+
+/// CSRF token for the session behind an authenticated cookie header. Send as `x-csrf-token` on writes.
+pub async fn csrf_token_from_cookie(pool: &sqlx::SqlitePool, cookie: &str) -> String {
+    let session_id = extract_session_id_from_cookie(cookie).expect("cookie must contain session_id");
+    let session = boardtask::app::db::sessions::find_valid(pool, session_id)
+        .await
+        .unwrap()
+        .expect("session should be valid");
+    session.csrf_token
+}
+
+/// Log in an existing user with the shared test password and return their cookie header.
+pub async fn login_cookie(app: &axum::Router, email: &str) -> String {
+    let request = http::Request::builder()
+        .method("POST")
+        .uri("/login")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(Body::from(login_form_body(email, "Password123")))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::SEE_OTHER);
+    let set_cookie = response.headers().get("set-cookie").unwrap().to_str().unwrap();
+    set_cookie.split(';').next().unwrap_or("").to_string()
+}
+
+/// POST a form body with the session's CSRF token. Returns the status and any redirect location.
+pub async fn post_form(
+    app: &axum::Router,
+    pool: &SqlitePool,
+    cookie: &str,
+    uri: &str,
+    body: &str,
+) -> (http::StatusCode, String) {
+    let request = http::Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/x-www-form-urlencoded")
+        .header("cookie", cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(pool, cookie).await)
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let location = response
+        .headers()
+        .get("location")
+        .map(|v| v.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    (response.status(), location)
+}
+
+/// GET `uri` with the cookie and parse the body as JSON (Null when it isn't JSON).
+pub async fn get_json(app: &axum::Router, cookie: &str, uri: &str) -> (http::StatusCode, serde_json::Value) {
+    use http_body_util::BodyExt;
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(uri)
+        .header("cookie", cookie)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+/// Insert a task node titled "Task" straight into the DB. Returns its id.
+pub async fn insert_task(
+    pool: &SqlitePool,
+    project_id: &str,
+    status_id: &str,
+    estimated_minutes: Option<i64>,
+    slot_id: Option<&str>,
+    assigned_user_id: Option<&str>,
+) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let node = boardtask::app::db::nodes::NewNode {
+        id: id.clone(),
+        project_id: project_id.to_string(),
+        node_type_id: "01JNODETYPE00000000TASK000".to_string(),
+        status_id: status_id.to_string(),
+        title: "Task".to_string(),
+        description: None,
+        estimated_minutes,
+        slot_id: slot_id.map(String::from),
+        parent_id: None,
+        assigned_user_id: assigned_user_id.map(String::from),
+    };
+    boardtask::app::db::nodes::insert(pool, &node).await.unwrap();
+    id
+}
+
+/// Mailer that records every message instead of sending it.
+#[derive(Clone, Default)]
+pub struct RecordingMailer {
+    sent: std::sync::Arc<std::sync::Mutex<Vec<boardtask::app::mail::EmailMessage>>>,
+}
+
+impl RecordingMailer {
+    /// Messages sent to the given address, in send order.
+    pub fn sent_to(&self, email: &str) -> Vec<boardtask::app::mail::EmailMessage> {
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| m.to.as_str() == email)
+            .cloned()
+            .collect()
+    }
+
+    /// Every message sent so far.
+    pub fn all(&self) -> Vec<boardtask::app::mail::EmailMessage> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl boardtask::app::mail::EmailSender for RecordingMailer {
+    async fn send(
+        &self,
+        message: &boardtask::app::mail::EmailMessage,
+    ) -> Result<(), boardtask::app::mail::EmailError> {
+        self.sent.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
+/// TXT resolver answering from a fixed table; names not in it have no records.
+#[derive(Clone, Default)]
+pub struct StaticTxtResolver {
+    records: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, Vec<String>>>>,
+}
+
+impl StaticTxtResolver {
+    /// Publish `value` as a TXT record at `name`.
+    pub fn publish(&self, name: &str, value: &str) {
+        self.records.lock().unwrap().entry(name.to_lowercase()).or_default().push(value.to_string());
+    }
+}
+
+#[async_trait::async_trait]
+impl boardtask::app::dns::TxtResolver for StaticTxtResolver {
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, boardtask::app::dns::DnsError> {
+        Ok(self.records.lock().unwrap().get(&name.to_lowercase()).cloned().unwrap_or_default())
+    }
+}
+
+/// Router whose DNS lookups are answered by `dns`.
+pub fn test_router_with_dns(pool: SqlitePool, dns: StaticTxtResolver) -> axum::Router {
+    let mut state = test_state(pool);
+    state.dns = std::sync::Arc::new(dns);
+    create_router(state)
+}
+
+/// Create a verified user and add them to `organization_id` as a member. Returns their user id.
+pub async fn add_org_member(pool: &SqlitePool, organization_id: &str, email: &str) -> String {
+    let (user_id, _, _) = create_verified_user(pool, email, "Password123").await;
+    let org_id = boardtask::app::domain::OrganizationId::from_string(organization_id).unwrap();
+    boardtask::app::db::organizations::add_member(
+        pool,
+        &org_id,
+        &user_id,
+        boardtask::app::domain::OrganizationRole::Member,
+    )
+    .await
+    .unwrap();
+    user_id.as_str().to_string()
+}
+
+/// Add a new user to `org_id` with `role`, make it their current organization and log them in.
+/// Returns their user id and cookie header.
+pub async fn join_and_login(
+    pool: &SqlitePool,
+    app: &axum::Router,
+    org_id: &str,
+    email: &str,
+    role: boardtask::app::domain::OrganizationRole,
+) -> (String, String) {
+    use boardtask::app::domain::{OrganizationId, UserId};
+
+    let user_id = add_org_member(pool, org_id, email).await;
+    let uid = UserId::from_string(&user_id).unwrap();
+    let org = OrganizationId::from_string(org_id).unwrap();
+    boardtask::app::db::organizations::update_member_role(pool, &org, &uid, role).await.unwrap();
+    boardtask::app::db::users::update_organization_id(pool, &uid, &org).await.unwrap();
+    (user_id, login_cookie(app, email).await)
+}
+
+/// Current organization of the user behind the cookie.
+pub async fn owner_org(pool: &SqlitePool, cookie: &str) -> String {
+    let user_id = boardtask::app::domain::UserId::from_string(&user_id_from_cookie(pool, cookie).await).unwrap();
+    boardtask::app::db::users::find_by_id(pool, &user_id).await.unwrap().unwrap().organization_id
+}
+
+/// The user's role in the organization, None when they aren't a member.
+pub async fn role_of(
+    pool: &SqlitePool,
+    org_id: &str,
+    user_id: &str,
+) -> Option<boardtask::app::domain::OrganizationRole> {
+    use boardtask::app::domain::{OrganizationId, UserId};
+
+    let org = OrganizationId::from_string(org_id).unwrap();
+    let uid = UserId::from_string(user_id).unwrap();
+    boardtask::app::db::organizations::find_member_role(pool, &org, &uid).await.unwrap()
+}
+
+/// Blob storage kept in memory; clones share the same blobs so tests can inspect them.
+#[derive(Clone, Default)]
+pub struct MemoryStorage {
+    blobs: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, Vec<u8>>>>,
+}
+
+impl MemoryStorage {
+    /// Keys of the blobs currently stored.
+    pub fn keys(&self) -> Vec<String> {
+        self.blobs.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl boardtask::app::storage::BlobStorage for MemoryStorage {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), boardtask::app::storage::StorageError> {
+        self.blobs.lock().unwrap().insert(key.to_string(), bytes.to_vec());
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, boardtask::app::storage::StorageError> {
+        Ok(self.blobs.lock().unwrap().get(key).cloned())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), boardtask::app::storage::StorageError> {
+        self.blobs.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Router whose attachment blobs go to `storage`.
+pub fn test_router_with_storage(pool: SqlitePool, storage: MemoryStorage) -> axum::Router {
+    let mut state = test_state(pool);
+    state.storage = std::sync::Arc::new(storage);
+    create_router(state)
+}
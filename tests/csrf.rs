@@ -0,0 +1,125 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+
+fn node_request(project_id: &str, cookie: &str, token: Option<&str>) -> http::Request<axum::body::Body> {
+    let body = serde_json::json!({
+        "node_type_id": TASK_NODE_TYPE_ID,
+        "title": "Forged",
+    });
+    let mut builder = http::Request::builder()
+        .method("POST")
+        .uri(format!("/api/projects/{}/nodes", project_id))
+        .header("content-type", "application/json")
+        .header("cookie", cookie);
+    if let Some(token) = token {
+        builder = builder.header("x-csrf-token", token);
+    }
+    builder.body(axum::body::Body::from(body.to_string())).unwrap()
+}
+
+#[tokio::test]
+async fn api_write_without_token_is_forbidden() {
+    let (cookie, project_id, _pool, app, _) = setup_user_and_project("csrf-api@example.com", "Password123").await;
+
+    let response = app.oneshot(node_request(&project_id, &cookie, None)).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(body["error"], "Invalid CSRF token");
+}
+
+#[tokio::test]
+async fn api_write_with_wrong_token_is_forbidden() {
+    let (cookie, project_id, _pool, app, _) = setup_user_and_project("csrf-wrong@example.com", "Password123").await;
+
+    let forged = "0".repeat(64);
+    let response = app.oneshot(node_request(&project_id, &cookie, Some(&forged))).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn api_write_with_session_token_succeeds() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("csrf-ok@example.com", "Password123").await;
+    let token = csrf_token_from_cookie(&pool, &cookie).await;
+
+    let response = app.oneshot(node_request(&project_id, &cookie, Some(&token))).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn token_from_another_session_is_rejected() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("csrf-victim@example.com", "Password123").await;
+    let other_cookie = authenticated_cookie(&pool, &app, "csrf-attacker@example.com", "Password123").await;
+    let other_token = csrf_token_from_cookie(&pool, &other_cookie).await;
+
+    let response = app.oneshot(node_request(&project_id, &cookie, Some(&other_token))).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn cross_site_form_post_is_forbidden() {
+    let (cookie, _project_id, _pool, app, team_id) = setup_user_and_project("csrf-form@example.com", "Password123").await;
+
+    let request = http::Request::builder()
+        .method("POST")
+        .uri("/app/projects")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .header("cookie", &cookie)
+        .body(axum::body::Body::from(create_project_form_body("Forged", &team_id)))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn form_post_with_hidden_field_succeeds() {
+    let (cookie, _project_id, pool, app, team_id) = setup_user_and_project("csrf-field@example.com", "Password123").await;
+    let token = csrf_token_from_cookie(&pool, &cookie).await;
+
+    let body = format!("{}&csrf_token={}", create_project_form_body("Legit", &team_id), token);
+    let request = http::Request::builder()
+        .method("POST")
+        .uri("/app/projects")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .header("cookie", &cookie)
+        .body(axum::body::Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::SEE_OTHER);
+    assert_eq!(
+        response.headers().get("location").map(|v| v.to_str().unwrap()),
+        Some("/app/projects")
+    );
+}
+
+#[tokio::test]
+async fn account_page_embeds_token_in_forms() {
+    let (cookie, _project_id, pool, app, _) = setup_user_and_project("csrf-page@example.com", "Password123").await;
+    let token = csrf_token_from_cookie(&pool, &cookie).await;
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri("/app/account")
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body_str = String::from_utf8_lossy(&body_bytes);
+    assert!(body_str.contains(&format!("name=\"csrf_token\" value=\"{}\"", token)));
+    assert!(body_str.contains(&format!("name=\"csrf-token\" content=\"{}\"", token)));
+}
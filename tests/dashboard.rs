@@ -0,0 +1,175 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db::task_statuses::{DONE_STATUS_ID, IN_PROGRESS_STATUS_ID, TODO_STATUS_ID};
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+
+struct Task<'a> {
+    title: &'a str,
+    status_id: &'a str,
+    estimated_minutes: Option<i64>,
+    slot_id: Option<&'a str>,
+    assigned_user_id: Option<&'a str>,
+}
+
+impl Default for Task<'_> {
+    fn default() -> Self {
+        Self { title: "", status_id: TODO_STATUS_ID, estimated_minutes: None, slot_id: None, assigned_user_id: None }
+    }
+}
+
+async fn insert_task(pool: &sqlx::SqlitePool, project_id: &str, task: Task<'_>) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let node = boardtask::app::db::nodes::NewNode {
+        id: id.clone(),
+        project_id: project_id.to_string(),
+        node_type_id: TASK_NODE_TYPE_ID.to_string(),
+        status_id: task.status_id.to_string(),
+        title: task.title.to_string(),
+        description: None,
+        estimated_minutes: task.estimated_minutes,
+        slot_id: task.slot_id.map(String::from),
+        parent_id: None,
+        assigned_user_id: task.assigned_user_id.map(String::from),
+    };
+    boardtask::app::db::nodes::insert(pool, &node).await.unwrap();
+    id
+}
+
+async fn insert_slot(pool: &sqlx::SqlitePool, project_id: &str, assigned_user_id: &str) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let slot = boardtask::app::db::project_slots::NewProjectSlot {
+        id: id.clone(),
+        project_id: project_id.to_string(),
+        name: "Dev".to_string(),
+        sort_order: 0,
+        assigned_user_id: Some(assigned_user_id.to_string()),
+    };
+    boardtask::app::db::project_slots::insert(pool, &slot).await.unwrap();
+    id
+}
+
+async fn dashboard(app: &axum::Router, cookie: &str) -> String {
+    let request = http::Request::builder()
+        .method("GET")
+        .uri("/app")
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+fn my_work_section(body: &str) -> &str {
+    let start = body.find(">My work<").unwrap();
+    let end = body.find(">Recently updated<").unwrap();
+    &body[start..end]
+}
+
+#[tokio::test]
+async fn dashboard_lists_work_assigned_directly_and_through_slots() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("mywork@example.com", "Password123").await;
+    let me = user_id_from_cookie(&pool, &cookie).await;
+    let project = boardtask::app::db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap();
+    let colleague = add_org_member(&pool, &project.organization_id, "colleague@example.com").await;
+    let slot_id = insert_slot(&pool, &project_id, &me).await;
+
+    insert_task(&pool, &project_id, Task { title: "Direct task", estimated_minutes: Some(90), assigned_user_id: Some(&me), ..Default::default() }).await;
+    insert_task(&pool, &project_id, Task { title: "Slot task", status_id: IN_PROGRESS_STATUS_ID, estimated_minutes: Some(30), slot_id: Some(&slot_id), ..Default::default() }).await;
+    insert_task(&pool, &project_id, Task { title: "Finished task", status_id: DONE_STATUS_ID, estimated_minutes: Some(600), assigned_user_id: Some(&me), ..Default::default() }).await;
+    // Assigned to someone else directly, even though the slot is mine.
+    insert_task(&pool, &project_id, Task { title: "Colleague task", slot_id: Some(&slot_id), assigned_user_id: Some(&colleague), ..Default::default() }).await;
+    insert_task(&pool, &project_id, Task { title: "Nobody task", ..Default::default() }).await;
+
+    let body = dashboard(&app, &cookie).await;
+    let section = my_work_section(&body);
+
+    assert!(section.contains("Direct task"));
+    assert!(section.contains("Slot task"));
+    assert!(section.contains("Finished task"));
+    assert!(!section.contains("Colleague task"));
+    assert!(!section.contains("Nobody task"));
+    // Grouped in status order: To do, In progress, Done.
+    let todo = section.find("To do").unwrap();
+    let in_progress = section.find("In progress").unwrap();
+    assert!(todo < section.find("Direct task").unwrap());
+    assert!(in_progress < section.find("Slot task").unwrap() && todo < in_progress);
+    assert!(body.contains(r#"data-testid="my-work-open">2<"#));
+    assert!(body.contains(r#"data-testid="my-work-remaining">2 h<"#));
+}
+
+#[tokio::test]
+async fn dashboard_flags_blocked_work() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("blockedwork@example.com", "Password123").await;
+    let me = user_id_from_cookie(&pool, &cookie).await;
+    // root -> prerequisite -> mine: mine is blocked by the unfinished prerequisite.
+    let root = insert_task(&pool, &project_id, Task { title: "Root", ..Default::default() }).await;
+    let prerequisite = insert_task(&pool, &project_id, Task { title: "Prerequisite", ..Default::default() }).await;
+    let mine = insert_task(&pool, &project_id, Task { title: "Waiting on others", assigned_user_id: Some(&me), ..Default::default() }).await;
+    for (parent_id, child_id) in [(&root, &prerequisite), (&prerequisite, &mine)] {
+        let edge = boardtask::app::db::node_edges::NewNodeEdge { parent_id: parent_id.clone(), child_id: child_id.clone() };
+        boardtask::app::db::node_edges::insert(&pool, &edge).await.unwrap();
+    }
+
+    let body = dashboard(&app, &cookie).await;
+
+    assert!(my_work_section(&body).contains("my-work__blocked"));
+    assert!(body.contains(r#"data-testid="my-work-blocked">1<"#));
+}
+
+/// Sign up another user and give them a project on their own organization's default team.
+async fn foreign_project(pool: &sqlx::SqlitePool, app: &axum::Router, email: &str) -> String {
+    use boardtask::app::{db, domain::{OrganizationId, UserId}};
+
+    let cookie = authenticated_cookie(pool, app, email, "Password123").await;
+    let user_id = user_id_from_cookie(pool, &cookie).await;
+    let user = db::users::find_by_id(pool, &UserId::from_string(&user_id).unwrap()).await.unwrap().unwrap();
+    let org_id = OrganizationId::from_string(&user.organization_id).unwrap();
+    let team = db::teams::find_default_for_org(pool, &org_id).await.unwrap().unwrap();
+    let project_id = ulid::Ulid::new().to_string();
+    let project = db::NewProject {
+        id: project_id.clone(),
+        title: "Foreign Project".to_string(),
+        user_id,
+        organization_id: user.organization_id,
+        team_id: team.id,
+    };
+    db::projects::insert(pool, &project).await.unwrap();
+    project_id
+}
+
+#[tokio::test]
+async fn dashboard_shows_recent_updates_only_from_my_organization() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("recent@example.com", "Password123").await;
+    let me = user_id_from_cookie(&pool, &cookie).await;
+    insert_task(&pool, &project_id, Task { title: "Fresh change", ..Default::default() }).await;
+    let other_project_id = foreign_project(&pool, &app, "elsewhere@example.com").await;
+    insert_task(&pool, &other_project_id, Task { title: "Foreign change", assigned_user_id: Some(&me), ..Default::default() }).await;
+
+    let body = dashboard(&app, &cookie).await;
+    let recent = &body[body.find(">Recently updated<").unwrap()..];
+
+    assert!(recent.contains("Fresh change"));
+    assert!(recent.contains("Test Project"));
+    assert!(recent.contains("just now"));
+    assert!(!body.contains("Foreign change"));
+}
+
+#[tokio::test]
+async fn dashboard_without_work_shows_empty_states() {
+    let pool = test_pool().await;
+    let app = test_router(pool.clone());
+    let cookie = authenticated_cookie(&pool, &app, "idle@example.com", "Password123").await;
+
+    let body = dashboard(&app, &cookie).await;
+
+    assert!(body.contains("Nothing is assigned to you yet."));
+    assert!(body.contains("No activity in your projects yet."));
+}
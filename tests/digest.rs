@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use boardtask::app::db::{self, task_statuses};
+use boardtask::app::domain::UserId;
+use boardtask::app::features::digest;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+const OWNER_EMAIL: &str = "digest@example.com";
+const WEEK: i64 = 7 * 24 * 60 * 60;
+
+struct Fixture {
+    cookie: String,
+    project_id: String,
+    pool: sqlx::SqlitePool,
+    app: axum::Router,
+    mailer: RecordingMailer,
+    state: boardtask::app::AppState,
+    user_id: UserId,
+}
+
+async fn setup() -> Fixture {
+    let mailer = RecordingMailer::default();
+    let (cookie, project_id, pool, app, _) =
+        setup_user_and_project_with_mail(OWNER_EMAIL, "Password123", Arc::new(mailer.clone())).await;
+    let mut state = test_state(pool.clone());
+    state.mail = Arc::new(mailer.clone());
+    let user_id = UserId::from_string(&user_id_from_cookie(&pool, &cookie).await).unwrap();
+    Fixture { cookie, project_id, pool, app, mailer, state, user_id }
+}
+
+async fn set_frequency(f: &Fixture, notifications: bool, frequency: &str) {
+    db::users::update_preferences(&f.pool, &f.user_id, notifications, "light", "en-US", frequency)
+        .await
+        .unwrap();
+}
+
+async fn insert_node(f: &Fixture, title: &str, status_id: &str, minutes: Option<i64>, assignee: Option<&str>) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let node = db::nodes::NewNode {
+        id: id.clone(),
+        project_id: f.project_id.clone(),
+        node_type_id: TASK_NODE_TYPE_ID.to_string(),
+        status_id: status_id.to_string(),
+        title: title.to_string(),
+        description: None,
+        estimated_minutes: minutes,
+        slot_id: None,
+        parent_id: None,
+        assigned_user_id: assignee.map(String::from),
+    };
+    db::nodes::insert(&f.pool, &node).await.unwrap();
+    id
+}
+
+async fn insert_edge(f: &Fixture, parent_id: &str, child_id: &str) {
+    let edge = db::node_edges::NewNodeEdge {
+        parent_id: parent_id.to_string(),
+        child_id: child_id.to_string(),
+    };
+    db::node_edges::insert(&f.pool, &edge).await.unwrap();
+}
+
+async fn set_status(f: &Fixture, node_id: &str, status_id: &str) {
+    let node = db::nodes::find_by_id(&f.pool, node_id).await.unwrap().unwrap();
+    db::nodes::update(
+        &f.pool,
+        node_id,
+        &node.title,
+        node.description.as_deref(),
+        &node.node_type_id,
+        status_id,
+        node.estimated_minutes,
+        node.slot_id.as_deref(),
+        node.parent_id.as_deref(),
+        node.assigned_user_id.as_deref(),
+    )
+    .await
+    .unwrap();
+}
+
+/// Mailer whose every send fails.
+struct FailingMailer;
+
+#[async_trait::async_trait]
+impl boardtask::app::mail::EmailSender for FailingMailer {
+    async fn send(&self, _: &boardtask::app::mail::EmailMessage) -> Result<(), boardtask::app::mail::EmailError> {
+        Err(boardtask::app::mail::EmailError::Send("unavailable".to_string()))
+    }
+}
+
+fn now() -> i64 {
+    time::OffsetDateTime::now_utc().unix_timestamp()
+}
+
+#[tokio::test]
+async fn weekly_digest_summarizes_project() {
+    let f = setup().await;
+    set_frequency(&f, true, "weekly").await;
+    let kickoff = insert_node(&f, "Kickoff", task_statuses::TODO_STATUS_ID, Some(30), None).await;
+    set_status(&f, &kickoff, task_statuses::DONE_STATUS_ID).await;
+    let me = f.user_id.as_str().to_string();
+    // A -> B -> C: C is blocked by the unfinished B.
+    let a = insert_node(&f, "Design", task_statuses::TODO_STATUS_ID, Some(60), None).await;
+    let b = insert_node(&f, "Build", task_statuses::TODO_STATUS_ID, Some(90), Some(&me)).await;
+    let c = insert_node(&f, "Ship", task_statuses::TODO_STATUS_ID, None, None).await;
+    insert_edge(&f, &a, &b).await;
+    insert_edge(&f, &b, &c).await;
+
+    let sent = digest::send_due(&f.state, now()).await.unwrap();
+    assert_eq!(sent, 1);
+
+    let mail = f.mailer.sent_to(OWNER_EMAIL);
+    assert_eq!(mail.len(), 1);
+    assert!(mail[0].subject.contains("weekly digest"));
+    assert!(mail[0].body.contains("Test Project"));
+    // The first digest has no baseline, so nothing is reported as newly blocked.
+    assert!(mail[0].body.contains("1 completed, 1 blocked, 2 h 30 min remaining"));
+    assert!(mail[0].body.contains("- Build"));
+    assert!(!mail[0].body.contains("- Design"));
+    assert!(mail[0].html_body.as_deref().unwrap().contains(&format!("/app/projects/{}", f.project_id)));
+}
+
+#[tokio::test]
+async fn digest_is_not_resent_within_the_period() {
+    let f = setup().await;
+    set_frequency(&f, true, "weekly").await;
+    let start = now();
+
+    assert_eq!(digest::send_due(&f.state, start).await.unwrap(), 1);
+    assert_eq!(digest::send_due(&f.state, start + WEEK - 1).await.unwrap(), 0);
+    assert_eq!(digest::send_due(&f.state, start + WEEK).await.unwrap(), 1);
+    assert_eq!(f.mailer.sent_to(OWNER_EMAIL).len(), 2);
+}
+
+#[tokio::test]
+async fn blocked_delta_is_relative_to_previous_digest() {
+    let f = setup().await;
+    set_frequency(&f, true, "daily").await;
+    let a = insert_node(&f, "Design", task_statuses::TODO_STATUS_ID, None, None).await;
+    let b = insert_node(&f, "Build", task_statuses::TODO_STATUS_ID, None, None).await;
+    let c = insert_node(&f, "Ship", task_statuses::TODO_STATUS_ID, None, None).await;
+    insert_edge(&f, &a, &b).await;
+    insert_edge(&f, &b, &c).await;
+    let start = now();
+    digest::send_due(&f.state, start).await.unwrap();
+
+    let d = insert_node(&f, "Announce", task_statuses::TODO_STATUS_ID, None, None).await;
+    insert_edge(&f, &b, &d).await;
+    digest::send_due(&f.state, start + 24 * 60 * 60).await.unwrap();
+
+    let mail = f.mailer.sent_to(OWNER_EMAIL);
+    assert_eq!(mail.len(), 2);
+    assert!(mail[1].body.contains("2 blocked (1 new)"));
+}
+
+#[tokio::test]
+async fn no_digest_when_off_or_notifications_disabled() {
+    let f = setup().await;
+    assert_eq!(digest::send_due(&f.state, now()).await.unwrap(), 0);
+
+    set_frequency(&f, false, "weekly").await;
+    assert_eq!(digest::send_due(&f.state, now()).await.unwrap(), 0);
+    assert!(f.mailer.all().is_empty());
+}
+
+#[tokio::test]
+async fn account_preferences_save_digest_frequency() {
+    let f = setup().await;
+
+    let post = |frequency: &'static str| {
+        let f = &f;
+        async move {
+            let request = http::Request::builder()
+                .method("POST")
+                .uri("/app/account/update-preferences")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .header("cookie", &f.cookie)
+                .header("x-csrf-token", csrf_token_from_cookie(&f.pool, &f.cookie).await)
+                .body(axum::body::Body::from(format!(
+                    "email_notifications=1&theme_mode=light&language=en-US&digest_frequency={}",
+                    frequency
+                )))
+                .unwrap();
+            let response = f.app.clone().oneshot(request).await.unwrap();
+            response.headers().get("location").unwrap().to_str().unwrap().to_string()
+        }
+    };
+
+    assert!(post("daily").await.contains("success=preferences_updated"));
+    let user = db::users::find_by_id(&f.pool, &f.user_id).await.unwrap().unwrap();
+    assert_eq!(user.digest_frequency, "daily");
+
+    assert!(post("hourly").await.contains("error="));
+    let user = db::users::find_by_id(&f.pool, &f.user_id).await.unwrap().unwrap();
+    assert_eq!(user.digest_frequency, "daily");
+}
+
+#[tokio::test]
+async fn newly_blocked_compares_nodes_not_counts() {
+    let f = setup().await;
+    set_frequency(&f, true, "daily").await;
+    // A -> B -> C blocks C; B -> D is added later while C gets unblocked.
+    let a = insert_node(&f, "Design", task_statuses::TODO_STATUS_ID, None, None).await;
+    let b = insert_node(&f, "Build", task_statuses::TODO_STATUS_ID, None, None).await;
+    let c = insert_node(&f, "Ship", task_statuses::TODO_STATUS_ID, None, None).await;
+    insert_edge(&f, &a, &b).await;
+    insert_edge(&f, &b, &c).await;
+    let start = now();
+    digest::send_due(&f.state, start).await.unwrap();
+
+    set_status(&f, &c, task_statuses::DONE_STATUS_ID).await;
+    let d = insert_node(&f, "Announce", task_statuses::TODO_STATUS_ID, None, None).await;
+    insert_edge(&f, &b, &d).await;
+    digest::send_due(&f.state, start + 24 * 60 * 60).await.unwrap();
+
+    let mail = f.mailer.sent_to(OWNER_EMAIL);
+    assert!(mail[1].body.contains("1 blocked (1 new)"));
+}
+
+#[tokio::test]
+async fn editing_an_old_done_node_does_not_count_as_completed() {
+    let f = setup().await;
+    set_frequency(&f, true, "daily").await;
+    let node = insert_node(&f, "Kickoff", task_statuses::TODO_STATUS_ID, None, None).await;
+    set_status(&f, &node, task_statuses::DONE_STATUS_ID).await;
+    let start = now() + 1;
+    digest::send_due(&f.state, start).await.unwrap();
+
+    // Still done, only retitled: not a new completion.
+    let mut edited = db::nodes::find_by_id(&f.pool, &node).await.unwrap().unwrap();
+    edited.title = "Kickoff meeting".to_string();
+    db::nodes::update(&f.pool, &node, &edited.title, None, &edited.node_type_id, &edited.status_id, None, None, None, None)
+        .await
+        .unwrap();
+    digest::send_due(&f.state, start + 24 * 60 * 60).await.unwrap();
+
+    let mail = f.mailer.sent_to(OWNER_EMAIL);
+    assert!(mail[0].body.contains("1 completed"));
+    assert!(mail[1].body.contains("0 completed"));
+}
+
+#[tokio::test]
+async fn node_created_as_done_counts_as_completed() {
+    let f = setup().await;
+    set_frequency(&f, true, "daily").await;
+    insert_node(&f, "Already shipped", task_statuses::DONE_STATUS_ID, None, None).await;
+
+    digest::send_due(&f.state, now() + 1).await.unwrap();
+
+    let mail = f.mailer.sent_to(OWNER_EMAIL);
+    assert!(mail[0].body.contains("1 completed"), "{}", mail[0].body);
+}
+
+#[tokio::test]
+async fn failed_send_keeps_the_previous_baseline() {
+    let f = setup().await;
+    set_frequency(&f, true, "daily").await;
+    let a = insert_node(&f, "Design", task_statuses::TODO_STATUS_ID, None, None).await;
+    let b = insert_node(&f, "Build", task_statuses::TODO_STATUS_ID, None, None).await;
+    insert_edge(&f, &a, &b).await;
+    let start = now();
+    digest::send_due(&f.state, start).await.unwrap();
+
+    let c = insert_node(&f, "Ship", task_statuses::TODO_STATUS_ID, None, None).await;
+    insert_edge(&f, &b, &c).await;
+    let mut failing = f.state.clone();
+    failing.mail = Arc::new(FailingMailer);
+    assert_eq!(digest::send_due(&failing, start + 24 * 60 * 60).await.unwrap(), 0);
+
+    // The retry still reports Ship as new.
+    assert_eq!(digest::send_due(&f.state, start + 24 * 60 * 60 + 1).await.unwrap(), 1);
+    let mail = f.mailer.sent_to(OWNER_EMAIL);
+    assert_eq!(mail.len(), 2);
+    assert!(mail[1].body.contains("1 blocked (1 new)"));
+}
@@ -0,0 +1,187 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use boardtask::app::db::email_outbox;
+use boardtask::app::mail::{outbox, EmailError, EmailMessage, EmailSender, OutboxMailer};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+/// Transport that always fails, counting attempts.
+#[derive(Default)]
+struct FailingTransport {
+    attempts: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl EmailSender for FailingTransport {
+    async fn send(&self, _message: &EmailMessage) -> Result<(), EmailError> {
+        self.attempts.fetch_add(1, Ordering::SeqCst);
+        Err(EmailError::Smtp("connection refused".to_string()))
+    }
+}
+
+/// Far enough ahead that freshly queued mail is due, with room to step past every backoff.
+const LATER: i64 = 4_102_444_800;
+
+/// Run the dispatcher once per allowed attempt, stepping the clock past each backoff.
+async fn fail_until_dead(pool: &sqlx::SqlitePool, transport: &FailingTransport) {
+    for i in 0..outbox::MAX_ATTEMPTS {
+        outbox::dispatch_due(pool, transport, LATER + i * 2 * 3600).await.unwrap();
+    }
+}
+
+async fn enqueue(pool: &sqlx::SqlitePool, to: &str) -> String {
+    let mailer = OutboxMailer::new(pool.clone());
+    let message = EmailMessage::new(
+        boardtask::app::domain::Email::new(to.to_string()).unwrap(),
+        "Hello".to_string(),
+        "Body".to_string(),
+        "noreply@example.com",
+    );
+    mailer.send(&message).await.unwrap();
+    email_outbox::find_due(pool, LATER, 100)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.to_email == to)
+        .expect("queued email")
+        .id
+}
+
+#[tokio::test]
+async fn signup_enqueues_verification_email() {
+    let pool = test_pool().await;
+    let mut state = test_state(pool.clone());
+    state.mail = Arc::new(OutboxMailer::new(pool.clone()));
+    let app = boardtask::create_router(state);
+
+    let request = http::Request::builder()
+        .method("POST")
+        .uri("/signup")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(axum::body::Body::from(signup_form_body("queued@example.com", "Password123", "Password123")))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::SEE_OTHER);
+
+    let due = email_outbox::find_due(&pool, LATER, 100).await.unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].to_email, "queued@example.com");
+    assert_eq!(due[0].status, email_outbox::STATUS_PENDING);
+}
+
+#[tokio::test]
+async fn dispatch_delivers_and_marks_sent() {
+    let pool = test_pool().await;
+    let id = enqueue(&pool, "ok@example.com").await;
+    let transport = RecordingMailer::default();
+
+    let sent = outbox::dispatch_due(&pool, &transport, LATER).await.unwrap();
+
+    assert_eq!(sent, 1);
+    assert_eq!(transport.sent_to("ok@example.com").len(), 1);
+    let email = email_outbox::find_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(email.status, email_outbox::STATUS_SENT);
+    assert!(email.sent_at.is_some());
+}
+
+#[tokio::test]
+async fn failed_delivery_backs_off_exponentially() {
+    let pool = test_pool().await;
+    let id = enqueue(&pool, "retry@example.com").await;
+    let transport = FailingTransport::default();
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+
+    outbox::dispatch_due(&pool, &transport, now).await.unwrap();
+    let email = email_outbox::find_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(email.status, email_outbox::STATUS_PENDING);
+    assert_eq!(email.attempts, 1);
+    assert_eq!(email.next_attempt_at, now + outbox::backoff_secs(1));
+    assert!(email.last_error.unwrap().contains("connection refused"));
+
+    // Not due yet: nothing is attempted.
+    outbox::dispatch_due(&pool, &transport, now + 1).await.unwrap();
+    assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+
+    let next = email.next_attempt_at;
+    outbox::dispatch_due(&pool, &transport, next).await.unwrap();
+    let email = email_outbox::find_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(email.attempts, 2);
+    assert_eq!(email.next_attempt_at, next + outbox::backoff_secs(2));
+}
+
+#[tokio::test]
+async fn exhausted_retries_dead_letter_the_email() {
+    let pool = test_pool().await;
+    let id = enqueue(&pool, "dead@example.com").await;
+    let transport = FailingTransport::default();
+
+    fail_until_dead(&pool, &transport).await;
+
+    let email = email_outbox::find_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(email.status, email_outbox::STATUS_DEAD);
+    assert_eq!(email.attempts, outbox::MAX_ATTEMPTS);
+
+    // Dead letters are never retried automatically.
+    outbox::dispatch_due(&pool, &transport, LATER * 2).await.unwrap();
+    assert_eq!(transport.attempts.load(Ordering::SeqCst), outbox::MAX_ATTEMPTS as usize);
+}
+
+#[tokio::test]
+async fn admin_page_hidden_from_non_admins() {
+    let (cookie, _project_id, _pool, app, _) = setup_user_and_project("member@example.com", "Password123").await;
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri("/app/admin/email")
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn admin_can_view_and_retry_dead_letters() {
+    let pool = test_pool().await;
+    let mut state = test_state(pool.clone());
+    state.config.admin_emails = vec!["admin@example.com".to_string()];
+    let app = boardtask::create_router(state);
+    let cookie = authenticated_cookie(&pool, &app, "admin@example.com", "Password123").await;
+
+    let id = enqueue(&pool, "bounced@example.com").await;
+    let transport = FailingTransport::default();
+    fail_until_dead(&pool, &transport).await;
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri("/app/admin/email")
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body_str = String::from_utf8_lossy(&body_bytes);
+    assert!(body_str.contains("bounced@example.com"));
+    assert!(body_str.contains("connection refused"));
+
+    let request = http::Request::builder()
+        .method("POST")
+        .uri(format!("/app/admin/email/{}/retry", id))
+        .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::SEE_OTHER);
+
+    let email = email_outbox::find_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(email.status, email_outbox::STATUS_PENDING);
+    assert_eq!(email.attempts, 0);
+}
@@ -67,7 +67,7 @@ async fn post_node_returns_401_without_valid_session() {
 
 #[tokio::test]
 async fn post_node_succeeds() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("node@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("node@example.com", "Password123").await;
 
     let request_body = serde_json::json!({
         "node_type_id": TASK_NODE_TYPE_ID,
@@ -81,6 +81,7 @@ async fn post_node_succeeds() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -118,6 +119,7 @@ async fn post_node_404_for_nonexistent_project() {
         .uri(&format!("/api/projects/{}/nodes", nonexistent_project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -133,6 +135,7 @@ async fn post_node_404_for_nonexistent_project() {
         .uri(&format!("/api/projects/{}/slots", nonexistent_project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(serde_json::json!({ "name": "FE 1" }).to_string()))
         .unwrap();
     let slot_res = app.oneshot(post_slot_req).await.unwrap();
@@ -155,6 +158,7 @@ async fn post_node_404_for_project_owned_by_other_user() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie_b)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie_b).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -168,7 +172,7 @@ async fn post_node_404_for_project_owned_by_other_user() {
 
 #[tokio::test]
 async fn post_node_invalid_node_type_returns_error() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("invalidtype@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("invalidtype@example.com", "Password123").await;
 
     let request_body = serde_json::json!({
         "node_type_id": "invalid-node-type-id",
@@ -180,6 +184,7 @@ async fn post_node_invalid_node_type_returns_error() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -193,7 +198,7 @@ async fn post_node_invalid_node_type_returns_error() {
 
 #[tokio::test]
 async fn post_node_invalid_status_id_returns_error() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("invalidstatus@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("invalidstatus@example.com", "Password123").await;
 
     let request_body = serde_json::json!({
         "node_type_id": TASK_NODE_TYPE_ID,
@@ -206,6 +211,7 @@ async fn post_node_invalid_status_id_returns_error() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -219,13 +225,14 @@ async fn post_node_invalid_status_id_returns_error() {
 
 #[tokio::test]
 async fn post_node_with_slot_id_succeeds() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("nodeslot@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("nodeslot@example.com", "Password123").await;
 
     let post_slot_req = http::Request::builder()
         .method("POST")
         .uri(&format!("/api/projects/{}/slots", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(serde_json::json!({ "name": "FE 1" }).to_string()))
         .unwrap();
     let post_slot_res = app.clone().oneshot(post_slot_req).await.unwrap();
@@ -244,6 +251,7 @@ async fn post_node_with_slot_id_succeeds() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -271,6 +279,7 @@ async fn post_node_with_assigned_user_id_succeeds() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -284,7 +293,7 @@ async fn post_node_with_assigned_user_id_succeeds() {
 
 #[tokio::test]
 async fn post_node_invalid_assigned_user_id_returns_error() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("invassignee@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("invassignee@example.com", "Password123").await;
 
     let request_body = serde_json::json!({
         "node_type_id": TASK_NODE_TYPE_ID,
@@ -297,6 +306,7 @@ async fn post_node_invalid_assigned_user_id_returns_error() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -310,7 +320,7 @@ async fn post_node_invalid_assigned_user_id_returns_error() {
 
 #[tokio::test]
 async fn post_node_invalid_slot_id_returns_error() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("invslot@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("invslot@example.com", "Password123").await;
 
     let request_body = serde_json::json!({
         "node_type_id": TASK_NODE_TYPE_ID,
@@ -323,6 +333,7 @@ async fn post_node_invalid_slot_id_returns_error() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -379,6 +390,7 @@ async fn post_node_slot_id_from_other_project_returns_error() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -400,6 +412,7 @@ async fn post_slot_with_assigned_user_id_succeeds() {
         .uri(&format!("/api/projects/{}/slots", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(
             serde_json::json!({ "name": "FE 1", "assigned_user_id": user_id }).to_string(),
         ))
@@ -415,13 +428,14 @@ async fn post_slot_with_assigned_user_id_succeeds() {
 
 #[tokio::test]
 async fn post_slot_invalid_assigned_user_id_returns_error() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("slotinvassignee@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("slotinvassignee@example.com", "Password123").await;
 
     let post_slot_req = http::Request::builder()
         .method("POST")
         .uri(&format!("/api/projects/{}/slots", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(
             serde_json::json!({ "name": "FE 1", "assigned_user_id": "01JXXXXXXXXXXXXXX" }).to_string(),
         ))
@@ -444,6 +458,7 @@ async fn patch_slot_set_assigned_user_id_then_clear() {
         .uri(&format!("/api/projects/{}/slots", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(serde_json::json!({ "name": "BE 1" }).to_string()))
         .unwrap();
     let post_slot_res = app.clone().oneshot(post_slot_req).await.unwrap();
@@ -458,6 +473,7 @@ async fn patch_slot_set_assigned_user_id_then_clear() {
         .uri(&format!("/api/projects/{}/slots/{}", project_id, slot_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(
             serde_json::json!({ "assigned_user_id": user_id }).to_string(),
         ))
@@ -473,6 +489,7 @@ async fn patch_slot_set_assigned_user_id_then_clear() {
         .uri(&format!("/api/projects/{}/slots/{}", project_id, slot_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(
             serde_json::json!({ "name": "BE 1", "assigned_user_id": "" }).to_string(),
         ))
@@ -508,6 +525,7 @@ async fn patch_slot_without_assigned_user_id_leaves_unchanged() {
         .uri(&format!("/api/projects/{}/slots", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(
             serde_json::json!({ "name": "BE 1", "assigned_user_id": user_id }).to_string(),
         ))
@@ -523,6 +541,7 @@ async fn patch_slot_without_assigned_user_id_leaves_unchanged() {
         .uri(&format!("/api/projects/{}/slots/{}", project_id, slot_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(serde_json::json!({ "name": "Back-end Developer 1" }).to_string()))
         .unwrap();
     let patch_res = app.oneshot(patch_req).await.unwrap();
@@ -535,13 +554,14 @@ async fn patch_slot_without_assigned_user_id_leaves_unchanged() {
 
 #[tokio::test]
 async fn post_node_with_parent_id_succeeds() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("parentid@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("parentid@example.com", "Password123").await;
 
     let post_group_req = http::Request::builder()
         .method("POST")
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(
             serde_json::json!({ "node_type_id": TASK_NODE_TYPE_ID, "title": "Group" }).to_string(),
         ))
@@ -563,6 +583,7 @@ async fn post_node_with_parent_id_succeeds() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -591,7 +612,7 @@ async fn post_node_with_parent_id_succeeds() {
 
 #[tokio::test]
 async fn post_node_invalid_parent_id_returns_error() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("invparent@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("invparent@example.com", "Password123").await;
 
     let request_body = serde_json::json!({
         "node_type_id": TASK_NODE_TYPE_ID,
@@ -604,6 +625,7 @@ async fn post_node_invalid_parent_id_returns_error() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -665,6 +687,7 @@ async fn post_node_parent_id_from_other_project_returns_error() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -678,7 +701,7 @@ async fn post_node_parent_id_from_other_project_returns_error() {
 
 #[tokio::test]
 async fn post_node_with_status_then_patch_and_get_graph() {
-    let (cookie, project_id, _pool, app, _) = setup_user_and_project("statusflow@example.com", "Password123").await;
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("statusflow@example.com", "Password123").await;
 
     // Create node with explicit "In progress" status
     let request_body = serde_json::json!({
@@ -692,6 +715,7 @@ async fn post_node_with_status_then_patch_and_get_graph() {
         .uri(&format!("/api/projects/{}/nodes", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -710,6 +734,7 @@ async fn post_node_with_status_then_patch_and_get_graph() {
         .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(patch_body.to_string()))
         .unwrap();
 
@@ -806,6 +831,7 @@ async fn post_node_with_status_then_patch_and_get_graph() {
             .method("DELETE")
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, b_id))
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::empty())
             .unwrap();
         let delete_response = app.clone().oneshot(delete_request).await.unwrap();
@@ -919,6 +945,7 @@ async fn post_node_with_status_then_patch_and_get_graph() {
             .method("DELETE")
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, m_id))
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::empty())
             .unwrap();
         let delete_response = app.clone().oneshot(delete_request).await.unwrap();
@@ -991,6 +1018,7 @@ async fn post_node_with_status_then_patch_and_get_graph() {
             .method("DELETE")
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::empty())
             .unwrap();
         let delete_response = app.clone().oneshot(delete_request).await.unwrap();
@@ -1055,6 +1083,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(request_body.to_string()))
             .unwrap();
 
@@ -1095,6 +1124,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/slots", project_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(serde_json::json!({ "name": "BE 1" }).to_string()))
             .unwrap();
         let post_slot_res = app.clone().oneshot(post_slot_req).await.unwrap();
@@ -1107,6 +1137,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/slots/{}", project_id, slot_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(serde_json::json!({ "name": "Back-end Developer 1" }).to_string()))
             .unwrap();
         let patch_slot_res = app.clone().oneshot(patch_slot_req).await.unwrap();
@@ -1135,6 +1166,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(patch_body.to_string()))
             .unwrap();
 
@@ -1165,6 +1197,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(clear_body.to_string()))
             .unwrap();
 
@@ -1194,6 +1227,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(serde_json::json!({ "slot_id": slot_id }).to_string()))
             .unwrap();
         let _ = app.clone().oneshot(set_slot_req).await.unwrap();
@@ -1202,6 +1236,7 @@ mod patch_node {
             .method("DELETE")
             .uri(&format!("/api/projects/{}/slots/{}", project_id, slot_id))
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::empty())
             .unwrap();
         let delete_slot_res = app.clone().oneshot(delete_slot_req).await.unwrap();
@@ -1236,6 +1271,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(patch_body.to_string()))
             .unwrap();
 
@@ -1273,6 +1309,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(patch_body.to_string()))
             .unwrap();
 
@@ -1303,6 +1340,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(clear_body.to_string()))
             .unwrap();
 
@@ -1316,13 +1354,14 @@ mod patch_node {
 
     #[tokio::test]
     async fn patch_node_set_parent_id_then_clear() {
-        let (cookie, project_id, _pool, app, _) = setup_user_and_project("patchparent@example.com", "Password123").await;
+        let (cookie, project_id, pool, app, _) = setup_user_and_project("patchparent@example.com", "Password123").await;
 
         let post_group_req = http::Request::builder()
             .method("POST")
             .uri(&format!("/api/projects/{}/nodes", project_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(
                 serde_json::json!({ "node_type_id": TASK_NODE_TYPE_ID, "title": "Group" }).to_string(),
             ))
@@ -1338,6 +1377,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes", project_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(
                 serde_json::json!({ "node_type_id": TASK_NODE_TYPE_ID, "title": "Child" }).to_string(),
             ))
@@ -1354,6 +1394,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, child_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(patch_body.to_string()))
             .unwrap();
 
@@ -1384,6 +1425,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, child_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(clear_body.to_string()))
             .unwrap();
 
@@ -1433,6 +1475,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, node_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(patch_body.to_string()))
             .unwrap();
 
@@ -1504,6 +1547,7 @@ mod patch_node {
             .uri(&format!("/api/projects/{}/nodes/{}", project_id, project_node_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(patch_body.to_string()))
             .unwrap();
 
@@ -1590,6 +1634,7 @@ async fn post_edge_succeeds() {
         .uri(&format!("/api/projects/{}/edges", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -1633,6 +1678,7 @@ async fn post_edge_rejects_self_referential() {
         .uri(&format!("/api/projects/{}/edges", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -1687,6 +1733,7 @@ async fn post_edge_duplicate_returns_conflict_or_error() {
         .uri(&format!("/api/projects/{}/edges", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
     let response1 = app.clone().oneshot(request1).await.unwrap();
@@ -1697,6 +1744,7 @@ async fn post_edge_duplicate_returns_conflict_or_error() {
         .uri(&format!("/api/projects/{}/edges", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
     let response2 = app.oneshot(request2).await.unwrap();
@@ -1771,6 +1819,7 @@ async fn post_edge_404_when_node_not_in_project() {
         .uri(&format!("/api/projects/{}/edges", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(request_body.to_string()))
         .unwrap();
 
@@ -1841,6 +1890,7 @@ async fn post_edge_404_when_node_not_in_project() {
             .uri(&format!("/api/projects/{}/edges/insert-between", project_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(request_body.to_string()))
             .unwrap();
 
@@ -1945,6 +1995,7 @@ async fn post_edge_404_when_node_not_in_project() {
             .uri(&format!("/api/projects/{}/edges/insert-between", project_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(request_body.to_string()))
             .unwrap();
 
@@ -2010,6 +2061,7 @@ mod get_graph {
             .uri(&format!("/api/projects/{}/slots", project_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(post_slot_body.to_string()))
             .unwrap();
         let post_slot_res = app.clone().oneshot(post_slot_req).await.unwrap();
@@ -2033,6 +2085,7 @@ mod get_graph {
             .uri(&format!("/api/projects/{}/slots", project_id))
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(serde_json::json!({ "name": "Front-end Developer 1" }).to_string()))
             .unwrap();
         let post_dup_res = app.clone().oneshot(post_dup_req).await.unwrap();
@@ -2112,6 +2165,123 @@ mod get_graph {
     }
 }
 
+mod get_graph_filters {
+    use super::*;
+
+    async fn insert_node(
+        pool: &sqlx::SqlitePool,
+        project_id: &str,
+        title: &str,
+        status_id: &str,
+        estimated_minutes: Option<i64>,
+    ) -> String {
+        let id = ulid::Ulid::new().to_string();
+        let node = db::nodes::NewNode {
+            id: id.clone(),
+            project_id: project_id.to_string(),
+            node_type_id: TASK_NODE_TYPE_ID.to_string(),
+            status_id: status_id.to_string(),
+            title: title.to_string(),
+            description: None,
+            estimated_minutes,
+            slot_id: None,
+            assigned_user_id: None,
+            parent_id: None,
+        };
+        db::nodes::insert(pool, &node).await.unwrap();
+        id
+    }
+
+    async fn insert_edge(pool: &sqlx::SqlitePool, parent_id: &str, child_id: &str) {
+        let edge = db::node_edges::NewNodeEdge {
+            parent_id: parent_id.to_string(),
+            child_id: child_id.to_string(),
+        };
+        db::node_edges::insert(pool, &edge).await.unwrap();
+    }
+
+    async fn get(app: &axum::Router, cookie: &str, uri: String) -> (http::StatusCode, serde_json::Value) {
+        let request = http::Request::builder()
+            .method("GET")
+            .uri(uri)
+            .header("cookie", cookie)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body_bytes).unwrap())
+    }
+
+    fn titles(body: &serde_json::Value) -> Vec<&str> {
+        body["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["title"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn filters_by_status_and_text() {
+        let (cookie, project_id, pool, app, _) = setup_user_and_project("filterstatus@example.com", "Password123").await;
+        insert_node(&pool, &project_id, "Write spec", DEFAULT_STATUS_ID, None).await;
+        insert_node(&pool, &project_id, "Write tests", db::task_statuses::DONE_STATUS_ID, None).await;
+        insert_node(&pool, &project_id, "Deploy", DEFAULT_STATUS_ID, None).await;
+
+        let (status, body) = get(&app, &cookie, format!("/api/projects/{}/graph?status={}&q=WRITE", project_id, DEFAULT_STATUS_ID)).await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(titles(&body), vec!["Write spec"]);
+        assert_eq!(body["matched_node_ids"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn blocked_filter_with_neighbors_keeps_graph_connected() {
+        let (cookie, project_id, pool, app, _) = setup_user_and_project("filterblocked@example.com", "Password123").await;
+        // A -> B -> C: only C is blocked.
+        let a = insert_node(&pool, &project_id, "A", DEFAULT_STATUS_ID, None).await;
+        let b = insert_node(&pool, &project_id, "B", DEFAULT_STATUS_ID, None).await;
+        let c = insert_node(&pool, &project_id, "C", DEFAULT_STATUS_ID, None).await;
+        insert_edge(&pool, &a, &b).await;
+        insert_edge(&pool, &b, &c).await;
+
+        let (_, body) = get(&app, &cookie, format!("/api/projects/{}/graph?blocked=true", project_id)).await;
+        assert_eq!(titles(&body), vec!["C"]);
+        assert!(body["edges"].as_array().unwrap().is_empty());
+
+        let (_, body) = get(&app, &cookie, format!("/api/projects/{}/graph?blocked=true&neighbors=true", project_id)).await;
+        assert_eq!(titles(&body), vec!["B", "C"]);
+        assert_eq!(body["edges"].as_array().unwrap().len(), 1);
+        assert_eq!(body["matched_node_ids"], serde_json::json!([c]));
+    }
+
+    #[tokio::test]
+    async fn sorts_by_estimate_descending() {
+        let (cookie, project_id, pool, app, _) = setup_user_and_project("filtersort@example.com", "Password123").await;
+        insert_node(&pool, &project_id, "Small", DEFAULT_STATUS_ID, Some(15)).await;
+        insert_node(&pool, &project_id, "Unestimated", DEFAULT_STATUS_ID, None).await;
+        insert_node(&pool, &project_id, "Large", DEFAULT_STATUS_ID, Some(240)).await;
+
+        let (status, body) = get(&app, &cookie, format!("/api/projects/{}/graph?sort=-estimate", project_id)).await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(titles(&body), vec!["Large", "Small", "Unestimated"]);
+        assert!(body.get("matched_node_ids").is_none());
+    }
+
+    #[tokio::test]
+    async fn invalid_filter_returns_400() {
+        let (cookie, project_id, _pool, app, _) = setup_user_and_project("filterbad@example.com", "Password123").await;
+
+        let (status, _) = get(&app, &cookie, format!("/api/projects/{}/graph?sort=priority", project_id)).await;
+        assert_eq!(status, http::StatusCode::BAD_REQUEST);
+
+        let (status, _) = get(&app, &cookie, format!("/api/projects/{}/graph?blocked=maybe", project_id)).await;
+        assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    }
+}
+
 mod get_project_members {
     use super::*;
 
@@ -222,6 +222,7 @@ mod import_tests {
             .uri("/api/projects/import")
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(body.to_string()))
             .unwrap();
 
@@ -251,6 +252,7 @@ mod import_tests {
             .uri("/api/projects/import")
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(body.to_string()))
             .unwrap();
 
@@ -273,6 +275,7 @@ mod import_tests {
             .uri("/api/projects/import")
             .header("content-type", "application/json")
             .header("cookie", &cookie)
+            .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
             .body(axum::body::Body::from(body.to_string()))
             .unwrap();
 
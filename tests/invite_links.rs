@@ -0,0 +1,149 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db;
+use boardtask::app::domain::{OrganizationId, OrganizationRole, UserId};
+
+async fn get(app: &axum::Router, cookie: &str, uri: &str) -> (http::StatusCode, String, String) {
+    let request = http::Request::builder()
+        .uri(uri)
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let location = response
+        .headers()
+        .get("location")
+        .map(|v| v.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, location, String::from_utf8_lossy(&bytes).to_string())
+}
+
+/// Confirm joining through a link, as the confirm page's form does.
+async fn join(app: &axum::Router, pool: &sqlx::SqlitePool, cookie: &str, token: &str) -> (http::StatusCode, String) {
+    post_form(app, pool, cookie, "/join/confirm", &format!("token={}", urlencoding::encode(token))).await
+}
+
+async fn add_team(pool: &sqlx::SqlitePool, org_id: &str, name: &str) -> String {
+    let id = ulid::Ulid::new().to_string();
+    db::teams::insert(pool, &db::teams::NewTeam {
+        id: id.clone(),
+        organization_id: org_id.to_string(),
+        name: name.to_string(),
+    })
+    .await
+    .unwrap();
+    id
+}
+
+#[tokio::test]
+async fn bulk_invites_send_valid_rows_and_report_the_rest() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("bulkowner@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let design_id = add_team(&pool, &org_id, "Design").await;
+
+    let entries = "email,role,team\nada@example.com\ngrace@example.com,admin,design\nnobody\nlin@example.com,member,Marketing\n";
+    let body = format!("entries={}&default_role=viewer", urlencoding::encode(entries));
+    let (status, location) = post_form(&app, &pool, &owner, "/app/settings/organization/invite/bulk", &body).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    let location = urlencoding::decode(&location).unwrap().replace('+', " ");
+    assert!(location.contains("Sent 2 invites."), "{}", location);
+    assert!(location.contains("nobody") && location.contains("Marketing"), "{}", location);
+
+    let org = OrganizationId::from_string(&org_id).unwrap();
+    let pending = db::organization_invites::list_pending_for_org(&pool, &org).await.unwrap();
+    assert_eq!(pending.len(), 2);
+    let ada = pending.iter().find(|i| i.email == "ada@example.com").unwrap();
+    assert_eq!((ada.role.as_str(), ada.team_id.as_deref()), ("viewer", None));
+    let grace = pending.iter().find(|i| i.email == "grace@example.com").unwrap();
+    assert_eq!((grace.role.as_str(), grace.team_id.as_deref()), ("admin", Some(design_id.as_str())));
+}
+
+#[tokio::test]
+async fn invite_link_joins_until_its_uses_run_out() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("linkowner@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let team_id = add_team(&pool, &org_id, "Support").await;
+
+    let body = format!("role=viewer&team_id={}&max_uses=1&expires_in_days=7", team_id);
+    let (_, location) = post_form(&app, &pool, &owner, "/app/settings/organization/invite-links", &body).await;
+    assert!(location.contains("success="), "{}", location);
+    let link = db::organization_invite_links::list_active_for_org(&pool, &org_id).await.unwrap().remove(0);
+    let (_, _, page) = get(&app, &owner, "/app/settings/organization").await;
+    assert!(page.contains(&format!("/join?token={}", link.token)));
+
+    let (_, _, page) = get(&app, "", &format!("/join?token={}", link.token)).await;
+    assert!(page.contains("Join Test Org"), "{}", page);
+
+    let joiner = authenticated_cookie(&pool, &app, "joiner@example.com", "Password123").await;
+    let joiner_id = UserId::from_string(&user_id_from_cookie(&pool, &joiner).await).unwrap();
+    let org = OrganizationId::from_string(&org_id).unwrap();
+    let (status, _, page) = get(&app, &joiner, &format!("/join/confirm?token={}", link.token)).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(page.contains("action=\"/join/confirm\""), "{}", page);
+    assert_eq!(db::organizations::find_member_role(&pool, &org, &joiner_id).await.unwrap(), None, "the page alone doesn't join");
+
+    let (status, location) = join(&app, &pool, &joiner, &link.token).await;
+    assert_eq!((status, location.as_str()), (http::StatusCode::SEE_OTHER, "/app"));
+    assert_eq!(db::organizations::find_member_role(&pool, &org, &joiner_id).await.unwrap(), Some(OrganizationRole::Viewer));
+    assert!(db::team_members::is_member(&pool, &team_id, &joiner_id).await.unwrap());
+
+    let late = authenticated_cookie(&pool, &app, "late@example.com", "Password123").await;
+    let (_, location) = join(&app, &pool, &late, &link.token).await;
+    assert!(location.starts_with("/join?token="), "used-up link sends them back: {}", location);
+    let (_, location, _) = get(&app, &late, &format!("/join/confirm?token={}", link.token)).await;
+    assert!(location.starts_with("/join?token="), "{}", location);
+    let (_, _, page) = get(&app, "", &format!("/join?token={}", link.token)).await;
+    assert!(page.contains("used up"));
+}
+
+#[tokio::test]
+async fn revoked_links_stop_working_and_links_cannot_make_owners() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("revoker@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+
+    let (_, location) = post_form(&app, &pool, &owner, "/app/settings/organization/invite-links",
+        "role=owner&team_id=&max_uses=5&expires_in_days=7").await;
+    assert!(location.contains("error="), "{}", location);
+
+    post_form(&app, &pool, &owner, "/app/settings/organization/invite-links",
+        "role=member&team_id=&max_uses=5&expires_in_days=7").await;
+    let link = db::organization_invite_links::list_active_for_org(&pool, &org_id).await.unwrap().remove(0);
+    let (_, location) = post_form(&app, &pool, &owner,
+        &format!("/app/settings/organization/invite-links/{}/revoke", link.id), "").await;
+    assert!(location.contains("success="), "{}", location);
+
+    let joiner = authenticated_cookie(&pool, &app, "toolate@example.com", "Password123").await;
+    let (_, location) = join(&app, &pool, &joiner, &link.token).await;
+    assert!(location.starts_with("/join?token="), "{}", location);
+}
+
+#[tokio::test]
+async fn joining_through_a_link_requires_the_csrf_token() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("csrflink@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    post_form(&app, &pool, &owner, "/app/settings/organization/invite-links",
+        "role=member&team_id=&max_uses=5&expires_in_days=7").await;
+    let link = db::organization_invite_links::list_active_for_org(&pool, &org_id).await.unwrap().remove(0);
+
+    let victim = authenticated_cookie(&pool, &app, "victim@example.com", "Password123").await;
+    let victim_id = UserId::from_string(&user_id_from_cookie(&pool, &victim).await).unwrap();
+    let request = http::Request::builder()
+        .method("POST")
+        .uri("/join/confirm")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .header("cookie", &victim)
+        .body(axum::body::Body::from(format!("token={}", link.token)))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+    let org = OrganizationId::from_string(&org_id).unwrap();
+    assert_eq!(db::organizations::find_member_role(&pool, &org, &victim_id).await.unwrap(), None);
+}
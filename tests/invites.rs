@@ -55,6 +55,7 @@ async fn create_invite_as_owner_succeeds_and_creates_db_row() {
         .uri("/app/settings/organization/invite")
         .header("content-type", "application/x-www-form-urlencoded")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(body))
         .unwrap();
     let response = app.clone().oneshot(request).await.unwrap();
@@ -76,6 +77,32 @@ async fn create_invite_as_owner_succeeds_and_creates_db_row() {
     assert_eq!(pending[0].role, "member");
 }
 
+#[tokio::test]
+async fn create_invite_sends_multipart_email_branded_with_org_name() {
+    let mailer = RecordingMailer::default();
+    let (cookie, _project_id, pool, app, _) =
+        setup_user_and_project_with_mail("brand@example.com", "Password123", std::sync::Arc::new(mailer.clone())).await;
+
+    let request = http::Request::builder()
+        .method("POST")
+        .uri("/app/settings/organization/invite")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
+        .body(axum::body::Body::from(invite_form_body("branded@example.com", "member")))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::SEE_OTHER);
+
+    let sent = mailer.sent_to("branded@example.com");
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].subject, "You're invited to join Test Org");
+    assert!(sent[0].body.contains("/accept-invite?token="));
+    let html = sent[0].html_body.as_deref().expect("html part");
+    assert!(html.contains("Test Org"));
+    assert!(html.contains("Accept invitation"));
+}
+
 #[tokio::test]
 async fn create_invite_same_email_different_role_cancels_previous_and_creates_new() {
     let (cookie, _project_id, pool, app, _) = setup_user_and_project("owner2@example.com", "Password123").await;
@@ -96,6 +123,7 @@ async fn create_invite_same_email_different_role_cancels_previous_and_creates_ne
         .uri("/app/settings/organization/invite")
         .header("content-type", "application/x-www-form-urlencoded")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(body1))
         .unwrap();
     let response1 = app.clone().oneshot(request1).await.unwrap();
@@ -118,6 +146,7 @@ async fn create_invite_same_email_different_role_cancels_previous_and_creates_ne
         .uri("/app/settings/organization/invite")
         .header("content-type", "application/x-www-form-urlencoded")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(body2))
         .unwrap();
     let response2 = app.oneshot(request2).await.unwrap();
@@ -164,6 +193,7 @@ async fn accept_invite_new_user_get_shows_signup_link_no_password_form() {
         token: token.to_string(),
         expires_at: now + 86400 * 7,
         created_at: now,
+        team_id: None,
     };
     boardtask::app::db::organization_invites::insert(&pool, &invite).await.unwrap();
 
@@ -242,6 +272,7 @@ async fn accept_invite_as_new_user_creates_user_and_redirects_to_app() {
         token: token.to_string(),
         expires_at,
         created_at: now,
+        team_id: None,
     };
     boardtask::app::db::organization_invites::insert(&pool, &invite).await.unwrap();
 
@@ -347,6 +378,7 @@ async fn accept_invite_as_new_user_then_app_accessible() {
         token: token.to_string(),
         expires_at: now + 86400 * 7,
         created_at: now,
+        team_id: None,
     };
     boardtask::app::db::organization_invites::insert(&pool, &invite).await.unwrap();
 
@@ -460,6 +492,7 @@ async fn accept_invite_as_existing_user_switches_org() {
         token: token.to_string(),
         expires_at: now + 86400 * 7,
         created_at: now,
+        team_id: None,
     };
     boardtask::app::db::organization_invites::insert(&pool, &invite).await.unwrap();
 
@@ -552,6 +585,7 @@ async fn accept_invite_as_existing_user_session_and_user_org_stay_in_sync() {
         token: token.to_string(),
         expires_at: now + 86400 * 7,
         created_at: now,
+        team_id: None,
     };
     boardtask::app::db::organization_invites::insert(&pool, &invite).await.unwrap();
 
@@ -668,6 +702,7 @@ async fn accept_invite_expired_token_shows_error() {
         token: token.to_string(),
         expires_at: now - 1,
         created_at: now - 86400,
+        team_id: None,
     };
     boardtask::app::db::organization_invites::insert(&pool, &invite).await.unwrap();
 
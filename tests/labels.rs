@@ -0,0 +1,242 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+
+async fn send_json(
+    app: &axum::Router,
+    pool: &sqlx::SqlitePool,
+    cookie: &str,
+    method: &str,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> (http::StatusCode, serde_json::Value) {
+    let request = http::Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("cookie", cookie)
+        .header("content-type", "application/json")
+        .header("x-csrf-token", csrf_token_from_cookie(pool, cookie).await)
+        .body(axum::body::Body::from(body.map(|b| b.to_string()).unwrap_or_default()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+async fn create_label(app: &axum::Router, pool: &sqlx::SqlitePool, cookie: &str, project_id: &str, name: &str) -> String {
+    let (status, body) = send_json(
+        app,
+        pool,
+        cookie,
+        "POST",
+        &format!("/api/projects/{}/labels", project_id),
+        Some(serde_json::json!({ "name": name, "color": "#FF8800" })),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::CREATED);
+    assert_eq!(body["color"], "#ff8800");
+    body["id"].as_str().unwrap().to_string()
+}
+
+async fn create_node(
+    app: &axum::Router,
+    pool: &sqlx::SqlitePool,
+    cookie: &str,
+    project_id: &str,
+    title: &str,
+    label_ids: &[&str],
+) -> String {
+    let (status, body) = send_json(
+        app,
+        pool,
+        cookie,
+        "POST",
+        &format!("/api/projects/{}/nodes", project_id),
+        Some(serde_json::json!({ "node_type_id": TASK_NODE_TYPE_ID, "title": title, "label_ids": label_ids })),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::CREATED);
+    body["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn labels_are_set_on_nodes_and_returned_with_the_graph() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("labels@example.com", "Password123").await;
+    ensure_graph_seeds(&pool).await;
+    let bug = create_label(&app, &pool, &cookie, &project_id, "Bug").await;
+    let ux = create_label(&app, &pool, &cookie, &project_id, "UX").await;
+
+    let (status, _) = send_json(
+        &app,
+        &pool,
+        &cookie,
+        "POST",
+        &format!("/api/projects/{}/labels", project_id),
+        Some(serde_json::json!({ "name": "bug", "color": "#000000" })),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    let (status, _) = send_json(
+        &app,
+        &pool,
+        &cookie,
+        "POST",
+        &format!("/api/projects/{}/labels", project_id),
+        Some(serde_json::json!({ "name": "Docs", "color": "orange" })),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+
+    let crash = create_node(&app, &pool, &cookie, &project_id, "Crash on save", &[&bug, &ux]).await;
+    create_node(&app, &pool, &cookie, &project_id, "Plain task", &[]).await;
+
+    let (_, graph) = send_json(&app, &pool, &cookie, "GET", &format!("/api/projects/{}/graph", project_id), None).await;
+    assert_eq!(graph["labels"].as_array().unwrap().len(), 2);
+    assert_eq!(graph["node_labels"].as_array().unwrap().len(), 2);
+
+    let (_, filtered) =
+        send_json(&app, &pool, &cookie, "GET", &format!("/api/projects/{}/graph?label={}", project_id, ux), None).await;
+    assert_eq!(filtered["matched_node_ids"], serde_json::json!([crash]));
+
+    // Replace the node's labels, then clear them.
+    let (status, node) = send_json(
+        &app,
+        &pool,
+        &cookie,
+        "PATCH",
+        &format!("/api/projects/{}/nodes/{}", project_id, crash),
+        Some(serde_json::json!({ "label_ids": [ux] })),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(node["label_ids"], serde_json::json!([ux]));
+    let (_, node) = send_json(
+        &app,
+        &pool,
+        &cookie,
+        "PATCH",
+        &format!("/api/projects/{}/nodes/{}", project_id, crash),
+        Some(serde_json::json!({ "title": "Crash on save!" })),
+    )
+    .await;
+    assert_eq!(node["label_ids"], serde_json::json!([ux]));
+
+    // Deleting a label takes it off its nodes.
+    let (status, _) =
+        send_json(&app, &pool, &cookie, "DELETE", &format!("/api/projects/{}/labels/{}", project_id, ux), None).await;
+    assert_eq!(status, http::StatusCode::NO_CONTENT);
+    let (_, graph) = send_json(&app, &pool, &cookie, "GET", &format!("/api/projects/{}/graph", project_id), None).await;
+    assert!(graph["node_labels"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn labels_from_another_organization_are_rejected() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("mine@example.com", "Password123").await;
+    ensure_graph_seeds(&pool).await;
+    let other = authenticated_cookie(&pool, &app, "theirs@example.com", "Password123").await;
+    let other_user = user_id_from_cookie(&pool, &other).await;
+    let other_org = boardtask::app::db::users::find_by_id(&pool, &boardtask::app::domain::UserId::from_string(&other_user).unwrap())
+        .await
+        .unwrap()
+        .unwrap()
+        .organization_id;
+    let foreign = ulid::Ulid::new().to_string();
+    boardtask::app::db::labels::insert(&pool, &boardtask::app::db::labels::NewLabel {
+        id: foreign.clone(),
+        organization_id: other_org,
+        name: "Secret".to_string(),
+        color: "#123456".to_string(),
+    })
+    .await
+    .unwrap();
+
+    let (status, _) = send_json(
+        &app,
+        &pool,
+        &cookie,
+        "POST",
+        &format!("/api/projects/{}/nodes", project_id),
+        Some(serde_json::json!({ "node_type_id": TASK_NODE_TYPE_ID, "title": "Task", "label_ids": [foreign] })),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    let (status, _) = send_json(
+        &app,
+        &pool,
+        &cookie,
+        "DELETE",
+        &format!("/api/projects/{}/labels/{}", project_id, foreign),
+        None,
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn list_view_filters_by_label() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("listlabels@example.com", "Password123").await;
+    ensure_graph_seeds(&pool).await;
+    let bug = create_label(&app, &pool, &cookie, &project_id, "Bug").await;
+    create_node(&app, &pool, &cookie, &project_id, "Labelled task", &[&bug]).await;
+    create_node(&app, &pool, &cookie, &project_id, "Other task", &[]).await;
+
+    let request = http::Request::builder()
+        .uri(format!("/app/projects/{}/list?label={}", project_id, bug))
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let page = String::from_utf8_lossy(&bytes);
+    assert!(page.contains("Labelled task"));
+    assert!(!page.contains("Other task"));
+    assert!(page.contains(&format!("data-label-id=\"{}\"", bug)));
+}
+
+#[tokio::test]
+async fn labels_survive_export_import_and_node_restore() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("roundtrip@example.com", "Password123").await;
+    ensure_graph_seeds(&pool).await;
+    let bug = create_label(&app, &pool, &cookie, &project_id, "Bug").await;
+    let node = create_node(&app, &pool, &cookie, &project_id, "Labelled task", &[&bug]).await;
+
+    let (_, export) = send_json(&app, &pool, &cookie, "GET", &format!("/api/projects/{}/export", project_id), None).await;
+    assert_eq!(export["labels"][0]["name"], "Bug");
+    assert_eq!(export["nodes"][0]["label_ids"], serde_json::json!([bug]));
+
+    // Importing into the same organization reuses the label rather than duplicating it.
+    let (status, _) = send_json(&app, &pool, &cookie, "POST", "/api/projects/import", Some(export)).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    let (_, labels) = send_json(&app, &pool, &cookie, "GET", &format!("/api/projects/{}/labels", project_id), None).await;
+    assert_eq!(labels["labels"].as_array().unwrap().len(), 1);
+    let org_id = boardtask::app::db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap().organization_id;
+    let mut uses = 0;
+    for project in boardtask::app::db::projects::list_for_org(&pool, &org_id).await.unwrap() {
+        let node_labels = boardtask::app::db::labels::find_node_labels_by_project(&pool, &project.id).await.unwrap();
+        uses += node_labels.iter().filter(|nl| nl.label_id == bug).count();
+    }
+    assert_eq!(uses, 2);
+
+    let (status, _) =
+        send_json(&app, &pool, &cookie, "DELETE", &format!("/api/projects/{}/nodes/{}", project_id, node), None).await;
+    assert_eq!(status, http::StatusCode::NO_CONTENT);
+    let (status, _) = send_json(
+        &app,
+        &pool,
+        &cookie,
+        "POST",
+        &format!("/api/projects/{}/nodes/{}/restore", project_id, node),
+        None,
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    let restored = boardtask::app::db::labels::find_ids_for_node(&pool, &node).await.unwrap();
+    assert_eq!(restored, vec![bug]);
+}
@@ -0,0 +1,197 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db;
+use boardtask::app::db::task_statuses::{DONE_STATUS_ID, TODO_STATUS_ID};
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+
+async fn insert_task(pool: &sqlx::SqlitePool, project_id: &str, title: &str, parent_id: Option<&str>) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let node = db::nodes::NewNode {
+        id: id.clone(),
+        project_id: project_id.to_string(),
+        node_type_id: TASK_NODE_TYPE_ID.to_string(),
+        status_id: TODO_STATUS_ID.to_string(),
+        title: title.to_string(),
+        description: None,
+        estimated_minutes: None,
+        slot_id: None,
+        parent_id: parent_id.map(str::to_string),
+        assigned_user_id: None,
+    };
+    db::nodes::insert(pool, &node).await.unwrap();
+    id
+}
+
+async fn link(pool: &sqlx::SqlitePool, parent_id: &str, child_id: &str) {
+    let edge = db::node_edges::NewNodeEdge {
+        parent_id: parent_id.to_string(),
+        child_id: child_id.to_string(),
+    };
+    db::node_edges::insert(pool, &edge).await.unwrap();
+}
+
+async fn edges(pool: &sqlx::SqlitePool, project_id: &str) -> Vec<(String, String)> {
+    let mut edges: Vec<_> = db::node_edges::find_by_project(pool, project_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|e| (e.parent_id, e.child_id))
+        .collect();
+    edges.sort();
+    edges
+}
+
+fn pair(parent: &str, child: &str) -> (String, String) {
+    (parent.to_string(), child.to_string())
+}
+
+async fn send(
+    app: &axum::Router,
+    pool: &sqlx::SqlitePool,
+    cookie: &str,
+    method: &str,
+    uri: &str,
+) -> (http::StatusCode, serde_json::Value) {
+    let request = http::Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("cookie", cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(pool, cookie).await)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+#[tokio::test]
+async fn restore_undoes_the_rewiring_of_a_deleted_middle_node() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("undo@example.com", "Password123").await;
+    let a = insert_task(&pool, &project_id, "A", None).await;
+    let b = insert_task(&pool, &project_id, "B", None).await;
+    let c = insert_task(&pool, &project_id, "C", None).await;
+    let d = insert_task(&pool, &project_id, "D", None).await;
+    link(&pool, &a, &b).await;
+    link(&pool, &b, &c).await;
+    link(&pool, &b, &d).await;
+    // A → D existed before the delete, so restoring B must keep it.
+    link(&pool, &a, &d).await;
+    let before = edges(&pool, &project_id).await;
+
+    let node_uri = format!("/api/projects/{}/nodes/{}", project_id, b);
+    let (status, _) = send(&app, &pool, &cookie, "DELETE", &node_uri).await;
+    assert_eq!(status, http::StatusCode::NO_CONTENT);
+    let mut bridged = vec![pair(&a, &c), pair(&a, &d)];
+    bridged.sort();
+    assert_eq!(edges(&pool, &project_id).await, bridged);
+
+    let (status, body) = send(&app, &pool, &cookie, "POST", &format!("{}/restore", node_uri)).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(body["id"], b);
+    assert_eq!(body["title"], "B");
+    assert_eq!(edges(&pool, &project_id).await, before);
+
+    let (status, _) = send(&app, &pool, &cookie, "POST", &format!("{}/restore", node_uri)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn restore_keeps_a_bypass_edge_that_was_re_added_since() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("re-added@example.com", "Password123").await;
+    let a = insert_task(&pool, &project_id, "A", None).await;
+    let b = insert_task(&pool, &project_id, "B", None).await;
+    let c = insert_task(&pool, &project_id, "C", None).await;
+    link(&pool, &a, &b).await;
+    link(&pool, &b, &c).await;
+
+    let node_uri = format!("/api/projects/{}/nodes/{}", project_id, b);
+    send(&app, &pool, &cookie, "DELETE", &node_uri).await;
+    assert_eq!(edges(&pool, &project_id).await, vec![pair(&a, &c)]);
+    // Someone removes the bridge and draws A → C themselves.
+    db::node_edges::delete(&pool, &a, &c).await.unwrap();
+    let edge = db::node_edges::NewNodeEdge { parent_id: a.clone(), child_id: c.clone() };
+    let later = time::OffsetDateTime::now_utc().unix_timestamp() + 60;
+    db::node_edges::insert_if_not_exists_at(&pool, &edge, later).await.unwrap();
+
+    let (status, _) = send(&app, &pool, &cookie, "POST", &format!("{}/restore", node_uri)).await;
+    assert_eq!(status, http::StatusCode::OK);
+    let mut expected = vec![pair(&a, &b), pair(&a, &c), pair(&b, &c)];
+    expected.sort();
+    assert_eq!(edges(&pool, &project_id).await, expected);
+}
+
+#[tokio::test]
+async fn restore_brings_back_group_members_and_time_entries() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("group-undo@example.com", "Password123").await;
+    let user_id = user_id_from_cookie(&pool, &cookie).await;
+    let group = insert_task(&pool, &project_id, "Group", None).await;
+    let member = insert_task(&pool, &project_id, "Member", Some(&group)).await;
+    db::time_entries::insert(
+        &pool,
+        &db::time_entries::NewTimeEntry {
+            id: ulid::Ulid::new().to_string(),
+            node_id: group.clone(),
+            user_id,
+            minutes: 25,
+            entry_date: "2026-03-02".to_string(),
+            note: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let node_uri = format!("/api/projects/{}/nodes/{}", project_id, group);
+    let (status, _) = send(&app, &pool, &cookie, "DELETE", &node_uri).await;
+    assert_eq!(status, http::StatusCode::NO_CONTENT);
+    let orphan = db::nodes::find_by_id(&pool, &member).await.unwrap().unwrap();
+    assert_eq!(orphan.parent_id, None);
+
+    let (status, _) = send(&app, &pool, &cookie, "POST", &format!("{}/restore", node_uri)).await;
+    assert_eq!(status, http::StatusCode::OK);
+    let member = db::nodes::find_by_id(&pool, &member).await.unwrap().unwrap();
+    assert_eq!(member.parent_id.as_deref(), Some(group.as_str()));
+    let entries = db::time_entries::find_by_node(&pool, &group).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].minutes, 25);
+}
+
+#[tokio::test]
+async fn restore_keeps_when_the_node_was_completed() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("done-undo@example.com", "Password123").await;
+    let node = insert_task(&pool, &project_id, "Shipped", None).await;
+    let before = db::nodes::find_by_id(&pool, &node).await.unwrap().unwrap();
+    db::nodes::update(&pool, &node, &before.title, None, &before.node_type_id, DONE_STATUS_ID, None, None, None, None)
+        .await
+        .unwrap();
+    let completed_at = db::nodes::find_by_id(&pool, &node).await.unwrap().unwrap().completed_at;
+    assert!(completed_at.is_some());
+
+    let node_uri = format!("/api/projects/{}/nodes/{}", project_id, node);
+    send(&app, &pool, &cookie, "DELETE", &node_uri).await;
+    let (status, _) = send(&app, &pool, &cookie, "POST", &format!("{}/restore", node_uri)).await;
+    assert_eq!(status, http::StatusCode::OK);
+
+    let restored = db::nodes::find_by_id(&pool, &node).await.unwrap().unwrap();
+    assert_eq!(restored.completed_at, completed_at);
+}
+
+#[tokio::test]
+async fn restore_is_scoped_to_the_organization() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("owner-undo@example.com", "Password123").await;
+    let node = insert_task(&pool, &project_id, "Secret", None).await;
+    let node_uri = format!("/api/projects/{}/nodes/{}", project_id, node);
+    let (status, _) = send(&app, &pool, &cookie, "DELETE", &node_uri).await;
+    assert_eq!(status, http::StatusCode::NO_CONTENT);
+
+    let outsider = authenticated_cookie(&pool, &app, "stranger-undo@example.com", "Password123").await;
+    let (status, _) = send(&app, &pool, &outsider, "POST", &format!("{}/restore", node_uri)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    assert!(db::nodes::find_by_id(&pool, &node).await.unwrap().is_none());
+}
@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+const DONE_STATUS_ID: &str = boardtask::app::db::task_statuses::DONE_STATUS_ID;
+
+struct Fixture {
+    cookie: String,
+    project_id: String,
+    pool: sqlx::SqlitePool,
+    app: axum::Router,
+    mailer: RecordingMailer,
+    teammate_id: String,
+}
+
+const TEAMMATE_EMAIL: &str = "teammate@example.com";
+
+async fn setup(email: &str) -> Fixture {
+    let mailer = RecordingMailer::default();
+    let (cookie, project_id, pool, app, _) =
+        setup_user_and_project_with_mail(email, "Password123", Arc::new(mailer.clone())).await;
+    let project = boardtask::app::db::projects::find_by_id(&pool, &project_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let teammate_id = add_org_member(&pool, &project.organization_id, TEAMMATE_EMAIL).await;
+    Fixture { cookie, project_id, pool, app, mailer, teammate_id }
+}
+
+async fn send_json(f: &Fixture, method: &str, uri: String, body: serde_json::Value) -> http::StatusCode {
+    let request = http::Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("cookie", &f.cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&f.pool, &f.cookie).await)
+        .body(axum::body::Body::from(body.to_string()))
+        .unwrap();
+    f.app.clone().oneshot(request).await.unwrap().status()
+}
+
+async fn insert_node(f: &Fixture, title: &str, assigned_user_id: Option<&str>) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let node = boardtask::app::db::nodes::NewNode {
+        id: id.clone(),
+        project_id: f.project_id.clone(),
+        node_type_id: TASK_NODE_TYPE_ID.to_string(),
+        status_id: boardtask::app::db::task_statuses::TODO_STATUS_ID.to_string(),
+        title: title.to_string(),
+        description: None,
+        estimated_minutes: None,
+        slot_id: None,
+        parent_id: None,
+        assigned_user_id: assigned_user_id.map(String::from),
+    };
+    boardtask::app::db::nodes::insert(&f.pool, &node).await.unwrap();
+    id
+}
+
+async fn insert_edge(f: &Fixture, parent_id: &str, child_id: &str) {
+    let edge = boardtask::app::db::node_edges::NewNodeEdge {
+        parent_id: parent_id.to_string(),
+        child_id: child_id.to_string(),
+    };
+    boardtask::app::db::node_edges::insert(&f.pool, &edge).await.unwrap();
+}
+
+#[tokio::test]
+async fn assigning_teammate_on_create_emails_them() {
+    let f = setup("assigner@example.com").await;
+
+    let status = send_json(
+        &f,
+        "POST",
+        format!("/api/projects/{}/nodes", f.project_id),
+        serde_json::json!({
+            "node_type_id": TASK_NODE_TYPE_ID,
+            "title": "Write docs",
+            "assigned_user_id": f.teammate_id,
+        }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::CREATED);
+
+    let sent = f.mailer.sent_to(TEAMMATE_EMAIL);
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].subject.contains("Write docs"));
+    assert!(sent[0].body.contains(&format!("/app/projects/{}", f.project_id)));
+    assert!(sent[0].html_body.as_deref().unwrap().contains("Test Org"));
+}
+
+#[tokio::test]
+async fn self_assignment_sends_nothing() {
+    let f = setup("self@example.com").await;
+    let me = user_id_from_cookie(&f.pool, &f.cookie).await;
+
+    let status = send_json(
+        &f,
+        "POST",
+        format!("/api/projects/{}/nodes", f.project_id),
+        serde_json::json!({
+            "node_type_id": TASK_NODE_TYPE_ID,
+            "title": "Mine",
+            "assigned_user_id": me,
+        }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::CREATED);
+    assert!(f.mailer.all().is_empty());
+}
+
+#[tokio::test]
+async fn disabled_preference_suppresses_email() {
+    let f = setup("prefs@example.com").await;
+    let teammate = boardtask::app::domain::UserId::from_string(&f.teammate_id).unwrap();
+    boardtask::app::db::users::update_preferences(&f.pool, &teammate, false, "light", "en-US", "off")
+        .await
+        .unwrap();
+
+    let status = send_json(
+        &f,
+        "POST",
+        format!("/api/projects/{}/nodes", f.project_id),
+        serde_json::json!({
+            "node_type_id": TASK_NODE_TYPE_ID,
+            "title": "Quiet",
+            "assigned_user_id": f.teammate_id,
+        }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::CREATED);
+    assert!(f.mailer.sent_to(TEAMMATE_EMAIL).is_empty());
+}
+
+#[tokio::test]
+async fn reassignment_notifies_once() {
+    let f = setup("reassign@example.com").await;
+    let node_id = insert_node(&f, "Triage", None).await;
+    let uri = format!("/api/projects/{}/nodes/{}", f.project_id, node_id);
+
+    let status = send_json(&f, "PATCH", uri.clone(), serde_json::json!({ "assigned_user_id": f.teammate_id })).await;
+    assert_eq!(status, http::StatusCode::OK);
+    // Editing the title keeps the same assignee and must not re-notify.
+    let status = send_json(&f, "PATCH", uri, serde_json::json!({ "title": "Triage bugs" })).await;
+    assert_eq!(status, http::StatusCode::OK);
+
+    assert_eq!(f.mailer.sent_to(TEAMMATE_EMAIL).len(), 1);
+}
+
+#[tokio::test]
+async fn slot_owner_hears_about_new_nodes() {
+    let f = setup("slots@example.com").await;
+    let slot_id = ulid::Ulid::new().to_string();
+    let slot = boardtask::app::db::project_slots::NewProjectSlot {
+        id: slot_id.clone(),
+        project_id: f.project_id.clone(),
+        name: "Backend".to_string(),
+        sort_order: 0,
+        assigned_user_id: Some(f.teammate_id.clone()),
+    };
+    boardtask::app::db::project_slots::insert(&f.pool, &slot).await.unwrap();
+
+    let status = send_json(
+        &f,
+        "POST",
+        format!("/api/projects/{}/nodes", f.project_id),
+        serde_json::json!({
+            "node_type_id": TASK_NODE_TYPE_ID,
+            "title": "API endpoint",
+            "slot_id": slot_id,
+        }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::CREATED);
+
+    let sent = f.mailer.sent_to(TEAMMATE_EMAIL);
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].subject.contains("Backend"));
+}
+
+#[tokio::test]
+async fn completing_blocker_notifies_unblocked_assignee() {
+    let f = setup("unblock@example.com").await;
+    // A -> B -> C: C is blocked by B (B is not a root).
+    let a = insert_node(&f, "Design", None).await;
+    let b = insert_node(&f, "Build", None).await;
+    let c = insert_node(&f, "Ship", Some(&f.teammate_id)).await;
+    insert_edge(&f, &a, &b).await;
+    insert_edge(&f, &b, &c).await;
+
+    let status = send_json(
+        &f,
+        "PATCH",
+        format!("/api/projects/{}/nodes/{}", f.project_id, b),
+        serde_json::json!({ "status_id": DONE_STATUS_ID }),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+
+    let sent = f.mailer.sent_to(TEAMMATE_EMAIL);
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].subject.contains("Ship"));
+}
+
+#[tokio::test]
+async fn deleting_blocker_notifies_unblocked_assignee() {
+    let f = setup("deleteblocker@example.com").await;
+    // A -> B -> C: deleting B leaves A -> C, and C's only parent is now a root.
+    let a = insert_node(&f, "Design", None).await;
+    let b = insert_node(&f, "Build", None).await;
+    let c = insert_node(&f, "Ship", Some(&f.teammate_id)).await;
+    insert_edge(&f, &a, &b).await;
+    insert_edge(&f, &b, &c).await;
+
+    let status = send_json(
+        &f,
+        "DELETE",
+        format!("/api/projects/{}/nodes/{}", f.project_id, b),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::NO_CONTENT);
+
+    let sent = f.mailer.sent_to(TEAMMATE_EMAIL);
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].subject.contains("Ship"));
+}
@@ -0,0 +1,124 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db;
+use boardtask::app::domain::{OrganizationId, OrganizationRole, UserId};
+
+async fn get(app: &axum::Router, cookie: &str, uri: &str) -> (http::StatusCode, String, String) {
+    let request = http::Request::builder()
+        .uri(uri)
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let location = response
+        .headers()
+        .get("location")
+        .map(|v| v.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, location, String::from_utf8_lossy(&bytes).to_string())
+}
+
+/// An app whose DNS answers come from the returned resolver, and an owner logged in to their org.
+async fn setup(owner_email: &str) -> (sqlx::SqlitePool, axum::Router, StaticTxtResolver, String, String) {
+    let pool = test_pool().await;
+    let dns = StaticTxtResolver::default();
+    let app = test_router_with_dns(pool.clone(), dns.clone());
+    ensure_graph_seeds(&pool).await;
+    let owner = authenticated_cookie(&pool, &app, owner_email, "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    (pool, app, dns, owner, org_id)
+}
+
+/// Claim `domain`, publish its TXT record and verify it.
+async fn claim_and_verify(pool: &sqlx::SqlitePool, app: &axum::Router, dns: &StaticTxtResolver, cookie: &str, org_id: &str, body: &str) {
+    post_form(app, pool, cookie, "/app/settings/organization/domains", body).await;
+    let claim = db::organization_domains::list_for_org(pool, org_id).await.unwrap().remove(0);
+    dns.publish(
+        &format!("_boardtask-verification.{}", claim.domain),
+        &format!("boardtask-verification={}", claim.verification_token),
+    );
+    let (_, location) = post_form(app, pool, cookie, &format!("/app/settings/organization/domains/{}/verify", claim.id), "").await;
+    assert!(location.contains("success="), "{}", location);
+}
+
+#[tokio::test]
+async fn domains_verify_only_once_the_txt_record_is_published() {
+    let (pool, app, dns, owner, org_id) = setup("it@acme.example").await;
+
+    let (_, location) = post_form(&app, &pool, &owner, "/app/settings/organization/domains",
+        "domain=%40Acme.Example&default_role=member&team_id=").await;
+    assert!(location.contains("success="), "{}", location);
+    let claim = db::organization_domains::list_for_org(&pool, &org_id).await.unwrap().remove(0);
+    assert_eq!(claim.domain, "acme.example");
+
+    let (_, _, page) = get(&app, &owner, "/app/settings/organization").await;
+    assert!(page.contains("_boardtask-verification.acme.example"));
+    assert!(page.contains(&claim.verification_token));
+
+    let verify_uri = format!("/app/settings/organization/domains/{}/verify", claim.id);
+    dns.publish("_boardtask-verification.acme.example", "boardtask-verification=wrong");
+    let (_, location) = post_form(&app, &pool, &owner, &verify_uri, "").await;
+    assert!(location.contains("error="), "{}", location);
+
+    dns.publish("_boardtask-verification.acme.example", &format!("boardtask-verification={}", claim.verification_token));
+    let (_, location) = post_form(&app, &pool, &owner, &verify_uri, "").await;
+    assert!(location.contains("success="), "{}", location);
+    let claim = db::organization_domains::list_for_org(&pool, &org_id).await.unwrap().remove(0);
+    assert!(claim.verified_at.is_some());
+
+    // Another organization can't claim a verified domain.
+    let rival = authenticated_cookie(&pool, &app, "rival@elsewhere.example", "Password123").await;
+    let (_, location) = post_form(&app, &pool, &rival, "/app/settings/organization/domains",
+        "domain=acme.example&default_role=member&team_id=").await;
+    assert!(location.contains("error="), "{}", location);
+}
+
+#[tokio::test]
+async fn signups_from_a_verified_domain_are_offered_to_join() {
+    let (pool, app, dns, owner, org_id) = setup("founder@widgets.example").await;
+    let team_id = ulid::Ulid::new().to_string();
+    db::teams::insert(&pool, &db::teams::NewTeam {
+        id: team_id.clone(),
+        organization_id: org_id.clone(),
+        name: "Engineering".to_string(),
+    })
+    .await
+    .unwrap();
+    claim_and_verify(&pool, &app, &dns, &owner, &org_id,
+        &format!("domain=widgets.example&default_role=viewer&team_id={}", team_id)).await;
+
+    let request = http::Request::builder()
+        .method("POST")
+        .uri("/signup")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(axum::body::Body::from(signup_form_body("newhire@widgets.example", "Password123", "Password123")))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let location = response.headers().get("location").unwrap().to_str().unwrap().to_string();
+    assert!(location.contains(&urlencoding::encode("/app/domain-join").into_owned()), "{}", location);
+
+    let colleague = authenticated_cookie(&pool, &app, "colleague@widgets.example", "Password123").await;
+    let colleague_id = UserId::from_string(&user_id_from_cookie(&pool, &colleague).await).unwrap();
+    let (status, _, page) = get(&app, &colleague, "/app/domain-join").await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(page.contains("widgets.example"));
+    let domain_id = db::organization_domains::list_for_org(&pool, &org_id).await.unwrap().remove(0).id;
+
+    let (status, location) = post_form(&app, &pool, &colleague, "/app/domain-join", &format!("domain_id={}", domain_id)).await;
+    assert_eq!((status, location.as_str()), (http::StatusCode::SEE_OTHER, "/app"));
+    let org = OrganizationId::from_string(&org_id).unwrap();
+    assert_eq!(db::organizations::find_member_role(&pool, &org, &colleague_id).await.unwrap(), Some(OrganizationRole::Viewer));
+    assert!(db::team_members::is_member(&pool, &team_id, &colleague_id).await.unwrap());
+
+    // Outsiders get no offer.
+    let outsider = authenticated_cookie(&pool, &app, "someone@other.example", "Password123").await;
+    let (status, location, _) = get(&app, &outsider, "/app/domain-join").await;
+    assert_eq!((status, location.as_str()), (http::StatusCode::SEE_OTHER, "/app"));
+}
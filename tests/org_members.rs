@@ -0,0 +1,139 @@
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db;
+use boardtask::app::db::task_statuses::TODO_STATUS_ID;
+use boardtask::app::domain::{OrganizationId, OrganizationRole, UserId};
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+
+/// Add a user to the org with `role`, make it their current org and log them in there.
+#[tokio::test]
+async fn removing_a_member_cleans_up_their_access_and_assignments() {
+    let (owner, project_id, pool, app, team_id) = setup_user_and_project("remover@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let (member_id, member) = join_and_login(&pool, &app, &org_id, "leaver@example.com", OrganizationRole::Member).await;
+    let member_uid = UserId::from_string(&member_id).unwrap();
+    db::team_members::add_member(&pool, &team_id, &member_uid).await.unwrap();
+
+    let slot_id = ulid::Ulid::new().to_string();
+    db::project_slots::insert(&pool, &db::project_slots::NewProjectSlot {
+        id: slot_id.clone(),
+        project_id: project_id.clone(),
+        name: "Dev".to_string(),
+        sort_order: 0,
+        assigned_user_id: Some(member_id.clone()),
+    })
+    .await
+    .unwrap();
+    let node_id = ulid::Ulid::new().to_string();
+    db::nodes::insert(&pool, &db::nodes::NewNode {
+        id: node_id.clone(),
+        project_id: project_id.clone(),
+        node_type_id: TASK_NODE_TYPE_ID.to_string(),
+        status_id: TODO_STATUS_ID.to_string(),
+        title: "Theirs".to_string(),
+        description: None,
+        estimated_minutes: None,
+        slot_id: None,
+        parent_id: None,
+        assigned_user_id: Some(member_id.clone()),
+    })
+    .await
+    .unwrap();
+
+    let (status, location) = post_form(&app, &pool, &owner,
+        &format!("/app/settings/organization/members/{}/remove", member_id), "").await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    assert!(location.contains("success="), "{}", location);
+
+    assert_eq!(role_of(&pool, &org_id, &member_id).await, None);
+    assert!(!db::team_members::is_member(&pool, &team_id, &member_uid).await.unwrap());
+    let node = db::nodes::find_by_id(&pool, &node_id).await.unwrap().unwrap();
+    assert_eq!(node.assigned_user_id, None);
+    let slot = db::project_slots::find_by_id(&pool, &slot_id).await.unwrap().unwrap();
+    assert_eq!(slot.assigned_user_id, None);
+
+    let request = http::Request::builder()
+        .uri("/app/projects")
+        .header("cookie", &member)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::SEE_OTHER, "removed member's session is gone");
+    let user = db::users::find_by_id(&pool, &member_uid).await.unwrap().unwrap();
+    assert_ne!(user.organization_id, org_id, "removed member falls back to their own org");
+}
+
+#[tokio::test]
+async fn removed_member_without_another_org_gets_a_personal_one() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("onlyorg@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let (member_id, _member) = join_and_login(&pool, &app, &org_id, "stranded@example.com", OrganizationRole::Member).await;
+    let member_uid = UserId::from_string(&member_id).unwrap();
+    // Drop the personal org they signed up with so this org is their only one.
+    for membership in db::organizations::list_memberships_for_user(&pool, &member_uid).await.unwrap() {
+        if membership.organization_id != org_id {
+            let other = OrganizationId::from_string(&membership.organization_id).unwrap();
+            db::organizations::remove_member(&pool, &other, &member_uid).await.unwrap();
+        }
+    }
+
+    let (status, location) = post_form(&app, &pool, &owner,
+        &format!("/app/settings/organization/members/{}/remove", member_id), "").await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    assert!(location.contains("success="), "{}", location);
+
+    let user = db::users::find_by_id(&pool, &member_uid).await.unwrap().unwrap();
+    assert_ne!(user.organization_id, org_id);
+    assert_eq!(role_of(&pool, &user.organization_id, &member_id).await, Some(OrganizationRole::Owner));
+}
+
+#[tokio::test]
+async fn owners_are_protected_from_admins_and_from_losing_the_last_one() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("soleowner@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let owner_id = user_id_from_cookie(&pool, &owner).await;
+    let (admin_id, admin) = join_and_login(&pool, &app, &org_id, "admin@example.com", OrganizationRole::Admin).await;
+    let (member_id, _) = join_and_login(&pool, &app, &org_id, "plain@example.com", OrganizationRole::Member).await;
+
+    let owner_role_uri = format!("/app/settings/organization/members/{}/role", owner_id);
+    let (_, location) = post_form(&app, &pool, &owner, &owner_role_uri, "role=admin").await;
+    assert!(location.contains("error="), "last owner can't step down: {}", location);
+    assert_eq!(role_of(&pool, &org_id, &owner_id).await, Some(OrganizationRole::Owner));
+
+    let (_, location) = post_form(&app, &pool, &admin, &owner_role_uri, "role=member").await;
+    assert!(location.contains("error="));
+    let (_, location) = post_form(&app, &pool, &admin,
+        &format!("/app/settings/organization/members/{}/remove", owner_id), "").await;
+    assert!(location.contains("error="));
+    assert_eq!(role_of(&pool, &org_id, &owner_id).await, Some(OrganizationRole::Owner));
+
+    let member_role_uri = format!("/app/settings/organization/members/{}/role", member_id);
+    post_form(&app, &pool, &admin, &member_role_uri, "role=owner").await;
+    assert_eq!(role_of(&pool, &org_id, &member_id).await, Some(OrganizationRole::Member));
+    post_form(&app, &pool, &admin, &member_role_uri, "role=viewer").await;
+    assert_eq!(role_of(&pool, &org_id, &member_id).await, Some(OrganizationRole::Viewer));
+
+    // With a second owner, the first may step down.
+    post_form(&app, &pool, &owner, &format!("/app/settings/organization/members/{}/role", admin_id), "role=owner").await;
+    assert_eq!(role_of(&pool, &org_id, &admin_id).await, Some(OrganizationRole::Owner));
+    post_form(&app, &pool, &owner, &owner_role_uri, "role=admin").await;
+    assert_eq!(role_of(&pool, &org_id, &owner_id).await, Some(OrganizationRole::Admin));
+}
+
+#[tokio::test]
+async fn members_cannot_manage_members() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("boss@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let owner_id = user_id_from_cookie(&pool, &owner).await;
+    let (_, member) = join_and_login(&pool, &app, &org_id, "worker@example.com", OrganizationRole::Member).await;
+
+    let (status, _) = post_form(&app, &pool, &member,
+        &format!("/app/settings/organization/members/{}/remove", owner_id), "").await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    assert_eq!(role_of(&pool, &org_id, &owner_id).await, Some(OrganizationRole::Owner));
+}
@@ -0,0 +1,126 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db;
+use boardtask::app::domain::{OrganizationId, OrganizationRole, UserId};
+use boardtask::app::features::organization::deletion;
+
+async fn get(app: &axum::Router, cookie: &str, uri: &str) -> (http::StatusCode, String) {
+    let request = http::Request::builder()
+        .uri(uri)
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8_lossy(&bytes).to_string())
+}
+
+/// Add a member to the org, make it their current org and log them in there.
+#[tokio::test]
+async fn ownership_moves_only_when_the_recipient_accepts() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("founder@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let owner_id = user_id_from_cookie(&pool, &owner).await;
+    let (heir_id, heir) = join_and_login(&pool, &app, &org_id, "heir@example.com", OrganizationRole::Member).await;
+    let (_, bystander) = join_and_login(&pool, &app, &org_id, "bystander@example.com", OrganizationRole::Member).await;
+
+    let (_, location) = post_form(&app, &pool, &owner, "/app/settings/organization/transfer",
+        &format!("to_user_id={}", heir_id)).await;
+    assert!(location.contains("success="), "{}", location);
+    let transfer = db::organization_ownership_transfers::find_for_org(&pool, &org_id).await.unwrap().expect("pending transfer");
+    assert_eq!(role_of(&pool, &org_id, &heir_id).await, Some(OrganizationRole::Member), "nothing moves before acceptance");
+
+    let (_, page) = get(&app, &owner, "/app/settings/organization").await;
+    assert!(page.contains("heir@example.com"));
+    assert!(page.contains("/app/settings/organization/transfer/cancel"));
+
+    let accept_uri = format!("/app/ownership-transfer?token={}", transfer.token);
+    let (status, _) = get(&app, &bystander, &accept_uri).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    let (status, _) = post_form(&app, &pool, &bystander, "/app/ownership-transfer/accept",
+        &format!("token={}", transfer.token)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+
+    let (status, page) = get(&app, &heir, &accept_uri).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(page.contains("founder@example.com"));
+    let (status, location) = post_form(&app, &pool, &heir, "/app/ownership-transfer/accept",
+        &format!("token={}", transfer.token)).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    assert!(location.contains("success="), "{}", location);
+
+    assert_eq!(role_of(&pool, &org_id, &heir_id).await, Some(OrganizationRole::Owner));
+    assert_eq!(role_of(&pool, &org_id, &owner_id).await, Some(OrganizationRole::Admin));
+    assert!(db::organization_ownership_transfers::find_for_org(&pool, &org_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn only_owners_can_offer_ownership() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("holder@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let (admin_id, admin) = join_and_login(&pool, &app, &org_id, "ambitious@example.com", OrganizationRole::Admin).await;
+
+    let (status, _) = post_form(&app, &pool, &admin, "/app/settings/organization/transfer",
+        &format!("to_user_id={}", admin_id)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    let (_, location) = post_form(&app, &pool, &owner, "/app/settings/organization/transfer",
+        &format!("to_user_id={}", user_id_from_cookie(&pool, &owner).await)).await;
+    assert!(location.contains("error="), "can't transfer to yourself: {}", location);
+    assert!(db::organization_ownership_transfers::find_for_org(&pool, &org_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn deletion_needs_the_exact_name_and_can_be_cancelled() {
+    let (owner, _project_id, pool, app, _) = setup_user_and_project("closer@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let org = OrganizationId::from_string(&org_id).unwrap();
+    let name = db::organizations::find_by_id(&pool, &org).await.unwrap().unwrap().name;
+
+    let (_, location) = post_form(&app, &pool, &owner, "/app/settings/organization/delete", "confirm_name=wrong").await;
+    assert!(location.contains("error="), "{}", location);
+    assert!(db::organizations::find_by_id(&pool, &org).await.unwrap().unwrap().deletion_requested_at.is_none());
+
+    let body = format!("confirm_name={}", urlencoding::encode(&name));
+    let (_, location) = post_form(&app, &pool, &owner, "/app/settings/organization/delete", &body).await;
+    assert!(location.contains("success="), "{}", location);
+    assert!(db::organizations::find_by_id(&pool, &org).await.unwrap().unwrap().deletion_requested_at.is_some());
+    let (_, page) = get(&app, &owner, "/app/settings/organization").await;
+    assert!(page.contains("data-testid=\"deletion-scheduled\""));
+
+    post_form(&app, &pool, &owner, "/app/settings/organization/delete/cancel", "").await;
+    assert!(db::organizations::find_by_id(&pool, &org).await.unwrap().unwrap().deletion_requested_at.is_none());
+}
+
+#[tokio::test]
+async fn purge_removes_the_org_once_the_grace_period_is_over() {
+    let (owner, project_id, pool, app, team_id) = setup_user_and_project("sunset@example.com", "Password123").await;
+    let org_id = owner_org(&pool, &owner).await;
+    let org = OrganizationId::from_string(&org_id).unwrap();
+    let owner_id = UserId::from_string(&user_id_from_cookie(&pool, &owner).await).unwrap();
+    let (member_id, _) = join_and_login(&pool, &app, &org_id, "drifter@example.com", OrganizationRole::Member).await;
+    let member_uid = UserId::from_string(&member_id).unwrap();
+
+    let requested_at = 1_000_000;
+    db::organizations::set_deletion_requested_at(&pool, &org, Some(requested_at)).await.unwrap();
+    let one_day = 24 * 60 * 60;
+    assert_eq!(deletion::purge_due(&pool, 14, requested_at + 13 * one_day).await.unwrap(), 0);
+    assert!(db::organizations::find_by_id(&pool, &org).await.unwrap().is_some());
+
+    assert_eq!(deletion::purge_due(&pool, 14, requested_at + 15 * one_day).await.unwrap(), 1);
+    assert!(db::organizations::find_by_id(&pool, &org).await.unwrap().is_none());
+    assert!(db::projects::find_by_id(&pool, &project_id).await.unwrap().is_none());
+    assert!(db::teams::find_by_id(&pool, &team_id).await.unwrap().is_none());
+
+    for uid in [&owner_id, &member_uid] {
+        let user = db::users::find_by_id(&pool, uid).await.unwrap().expect("accounts survive");
+        assert_ne!(user.organization_id, org_id);
+        let fallback = OrganizationId::from_string(&user.organization_id).unwrap();
+        assert_eq!(db::organizations::find_member_role(&pool, &fallback, uid).await.unwrap(), Some(OrganizationRole::Owner));
+    }
+}
@@ -0,0 +1,110 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::features::projects::trash;
+
+async fn get(app: &axum::Router, cookie: &str, uri: &str) -> (http::StatusCode, String) {
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(uri)
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8_lossy(&bytes).to_string())
+}
+
+#[tokio::test]
+async fn archived_project_is_hidden_from_list_but_still_readable() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("archiver@example.com", "Password123").await;
+
+    assert_eq!(post_form(&app, &pool, &cookie, &format!("/api/projects/{}/archive", project_id), "").await.0, http::StatusCode::SEE_OTHER);
+
+    let (_, list) = get(&app, &cookie, "/app/projects").await;
+    assert!(!list.contains(&format!("/app/projects/{}", project_id)));
+    let (status, page) = get(&app, &cookie, &format!("/app/projects/{}", project_id)).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(page.contains("data-testid=\"unarchive-project\""));
+    let (status, _) = get(&app, &cookie, &format!("/api/projects/{}/graph", project_id)).await;
+    assert_eq!(status, http::StatusCode::OK);
+    let (_, archive) = get(&app, &cookie, "/app/projects/archive").await;
+    assert!(archive.contains(&format!("data-archived-project-id=\"{}\"", project_id)));
+
+    assert_eq!(post_form(&app, &pool, &cookie, &format!("/api/projects/{}/unarchive", project_id), "").await.0, http::StatusCode::SEE_OTHER);
+    let (_, list) = get(&app, &cookie, "/app/projects").await;
+    assert!(list.contains(&format!("/app/projects/{}", project_id)));
+}
+
+#[tokio::test]
+async fn trashed_project_is_unreachable_until_restored() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("trasher@example.com", "Password123").await;
+
+    assert_eq!(post_form(&app, &pool, &cookie, &format!("/api/projects/{}/delete", project_id), "").await.0, http::StatusCode::SEE_OTHER);
+
+    let (status, _) = get(&app, &cookie, &format!("/app/projects/{}", project_id)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    let (status, _) = get(&app, &cookie, &format!("/api/projects/{}/graph", project_id)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    let (_, archive) = get(&app, &cookie, "/app/projects/archive").await;
+    assert!(archive.contains(&format!("data-trashed-project-id=\"{}\"", project_id)));
+    assert!(archive.contains("30 days left to restore"));
+    // Deleting again is a 404: it is already in the trash.
+    assert_eq!(post_form(&app, &pool, &cookie, &format!("/api/projects/{}/delete", project_id), "").await.0, http::StatusCode::NOT_FOUND);
+
+    assert_eq!(post_form(&app, &pool, &cookie, &format!("/api/projects/{}/restore", project_id), "").await.0, http::StatusCode::SEE_OTHER);
+    let (status, _) = get(&app, &cookie, &format!("/app/projects/{}", project_id)).await;
+    assert_eq!(status, http::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn purge_is_for_admins_and_only_for_trashed_projects() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("purger@example.com", "Password123").await;
+    let org_id = boardtask::app::db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap().organization_id;
+    add_org_member(&pool, &org_id, "member@example.com").await;
+    let member = login_cookie(&app, "member@example.com").await;
+    let purge_uri = format!("/api/projects/{}/purge", project_id);
+
+    // Not in the trash yet.
+    assert_eq!(post_form(&app, &pool, &cookie, &purge_uri, "").await.0, http::StatusCode::NOT_FOUND);
+    assert_eq!(post_form(&app, &pool, &cookie, &format!("/api/projects/{}/delete", project_id), "").await.0, http::StatusCode::SEE_OTHER);
+
+    let (_, archive) = get(&app, &member, "/app/projects/archive").await;
+    assert!(!archive.contains("Delete forever"));
+    assert_eq!(post_form(&app, &pool, &member, &purge_uri, "").await.0, http::StatusCode::NOT_FOUND);
+
+    assert_eq!(post_form(&app, &pool, &cookie, &purge_uri, "").await.0, http::StatusCode::SEE_OTHER);
+    assert!(boardtask::app::db::projects::list_trashed_for_org(&pool, &org_id).await.unwrap().is_empty());
+    assert_eq!(post_form(&app, &pool, &cookie, &format!("/api/projects/{}/restore", project_id), "").await.0, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn purge_job_removes_only_expired_trash() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("retention@example.com", "Password123").await;
+    let org_id = boardtask::app::db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap().organization_id;
+    assert_eq!(post_form(&app, &pool, &cookie, &format!("/api/projects/{}/delete", project_id), "").await.0, http::StatusCode::SEE_OTHER);
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+
+    assert_eq!(trash::purge_expired(&pool, 30, now).await.unwrap(), 0);
+    assert_eq!(boardtask::app::db::projects::list_trashed_for_org(&pool, &org_id).await.unwrap().len(), 1);
+
+    assert_eq!(trash::purge_expired(&pool, 30, now + 31 * 24 * 60 * 60).await.unwrap(), 1);
+    assert!(boardtask::app::db::projects::list_trashed_for_org(&pool, &org_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn archive_actions_are_scoped_to_the_session_organization() {
+    let (_, project_id, pool, app, _) = setup_user_and_project("scoped@example.com", "Password123").await;
+    let outsider = authenticated_cookie(&pool, &app, "elsewhere@example.com", "Password123").await;
+
+    for action in ["archive", "delete", "restore", "purge"] {
+        let status = post_form(&app, &pool, &outsider, &format!("/api/projects/{}/{}", project_id, action), "").await.0;
+        assert_eq!(status, http::StatusCode::NOT_FOUND, "{}", action);
+    }
+    assert!(boardtask::app::db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap().archived_at.is_none());
+}
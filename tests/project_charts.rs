@@ -0,0 +1,100 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db::project_snapshots::{self, ProjectSnapshot};
+use boardtask::app::db::task_statuses::{DONE_STATUS_ID, TODO_STATUS_ID};
+use boardtask::app::domain::validation_helpers::parse_iso_date;
+use boardtask::app::features::projects::snapshots;
+
+async fn get(app: &axum::Router, cookie: &str, uri: &str) -> (http::StatusCode, String) {
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(uri)
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8_lossy(&bytes).to_string())
+}
+
+#[tokio::test]
+async fn snapshot_job_records_counts_and_rewrites_the_same_day() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("snap@example.com", "Password123").await;
+    insert_task(&pool, &project_id, TODO_STATUS_ID, Some(120), None, None).await;
+    insert_task(&pool, &project_id, DONE_STATUS_ID, Some(60), None, None).await;
+    let day = parse_iso_date("2026-10-01").unwrap();
+
+    assert!(snapshots::record_all(&pool, day).await.unwrap() >= 1);
+    insert_task(&pool, &project_id, TODO_STATUS_ID, Some(30), None, None).await;
+    snapshots::record_all(&pool, day).await.unwrap();
+
+    let (status, body) = get(&app, &cookie, &format!("/api/projects/{}/snapshots?from=2026-09-01&to=2026-10-01", project_id)).await;
+    assert_eq!(status, http::StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let rows = body["snapshots"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["snapshot_date"], "2026-10-01");
+    assert_eq!(rows[0]["todo_count"], 2);
+    assert_eq!(rows[0]["done_count"], 1);
+    assert_eq!(rows[0]["estimated_remaining_minutes"], 150);
+}
+
+#[tokio::test]
+async fn snapshots_api_validates_range_and_hides_other_orgs() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("snaprange@example.com", "Password123").await;
+
+    let (status, _) = get(&app, &cookie, &format!("/api/projects/{}/snapshots?from=2026-10-10&to=2026-10-01", project_id)).await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    let (status, _) = get(&app, &cookie, &format!("/api/projects/{}/snapshots?from=2026-13-01", project_id)).await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+
+    let outsider = authenticated_cookie(&pool, &app, "snapoutsider@example.com", "Password123").await;
+    let (status, _) = get(&app, &outsider, &format!("/api/projects/{}/snapshots", project_id)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    let (status, _) = get(&app, &outsider, &format!("/app/projects/{}/charts", project_id)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn charts_page_draws_burndown_and_burnup_lines() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("charts@example.com", "Password123").await;
+    for (day, todo, done, remaining) in [("2026-10-01", 4, 0, 600), ("2026-10-05", 3, 2, 300)] {
+        let snapshot = ProjectSnapshot {
+            snapshot_date: day.to_string(),
+            todo_count: todo,
+            in_progress_count: 0,
+            done_count: done,
+            blocked_count: 0,
+            estimated_remaining_minutes: remaining,
+        };
+        project_snapshots::upsert(&pool, &project_id, &snapshot, 0).await.unwrap();
+    }
+
+    let (status, body) = get(&app, &cookie, &format!("/app/projects/{}/charts?from=2026-10-01&to=2026-10-10", project_id)).await;
+
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(body.contains("aria-label=\"Burndown chart\""));
+    assert!(body.contains("aria-label=\"Burnup chart\""));
+    for series in ["Remaining", "Ideal", "Scope", "Done"] {
+        assert!(body.contains(&format!("data-series=\"{}\"", series)), "missing {}", series);
+    }
+    assert!(body.contains("10 h"));
+    assert!(body.contains("value=\"2026-10-01\""));
+}
+
+#[tokio::test]
+async fn charts_page_explains_when_there_are_no_snapshots() {
+    let (cookie, project_id, _pool, app, _) = setup_user_and_project("nocharts@example.com", "Password123").await;
+
+    let (status, body) = get(&app, &cookie, &format!("/app/projects/{}/charts", project_id)).await;
+
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(body.contains("No snapshots in this range yet"));
+    assert!(!body.contains("project-chart__series"));
+}
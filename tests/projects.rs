@@ -47,7 +47,7 @@ async fn create_post_requires_authentication() {
 
 #[tokio::test]
 async fn create_project_succeeds() {
-    let (cookie, _project_id, _pool, app, team_id) = setup_user_and_project("create@example.com", "Password123").await;
+    let (cookie, _project_id, pool, app, team_id) = setup_user_and_project("create@example.com", "Password123").await;
 
     let body = create_project_form_body("My Project", &team_id);
     let request = http::Request::builder()
@@ -55,6 +55,7 @@ async fn create_project_succeeds() {
         .uri("/app/projects")
         .header("content-type", "application/x-www-form-urlencoded")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(body))
         .unwrap();
     let response = app.clone().oneshot(request).await.unwrap();
@@ -86,7 +87,7 @@ async fn create_project_succeeds() {
 
 #[tokio::test]
 async fn create_project_empty_title_returns_error() {
-    let (cookie, _project_id, _pool, app, team_id) = setup_user_and_project("empty@example.com", "Password123").await;
+    let (cookie, _project_id, pool, app, team_id) = setup_user_and_project("empty@example.com", "Password123").await;
 
     let body = create_project_form_body("", &team_id);
     let request = http::Request::builder()
@@ -94,6 +95,7 @@ async fn create_project_empty_title_returns_error() {
         .uri("/app/projects")
         .header("content-type", "application/x-www-form-urlencoded")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(body))
         .unwrap();
     let response = app.oneshot(request).await.unwrap();
@@ -287,7 +289,7 @@ async fn patch_project_settings_requires_authentication() {
 
 #[tokio::test]
 async fn patch_project_settings_invalid_default_view_mode_returns_error() {
-    let (cookie, project_id, _pool, app, _) =
+    let (cookie, project_id, pool, app, _) =
         setup_user_and_project("patch_invalid@example.com", "Password123").await;
 
     let body = r#"{"default_view_mode":"invalid"}"#;
@@ -296,6 +298,7 @@ async fn patch_project_settings_invalid_default_view_mode_returns_error() {
         .uri(&format!("/api/projects/{}", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(body))
         .unwrap();
     let response = app.oneshot(request).await.unwrap();
@@ -311,7 +314,7 @@ async fn patch_project_settings_invalid_default_view_mode_returns_error() {
 
 #[tokio::test]
 async fn patch_project_settings_updates_and_returns() {
-    let (cookie, project_id, _pool, app, _) =
+    let (cookie, project_id, pool, app, _) =
         setup_user_and_project("patch_ok@example.com", "Password123").await;
 
     let body = r#"{"default_view_mode":"list"}"#;
@@ -320,6 +323,7 @@ async fn patch_project_settings_updates_and_returns() {
         .uri(&format!("/api/projects/{}", project_id))
         .header("content-type", "application/json")
         .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
         .body(axum::body::Body::from(body))
         .unwrap();
     let response = app.clone().oneshot(request).await.unwrap();
@@ -381,6 +385,7 @@ async fn patch_project_settings_tenant_isolation() {
         .uri(&format!("/api/projects/{}", project_id))
         .header("content-type", "application/json")
         .header("cookie", &other_cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &other_cookie).await)
         .body(axum::body::Body::from(body))
         .unwrap();
     let response = app.oneshot(request).await.unwrap();
@@ -423,3 +428,142 @@ async fn show_project_404_when_user_in_different_org() {
         "User in different org must not access project"
     );
 }
+
+#[tokio::test]
+async fn list_view_filters_to_my_open_tasks_sorted_by_title() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("listfilter@example.com", "Password123").await;
+    let me = user_id_from_cookie(&pool, &cookie).await;
+    for (title, assignee) in [("Zeta task", Some(me.as_str())), ("Alpha task", Some(me.as_str())), ("Someone else's", None)] {
+        let node = boardtask::app::db::nodes::NewNode {
+            id: ulid::Ulid::new().to_string(),
+            project_id: project_id.clone(),
+            node_type_id: "01JNODETYPE00000000TASK000".to_string(),
+            status_id: boardtask::app::db::task_statuses::TODO_STATUS_ID.to_string(),
+            title: title.to_string(),
+            description: None,
+            estimated_minutes: None,
+            slot_id: None,
+            parent_id: None,
+            assigned_user_id: assignee.map(String::from),
+        };
+        boardtask::app::db::nodes::insert(&pool, &node).await.unwrap();
+    }
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(format!("/app/projects/{}/list?assignee=me&sort=title", project_id))
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8_lossy(&body_bytes);
+    let alpha = body.find("Alpha task").expect("Alpha task listed");
+    let zeta = body.find("Zeta task").expect("Zeta task listed");
+    assert!(alpha < zeta);
+    assert!(!body.contains("Someone else&#x27;s") && !body.contains("Someone else's"));
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(format!("/app/projects/{}/list?sort=bogus", project_id))
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+}
+
+async fn insert_task(pool: &sqlx::SqlitePool, project_id: &str, title: &str, status_id: &str) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let node = boardtask::app::db::nodes::NewNode {
+        id: id.clone(),
+        project_id: project_id.to_string(),
+        node_type_id: "01JNODETYPE00000000TASK000".to_string(),
+        status_id: status_id.to_string(),
+        title: title.to_string(),
+        description: None,
+        estimated_minutes: None,
+        slot_id: None,
+        parent_id: None,
+        assigned_user_id: None,
+    };
+    boardtask::app::db::nodes::insert(pool, &node).await.unwrap();
+    id
+}
+
+#[tokio::test]
+async fn board_groups_cards_by_status_and_flags_blocked() {
+    use boardtask::app::db::task_statuses::{DONE_STATUS_ID, TODO_STATUS_ID};
+
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("board@example.com", "Password123").await;
+    // A -> B -> C: C is blocked by B.
+    let a = insert_task(&pool, &project_id, "Card A", DONE_STATUS_ID).await;
+    let b = insert_task(&pool, &project_id, "Card B", TODO_STATUS_ID).await;
+    let c = insert_task(&pool, &project_id, "Card C", TODO_STATUS_ID).await;
+    for (parent_id, child_id) in [(&a, &b), (&b, &c)] {
+        let edge = boardtask::app::db::node_edges::NewNodeEdge {
+            parent_id: parent_id.clone(),
+            child_id: child_id.clone(),
+        };
+        boardtask::app::db::node_edges::insert(&pool, &edge).await.unwrap();
+    }
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(format!("/app/projects/{}/board", project_id))
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8_lossy(&body_bytes);
+
+    // Columns follow status order: To do, In progress, Done.
+    let todo_column = body.find(&format!("data-status-id=\"{}\"", TODO_STATUS_ID)).unwrap();
+    let done_column = body.find(&format!("data-status-id=\"{}\"", DONE_STATUS_ID)).unwrap();
+    let card_a = body.find("Card A").unwrap();
+    let card_c = body.find("Card C").unwrap();
+    assert!(todo_column < card_c && card_c < done_column && done_column < card_a);
+    assert_eq!(body.matches("board-card__blocked").count(), 1);
+    let blocked_badge = body.find("board-card__blocked").unwrap();
+    assert!(card_c < blocked_badge && blocked_badge < done_column);
+}
+
+#[tokio::test]
+async fn board_swimlanes_by_assignee_and_board_default_view() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("lanes@example.com", "Password123").await;
+    insert_task(&pool, &project_id, "Loose end", boardtask::app::db::task_statuses::TODO_STATUS_ID).await;
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(format!("/app/projects/{}/board?lanes=assignee", project_id))
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(String::from_utf8_lossy(&body_bytes).contains("Unassigned"));
+
+    let request = http::Request::builder()
+        .method("PATCH")
+        .uri(format!("/api/projects/{}", project_id))
+        .header("content-type", "application/json")
+        .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
+        .body(axum::body::Body::from(r#"{"default_view_mode":"board"}"#))
+        .unwrap();
+    assert_eq!(app.clone().oneshot(request).await.unwrap().status(), http::StatusCode::OK);
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri("/app/projects")
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(String::from_utf8_lossy(&body_bytes).contains(&format!("/app/projects/{}/board", project_id)));
+}
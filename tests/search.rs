@@ -0,0 +1,146 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+
+async fn insert_node(pool: &sqlx::SqlitePool, project_id: &str, title: &str, description: Option<&str>) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let node = boardtask::app::db::nodes::NewNode {
+        id: id.clone(),
+        project_id: project_id.to_string(),
+        node_type_id: TASK_NODE_TYPE_ID.to_string(),
+        status_id: boardtask::app::db::task_statuses::TODO_STATUS_ID.to_string(),
+        title: title.to_string(),
+        description: description.map(String::from),
+        estimated_minutes: None,
+        slot_id: None,
+        parent_id: None,
+        assigned_user_id: None,
+    };
+    boardtask::app::db::nodes::insert(pool, &node).await.unwrap();
+    id
+}
+
+async fn search(app: &axum::Router, cookie: &str, q: &str) -> (http::StatusCode, serde_json::Value) {
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(format!("/api/search?q={}", urlencoding::encode(q)))
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+fn titles(body: &serde_json::Value) -> Vec<String> {
+    body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["title"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn finds_nodes_by_title_with_project_and_status() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("search@example.com", "Password123").await;
+    let node_id = insert_node(&pool, &project_id, "Write release notes", None).await;
+    insert_node(&pool, &project_id, "Fix login bug", None).await;
+
+    let (status, body) = search(&app, &cookie, "release").await;
+
+    assert_eq!(status, http::StatusCode::OK);
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["node_id"], node_id.as_str());
+    assert_eq!(results[0]["project_id"], project_id.as_str());
+    assert_eq!(results[0]["project_title"], "Test Project");
+    assert_eq!(results[0]["status_name"], "To do");
+    assert!(results[0]["snippet_html"].as_str().unwrap().contains("<mark>release</mark>"));
+}
+
+#[tokio::test]
+async fn matches_description_prefix_and_escapes_snippet() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("snippet@example.com", "Password123").await;
+    insert_node(&pool, &project_id, "Hardening", Some("Sanitize <script> tags in comments")).await;
+
+    let (_, body) = search(&app, &cookie, "sanit").await;
+
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    let snippet = results[0]["snippet_html"].as_str().unwrap();
+    assert!(snippet.contains("<mark>Sanitize</mark>"));
+    assert!(snippet.contains("&lt;script&gt;"));
+}
+
+#[tokio::test]
+async fn index_follows_updates_and_deletes() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("sync@example.com", "Password123").await;
+    let node_id = insert_node(&pool, &project_id, "Draft roadmap", None).await;
+    let csrf = csrf_token_from_cookie(&pool, &cookie).await;
+
+    let request = http::Request::builder()
+        .method("PATCH")
+        .uri(format!("/api/projects/{}/nodes/{}", project_id, node_id))
+        .header("content-type", "application/json")
+        .header("cookie", &cookie)
+        .header("x-csrf-token", &csrf)
+        .body(axum::body::Body::from(serde_json::json!({ "title": "Publish roadmap" }).to_string()))
+        .unwrap();
+    assert_eq!(app.clone().oneshot(request).await.unwrap().status(), http::StatusCode::OK);
+
+    assert!(titles(&search(&app, &cookie, "draft").await.1).is_empty());
+    assert_eq!(titles(&search(&app, &cookie, "publish").await.1), vec!["Publish roadmap"]);
+
+    let request = http::Request::builder()
+        .method("DELETE")
+        .uri(format!("/api/projects/{}/nodes/{}", project_id, node_id))
+        .header("cookie", &cookie)
+        .header("x-csrf-token", &csrf)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    assert!(app.clone().oneshot(request).await.unwrap().status().is_success());
+
+    assert!(titles(&search(&app, &cookie, "publish").await.1).is_empty());
+}
+
+#[tokio::test]
+async fn results_are_scoped_to_the_organization() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("mine@example.com", "Password123").await;
+    insert_node(&pool, &project_id, "Quarterly budget", None).await;
+    let other_cookie = authenticated_cookie(&pool, &app, "other@example.com", "Password123").await;
+
+    assert_eq!(titles(&search(&app, &cookie, "budget").await.1).len(), 1);
+    assert!(titles(&search(&app, &other_cookie, "budget").await.1).is_empty());
+}
+
+#[tokio::test]
+async fn query_syntax_in_input_is_treated_literally() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("syntax@example.com", "Password123").await;
+    insert_node(&pool, &project_id, "Migrate NEAR database", None).await;
+
+    // NEAR( and a stray quote would be FTS5 syntax errors; here they are just words.
+    let (status, body) = search(&app, &cookie, "NEAR( \"database").await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(titles(&body), vec!["Migrate NEAR database"]);
+
+    let (status, body) = search(&app, &cookie, "   ").await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(titles(&body).is_empty());
+}
+
+#[tokio::test]
+async fn search_requires_authentication() {
+    let pool = test_pool().await;
+    let app = test_router(pool);
+
+    let (status, _) = search(&app, "", "anything").await;
+
+    assert_eq!(status, http::StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,204 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db;
+use boardtask::app::domain::{Email, OrganizationId, OrganizationRole, SignupMode, UserId};
+
+/// POST /signup; returns the status and either the redirect location or the page body.
+async fn signup(app: &axum::Router, body: String) -> (http::StatusCode, String) {
+    let request = http::Request::builder()
+        .method("POST")
+        .uri("/signup")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(axum::body::Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    if let Some(location) = response.headers().get("location") {
+        return (status, location.to_str().unwrap().to_string());
+    }
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8_lossy(&bytes).to_string())
+}
+
+async fn get(app: &axum::Router, cookie: &str, uri: &str) -> (http::StatusCode, String) {
+    let request = http::Request::builder()
+        .uri(uri)
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8_lossy(&bytes).to_string())
+}
+
+async fn user_exists(pool: &sqlx::SqlitePool, email: &str) -> bool {
+    db::find_by_email(pool, &Email::new(email.to_string()).unwrap()).await.unwrap().is_some()
+}
+
+/// Invite `email` to a fresh organization and return the invite token.
+async fn invite(pool: &sqlx::SqlitePool, email: &str) -> String {
+    let (inviter_id, _, _) = create_verified_user(pool, "inviter@example.com", "Password123").await;
+    let org_id = db::users::find_by_id(pool, &inviter_id).await.unwrap().unwrap().organization_id;
+    let token = ulid::Ulid::new().to_string();
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    db::organization_invites::insert(pool, &db::organization_invites::NewOrganizationInvite {
+        id: ulid::Ulid::new().to_string(),
+        organization_id: OrganizationId::from_string(&org_id).unwrap(),
+        email: email.to_string(),
+        role: OrganizationRole::Member,
+        invited_by_user_id: inviter_id,
+        token: token.clone(),
+        expires_at: now + 3600,
+        created_at: now,
+        team_id: None,
+    })
+    .await
+    .unwrap();
+    token
+}
+
+#[tokio::test]
+async fn closed_signup_hides_the_form_and_rejects_submissions() {
+    let pool = test_pool().await;
+    let mut state = test_state(pool.clone());
+    state.config.signup_mode = SignupMode::Closed;
+    let app = boardtask::create_router(state);
+
+    let (status, page) = get(&app, "", "/signup").await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(page.contains("signup-blocked"));
+    assert!(!page.contains("name=\"password\""));
+
+    let (status, page) = signup(&app, signup_form_body("new@example.com", "Password123", "Password123")).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(page.contains("isn&#x27;t accepting new accounts"));
+    assert!(!user_exists(&pool, "new@example.com").await);
+}
+
+#[tokio::test]
+async fn invite_only_signup_needs_an_invite_for_that_address() {
+    let pool = test_pool().await;
+    let mut state = test_state(pool.clone());
+    state.config.signup_mode = SignupMode::InviteOnly;
+    let app = boardtask::create_router(state);
+    let token = invite(&pool, "invited@example.com").await;
+
+    let (_, page) = signup(&app, signup_form_body("stranger@example.com", "Password123", "Password123")).await;
+    assert!(page.contains("New accounts need an invitation"));
+    assert!(!user_exists(&pool, "stranger@example.com").await);
+
+    // Someone else's invite doesn't count.
+    let body = format!("{}&invite={}", signup_form_body("stranger@example.com", "Password123", "Password123"), token);
+    let (_, page) = signup(&app, body).await;
+    assert!(page.contains("New accounts need an invitation"));
+
+    let (_, page) = get(&app, "", &format!("/accept-invite?token={}", token)).await;
+    assert!(page.contains(&format!("invite={}", token)));
+    let body = format!("{}&invite={}", signup_form_body("invited@example.com", "Password123", "Password123"), token);
+    let (status, location) = signup(&app, body).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    assert!(location.starts_with("/check-email"));
+    assert!(user_exists(&pool, "invited@example.com").await);
+
+    // The account belongs to the inviting organization; it gets no personal one and the invite is used.
+    let user = db::find_by_email(&pool, &Email::new("invited@example.com".to_string()).unwrap()).await.unwrap().unwrap();
+    let memberships = db::organizations::list_memberships_for_user(&pool, &UserId::from_string(&user.id).unwrap()).await.unwrap();
+    assert_eq!(memberships.len(), 1);
+    assert_eq!(memberships[0].organization_id, user.organization_id);
+    assert!(db::organization_invites::find_by_token(&pool, &token).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn invite_only_signup_through_a_link_joins_its_organization() {
+    let pool = test_pool().await;
+    let mut state = test_state(pool.clone());
+    state.config.signup_mode = SignupMode::InviteOnly;
+    let app = boardtask::create_router(state);
+    let (owner_id, _, _) = create_verified_user(&pool, "linker@example.com", "Password123").await;
+    let org_id = db::users::find_by_id(&pool, &owner_id).await.unwrap().unwrap().organization_id;
+    let token = ulid::Ulid::new().to_string();
+    db::organization_invite_links::insert(&pool, &db::organization_invite_links::NewInviteLink {
+        id: ulid::Ulid::new().to_string(),
+        organization_id: org_id.clone(),
+        token: token.clone(),
+        role: OrganizationRole::Viewer,
+        team_id: None,
+        max_uses: 1,
+        created_by_user_id: owner_id.as_str().to_string(),
+        expires_at: time::OffsetDateTime::now_utc().unix_timestamp() + 3600,
+    })
+    .await
+    .unwrap();
+
+    let body = format!("{}&invite={}", signup_form_body("linked@example.com", "Password123", "Password123"), token);
+    let (status, location) = signup(&app, body).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    assert!(location.starts_with("/check-email"), "{}", location);
+    let user = db::find_by_email(&pool, &Email::new("linked@example.com".to_string()).unwrap()).await.unwrap().unwrap();
+    assert_eq!(user.organization_id, org_id);
+    assert_eq!(role_of(&pool, &org_id, &user.id).await, Some(OrganizationRole::Viewer));
+    assert_eq!(db::organizations::list_memberships_for_user(&pool, &UserId::from_string(&user.id).unwrap()).await.unwrap().len(), 1);
+
+    // The link's only use went to that account.
+    let body = format!("{}&invite={}", signup_form_body("second@example.com", "Password123", "Password123"), token);
+    let (_, page) = signup(&app, body).await;
+    assert!(page.contains("New accounts need an invitation"));
+    assert!(!user_exists(&pool, "second@example.com").await);
+}
+
+#[tokio::test]
+async fn allowed_domains_limit_signups_and_invites() {
+    let pool = test_pool().await;
+    let mut state = test_state(pool.clone());
+    state.config.signup_allowed_domains = vec!["acme.com".to_string()];
+    let app = boardtask::create_router(state);
+
+    let (_, page) = signup(&app, signup_form_body("jo@other.com", "Password123", "Password123")).await;
+    assert!(page.contains("limited to email addresses at acme.com"));
+    assert!(!user_exists(&pool, "jo@other.com").await);
+
+    let (status, _) = signup(&app, signup_form_body("jo@acme.com", "Password123", "Password123")).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+
+    // An invite to an address outside the allowed domains can't be turned into an account.
+    let token = invite(&pool, "contractor@other.com").await;
+    let (_, page) = get(&app, "", &format!("/accept-invite?token={}", token)).await;
+    assert!(page.contains("signup-blocked"));
+    assert!(!page.contains("Create account"));
+}
+
+#[tokio::test]
+async fn admin_override_takes_precedence_until_reset() {
+    let pool = test_pool().await;
+    let mut state = test_state(pool.clone());
+    state.config.admin_emails = vec!["admin@example.com".to_string()];
+    let app = boardtask::create_router(state);
+    let admin = authenticated_cookie(&pool, &app, "admin@example.com", "Password123").await;
+    let member = authenticated_cookie(&pool, &app, "member@example.com", "Password123").await;
+
+    let (status, _) = get(&app, &member, "/app/admin/signup").await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    let (status, _) = post_form(&app, &pool, &member, "/app/admin/signup", "mode=closed").await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+
+    let (_, location) = post_form(&app, &pool, &admin, "/app/admin/signup", "mode=open&allowed_domains=acme.com%0Anot+a+domain").await;
+    assert!(location.contains("error="));
+
+    let (_, location) = post_form(&app, &pool, &admin, "/app/admin/signup", "mode=closed&allowed_domains=").await;
+    assert!(location.contains("success="));
+    let (status, page) = get(&app, &admin, "/app/admin/signup").await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(page.contains("value=\"closed\" class=\"mt-1\" checked"));
+    let (_, page) = signup(&app, signup_form_body("late@example.com", "Password123", "Password123")).await;
+    assert!(page.contains("isn&#x27;t accepting new accounts"));
+
+    post_form(&app, &pool, &admin, "/app/admin/signup/reset", "").await;
+    let (status, _) = signup(&app, signup_form_body("late@example.com", "Password123", "Password123")).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+}
@@ -175,3 +175,109 @@ async fn team_show_404_when_team_belongs_to_another_organization() {
         "Must not leak other org teams; expect 404"
     );
 }
+
+async fn org_of(pool: &sqlx::SqlitePool, team_id: &str) -> String {
+    db::teams::find_by_id(pool, team_id).await.unwrap().unwrap().organization_id
+}
+
+#[tokio::test]
+async fn admin_creates_renames_and_staffs_a_team() {
+    let (cookie, _project_id, pool, app, default_team) =
+        setup_user_and_project("teamadmin@example.com", "Password123").await;
+    let org_id = org_of(&pool, &default_team).await;
+    let colleague = add_org_member(&pool, &org_id, "colleague@example.com").await;
+
+    let (status, location) = post_form(&app, &pool, &cookie, "/app/teams", "name=%20Platform%20").await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    let teams = db::teams::find_by_organization(&pool, &org_id).await.unwrap();
+    let team = teams.iter().find(|t| t.name == "Platform").expect("team created with trimmed name");
+    assert!(location.starts_with(&format!("/app/teams/{}?success=", team.id)));
+
+    let (_, location) = post_form(&app, &pool, &cookie, "/app/teams", "name=platform").await;
+    assert!(location.starts_with("/app/teams?error="), "duplicate names are rejected: {}", location);
+
+    let uri = format!("/app/teams/{}/rename", team.id);
+    post_form(&app, &pool, &cookie, &uri, "name=Infrastructure").await;
+    assert_eq!(db::teams::find_by_id(&pool, &team.id).await.unwrap().unwrap().name, "Infrastructure");
+
+    let members_uri = format!("/app/teams/{}/members", team.id);
+    post_form(&app, &pool, &cookie, &members_uri, &format!("user_id={}", colleague)).await;
+    let colleague_id = boardtask::app::domain::UserId::from_string(&colleague).unwrap();
+    assert!(db::team_members::is_member(&pool, &team.id, &colleague_id).await.unwrap());
+
+    let (stranger_id, _, _) = create_verified_user(&pool, "notinorg@example.com", "Password123").await;
+    let (_, location) = post_form(&app, &pool, &cookie, &members_uri, &format!("user_id={}", stranger_id.as_str())).await;
+    assert!(location.contains("error="), "non-members of the org can't join its teams");
+    assert!(!db::team_members::is_member(&pool, &team.id, &stranger_id).await.unwrap());
+
+    let remove_uri = format!("/app/teams/{}/members/{}/remove", team.id, colleague);
+    post_form(&app, &pool, &cookie, &remove_uri, "").await;
+    assert!(!db::team_members::is_member(&pool, &team.id, &colleague_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn deleting_a_team_moves_its_projects_and_the_last_team_stays() {
+    let (cookie, project_id, pool, app, default_team) =
+        setup_user_and_project("teamdelete@example.com", "Password123").await;
+    let org_id = org_of(&pool, &default_team).await;
+
+    let (_, location) = post_form(&app, &pool, &cookie, &format!("/app/teams/{}/delete", default_team),
+        &format!("reassign_to_team_id={}", default_team)).await;
+    assert!(location.contains("error="));
+    assert!(db::teams::find_by_id(&pool, &default_team).await.unwrap().is_some());
+
+    post_form(&app, &pool, &cookie, "/app/teams", "name=Other").await;
+    let other = db::teams::find_by_organization(&pool, &org_id).await.unwrap()
+        .into_iter().find(|t| t.name == "Other").unwrap();
+
+    let (status, location) = post_form(&app, &pool, &cookie, &format!("/app/teams/{}/delete", default_team),
+        &format!("reassign_to_team_id={}", other.id)).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    assert!(location.starts_with("/app/teams?success="));
+    assert!(db::teams::find_by_id(&pool, &default_team).await.unwrap().is_none());
+    let project = db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap();
+    assert_eq!(project.team_id.as_deref(), Some(other.id.as_str()));
+}
+
+#[tokio::test]
+async fn admin_moves_a_project_between_teams() {
+    let (cookie, project_id, pool, app, default_team) =
+        setup_user_and_project("teammove@example.com", "Password123").await;
+    let org_id = org_of(&pool, &default_team).await;
+    post_form(&app, &pool, &cookie, "/app/teams", "name=Design").await;
+    let design = db::teams::find_by_organization(&pool, &org_id).await.unwrap()
+        .into_iter().find(|t| t.name == "Design").unwrap();
+
+    let uri = format!("/app/teams/{}/projects/{}/move", default_team, project_id);
+    let (status, _) = post_form(&app, &pool, &cookie, &uri, &format!("to_team_id={}", design.id)).await;
+    assert_eq!(status, http::StatusCode::SEE_OTHER);
+    let project = db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap();
+    assert_eq!(project.team_id.as_deref(), Some(design.id.as_str()));
+}
+
+#[tokio::test]
+async fn team_management_is_for_admins_only() {
+    let (_cookie, _project_id, pool, app, default_team) =
+        setup_user_and_project("teamowner@example.com", "Password123").await;
+    let org_id = org_of(&pool, &default_team).await;
+    add_org_member(&pool, &org_id, "plainmember@example.com").await;
+    let member = login_cookie(&app, "plainmember@example.com").await;
+
+    let (status, _) = post_form(&app, &pool, &member, &format!("/app/teams/{}/rename", default_team), "name=Mine").await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    let (status, _) = post_form(&app, &pool, &member, &format!("/app/teams/{}/members/{}/remove", default_team,
+        user_id_from_cookie(&pool, &member).await), "").await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    assert_eq!(db::teams::find_by_id(&pool, &default_team).await.unwrap().unwrap().name, "Test Org");
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(format!("/app/teams/{}", default_team))
+        .header("cookie", &member)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert!(!String::from_utf8_lossy(&body).contains("/rename"));
+}
@@ -0,0 +1,146 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db::task_statuses::{DONE_STATUS_ID, TODO_STATUS_ID};
+
+async fn send_json(
+    app: &axum::Router,
+    pool: &sqlx::SqlitePool,
+    cookie: &str,
+    method: &str,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> (http::StatusCode, serde_json::Value) {
+    let mut builder = http::Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("cookie", cookie);
+    if method != "GET" {
+        builder = builder.header("x-csrf-token", csrf_token_from_cookie(pool, cookie).await);
+    }
+    let request = match body {
+        Some(body) => builder
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap(),
+        None => builder.body(axum::body::Body::empty()).unwrap(),
+    };
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+#[tokio::test]
+async fn logged_entries_are_summed_against_the_estimate() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("logger@example.com", "Password123").await;
+    let node_id = insert_task(&pool, &project_id, TODO_STATUS_ID, Some(60), None, None).await;
+    let uri = format!("/api/projects/{}/nodes/{}/time-entries", project_id, node_id);
+
+    let (status, entry) = send_json(&app, &pool, &cookie, "POST", &uri,
+        Some(serde_json::json!({ "minutes": 45, "date": "2026-03-02", "note": "  spike  " }))).await;
+    assert_eq!(status, http::StatusCode::CREATED);
+    assert_eq!(entry["entry_date"], "2026-03-02");
+    assert_eq!(entry["note"], "spike");
+    let (status, _) = send_json(&app, &pool, &cookie, "POST", &uri, Some(serde_json::json!({ "minutes": 30 }))).await;
+    assert_eq!(status, http::StatusCode::CREATED);
+
+    let (status, body) = send_json(&app, &pool, &cookie, "GET", &uri, None).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(body["estimated_minutes"], 60);
+    assert_eq!(body["actual_minutes"], 75);
+    assert_eq!(body["over_estimate"], true);
+    assert_eq!(body["entries"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn invalid_entries_are_rejected_and_only_own_entries_can_be_deleted() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("strict@example.com", "Password123").await;
+    let node_id = insert_task(&pool, &project_id, TODO_STATUS_ID, None, None, None).await;
+    let uri = format!("/api/projects/{}/nodes/{}/time-entries", project_id, node_id);
+
+    for body in [
+        serde_json::json!({ "minutes": 0 }),
+        serde_json::json!({ "minutes": 10, "date": "2026-02-30" }),
+        serde_json::json!({ "minutes": 10, "date": "March 2nd" }),
+    ] {
+        let (status, _) = send_json(&app, &pool, &cookie, "POST", &uri, Some(body)).await;
+        assert_eq!(status, http::StatusCode::BAD_REQUEST);
+    }
+
+    let (_, entry) = send_json(&app, &pool, &cookie, "POST", &uri, Some(serde_json::json!({ "minutes": 10 }))).await;
+    let entry_uri = format!("{}/{}", uri, entry["id"].as_str().unwrap());
+
+    let org_id = boardtask::app::db::projects::find_by_id(&pool, &project_id).await.unwrap().unwrap().organization_id;
+    add_org_member(&pool, &org_id, "colleague@example.com").await;
+    let colleague = login_cookie(&app, "colleague@example.com").await;
+    let (status, _) = send_json(&app, &pool, &colleague, "DELETE", &entry_uri, None).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+
+    let outsider = authenticated_cookie(&pool, &app, "stranger@example.com", "Password123").await;
+    let (status, _) = send_json(&app, &pool, &outsider, "GET", &uri, None).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+
+    let (status, _) = send_json(&app, &pool, &cookie, "DELETE", &entry_uri, None).await;
+    assert_eq!(status, http::StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn running_timer_starts_once_and_logs_an_entry_when_stopped() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("timer@example.com", "Password123").await;
+    let node_id = insert_task(&pool, &project_id, TODO_STATUS_ID, Some(30), None, None).await;
+    let other_id = insert_task(&pool, &project_id, TODO_STATUS_ID, None, None, None).await;
+
+    let (status, body) = send_json(&app, &pool, &cookie, "GET", "/api/timer", None).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(body["timer"].is_null());
+
+    let start = format!("/api/projects/{}/nodes/{}/timer", project_id, node_id);
+    let (status, body) = send_json(&app, &pool, &cookie, "POST", &start, None).await;
+    assert_eq!(status, http::StatusCode::CREATED);
+    assert_eq!(body["timer"]["node_id"], node_id.as_str());
+
+    let other = format!("/api/projects/{}/nodes/{}/timer", project_id, other_id);
+    let (status, _) = send_json(&app, &pool, &cookie, "POST", &other, None).await;
+    assert_eq!(status, http::StatusCode::BAD_REQUEST);
+
+    let (status, entry) = send_json(&app, &pool, &cookie, "POST", "/api/timer/stop", None).await;
+    assert_eq!(status, http::StatusCode::CREATED);
+    assert_eq!(entry["node_id"], node_id.as_str());
+    assert_eq!(entry["minutes"], 1);
+
+    let (_, body) = send_json(&app, &pool, &cookie, "GET", "/api/timer", None).await;
+    assert!(body["timer"].is_null());
+    let (status, _) = send_json(&app, &pool, &cookie, "POST", "/api/timer/stop", None).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn project_page_shows_time_spent_next_to_remaining_estimate() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("spent@example.com", "Password123").await;
+    let done_id = insert_task(&pool, &project_id, DONE_STATUS_ID, Some(120), None, None).await;
+    let open_id = insert_task(&pool, &project_id, TODO_STATUS_ID, Some(60), None, None).await;
+    for (node_id, minutes) in [(&done_id, 150), (&open_id, 30)] {
+        let uri = format!("/api/projects/{}/nodes/{}/time-entries", project_id, node_id);
+        let (status, _) = send_json(&app, &pool, &cookie, "POST", &uri, Some(serde_json::json!({ "minutes": minutes }))).await;
+        assert_eq!(status, http::StatusCode::CREATED);
+    }
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(format!("/app/projects/{}", project_id))
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let body = String::from_utf8_lossy(&response.into_body().collect().await.unwrap().to_bytes()).to_string();
+    assert!(body.contains("1 h remaining"));
+    assert!(body.contains("3 h spent"));
+    assert!(body.contains("Done: 2 h estimated, 2 h 30 min spent"));
+}
@@ -0,0 +1,172 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+const TASK_NODE_TYPE_ID: &str = "01JNODETYPE00000000TASK000";
+
+async fn insert_task(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    title: &str,
+    estimated_minutes: Option<i64>,
+    slot_id: Option<&str>,
+) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let node = boardtask::app::db::nodes::NewNode {
+        id: id.clone(),
+        project_id: project_id.to_string(),
+        node_type_id: TASK_NODE_TYPE_ID.to_string(),
+        status_id: boardtask::app::db::task_statuses::TODO_STATUS_ID.to_string(),
+        title: title.to_string(),
+        description: None,
+        estimated_minutes,
+        slot_id: slot_id.map(String::from),
+        parent_id: None,
+        assigned_user_id: None,
+    };
+    boardtask::app::db::nodes::insert(pool, &node).await.unwrap();
+    id
+}
+
+async fn insert_edge(pool: &sqlx::SqlitePool, parent_id: &str, child_id: &str) {
+    let edge = boardtask::app::db::node_edges::NewNodeEdge {
+        parent_id: parent_id.to_string(),
+        child_id: child_id.to_string(),
+    };
+    boardtask::app::db::node_edges::insert(pool, &edge).await.unwrap();
+}
+
+async fn insert_slot(pool: &sqlx::SqlitePool, project_id: &str, name: &str, assigned_user_id: Option<&str>) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let slot = boardtask::app::db::project_slots::NewProjectSlot {
+        id: id.clone(),
+        project_id: project_id.to_string(),
+        name: name.to_string(),
+        sort_order: 0,
+        assigned_user_id: assigned_user_id.map(String::from),
+    };
+    boardtask::app::db::project_slots::insert(pool, &slot).await.unwrap();
+    id
+}
+
+async fn get(app: &axum::Router, cookie: &str, uri: &str) -> (http::StatusCode, String) {
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(uri)
+        .header("cookie", cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8_lossy(&bytes).to_string())
+}
+
+/// Axis label of the first working day for a project created today.
+fn timeline_start_label() -> String {
+    let mut day = time::OffsetDateTime::now_utc().date();
+    while matches!(day.weekday(), time::Weekday::Saturday | time::Weekday::Sunday) {
+        day = day.next_day().unwrap();
+    }
+    format!("{} {:02}-{:02}", &day.weekday().to_string()[..3], u8::from(day.month()), day.day())
+}
+
+fn item<'a>(body: &'a serde_json::Value, node_id: &str) -> &'a serde_json::Value {
+    body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|i| i["node_id"] == node_id)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn timeline_schedules_by_dependencies_estimates_and_slots() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("timeline@example.com", "Password123").await;
+    let user_id = user_id_from_cookie(&pool, &cookie).await;
+    let slot_id = insert_slot(&pool, &project_id, "Backend", Some(&user_id)).await;
+    // design (2 h) -> build (3 h, Backend) ; api (1 h, Backend) runs after build in the same slot.
+    let design = insert_task(&pool, &project_id, "Design", Some(120), None).await;
+    let build = insert_task(&pool, &project_id, "Build", Some(180), Some(&slot_id)).await;
+    let api = insert_task(&pool, &project_id, "API", Some(60), Some(&slot_id)).await;
+    let docs = insert_task(&pool, &project_id, "Docs", None, None).await;
+    insert_edge(&pool, &design, &build).await;
+    insert_edge(&pool, &build, &api).await;
+
+    let (status, body) = get(&app, &cookie, &format!("/api/projects/{}/timeline", project_id)).await;
+    assert_eq!(status, http::StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+    let lanes = body["lanes"].as_array().unwrap();
+    assert_eq!(lanes.len(), 2);
+    assert_eq!(lanes[0]["slot_ids"], serde_json::json!([slot_id]));
+    assert_eq!(lanes[0]["assigned_user_id"], user_id.as_str());
+    assert_eq!(lanes[0]["name"], "Backend");
+    assert!(lanes[0]["assignee_name"].is_string());
+    assert!(lanes[1]["assigned_user_id"].is_null());
+    assert_eq!(lanes[1]["name"], "Unassigned");
+
+    assert_eq!(item(&body, &design)["start_minutes"], 0);
+    assert_eq!(item(&body, &design)["lane_index"], 1);
+    assert_eq!(item(&body, &build)["start_minutes"], 120);
+    assert_eq!(item(&body, &build)["end_minutes"], 300);
+    assert_eq!(item(&body, &build)["lane_index"], 0);
+    assert_eq!(item(&body, &api)["start_minutes"], 300);
+    assert_eq!(item(&body, &api)["blocked"], true);
+    assert_eq!(item(&body, &docs)["estimated"], false);
+    assert_eq!(body["total_minutes"], 360);
+}
+
+#[tokio::test]
+async fn timeline_api_is_scoped_to_the_organization() {
+    let (_, project_id, pool, app, _) = setup_user_and_project("owner@example.com", "Password123").await;
+    let other_cookie = authenticated_cookie(&pool, &app, "outsider@example.com", "Password123").await;
+
+    let (status, _) = get(&app, &other_cookie, &format!("/api/projects/{}/timeline", project_id)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+
+    let (status, _) = get(&app, &other_cookie, &format!("/app/projects/{}/timeline", project_id)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn timeline_page_renders_svg_bars() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("svg@example.com", "Password123").await;
+    let first = insert_task(&pool, &project_id, "Write spec", Some(60), None).await;
+    let second = insert_task(&pool, &project_id, "Review spec", None, None).await;
+    let third = insert_task(&pool, &project_id, "Ship spec", Some(30), None).await;
+    insert_edge(&pool, &first, &second).await;
+    insert_edge(&pool, &second, &third).await;
+
+    let (status, body) = get(&app, &cookie, &format!("/app/projects/{}/timeline", project_id)).await;
+
+    assert_eq!(status, http::StatusCode::OK);
+    assert!(body.contains("<svg class=\"timeline-chart\""));
+    assert!(body.contains(&format!("data-node-id=\"{}\"", first)));
+    assert!(body.contains("Write spec"));
+    assert!(body.contains("timeline-bar__blocked"));
+    assert!(body.contains("1 without an estimate"));
+    assert!(!body.contains("Day 1"));
+    assert!(body.contains(&timeline_start_label()));
+}
+
+#[tokio::test]
+async fn timeline_default_view_mode_opens_timeline_from_projects_list() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("tlmode@example.com", "Password123").await;
+
+    let request = http::Request::builder()
+        .method("PATCH")
+        .uri(format!("/api/projects/{}", project_id))
+        .header("content-type", "application/json")
+        .header("cookie", &cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(&pool, &cookie).await)
+        .body(axum::body::Body::from(r#"{"default_view_mode":"timeline"}"#))
+        .unwrap();
+    assert_eq!(app.clone().oneshot(request).await.unwrap().status(), http::StatusCode::OK);
+
+    let (_, body) = get(&app, &cookie, "/app/projects").await;
+    assert!(body.contains(&format!("/app/projects/{}/timeline", project_id)));
+}
@@ -0,0 +1,138 @@
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+mod common;
+
+use crate::common::*;
+
+use boardtask::app::db::task_statuses::{DONE_STATUS_ID, TODO_STATUS_ID};
+
+async fn insert_slot(pool: &sqlx::SqlitePool, project_id: &str, assigned_user_id: &str) -> String {
+    let id = ulid::Ulid::new().to_string();
+    let slot = boardtask::app::db::project_slots::NewProjectSlot {
+        id: id.clone(),
+        project_id: project_id.to_string(),
+        name: "Dev".to_string(),
+        sort_order: 0,
+        assigned_user_id: Some(assigned_user_id.to_string()),
+    };
+    boardtask::app::db::project_slots::insert(pool, &slot).await.unwrap();
+    id
+}
+
+async fn set_capacity(
+    app: &axum::Router,
+    pool: &sqlx::SqlitePool,
+    cookie: &str,
+    team_id: &str,
+    user_id: &str,
+    minutes: i64,
+) -> http::StatusCode {
+    let request = http::Request::builder()
+        .method("PATCH")
+        .uri(format!("/api/teams/{}/members/{}", team_id, user_id))
+        .header("content-type", "application/json")
+        .header("cookie", cookie)
+        .header("x-csrf-token", csrf_token_from_cookie(pool, cookie).await)
+        .body(axum::body::Body::from(serde_json::json!({ "weekly_capacity_minutes": minutes }).to_string()))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+fn member<'a>(body: &'a serde_json::Value, user_id: &str) -> &'a serde_json::Value {
+    body["members"].as_array().unwrap().iter().find(|m| m["user_id"] == user_id).unwrap()
+}
+
+async fn organization_id(pool: &sqlx::SqlitePool, project_id: &str) -> String {
+    boardtask::app::db::projects::find_by_id(pool, project_id).await.unwrap().unwrap().organization_id
+}
+
+#[tokio::test]
+async fn org_report_sums_open_estimates_per_assignee_and_flags_overload() {
+    let (cookie, project_id, pool, app, _) = setup_user_and_project("load@example.com", "Password123").await;
+    let me = user_id_from_cookie(&pool, &cookie).await;
+    let colleague = add_org_member(&pool, &organization_id(&pool, &project_id).await, "helper@example.com").await;
+    let colleague_slot = insert_slot(&pool, &project_id, &colleague).await;
+
+    insert_task(&pool, &project_id, TODO_STATUS_ID, Some(1500), None, Some(&me)).await;
+    insert_task(&pool, &project_id, TODO_STATUS_ID, Some(1200), None, Some(&me)).await;
+    insert_task(&pool, &project_id, DONE_STATUS_ID, Some(5000), None, Some(&me)).await;
+    insert_task(&pool, &project_id, TODO_STATUS_ID, Some(60), Some(&colleague_slot), None).await;
+    insert_task(&pool, &project_id, TODO_STATUS_ID, Some(30), None, None).await;
+
+    let (status, body) = get_json(&app, &cookie, "/api/workload").await;
+
+    assert_eq!(status, http::StatusCode::OK);
+    let mine = member(&body, &me);
+    assert_eq!(mine["open_count"], 2);
+    assert_eq!(mine["open_minutes"], 2700);
+    assert_eq!(mine["weekly_capacity_minutes"], 2400);
+    assert_eq!(mine["utilization_percent"], 112);
+    assert_eq!(mine["over_capacity"], true);
+    // The colleague is on no team, so has no capacity to compare against.
+    let theirs = member(&body, &colleague);
+    assert_eq!(theirs["open_minutes"], 60);
+    assert!(theirs["weekly_capacity_minutes"].is_null());
+    assert_eq!(theirs["over_capacity"], false);
+    assert_eq!(body["members"][0]["user_id"], me.as_str());
+    assert_eq!(body["unassigned_count"], 1);
+    assert_eq!(body["unassigned_minutes"], 30);
+}
+
+#[tokio::test]
+async fn owner_sets_capacity_used_by_team_report() {
+    let (cookie, project_id, pool, app, team_id) = setup_user_and_project("capacity@example.com", "Password123").await;
+    let me = user_id_from_cookie(&pool, &cookie).await;
+    insert_task(&pool, &project_id, TODO_STATUS_ID, Some(600), None, Some(&me)).await;
+
+    assert_eq!(set_capacity(&app, &pool, &cookie, &team_id, &me, 480).await, http::StatusCode::OK);
+    assert_eq!(set_capacity(&app, &pool, &cookie, &team_id, &me, -1).await, http::StatusCode::BAD_REQUEST);
+    // The range is checked before the team is looked up.
+    let unknown_team = ulid::Ulid::new().to_string();
+    assert_eq!(set_capacity(&app, &pool, &cookie, &unknown_team, &me, 100_000).await, http::StatusCode::BAD_REQUEST);
+    assert_eq!(set_capacity(&app, &pool, &cookie, &team_id, &ulid::Ulid::new().to_string(), 480).await, http::StatusCode::NOT_FOUND);
+
+    let (status, body) = get_json(&app, &cookie, &format!("/api/workload?team_id={}", team_id)).await;
+    assert_eq!(status, http::StatusCode::OK);
+    assert_eq!(body["members"].as_array().unwrap().len(), 1);
+    assert_eq!(member(&body, &me)["weekly_capacity_minutes"], 480);
+    assert_eq!(member(&body, &me)["over_capacity"], true);
+}
+
+#[tokio::test]
+async fn members_cannot_change_capacity_and_other_orgs_teams_are_hidden() {
+    let (cookie, project_id, pool, app, team_id) = setup_user_and_project("teamowner@example.com", "Password123").await;
+    let me = user_id_from_cookie(&pool, &cookie).await;
+    add_org_member(&pool, &organization_id(&pool, &project_id).await, "plain@example.com").await;
+    let member_cookie = login_cookie(&app, "plain@example.com").await;
+
+    assert_eq!(set_capacity(&app, &pool, &member_cookie, &team_id, &me, 60).await, http::StatusCode::NOT_FOUND);
+
+    let outsider = authenticated_cookie(&pool, &app, "outsider@example.com", "Password123").await;
+    let (status, _) = get_json(&app, &outsider, &format!("/api/workload?team_id={}", team_id)).await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn workload_page_renders_rows_and_over_capacity_badge() {
+    let (cookie, project_id, pool, app, team_id) = setup_user_and_project("loadpage@example.com", "Password123").await;
+    let me = user_id_from_cookie(&pool, &cookie).await;
+    insert_task(&pool, &project_id, TODO_STATUS_ID, Some(3000), None, Some(&me)).await;
+
+    let request = http::Request::builder()
+        .method("GET")
+        .uri(format!("/app/workload?team_id={}", team_id))
+        .header("cookie", &cookie)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::OK);
+    let body = String::from_utf8_lossy(&response.into_body().collect().await.unwrap().to_bytes()).to_string();
+    assert!(body.contains(&format!("data-user-id=\"{}\"", me)));
+    assert!(body.contains("workload-row__over"));
+    assert!(body.contains("1 over capacity"));
+    assert!(body.contains("50 h"));
+    // Owners viewing a team get the capacity editor.
+    assert!(body.contains("workloadCapacity("));
+}